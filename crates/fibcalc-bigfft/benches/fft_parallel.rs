@@ -0,0 +1,63 @@
+//! Criterion benchmarks comparing sequential and parallel FFT multiplication
+//! on both backends.
+//!
+//! Operand sizes match the final doubling steps of F(10^7) and F(10^8)
+//! (about 0.694 * n bits). Run with `RAYON_NUM_THREADS` to vary the pool.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use num_bigint::BigUint;
+use num_traits::One;
+
+use fibcalc_bigfft::{mul_with, sqr_with, FFTBackend, FFTConfig};
+
+/// A dense `bits`-bit operand.
+fn operand(bits: usize) -> BigUint {
+    (BigUint::one() << bits) / BigUint::from(7u32)
+}
+
+fn bench_fft_parallel(c: &mut Criterion) {
+    let mut configs = Vec::new();
+    for backend in [FFTBackend::Fermat, FFTBackend::Ntt] {
+        let base = FFTConfig::with_threshold(0).with_backend(backend);
+        configs.push((
+            format!("{}/sequential", backend.name()),
+            base.with_parallel_threshold(usize::MAX),
+        ));
+        configs.push((
+            format!("{}/parallel", backend.name()),
+            base.with_parallel_threshold(0),
+        ));
+    }
+
+    let sizes: [(&str, usize); 2] = [("F(10^7)", 3_470_000), ("F(10^8)", 34_700_000)];
+
+    let mut group = c.benchmark_group("fft_mul");
+    group.sample_size(10);
+    for &(label, bits) in &sizes {
+        let a = operand(bits);
+        let b = operand(bits + 1) + BigUint::one();
+        for (mode, config) in &configs {
+            group.bench_with_input(BenchmarkId::new(mode, label), &bits, |bench, _| {
+                bench.iter(|| black_box(mul_with(black_box(&a), black_box(&b), config)));
+            });
+        }
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("fft_sqr");
+    group.sample_size(10);
+    for &(label, bits) in &sizes {
+        let a = operand(bits);
+        for (mode, config) in &configs {
+            group.bench_with_input(BenchmarkId::new(mode, label), &bits, |bench, _| {
+                bench.iter(|| black_box(sqr_with(black_box(&a), config)));
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_fft_parallel);
+criterion_main!(benches);
@@ -0,0 +1,71 @@
+//! Criterion benchmark of transform reuse in a fast-doubling step.
+//!
+//! `independent` runs `F(k)*T`, `F(k)^2` and `F(k+1)^2` as separate FFT
+//! products (four forward transforms); `reused` transforms each operand
+//! once and reuses F(k)'s transform (three forward transforms); `fused` also sums
+//! `F(k)^2 + F(k+1)^2` in the transform domain (two inverse transforms
+//! instead of three). All use the Fermat backend, the only one with
+//! reusable transforms.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use num_bigint::BigUint;
+use num_traits::One;
+
+use fibcalc_bigfft::{mul_with, sqr_with, FFTBackend, FFTConfig, FFTPlan, TransformedOperand};
+
+fn independent(fk: &BigUint, fk1: &BigUint, config: &FFTConfig) -> (BigUint, BigUint) {
+    let t = (fk1 << 1u32) - fk;
+    let f2k = mul_with(fk, &t, config);
+    let f2k1 = sqr_with(fk, config) + sqr_with(fk1, config);
+    (f2k, f2k1)
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn reused(fk: &BigUint, fk1: &BigUint, config: &FFTConfig) -> (BigUint, BigUint) {
+    let t = (fk1 << 1u32) - fk;
+    let bits = t.bits() as usize;
+    let plan = FFTPlan::for_product(bits, bits);
+    let fk_hat = TransformedOperand::new(fk, plan, config);
+    let f2k = fk_hat.mul(&TransformedOperand::new(&t, plan, config), config);
+    let f2k1 = fk_hat.sqr(config) + TransformedOperand::new(fk1, plan, config).sqr(config);
+    (f2k, f2k1)
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn fused(fk: &BigUint, fk1: &BigUint, config: &FFTConfig) -> (BigUint, BigUint) {
+    let t = (fk1 << 1u32) - fk;
+    let bits = t.bits() as usize;
+    let plan = FFTPlan::for_product(bits, bits);
+    let fk_hat = TransformedOperand::new(fk, plan, config);
+    let fk1_hat = TransformedOperand::new(fk1, plan, config);
+    let f2k = fk_hat.mul(&TransformedOperand::new(&t, plan, config), config);
+    let f2k1 =
+        TransformedOperand::sum_of_products(&[(&fk_hat, &fk_hat), (&fk1_hat, &fk1_hat)], config);
+    (f2k, f2k1)
+}
+
+fn bench_transform_reuse(c: &mut Criterion) {
+    let config = FFTConfig::with_threshold(0).with_backend(FFTBackend::Fermat);
+
+    let mut group = c.benchmark_group("doubling_step");
+    group.sample_size(10);
+    for bits in [100_000usize, 1_000_000, 3_470_000] {
+        let fk = (BigUint::one() << bits) / BigUint::from(3u32);
+        let fk1 = (BigUint::one() << bits) / BigUint::from(2u32) + BigUint::one();
+        group.bench_with_input(BenchmarkId::new("independent", bits), &bits, |b, _| {
+            b.iter(|| black_box(independent(black_box(&fk), black_box(&fk1), &config)));
+        });
+        group.bench_with_input(BenchmarkId::new("reused", bits), &bits, |b, _| {
+            b.iter(|| black_box(reused(black_box(&fk), black_box(&fk1), &config)));
+        });
+        group.bench_with_input(BenchmarkId::new("fused", bits), &bits, |b, _| {
+            b.iter(|| black_box(fused(black_box(&fk), black_box(&fk1), &config)));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_transform_reuse);
+criterion_main!(benches);
@@ -1,3 +1,74 @@
-//! FFT bump allocator — re-exported from fibcalc-memory.
+//! FFT bump allocator — re-exported from fibcalc-memory — and the
+//! per-thread arenas used by the Fermat FFT: scratch limbs for the
+//! kernels, and the transform coefficients of each product.
+
+use std::cell::RefCell;
 
 pub use fibcalc_memory::arena::BumpArena as FFTBumpAllocator;
+
+thread_local! {
+    static FFT_BUMP: RefCell<FFTBumpAllocator> =
+        RefCell::new(FFTBumpAllocator::with_capacity(64 * 1024));
+    static COEFF_BUMP: RefCell<FFTBumpAllocator> = RefCell::new(FFTBumpAllocator::new());
+}
+
+/// Run `f` with this thread's scratch arena, then reset the arena.
+///
+/// Nested calls share the outermost call's arena, which is reset once
+/// that call returns. `f` must not hand work to rayon, or a stolen task
+/// could run on this thread while the arena is borrowed.
+pub(crate) fn with_scratch<R>(f: impl FnOnce(&FFTBumpAllocator) -> R) -> R {
+    FFT_BUMP.with(|cell| {
+        let result = f(&cell.borrow());
+        if let Ok(mut arena) = cell.try_borrow_mut() {
+            arena.reset();
+        }
+        result
+    })
+}
+
+/// Run `f` with this thread's coefficient arena, then reset the arena.
+///
+/// Unlike [`with_scratch`], `f` may hand work to rayon: a task stolen onto
+/// this thread shares the arena, and only the outermost call resets it, so
+/// no coefficient is freed while a transform still uses it. After a reset
+/// the arena keeps its largest chunk, so products of a steady size stop
+/// allocating coefficients once warm.
+///
+/// A memory budget (`memory_limit` below `usize::MAX`) only covers a
+/// product while it runs, so under one the arena frees its chunks instead
+/// of keeping them between products.
+pub(crate) fn with_coefficients<R>(
+    memory_limit: usize,
+    f: impl FnOnce(&FFTBumpAllocator) -> R,
+) -> R {
+    COEFF_BUMP.with(|cell| {
+        let result = f(&cell.borrow());
+        if let Ok(mut arena) = cell.try_borrow_mut() {
+            if memory_limit == usize::MAX {
+                arena.reset();
+            } else {
+                *arena = FFTBumpAllocator::new();
+            }
+        }
+        result
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scratch_is_shared_by_nested_calls() {
+        let total = with_scratch(|outer| {
+            let a = outer.alloc_slice(16);
+            a[0] = 7;
+            let inner = with_scratch(|arena| arena.alloc_slice(16).len());
+            // The inner call must not have reset the outer allocation.
+            assert_eq!(a[0], 7);
+            a.len() + inner
+        });
+        assert_eq!(total, 32);
+    }
+}
@@ -4,25 +4,68 @@
 //! Number Theoretic Transform (NTT) based multiplication.
 //!
 //! Add and subtract operate directly on u64 limbs to avoid
-//! heap-allocating `BigUint` conversions in hot loops.
+//! heap-allocating `BigUint` conversions in hot loops. Scratch limbs come
+//! from the thread-local bump arena ([`with_scratch`]), and the `BigUint`
+//! operands of coefficient products from a [`TempAllocator`].
+
+use std::ops::DerefMut;
 
 use num_bigint::BigUint;
 use num_traits::One;
 
-/// A Fermat number representation: value = data mod (2^shift + 1).
+use crate::allocator::{SimpleAllocator, TempAllocator};
+use crate::bump::{with_scratch, FFTBumpAllocator};
+
+/// Set `dst` to the value of the little-endian u64 `limbs`, reusing its
+/// allocation.
+#[allow(clippy::cast_possible_truncation)]
+pub(crate) fn load_limbs(limbs: &[u64], dst: &mut BigUint) {
+    with_scratch(|arena| {
+        let digits = arena
+            .bump()
+            .alloc_slice_fill_default::<u32>(2 * limbs.len());
+        for (pair, &limb) in digits.chunks_exact_mut(2).zip(limbs) {
+            pair[0] = limb as u32;
+            pair[1] = (limb >> 32) as u32;
+        }
+        dst.assign_from_slice(digits);
+    });
+}
+
+/// A residue modulo the Fermat-style number `2^shift + 1`.
+///
+/// Invariants, upheld by every constructor and operation:
+///
+/// - `shift > 0`;
+/// - the value is held in `shift.div_ceil(64) + 1` little-endian u64 limbs;
+/// - the value is reduced, i.e. strictly less than `2^shift + 1`.
+///
+/// The limbs are an owned `Vec<u64>` by default. The FFT products use
+/// `FermatNum<&mut [u64]>` coefficients carved from a bump arena, which
+/// support the same in-place arithmetic.
 #[derive(Debug, Clone)]
-pub struct FermatNum {
+pub struct FermatNum<S = Vec<u64>> {
     /// The value represented as limbs (little-endian u64).
-    pub data: Vec<u64>,
+    pub(crate) data: S,
     /// The shift: the Fermat modulus is 2^shift + 1.
-    pub shift: usize,
+    pub(crate) shift: usize,
 }
 
+/// Limb storage of a [`FermatNum`]: anything that derefs to a mutable
+/// `[u64]`, such as `Vec<u64>` or `&mut [u64]`.
+pub trait Limbs: DerefMut<Target = [u64]> + Send + Sync {}
+
+impl<T: DerefMut<Target = [u64]> + Send + Sync> Limbs for T {}
+
 impl FermatNum {
     /// Create a new zero Fermat number with the given shift.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shift` is zero.
     #[must_use]
-    #[allow(dead_code)] // Public API for future callers
     pub fn new(shift: usize) -> Self {
+        assert!(shift > 0, "Fermat shift must be positive");
         let num_limbs = shift.div_ceil(64) + 1;
         Self {
             data: vec![0; num_limbs],
@@ -30,26 +73,76 @@ impl FermatNum {
         }
     }
 
-    /// Create from a `BigUint` value.
+    /// Create from a `BigUint` value, reduced mod `2^shift + 1`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shift` is zero.
     #[must_use]
     pub fn from_biguint(value: &BigUint, shift: usize) -> Self {
-        let digits = value.to_u64_digits();
-        let num_limbs = shift.div_ceil(64) + 1;
-        let mut data = vec![0u64; num_limbs];
-        let copy_len = digits.len().min(num_limbs);
-        data[..copy_len].copy_from_slice(&digits[..copy_len]);
-        Self { data, shift }
+        let mut num = Self::new(shift);
+        let digits = if value.bits() > shift as u64 {
+            (value % num.modulus()).to_u64_digits()
+        } else {
+            value.to_u64_digits()
+        };
+        num.data[..digits.len()].copy_from_slice(&digits);
+        num
+    }
+
+    /// Add two Fermat numbers mod (2^shift + 1).
+    /// Uses in-place limb arithmetic instead of `BigUint` conversion.
+    #[must_use]
+    pub fn add(&self, other: &Self) -> Self {
+        assert_eq!(self.shift, other.shift);
+        let mut result = self.clone();
+        result.add_assign(other);
+        result
+    }
+
+    /// Subtract other from self mod (2^shift + 1).
+    /// Uses in-place limb arithmetic instead of `BigUint` conversion.
+    #[must_use]
+    pub fn sub(&self, other: &Self) -> Self {
+        assert_eq!(self.shift, other.shift);
+        let mut result = other.clone();
+        result.sub_from(&self.data);
+        result
+    }
+
+    /// Multiply two Fermat numbers mod (2^shift + 1).
+    #[must_use]
+    pub fn fermat_mul(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        result.mul_assign_with(other, &SimpleAllocator);
+        result
+    }
+}
+
+impl<S: Limbs> FermatNum<S> {
+    /// The shift: the modulus is `2^shift + 1`.
+    #[must_use]
+    pub fn shift(&self) -> usize {
+        self.shift
+    }
+
+    /// The reduced value as little-endian u64 limbs.
+    #[must_use]
+    pub fn limbs(&self) -> &[u64] {
+        &self.data
     }
 
     /// Convert back to `BigUint`.
     #[must_use]
     pub fn to_biguint(&self) -> BigUint {
-        let bytes: Vec<u8> = self
-            .data
-            .iter()
-            .flat_map(|&limb| limb.to_le_bytes())
-            .collect();
-        BigUint::from_bytes_le(&bytes)
+        let mut value = BigUint::ZERO;
+        self.load_into(&mut value);
+        value
+    }
+
+    /// Write the value into `dst`, reusing its allocation.
+    pub fn load_into(&self, dst: &mut BigUint) {
+        load_limbs(&self.data, dst);
     }
 
     /// Get the Fermat modulus: 2^shift + 1.
@@ -64,6 +157,10 @@ impl FermatNum {
     /// can be folded down by subtracting them from the low `shift` bits.
     /// This avoids any `BigUint` conversion.
     pub fn normalize(&mut self) {
+        with_scratch(|arena| self.normalize_in(arena));
+    }
+
+    fn normalize_in(&mut self, arena: &FFTBumpAllocator) {
         let shift = self.shift;
         let limb_idx = shift / 64;
         let bit_idx = shift % 64;
@@ -101,20 +198,19 @@ impl FermatNum {
             }
 
             // Extract high = value >> shift
-            let mut high = Vec::with_capacity(num_limbs - limb_idx);
+            let high = arena.alloc_slice(num_limbs - limb_idx);
             if bit_idx == 0 {
-                for i in limb_idx..num_limbs {
-                    high.push(self.data[i]);
-                }
+                high.copy_from_slice(&self.data[limb_idx..]);
             } else {
-                for i in limb_idx..num_limbs {
+                for (k, h) in high.iter_mut().enumerate() {
+                    let i = limb_idx + k;
                     let lo = self.data[i] >> bit_idx;
                     let hi = if i + 1 < num_limbs {
                         self.data[i + 1] << (64 - bit_idx)
                     } else {
                         0
                     };
-                    high.push(lo | hi);
+                    *h = lo | hi;
                 }
             }
 
@@ -161,7 +257,7 @@ impl FermatNum {
             // Since value >= modulus and value < 2 * modulus at this point,
             // a single subtraction gives the correct result.
             let mut borrow = 1u64; // subtract 1
-            for limb in &mut self.data {
+            for limb in self.data.iter_mut() {
                 let (diff, b) = limb.overflowing_sub(borrow);
                 *limb = diff;
                 borrow = u64::from(b);
@@ -184,71 +280,54 @@ impl FermatNum {
         }
     }
 
-    /// Add two Fermat numbers mod (2^shift + 1).
-    /// Uses in-place limb arithmetic instead of `BigUint` conversion.
-    #[must_use]
-    pub fn add(&self, other: &Self) -> Self {
+    /// Add `other` to self mod (2^shift + 1), in place.
+    pub fn add_assign<T: Limbs>(&mut self, other: &FermatNum<T>) {
         assert_eq!(self.shift, other.shift);
-        let n = self.data.len();
-        let mut result = Self {
-            data: vec![0u64; n],
-            shift: self.shift,
-        };
 
         // Limb-level addition with carry
         let mut carry = 0u64;
-        for i in 0..n {
-            let a = self.data[i];
-            let b = if i < other.data.len() {
-                other.data[i]
-            } else {
-                0
-            };
-            let (sum1, c1) = a.overflowing_add(b);
+        for (i, limb) in self.data.iter_mut().enumerate() {
+            let b = other.data.get(i).copied().unwrap_or(0);
+            let (sum1, c1) = limb.overflowing_add(b);
             let (sum2, c2) = sum1.overflowing_add(carry);
-            result.data[i] = sum2;
+            *limb = sum2;
             carry = u64::from(c1) + u64::from(c2);
         }
 
         // If carry or result >= modulus, reduce
-        if carry > 0 || result.ge_modulus() {
-            result.normalize();
+        if carry > 0 || self.ge_modulus() {
+            self.normalize();
         }
-        result
     }
 
-    /// Subtract other from self mod (2^shift + 1).
-    /// Uses in-place limb arithmetic instead of `BigUint` conversion.
-    #[must_use]
-    pub fn sub(&self, other: &Self) -> Self {
-        assert_eq!(self.shift, other.shift);
-        let n = self.data.len();
-        let mut result = Self {
-            data: vec![0u64; n],
-            shift: self.shift,
-        };
-
+    /// Set self to `minuend - self` mod (2^shift + 1), in place.
+    fn sub_from(&mut self, minuend: &[u64]) {
         // Limb-level subtraction with borrow
         let mut borrow = 0u64;
-        for i in 0..n {
-            let a = self.data[i];
-            let b = if i < other.data.len() {
-                other.data[i]
-            } else {
-                0
-            };
-            let (diff1, b1) = a.overflowing_sub(b);
+        for (i, limb) in self.data.iter_mut().enumerate() {
+            let a = minuend.get(i).copied().unwrap_or(0);
+            let (diff1, b1) = a.overflowing_sub(*limb);
             let (diff2, b2) = diff1.overflowing_sub(borrow);
-            result.data[i] = diff2;
+            *limb = diff2;
             borrow = u64::from(b1) + u64::from(b2);
         }
 
         if borrow > 0 {
             // Result is negative: add modulus (2^shift + 1)
-            result.add_modulus();
+            self.add_modulus();
         }
+    }
 
-        result
+    /// Butterfly `(u, t) -> (u + 2^s t, u - 2^s t)` mod (2^shift + 1),
+    /// in place, with `upper` as `u` and `lower` as `t`.
+    pub fn butterfly(upper: &mut Self, lower: &mut Self, s: usize) {
+        lower.shift_left(s);
+        with_scratch(|arena| {
+            let u = arena.alloc_slice(upper.data.len());
+            u.copy_from_slice(&upper.data);
+            upper.add_assign(lower);
+            lower.sub_from(u);
+        });
     }
 
     /// Check if self >= modulus (2^shift + 1).
@@ -310,7 +389,7 @@ impl FermatNum {
     fn add_modulus(&mut self) {
         // Add 1
         let mut carry = 1u64;
-        for limb in &mut self.data {
+        for limb in self.data.iter_mut() {
             let (sum, c) = limb.overflowing_add(carry);
             *limb = sum;
             carry = u64::from(c);
@@ -335,15 +414,80 @@ impl FermatNum {
         }
     }
 
-    /// Multiply two Fermat numbers mod (2^shift + 1).
-    #[must_use]
-    pub fn fermat_mul(&self, other: &Self) -> Self {
+    /// Multiply self by `other` mod (2^shift + 1), in place.
+    ///
+    /// The `BigUint` operands and product are taken from and returned to
+    /// `alloc`, so a pooled allocator reuses them across coefficients.
+    pub(crate) fn mul_assign_with<T: Limbs>(
+        &mut self,
+        other: &FermatNum<T>,
+        alloc: &dyn TempAllocator,
+    ) {
         assert_eq!(self.shift, other.shift);
-        let modulus = self.modulus();
-        let a = self.to_biguint();
-        let b = other.to_biguint();
-        let product = (a * b) % modulus;
-        Self::from_biguint(&product, self.shift)
+        let mut a = alloc.alloc(self.shift + 1);
+        let mut b = alloc.alloc(self.shift + 1);
+        self.load_into(&mut a);
+        other.load_into(&mut b);
+        let product = &a * &b;
+        self.reduce_from(&product);
+        alloc.free(a);
+        alloc.free(b);
+        alloc.free(product);
+    }
+
+    /// Square self mod (2^shift + 1), in place, with temporaries from
+    /// `alloc` as in [`Self::mul_assign_with`].
+    pub(crate) fn square_with(&mut self, alloc: &dyn TempAllocator) {
+        let mut a = alloc.alloc(self.shift + 1);
+        self.load_into(&mut a);
+        let product = &a * &a;
+        self.reduce_from(&product);
+        alloc.free(a);
+        alloc.free(product);
+    }
+
+    /// Set self to `product` mod (2^shift + 1), for a product of two
+    /// reduced values (at most `2^(2 * shift)`).
+    ///
+    /// Folds the bits above `shift` with `2^shift ≡ -1` instead of dividing.
+    fn reduce_from(&mut self, product: &BigUint) {
+        with_scratch(|arena| {
+            let wide = arena.alloc_slice(2 * self.data.len());
+            for (w, digit) in wide.iter_mut().zip(product.iter_u64_digits()) {
+                *w = digit;
+            }
+            let limb_idx = self.shift / 64;
+            let bit_idx = self.shift % 64;
+            let high_limb = |i: usize| {
+                let lo = wide.get(limb_idx + i).copied().unwrap_or(0);
+                if bit_idx == 0 {
+                    lo
+                } else {
+                    let hi = wide.get(limb_idx + i + 1).copied().unwrap_or(0);
+                    (lo >> bit_idx) | (hi << (64 - bit_idx))
+                }
+            };
+
+            // low = product mod 2^shift
+            self.data.fill(0);
+            self.data[..limb_idx].copy_from_slice(&wide[..limb_idx]);
+            if bit_idx != 0 {
+                self.data[limb_idx] = wide[limb_idx] & ((1u64 << bit_idx) - 1);
+            }
+
+            // self = low - high, high = product >> shift <= 2^shift
+            let mut borrow = 0u64;
+            for (i, limb) in self.data.iter_mut().enumerate() {
+                let (d1, b1) = limb.overflowing_sub(high_limb(i));
+                let (d2, b2) = d1.overflowing_sub(borrow);
+                *limb = d2;
+                borrow = u64::from(b1) + u64::from(b2);
+            }
+            if borrow > 0 {
+                self.add_modulus();
+            }
+        });
+        self.normalize();
     }
 
     /// Multiply by 2^s mod (2^shift + 1).
@@ -369,15 +513,21 @@ impl FermatNum {
             }
         }
 
-        // Now 0 < s < shift. After shifting, the value has < 2*shift+1
-        // bits. One fold (high = value >> shift, result = low - high)
-        // yields at most shift+1 bits, fitting in num_limbs.
+        with_scratch(|arena| self.shift_left_folded(s, arena));
+    }
+
+    /// Shift by `0 < s < shift` and fold the overflow back, with scratch
+    /// limbs from `arena`.
+    fn shift_left_folded(&mut self, s: usize, arena: &FFTBumpAllocator) {
+        // After shifting, the value has < 2*shift+1 bits. One fold
+        // (high = value >> shift, result = low - high) yields at most
+        // shift+1 bits, fitting in num_limbs.
         let num_limbs = self.data.len();
         let word_shift = s / 64;
         let bit_shift = s % 64;
 
         let new_len = num_limbs + word_shift + 1;
-        let mut new_data = vec![0u64; new_len];
+        let new_data = arena.alloc_slice(new_len);
 
         if bit_shift == 0 {
             new_data[word_shift..word_shift + num_limbs].copy_from_slice(&self.data[..num_limbs]);
@@ -396,7 +546,7 @@ impl FermatNum {
         // Extract high = new_data >> shift (at most shift+1 bits since
         // s < shift and original value < 2^(shift+1)).
         let high_count = new_len - limb_idx;
-        let mut high = vec![0u64; high_count];
+        let high = arena.alloc_slice(high_count);
         if bit_idx == 0 {
             high[..high_count].copy_from_slice(&new_data[limb_idx..limb_idx + high_count]);
         } else {
@@ -440,7 +590,7 @@ impl FermatNum {
 
         // After one fold with s < shift, value fits in num_limbs.
         // normalize handles the final ge_modulus check.
-        self.normalize();
+        self.normalize_in(arena);
     }
 
     /// Negate self mod (2^shift + 1): self = modulus - self.
@@ -448,20 +598,17 @@ impl FermatNum {
         if self.data.iter().all(|&x| x == 0) {
             return;
         }
-        let num_limbs = self.data.len();
         let limb_idx = self.shift / 64;
         let bit_idx = self.shift % 64;
 
-        // Build modulus = 2^shift + 1 in limb form.
-        let mut modulus_data = vec![0u64; num_limbs];
-        modulus_data[0] = 1;
-        if limb_idx < num_limbs {
-            modulus_data[limb_idx] |= 1u64 << bit_idx;
-        }
-
-        // self.data = modulus_data - self.data
+        // self.data = (2^shift + 1) - self.data, with the modulus limbs
+        // generated on the fly.
         let mut borrow = 0u64;
-        for (m, d) in modulus_data.iter().zip(self.data.iter_mut()) {
+        for (i, d) in self.data.iter_mut().enumerate() {
+            let mut m = u64::from(i == 0);
+            if i == limb_idx {
+                m |= 1u64 << bit_idx;
+            }
             let (diff1, b1) = m.overflowing_sub(*d);
             let (diff2, b2) = diff1.overflowing_sub(borrow);
             *d = diff2;
@@ -483,7 +630,6 @@ impl FermatNum {
 
     /// Check if this is zero.
     #[must_use]
-    #[allow(dead_code)] // Public API for future callers
     pub fn is_zero(&self) -> bool {
         self.data.iter().all(|&x| x == 0)
     }
@@ -491,11 +637,6 @@ impl FermatNum {
 
 /// Select optimal FFT parameters for multiplying two numbers.
 #[must_use]
-#[allow(
-    clippy::cast_possible_truncation,
-    clippy::cast_sign_loss,
-    clippy::cast_precision_loss
-)]
 pub fn select_fft_params(a_bits: usize, b_bits: usize) -> (usize, usize, usize) {
     let max_bits = a_bits.max(b_bits);
 
@@ -509,6 +650,22 @@ pub fn select_fft_params(a_bits: usize, b_bits: usize) -> (usize, usize, usize)
         4096
     };
 
+    fft_params_for_piece(a_bits, b_bits, piece_bits)
+}
+
+/// FFT parameters `(piece_bits, n, fermat_shift)` for a product split into
+/// `piece_bits`-bit coefficients.
+#[must_use]
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+pub fn fft_params_for_piece(
+    a_bits: usize,
+    b_bits: usize,
+    piece_bits: usize,
+) -> (usize, usize, usize) {
     let n_a = a_bits.div_ceil(piece_bits);
     let n_b = b_bits.div_ceil(piece_bits);
     let n = (n_a + n_b).max(4).next_power_of_two();
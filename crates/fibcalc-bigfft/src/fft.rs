@@ -3,24 +3,27 @@
 //! Routes to the NTT-based multiplication pipeline for large operands,
 //! and falls back to standard num-bigint multiplication for small ones.
 
-use std::cell::RefCell;
+use std::str::FromStr;
 use std::sync::LazyLock;
 
 use num_bigint::BigUint;
 use num_traits::Zero;
 
 use crate::allocator::PoolAllocator;
-use crate::bump::FFTBumpAllocator;
-use crate::fermat::select_fft_params;
+use crate::bump::with_coefficients;
+use crate::fermat::{load_limbs, select_fft_params, FermatNum, Limbs};
 use crate::fft_core::{fft_forward, fft_inverse};
-use crate::fft_poly::{pointwise_multiply, reassemble, Poly};
+use crate::fft_poly::{
+    add_reassembled, copy_in, pointwise_add, pointwise_multiply, pointwise_square, reassemble,
+    reassemble_into, split_in, Poly,
+};
+use crate::fft_recursion::{fft_inverse_recursive, fft_recursive};
+use crate::memory_est::{transforms_fit, FFTLayout};
+use crate::ntt::{ntt_multiply_to, ntt_square_to, ntt_sum_of_products_to};
 use crate::pool::PoolStats;
 
-thread_local! {
-    static FFT_BUMP: RefCell<FFTBumpAllocator> = RefCell::new(FFTBumpAllocator::with_capacity(1024 * 1024));
-}
-
-/// Global pool allocator for `BigUint` temporaries in FFT operations.
+/// Global pool allocator for `BigUint` temporaries in FFT operations:
+/// coefficient product operands and reassembled results.
 static POOL_ALLOCATOR: LazyLock<PoolAllocator> = LazyLock::new(PoolAllocator::new);
 
 /// Return a snapshot of the pool allocator statistics.
@@ -34,125 +37,849 @@ pub fn warm_global_pool(n: u64) {
     POOL_ALLOCATOR.warm(n);
 }
 
-/// Threshold in bits above which FFT multiplication is used.
-const FFT_BIT_THRESHOLD: usize = 10_000;
+/// Multiplication algorithm behind the FFT entry points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum FFTBackend {
+    /// Fermat below [`FFTConfig::ntt_threshold_bits`], NTT at or above it.
+    #[default]
+    Auto,
+    /// Schönhage-Strassen NTT over Fermat rings.
+    Fermat,
+    /// Three-prime NTT with 64-bit Montgomery arithmetic and CRT.
+    Ntt,
+}
+
+impl FFTBackend {
+    /// Lowercase name, as accepted by [`FromStr`].
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Fermat => "fermat",
+            Self::Ntt => "ntt",
+        }
+    }
+}
+
+impl FromStr for FFTBackend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "fermat" => Ok(Self::Fermat),
+            "ntt" => Ok(Self::Ntt),
+            other => Err(format!(
+                "unknown FFT backend '{other}' (expected auto, fermat or ntt)"
+            )),
+        }
+    }
+}
+
+/// Default operand size at which [`FFTBackend::Auto`] switches to NTT.
+///
+/// `usize::MAX` keeps Auto on the Fermat backend until calibration measures
+/// the crossover on this machine.
+pub const DEFAULT_NTT_THRESHOLD_BITS: usize = usize::MAX;
+
+/// Runtime configuration for the FFT multiplication entry points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FFTConfig {
+    /// Operand size in bits at which [`mul_with`] and [`sqr_with`] switch
+    /// from num-bigint multiplication to FFT. `0` always uses FFT.
+    pub threshold_bits: usize,
+    /// Operand size in bits at which the transforms and pointwise products
+    /// are split across the rayon pool. `usize::MAX` keeps FFT sequential.
+    pub parallel_threshold_bits: usize,
+    /// FFT algorithm used once `threshold_bits` is reached.
+    pub backend: FFTBackend,
+    /// Operand size in bits at which [`FFTBackend::Auto`] switches from
+    /// Fermat to NTT. `usize::MAX` keeps Auto on Fermat.
+    pub ntt_threshold_bits: usize,
+    /// Working-memory budget of one FFT product in bytes (`usize::MAX` =
+    /// unlimited). Over budget, products use smaller plans or segments.
+    pub memory_limit_bytes: usize,
+}
+
+impl FFTConfig {
+    /// Threshold used by [`mul`] and [`sqr`] when no configuration is given.
+    pub const DEFAULT_THRESHOLD_BITS: usize = 10_000;
+
+    /// Default operand size at which FFT multiplication goes parallel.
+    pub const DEFAULT_PARALLEL_THRESHOLD_BITS: usize = 5_000_000;
+
+    /// Work per sequential leaf of the parallel transform, in coefficient bits.
+    const PARALLEL_LEAF_BITS: usize = 1 << 20;
+
+    /// Create a configuration that switches to FFT at `threshold_bits`.
+    #[must_use]
+    pub const fn with_threshold(threshold_bits: usize) -> Self {
+        Self {
+            threshold_bits,
+            parallel_threshold_bits: Self::DEFAULT_PARALLEL_THRESHOLD_BITS,
+            backend: FFTBackend::Auto,
+            ntt_threshold_bits: DEFAULT_NTT_THRESHOLD_BITS,
+            memory_limit_bytes: usize::MAX,
+        }
+    }
+
+    /// Return this configuration with FFT products kept within `bytes`.
+    #[must_use]
+    pub const fn with_memory_limit(mut self, bytes: usize) -> Self {
+        self.memory_limit_bytes = bytes;
+        self
+    }
+
+    /// Return this configuration using `backend`.
+    #[must_use]
+    pub const fn with_backend(mut self, backend: FFTBackend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Return this configuration with [`FFTBackend::Auto`] switching to NTT
+    /// at `bits`.
+    #[must_use]
+    pub const fn with_ntt_threshold(mut self, bits: usize) -> Self {
+        self.ntt_threshold_bits = bits;
+        self
+    }
+
+    /// Concrete backend used for operands of `bits` bits.
+    #[must_use]
+    pub const fn backend_for(&self, bits: usize) -> FFTBackend {
+        match self.backend {
+            FFTBackend::Auto if bits >= self.ntt_threshold_bits => FFTBackend::Ntt,
+            FFTBackend::Auto => FFTBackend::Fermat,
+            other => other,
+        }
+    }
+
+    /// Return this configuration with FFT going parallel at `bits`.
+    #[must_use]
+    pub const fn with_parallel_threshold(mut self, bits: usize) -> Self {
+        self.parallel_threshold_bits = bits;
+        self
+    }
+
+    /// Whether operands of `bits` bits are multiplied with FFT.
+    #[must_use]
+    pub const fn uses_fft(&self, bits: usize) -> bool {
+        bits >= self.threshold_bits
+    }
+
+    /// Whether FFT products of `bits`-bit operands run in parallel.
+    #[must_use]
+    pub const fn uses_parallel(&self, bits: usize) -> bool {
+        bits >= self.parallel_threshold_bits
+    }
+
+    /// Largest sub-transform (in coefficients) computed sequentially by the
+    /// parallel transform, or `None` for a sequential transform.
+    const fn parallel_leaf(&self, bits: usize, fermat_shift: usize) -> Option<usize> {
+        if !self.uses_parallel(bits) {
+            return None;
+        }
+        let leaf = Self::PARALLEL_LEAF_BITS / fermat_shift;
+        Some(if leaf < 2 { 2 } else { leaf })
+    }
+}
+
+impl Default for FFTConfig {
+    fn default() -> Self {
+        Self::with_threshold(Self::DEFAULT_THRESHOLD_BITS)
+    }
+}
+
+/// Transform shape shared by every operand of one FFT product.
+///
+/// Operands transformed under the same plan can be multiplied pointwise,
+/// so a plan sized for the largest product of a computation lets each
+/// operand be transformed once and reused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FFTPlan {
+    /// Bits of the operand packed into each coefficient.
+    pub piece_bits: usize,
+    /// Number of coefficients (a power of two).
+    pub len: usize,
+    /// Coefficients live in Z/(2^`fermat_shift` + 1).
+    pub fermat_shift: usize,
+}
+
+impl FFTPlan {
+    /// Plan for the product of an `a_bits`-bit and a `b_bits`-bit operand.
+    #[must_use]
+    pub fn for_product(a_bits: usize, b_bits: usize) -> Self {
+        let (piece_bits, len, fermat_shift) = select_fft_params(a_bits, b_bits);
+        Self {
+            piece_bits,
+            len,
+            fermat_shift,
+        }
+    }
+
+    /// Whether a product of `a_bits` and `b_bits` bits fits this plan
+    /// without wrapping around the cyclic convolution.
+    #[must_use]
+    pub const fn fits(&self, a_bits: u64, b_bits: u64) -> bool {
+        let piece = self.piece_bits as u64;
+        let pieces = a_bits.div_ceil(piece) + b_bits.div_ceil(piece);
+        pieces <= self.len as u64
+    }
+
+    /// How many fitting products can be summed in the transform domain
+    /// before the coefficients wrap around the Fermat modulus.
+    ///
+    /// A product coefficient is below `2^(2 * piece_bits) * len / 2`, so
+    /// sums of up to `2^(fermat_shift + 1 - 2 * piece_bits - log2(len))`
+    /// products stay below `2^fermat_shift`.
+    #[must_use]
+    pub const fn max_terms(&self) -> usize {
+        let used = 2 * self.piece_bits + self.len.trailing_zeros() as usize;
+        let headroom = (self.fermat_shift + 1).saturating_sub(used);
+        if headroom >= usize::BITS as usize {
+            usize::MAX
+        } else {
+            1 << headroom
+        }
+    }
+}
+
+/// An operand in the FFT domain, ready to be multiplied by other operands
+/// transformed under the same [`FFTPlan`].
+///
+/// Transforms are always over Fermat rings; `config.backend` is ignored.
+#[derive(Debug, Clone)]
+pub struct TransformedOperand {
+    plan: FFTPlan,
+    bits: u64,
+    coeffs: Vec<FermatNum>,
+}
+
+impl TransformedOperand {
+    /// Split `value` into coefficients and run the forward transform.
+    ///
+    /// The transform runs in parallel when `value` reaches
+    /// `config.parallel_threshold_bits`.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn new(value: &BigUint, plan: FFTPlan, config: &FFTConfig) -> Self {
+        let bits = value.bits();
+        let leaf = config.parallel_leaf(bits as usize, plan.fermat_shift);
+        let mut coeffs =
+            Poly::from_biguint(value, plan.len, plan.piece_bits, plan.fermat_shift).coeffs;
+        forward(&mut coeffs, plan.fermat_shift, leaf);
+        Self { plan, bits, coeffs }
+    }
+
+    /// The plan this operand was transformed under.
+    #[must_use]
+    pub const fn plan(&self) -> FFTPlan {
+        self.plan
+    }
+
+    /// Bit length of the original operand.
+    #[must_use]
+    pub const fn bits(&self) -> u64 {
+        self.bits
+    }
+
+    /// Multiply by another transformed operand.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the plans differ or the product does not fit the plan.
+    #[must_use]
+    pub fn mul(&self, other: &Self, config: &FFTConfig) -> BigUint {
+        let mut product = BigUint::ZERO;
+        self.mul_to(&mut product, other, config);
+        product
+    }
+
+    /// [`mul`](Self::mul) into `dst`, reassembling into its allocation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the plans differ or the product does not fit the plan.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn mul_to(&self, dst: &mut BigUint, other: &Self, config: &FFTConfig) {
+        assert_eq!(self.plan, other.plan, "operands use different FFT plans");
+        assert!(
+            self.plan.fits(self.bits, other.bits),
+            "product of {} and {} bits does not fit the FFT plan",
+            self.bits,
+            other.bits
+        );
+        let shift = self.plan.fermat_shift;
+        let leaf = config.parallel_leaf(self.bits.max(other.bits) as usize, shift);
+        with_coefficients(config.memory_limit_bytes, |arena| {
+            let coeffs = copy_in(&self.coeffs, arena);
+            pointwise_multiply(coeffs, &other.coeffs, leaf.is_some(), &*POOL_ALLOCATOR);
+            inverse(coeffs, shift, leaf);
+            reassemble_into(coeffs, self.plan.piece_bits, dst);
+        });
+    }
+
+    /// Add `self * other`, shifted left by `bit_offset` bits, into the
+    /// limbs of `acc`; `other` being `self` squares it.
+    ///
+    /// `acc` needs `bit_offset / 64` limbs plus those of the plan's
+    /// reassembly accumulator.
+    #[allow(clippy::cast_possible_truncation)]
+    fn mul_add_shifted(
+        &self,
+        other: &Self,
+        acc: &mut [u64],
+        bit_offset: usize,
+        config: &FFTConfig,
+    ) {
+        assert_eq!(self.plan, other.plan, "operands use different FFT plans");
+        assert!(
+            self.plan.fits(self.bits, other.bits),
+            "product of {} and {} bits does not fit the FFT plan",
+            self.bits,
+            other.bits
+        );
+        let shift = self.plan.fermat_shift;
+        let leaf = config.parallel_leaf(self.bits.max(other.bits) as usize, shift);
+        with_coefficients(config.memory_limit_bytes, |arena| {
+            let coeffs = copy_in(&self.coeffs, arena);
+            if std::ptr::eq(self, other) {
+                pointwise_square(coeffs, leaf.is_some(), &*POOL_ALLOCATOR);
+            } else {
+                pointwise_multiply(coeffs, &other.coeffs, leaf.is_some(), &*POOL_ALLOCATOR);
+            }
+            inverse(coeffs, shift, leaf);
+            add_reassembled(acc, coeffs, self.plan.piece_bits, bit_offset);
+        });
+    }
+
+    /// Square this operand.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the square does not fit the plan.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn sqr(&self, config: &FFTConfig) -> BigUint {
+        assert!(
+            self.plan.fits(self.bits, self.bits),
+            "square of {} bits does not fit the FFT plan",
+            self.bits
+        );
+        let shift = self.plan.fermat_shift;
+        let leaf = config.parallel_leaf(self.bits as usize, shift);
+        with_coefficients(config.memory_limit_bytes, |arena| {
+            let coeffs = copy_in(&self.coeffs, arena);
+            pointwise_square(coeffs, leaf.is_some(), &*POOL_ALLOCATOR);
+            inverse(coeffs, shift, leaf);
+            reassemble(coeffs, self.plan.piece_bits, &*POOL_ALLOCATOR)
+        })
+    }
+
+    /// Sum of the products of transformed operand pairs.
+    ///
+    /// Products are added in the transform domain, so only one inverse
+    /// transform runs per [`FFTPlan::max_terms`] pairs. A pair whose two
+    /// sides are the same operand is squared.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the operands use different plans or a product does not
+    /// fit the plan.
+    #[must_use]
+    pub fn sum_of_products(terms: &[(&Self, &Self)], config: &FFTConfig) -> BigUint {
+        let mut sum = BigUint::ZERO;
+        Self::sum_of_products_to(&mut sum, terms, config);
+        sum
+    }
+
+    /// [`sum_of_products`](Self::sum_of_products) into `dst`, reassembling
+    /// into its allocation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the operands use different plans or a product does not
+    /// fit the plan.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn sum_of_products_to(dst: &mut BigUint, terms: &[(&Self, &Self)], config: &FFTConfig) {
+        let Some((first, _)) = terms.first() else {
+            dst.set_zero();
+            return;
+        };
+        let plan = first.plan;
+        let bits = terms
+            .iter()
+            .map(|(a, b)| a.bits.max(b.bits))
+            .max()
+            .unwrap_or(0);
+        let total = std::mem::take(dst);
+        *dst = with_coefficients(config.memory_limit_bytes, |arena| {
+            let mut sum = ProductSum::new(plan, config, bits as usize, total);
+            for &(a, b) in terms {
+                assert!(
+                    a.plan == plan && b.plan == plan,
+                    "operands use different FFT plans"
+                );
+                assert!(
+                    plan.fits(a.bits, b.bits),
+                    "product of {} and {} bits does not fit the FFT plan",
+                    a.bits,
+                    b.bits
+                );
+                let coeffs = copy_in(&a.coeffs, arena);
+                if std::ptr::eq(a, b) {
+                    pointwise_square(coeffs, sum.parallel(), &*POOL_ALLOCATOR);
+                } else {
+                    pointwise_multiply(coeffs, &b.coeffs, sum.parallel(), &*POOL_ALLOCATOR);
+                }
+                sum.add(coeffs);
+            }
+            sum.finish()
+        });
+    }
+}
+
+/// Transform coefficients carved from the coefficient arena.
+type ArenaCoeffs<'a> = &'a mut [FermatNum<&'a mut [u64]>];
+
+/// Pointwise products accumulated in the transform domain, with one
+/// inverse transform per [`FFTPlan::max_terms`] products.
+struct ProductSum<'a> {
+    plan: FFTPlan,
+    leaf: Option<usize>,
+    acc: Option<ArenaCoeffs<'a>>,
+    terms: usize,
+    /// Sum of the flushed products; holds a stale value until `flushed`.
+    total: BigUint,
+    flushed: bool,
+}
+
+impl<'a> ProductSum<'a> {
+    /// Start a sum that reassembles into the allocation of `dst`.
+    fn new(plan: FFTPlan, config: &FFTConfig, bits: usize, dst: BigUint) -> Self {
+        Self {
+            plan,
+            leaf: config.parallel_leaf(bits, plan.fermat_shift),
+            acc: None,
+            terms: 0,
+            total: dst,
+            flushed: false,
+        }
+    }
+
+    const fn parallel(&self) -> bool {
+        self.leaf.is_some()
+    }
+
+    /// Add one pointwise product.
+    fn add(&mut self, product: ArenaCoeffs<'a>) {
+        if self.terms == self.plan.max_terms() {
+            self.flush();
+        }
+        match &mut self.acc {
+            Some(acc) => pointwise_add(acc, product, self.leaf.is_some()),
+            None => self.acc = Some(product),
+        }
+        self.terms += 1;
+    }
+
+    /// Inverse-transform the accumulated products into `total`.
+    fn flush(&mut self) {
+        if let Some(acc) = self.acc.take() {
+            inverse(acc, self.plan.fermat_shift, self.leaf);
+            if self.flushed {
+                self.total += reassemble(acc, self.plan.piece_bits, &*POOL_ALLOCATOR);
+            } else {
+                reassemble_into(acc, self.plan.piece_bits, &mut self.total);
+                self.flushed = true;
+            }
+        }
+        self.terms = 0;
+    }
+
+    fn finish(mut self) -> BigUint {
+        self.flush();
+        if !self.flushed {
+            self.total.set_zero();
+        }
+        self.total
+    }
+}
 
 /// Multiply two `BigUints`, using FFT for large operands.
 #[must_use]
-#[allow(clippy::cast_possible_truncation)]
 pub fn mul(a: &BigUint, b: &BigUint) -> BigUint {
-    let max_bits = a.bits().max(b.bits()) as usize;
-    if max_bits >= FFT_BIT_THRESHOLD {
-        fft_multiply(a, b)
-    } else {
-        a * b
-    }
+    mul_with(a, b, &FFTConfig::default())
+}
+
+/// Multiply two `BigUints`, using FFT once `config.threshold_bits` is reached.
+#[must_use]
+pub fn mul_with(a: &BigUint, b: &BigUint, config: &FFTConfig) -> BigUint {
+    let mut product = BigUint::ZERO;
+    mul_to_with(&mut product, a, b, config);
+    product
 }
 
 /// Square a `BigUint`, using FFT with transform reuse for large operands.
 #[must_use]
-#[allow(clippy::cast_possible_truncation)]
 pub fn sqr(a: &BigUint) -> BigUint {
-    let bits = a.bits() as usize;
-    if bits >= FFT_BIT_THRESHOLD {
-        fft_square(a)
-    } else {
-        a * a
+    sqr_with(a, &FFTConfig::default())
+}
+
+/// Square a `BigUint`, using FFT once `config.threshold_bits` is reached.
+#[must_use]
+pub fn sqr_with(a: &BigUint, config: &FFTConfig) -> BigUint {
+    let mut square = BigUint::ZERO;
+    sqr_to_with(&mut square, a, config);
+    square
+}
+
+/// Sum of the products of `terms`, using FFT for large operands.
+#[must_use]
+pub fn sum_of_products(terms: &[(&BigUint, &BigUint)]) -> BigUint {
+    sum_of_products_with(terms, &FFTConfig::default())
+}
+
+/// Sum of the products of `terms`, using FFT once `config.threshold_bits`
+/// is reached.
+///
+/// On Fermat and NTT layouts the products are added in the transform
+/// domain, with one inverse transform per output (per
+/// [`FFTPlan::max_terms`] terms on Fermat), and a term whose two sides are
+/// the same value is squared with one forward transform. Segmented
+/// layouts sum separate products.
+#[must_use]
+pub fn sum_of_products_with(terms: &[(&BigUint, &BigUint)], config: &FFTConfig) -> BigUint {
+    let mut sum = BigUint::ZERO;
+    sum_of_products_to_with(&mut sum, terms, config);
+    sum
+}
+
+/// [`sum_of_products_with`] into `dst`, reusing its allocation for the
+/// result on Fermat and NTT layouts.
+#[allow(clippy::cast_possible_truncation)]
+pub fn sum_of_products_to_with(
+    dst: &mut BigUint,
+    terms: &[(&BigUint, &BigUint)],
+    config: &FFTConfig,
+) {
+    // Every term fits the plan of the largest larger and smaller operands.
+    let (mut hi, mut lo) = (0, 0);
+    for &(a, b) in terms {
+        let (a_bits, b_bits) = (a.bits() as usize, b.bits() as usize);
+        hi = hi.max(a_bits.max(b_bits));
+        lo = lo.max(a_bits.min(b_bits));
+    }
+    if lo == 0 || !config.uses_fft(hi) {
+        *dst = terms.iter().map(|&(a, b)| a * b).sum();
+        return;
     }
+    let plan = match FFTLayout::for_product(hi, lo, config) {
+        FFTLayout::Whole(plan) => plan,
+        FFTLayout::Ntt { .. } => {
+            ntt_sum_of_products_to(dst, terms, config.uses_parallel(hi));
+            return;
+        }
+        FFTLayout::Segmented { .. } => {
+            dst.set_zero();
+            for &(a, b) in terms {
+                if std::ptr::eq(a, b) {
+                    *dst += sqr_with(a, config);
+                } else {
+                    *dst += mul_with(a, b, config);
+                }
+            }
+            return;
+        }
+    };
+
+    let total = std::mem::take(dst);
+    *dst = with_coefficients(config.memory_limit_bytes, |arena| {
+        let mut sum = ProductSum::new(plan, config, hi, total);
+        for &(a, b) in terms {
+            if a.is_zero() || b.is_zero() {
+                continue;
+            }
+            let coeffs = split_in(a, plan.len, plan.piece_bits, plan.fermat_shift, arena);
+            forward(coeffs, plan.fermat_shift, sum.leaf);
+            if std::ptr::eq(a, b) {
+                pointwise_square(coeffs, sum.parallel(), &*POOL_ALLOCATOR);
+            } else {
+                let other = split_in(b, plan.len, plan.piece_bits, plan.fermat_shift, arena);
+                forward(other, plan.fermat_shift, sum.leaf);
+                pointwise_multiply(coeffs, other, sum.parallel(), &*POOL_ALLOCATOR);
+            }
+            sum.add(coeffs);
+        }
+        sum.finish()
+    });
 }
 
-/// Multiply and store result in destination.
+/// `a^2 + b^2`, using FFT for large operands.
+#[must_use]
+pub fn sqr_add_sqr(a: &BigUint, b: &BigUint) -> BigUint {
+    sqr_add_sqr_with(a, b, &FFTConfig::default())
+}
+
+/// `a^2 + b^2`, using FFT once `config.threshold_bits` is reached: two
+/// forward transforms and one inverse transform on Fermat layouts.
+#[must_use]
+pub fn sqr_add_sqr_with(a: &BigUint, b: &BigUint, config: &FFTConfig) -> BigUint {
+    sum_of_products_with(&[(a, a), (b, b)], config)
+}
+
+/// `a^2 + b^2` into `dst`; see [`sqr_add_sqr_with`] and
+/// [`sum_of_products_to_with`].
+pub fn sqr_add_sqr_to_with(dst: &mut BigUint, a: &BigUint, b: &BigUint, config: &FFTConfig) {
+    sum_of_products_to_with(dst, &[(a, a), (b, b)], config);
+}
+
+/// Multiply into `dst`, reusing its allocation for large operands.
 pub fn mul_to(dst: &mut BigUint, a: &BigUint, b: &BigUint) {
-    *dst = mul(a, b);
+    mul_to_with(dst, a, b, &FFTConfig::default());
 }
 
-/// Square and store result in destination.
+/// Multiply into `dst`, using FFT once `config.threshold_bits` is reached.
+///
+/// FFT products are reassembled directly into the existing allocation of
+/// `dst`, which is only grown when it is too small, so a destination
+/// reused across iterations stops allocating for the result once warm.
+/// Segmented layouts accumulate their block products into `dst`. Below
+/// the threshold the product comes from `num-bigint`, which always
+/// allocates a new value.
+#[allow(clippy::cast_possible_truncation)]
+pub fn mul_to_with(dst: &mut BigUint, a: &BigUint, b: &BigUint, config: &FFTConfig) {
+    let (a_bits, b_bits) = (a.bits() as usize, b.bits() as usize);
+    let max_bits = a_bits.max(b_bits);
+    if !config.uses_fft(max_bits) {
+        *dst = a * b;
+        return;
+    }
+    match FFTLayout::for_product(a_bits, b_bits, config) {
+        FFTLayout::Ntt { .. } => ntt_multiply_to(dst, a, b, config.uses_parallel(max_bits)),
+        FFTLayout::Whole(plan) => fft_multiply(dst, a, b, plan, config),
+        FFTLayout::Segmented { plan, segment_bits } => {
+            segmented_product(dst, a, Some(b), plan, segment_bits, config);
+        }
+    }
+}
+
+/// Square into `dst`, reusing its allocation for large operands.
 pub fn sqr_to(dst: &mut BigUint, a: &BigUint) {
-    *dst = sqr(a);
+    sqr_to_with(dst, a, &FFTConfig::default());
 }
 
-/// FFT multiplication core using Schönhage-Strassen NTT over Fermat ring.
+/// Square into `dst`, using FFT once `config.threshold_bits` is reached;
+/// see [`mul_to_with`].
 #[allow(clippy::cast_possible_truncation)]
-fn fft_multiply(a: &BigUint, b: &BigUint) -> BigUint {
-    if a.is_zero() || b.is_zero() {
-        return BigUint::ZERO;
+pub fn sqr_to_with(dst: &mut BigUint, a: &BigUint, config: &FFTConfig) {
+    let bits = a.bits() as usize;
+    if !config.uses_fft(bits) {
+        *dst = a * a;
+        return;
+    }
+    match FFTLayout::for_product(bits, bits, config) {
+        FFTLayout::Ntt { .. } => ntt_square_to(dst, a, config.uses_parallel(bits)),
+        FFTLayout::Whole(plan) => fft_square(dst, a, plan, config),
+        FFTLayout::Segmented { plan, segment_bits } => {
+            segmented_product(dst, a, None, plan, segment_bits, config);
+        }
     }
+}
 
-    FFT_BUMP.with(|bump| {
-        bump.borrow_mut().reset();
+/// Forward transform, parallel when `leaf` is set.
+fn forward<S: Limbs>(coeffs: &mut [FermatNum<S>], shift: usize, leaf: Option<usize>) {
+    match leaf {
+        Some(leaf) => fft_recursive(coeffs, shift, leaf),
+        None => fft_forward(coeffs, shift),
+    }
+}
 
-        let a_bits = a.bits() as usize;
-        let b_bits = b.bits() as usize;
-        let (piece_bits, n, fermat_shift) = select_fft_params(a_bits, b_bits);
+/// Inverse transform, parallel when `leaf` is set.
+fn inverse<S: Limbs>(coeffs: &mut [FermatNum<S>], shift: usize, leaf: Option<usize>) {
+    match leaf {
+        Some(leaf) => fft_inverse_recursive(coeffs, shift, leaf),
+        None => fft_inverse(coeffs, shift),
+    }
+}
 
-        // Split into polynomials
-        let poly_a = Poly::from_biguint(a, n, piece_bits, fermat_shift);
-        let poly_b = Poly::from_biguint(b, n, piece_bits, fermat_shift);
+/// FFT multiplication core using Schönhage-Strassen NTT over Fermat ring,
+/// writing the product into `dst`.
+#[allow(clippy::cast_possible_truncation)]
+fn fft_multiply(dst: &mut BigUint, a: &BigUint, b: &BigUint, plan: FFTPlan, config: &FFTConfig) {
+    if a.is_zero() || b.is_zero() {
+        dst.set_zero();
+        return;
+    }
 
-        let mut coeffs_a = poly_a.coeffs;
-        let mut coeffs_b = poly_b.coeffs;
+    let a_bits = a.bits() as usize;
+    let b_bits = b.bits() as usize;
+    let FFTPlan {
+        piece_bits,
+        len: n,
+        fermat_shift,
+    } = plan;
+    let leaf = config.parallel_leaf(a_bits.max(b_bits), fermat_shift);
 
-        // Forward NTT
-        fft_forward(&mut coeffs_a, fermat_shift);
-        fft_forward(&mut coeffs_b, fermat_shift);
+    with_coefficients(config.memory_limit_bytes, |arena| {
+        // Split into polynomials, with coefficients in the arena
+        let coeffs_a = split_in(a, n, piece_bits, fermat_shift, arena);
+        let coeffs_b = split_in(b, n, piece_bits, fermat_shift, arena);
 
-        // Pointwise multiply in transform domain
-        let mut result_coeffs = pointwise_multiply(&coeffs_a, &coeffs_b, fermat_shift);
+        // Forward NTT (both operands concurrently when parallel)
+        if leaf.is_some() {
+            rayon::join(
+                || forward(coeffs_a, fermat_shift, leaf),
+                || forward(coeffs_b, fermat_shift, leaf),
+            );
+        } else {
+            fft_forward(coeffs_a, fermat_shift);
+            fft_forward(coeffs_b, fermat_shift);
+        }
 
-        // Inverse NTT
-        fft_inverse(&mut result_coeffs, fermat_shift);
+        // Pointwise product into coeffs_a, then inverse NTT
+        pointwise_multiply(coeffs_a, coeffs_b, leaf.is_some(), &*POOL_ALLOCATOR);
+        inverse(coeffs_a, fermat_shift, leaf);
 
         // Reassemble from polynomial coefficients
-        let result = reassemble(&result_coeffs, piece_bits);
-
-        bump.borrow_mut().reset(); // free arena memory
-        result
-    })
+        reassemble_into(coeffs_a, piece_bits, dst);
+    });
 }
 
 /// FFT squaring with transform reuse optimization.
 ///
 /// Only performs one forward NTT instead of two.
 #[allow(clippy::cast_possible_truncation)]
-fn fft_square(a: &BigUint) -> BigUint {
+fn fft_square(dst: &mut BigUint, a: &BigUint, plan: FFTPlan, config: &FFTConfig) {
     if a.is_zero() {
-        return BigUint::ZERO;
+        dst.set_zero();
+        return;
     }
 
-    FFT_BUMP.with(|bump| {
-        bump.borrow_mut().reset();
+    let a_bits = a.bits() as usize;
+    let FFTPlan {
+        piece_bits,
+        len: n,
+        fermat_shift,
+    } = plan;
+    let leaf = config.parallel_leaf(a_bits, fermat_shift);
 
-        let a_bits = a.bits() as usize;
-        let (piece_bits, n, fermat_shift) = select_fft_params(a_bits, a_bits);
-
-        // Split into polynomial
-        let poly_a = Poly::from_biguint(a, n, piece_bits, fermat_shift);
-        let mut coeffs = poly_a.coeffs;
+    with_coefficients(config.memory_limit_bytes, |arena| {
+        // Split into polynomial, with coefficients in the arena
+        let coeffs = split_in(a, n, piece_bits, fermat_shift, arena);
 
         // Forward NTT (only once for squaring)
-        fft_forward(&mut coeffs, fermat_shift);
+        forward(coeffs, fermat_shift, leaf);
 
         // Pointwise square in-place (reuse same transform, no new allocation)
-        for coeff in &mut coeffs {
-            let squared = coeff.fermat_mul(coeff);
-            *coeff = squared;
-        }
+        pointwise_square(coeffs, leaf.is_some(), &*POOL_ALLOCATOR);
 
         // Inverse NTT
-        fft_inverse(&mut coeffs, fermat_shift);
+        inverse(coeffs, fermat_shift, leaf);
 
         // Reassemble
-        let result = reassemble(&coeffs, piece_bits);
+        reassemble_into(coeffs, piece_bits, dst);
+    });
+}
 
-        bump.borrow_mut().reset();
-        result
-    })
+/// Split `value` into `segment_bits`-bit blocks, least significant first.
+///
+/// Blocks are whole limbs, so each one is copied from its range of
+/// `value`'s digits.
+fn segments(value: &BigUint, segment_bits: usize) -> Vec<BigUint> {
+    debug_assert_eq!(segment_bits % 64, 0, "segments must be whole limbs");
+    let block_digits = segment_bits / 32;
+    let mut digits = value.iter_u32_digits();
+    let mut blocks = Vec::new();
+    loop {
+        let block: Vec<u32> = digits.by_ref().take(block_digits).collect();
+        if block.is_empty() {
+            return blocks;
+        }
+        blocks.push(BigUint::new(block));
+    }
+}
+
+/// Memory-bounded product (or square, when `b` is `None`) into `dst` that
+/// multiplies `segment_bits`-bit blocks pairwise under `plan`.
+///
+/// Block products are reassembled straight into one limb accumulator at
+/// their bit offsets. When the budget has room for them next to a block
+/// product, the transforms of the blocks of `b` (of `a`, for squares) are
+/// computed once up front; otherwise only one block transform per operand
+/// is live at a time and they are redone for every row. Squares reuse the
+/// row transform for the diagonal and double the off-diagonal products.
+#[allow(clippy::cast_possible_truncation)]
+fn segmented_product(
+    dst: &mut BigUint,
+    a: &BigUint,
+    b: Option<&BigUint>,
+    plan: FFTPlan,
+    segment_bits: usize,
+    config: &FFTConfig,
+) {
+    let a_blocks = segments(a, segment_bits);
+    let b_blocks = b.map(|b| segments(b, segment_bits));
+    let others = b_blocks.as_ref().unwrap_or(&a_blocks);
+    if a_blocks.is_empty() || others.is_empty() {
+        dst.set_zero();
+        return;
+    }
+    let cached: Option<Vec<TransformedOperand>> =
+        transforms_fit(&plan, others.len(), config).then(|| {
+            others
+                .iter()
+                .map(|block| TransformedOperand::new(block, plan, config))
+                .collect()
+        });
+
+    // Room for the last block product at its offset, plus the carries.
+    let block_limbs = ((plan.len - 1) * plan.piece_bits) / 64 + plan.fermat_shift.div_ceil(64) + 3;
+    let last_offset = (a_blocks.len() + others.len() - 2) * segment_bits + 1;
+    let mut acc = vec![0u64; last_offset / 64 + block_limbs];
+
+    for (i, a_block) in a_blocks.iter().enumerate() {
+        if a_block.is_zero() {
+            continue;
+        }
+        let row;
+        let a_hat = if let (Some(cached), None) = (&cached, b) {
+            &cached[i]
+        } else {
+            row = TransformedOperand::new(a_block, plan, config);
+            &row
+        };
+        let (first_j, doubling) = if b.is_some() {
+            (0, 0)
+        } else {
+            a_hat.mul_add_shifted(a_hat, &mut acc, 2 * i * segment_bits, config);
+            (i + 1, 1)
+        };
+        for (j, other) in others.iter().enumerate().skip(first_j) {
+            if other.is_zero() {
+                continue;
+            }
+            let offset = (i + j) * segment_bits + doubling;
+            if let Some(cached) = &cached {
+                a_hat.mul_add_shifted(&cached[j], &mut acc, offset, config);
+            } else {
+                let other_hat = TransformedOperand::new(other, plan, config);
+                a_hat.mul_add_shifted(&other_hat, &mut acc, offset, config);
+            }
+        }
+    }
+    load_limbs(&acc, dst);
 }
 
 /// Direct FFT multiply (always uses FFT, for testing purposes).
 #[cfg(test)]
+#[allow(clippy::cast_possible_truncation)]
 fn fft_multiply_direct(a: &BigUint, b: &BigUint) -> BigUint {
-    fft_multiply(a, b)
+    let plan = FFTPlan::for_product(a.bits() as usize, b.bits() as usize);
+    let mut product = BigUint::ZERO;
+    fft_multiply(&mut product, a, b, plan, &FFTConfig::default());
+    product
 }
 
 #[cfg(test)]
@@ -201,7 +928,9 @@ mod tests {
         for &bit_size in &[128, 256, 512, 1024] {
             let a = (BigUint::one() << bit_size) - BigUint::one();
             let expected = &a * &a;
-            let got = fft_square(&a);
+            let plan = FFTPlan::for_product(bit_size, bit_size);
+            let mut got = BigUint::ZERO;
+            fft_square(&mut got, &a, plan, &FFTConfig::default());
             assert_eq!(
                 expected, got,
                 "FFT square failed for {bit_size}-bit numbers"
@@ -221,7 +950,7 @@ mod tests {
 
     #[test]
     fn fft_multiply_with_bump_allocator() {
-        // Large enough to trigger FFT path (> FFT_BIT_THRESHOLD = 10_000 bits)
+        // Large enough to trigger FFT path (> FFTConfig::DEFAULT_THRESHOLD_BITS)
         let a = (BigUint::one() << 12_000) - BigUint::one();
         let b = (BigUint::one() << 12_000) - BigUint::from(3u64);
         let expected = &a * &b;
@@ -229,6 +958,266 @@ mod tests {
         assert_eq!(expected, got, "FFT multiply with bump allocator should be correct");
     }
 
+    #[test]
+    fn config_threshold_routes_small_operands_to_fft() {
+        let config = FFTConfig::with_threshold(0);
+        for &(x, y) in &[(1u64, 1u64), (3, 0), (12345, 67890), (u64::MAX, u64::MAX)] {
+            let a = BigUint::from(x);
+            let b = BigUint::from(y);
+            assert_eq!(mul_with(&a, &b, &config), &a * &b, "mul_with({x}, {y})");
+            assert_eq!(sqr_with(&a, &config), &a * &a, "sqr_with({x})");
+        }
+    }
+
+    #[test]
+    fn config_threshold_boundary() {
+        let config = FFTConfig::with_threshold(2048);
+        assert!(!config.uses_fft(2047));
+        assert!(config.uses_fft(2048));
+        assert_eq!(
+            FFTConfig::default().threshold_bits,
+            FFTConfig::DEFAULT_THRESHOLD_BITS
+        );
+
+        let a = (BigUint::one() << 2100) - BigUint::from(7u64);
+        let b = (BigUint::one() << 1500) + BigUint::one();
+        assert_eq!(mul_with(&a, &b, &config), &a * &b);
+        assert_eq!(sqr_with(&a, &config), &a * &a);
+        assert_eq!(mul_with(&b, &b, &config), sqr(&b));
+    }
+
+    #[test]
+    fn parallel_fft_matches_sequential() {
+        for backend in [FFTBackend::Fermat, FFTBackend::Ntt] {
+            let sequential = FFTConfig::with_threshold(0)
+                .with_parallel_threshold(usize::MAX)
+                .with_backend(backend);
+            let parallel = FFTConfig::with_threshold(0)
+                .with_parallel_threshold(0)
+                .with_backend(backend);
+            assert!(!sequential.uses_parallel(1 << 40));
+            assert!(parallel.uses_parallel(0));
+            for &bits in &[100usize, 5_000, 70_000] {
+                let a = (BigUint::one() << bits) - BigUint::from(12_345u64);
+                let b = (BigUint::one() << (bits / 2 + 17)) + BigUint::from(99u64);
+                let expected = &a * &b;
+                assert_eq!(
+                    mul_with(&a, &b, &parallel),
+                    expected,
+                    "{backend:?} mul {bits}"
+                );
+                assert_eq!(
+                    mul_with(&a, &b, &sequential),
+                    expected,
+                    "{backend:?} mul {bits}"
+                );
+                assert_eq!(sqr_with(&a, &parallel), &a * &a, "{backend:?} sqr {bits}");
+            }
+        }
+    }
+
+    #[test]
+    fn backends_agree() {
+        let fermat = FFTConfig::with_threshold(0).with_backend(FFTBackend::Fermat);
+        let ntt = FFTConfig::with_threshold(0).with_backend(FFTBackend::Ntt);
+        for &bits in &[1usize, 64, 1_000, 33_333] {
+            let a = (BigUint::one() << bits) / BigUint::from(7u64);
+            let b = (BigUint::one() << (bits + 5)) - BigUint::one();
+            assert_eq!(
+                mul_with(&a, &b, &fermat),
+                mul_with(&a, &b, &ntt),
+                "mul {bits}"
+            );
+            assert_eq!(sqr_with(&b, &fermat), sqr_with(&b, &ntt), "sqr {bits}");
+        }
+    }
+
+    #[test]
+    fn memory_limited_products_match_unlimited() {
+        let limited = FFTConfig::with_threshold(0)
+            .with_backend(FFTBackend::Fermat)
+            .with_memory_limit(1);
+        let a = (BigUint::one() << 200_000) / BigUint::from(7u64);
+        let b = (BigUint::one() << 150_003) - BigUint::from(99u64);
+        assert!(matches!(
+            FFTLayout::for_product(200_000, 200_000, &limited),
+            FFTLayout::Segmented { .. }
+        ));
+        assert_eq!(mul_with(&a, &b, &limited), &a * &b);
+        assert_eq!(mul_with(&b, &a, &limited), &a * &b);
+        assert_eq!(sqr_with(&a, &limited), &a * &a);
+    }
+
+    #[test]
+    fn segmented_products_with_and_without_cached_transforms() {
+        let fermat = FFTConfig::with_threshold(0).with_backend(FFTBackend::Fermat);
+        let segment_bits = 1 << 16;
+        let plan = FFTPlan::for_product(segment_bits, segment_bits);
+        let a = (BigUint::one() << 300_000) / BigUint::from(7u64);
+        let b = ((BigUint::one() << 200_017) - BigUint::from(99u64)) << 70_000u32;
+        for config in [fermat, fermat.with_memory_limit(1)] {
+            let cached = transforms_fit(&plan, 5, &config);
+            let mut dst = BigUint::from(u64::MAX);
+            segmented_product(&mut dst, &a, Some(&b), plan, segment_bits, &config);
+            assert_eq!(dst, &a * &b, "cached: {cached}");
+            segmented_product(&mut dst, &b, None, plan, segment_bits, &config);
+            assert_eq!(dst, &b * &b, "cached: {cached}");
+            segmented_product(
+                &mut dst,
+                &BigUint::ZERO,
+                Some(&b),
+                plan,
+                segment_bits,
+                &config,
+            );
+            assert_eq!(dst, BigUint::ZERO);
+        }
+        assert!(transforms_fit(&plan, 5, &fermat));
+        assert!(!transforms_fit(&plan, 5, &fermat.with_memory_limit(1)));
+    }
+
+    #[test]
+    fn backend_parse_and_resolve() {
+        for backend in [FFTBackend::Auto, FFTBackend::Fermat, FFTBackend::Ntt] {
+            assert_eq!(backend.name().parse::<FFTBackend>(), Ok(backend));
+        }
+        assert_eq!("NTT".parse::<FFTBackend>(), Ok(FFTBackend::Ntt));
+        assert!("karatsuba".parse::<FFTBackend>().is_err());
+        let config = FFTConfig::default();
+        assert_eq!(config.backend, FFTBackend::Auto);
+        assert_eq!(config.backend_for(1 << 30), FFTBackend::Fermat);
+        let auto = config.with_ntt_threshold(1 << 20);
+        assert_eq!(auto.backend_for((1 << 20) - 1), FFTBackend::Fermat);
+        assert_eq!(auto.backend_for(1 << 20), FFTBackend::Ntt);
+        let fermat = auto.with_backend(FFTBackend::Fermat);
+        assert_eq!(fermat.backend_for(usize::MAX), FFTBackend::Fermat);
+        let ntt = config.with_backend(FFTBackend::Ntt);
+        assert_eq!(ntt.backend_for(0), FFTBackend::Ntt);
+    }
+
+    #[test]
+    fn transformed_operands_match_direct_products() {
+        let config = FFTConfig::default();
+        let a = (BigUint::one() << 20_000) - BigUint::from(3u64);
+        let b = (BigUint::one() << 19_000) + BigUint::from(12_345u64);
+        let plan = FFTPlan::for_product(20_000, 20_000);
+        let ta = TransformedOperand::new(&a, plan, &config);
+        let tb = TransformedOperand::new(&b, plan, &config);
+        assert_eq!(ta.plan(), plan);
+        assert_eq!(ta.bits(), a.bits());
+        assert_eq!(ta.mul(&tb, &config), &a * &b);
+        assert_eq!(tb.mul(&ta, &config), &a * &b);
+        assert_eq!(ta.sqr(&config), &a * &a);
+        assert_eq!(tb.sqr(&config), &b * &b);
+
+        let zero = TransformedOperand::new(&BigUint::ZERO, plan, &config);
+        assert_eq!(zero.mul(&ta, &config), BigUint::ZERO);
+
+        let mut dst = BigUint::from(u64::MAX);
+        ta.mul_to(&mut dst, &tb, &config);
+        assert_eq!(dst, &a * &b);
+        TransformedOperand::sum_of_products_to(&mut dst, &[(&ta, &ta), (&tb, &ta)], &config);
+        assert_eq!(dst, &a * &a + &b * &a);
+        TransformedOperand::sum_of_products_to(&mut dst, &[], &config);
+        assert_eq!(dst, BigUint::ZERO);
+    }
+
+    #[test]
+    fn plan_fits_bounds_products() {
+        let plan = FFTPlan::for_product(1000, 1000);
+        assert!(plan.fits(1000, 1000));
+        assert!(!plan.fits(1000, plan.len as u64 * plan.piece_bits as u64));
+    }
+
+    #[test]
+    #[should_panic(expected = "different FFT plans")]
+    fn transformed_mul_rejects_mixed_plans() {
+        let config = FFTConfig::default();
+        let a = BigUint::from(7u64);
+        let ta = TransformedOperand::new(&a, FFTPlan::for_product(64, 64), &config);
+        let tb = TransformedOperand::new(&a, FFTPlan::for_product(50_000, 50_000), &config);
+        let _ = ta.mul(&tb, &config);
+    }
+
+    #[test]
+    fn sum_of_products_matches_schoolbook() {
+        let a = (BigUint::one() << 30_000) - BigUint::from(7u64);
+        let b = (BigUint::one() << 21_000) / BigUint::from(3u64);
+        let c = BigUint::from(12_345u64);
+        let expected = &a * &b + &c * &a + &b * &b;
+        for config in [
+            FFTConfig::with_threshold(0).with_backend(FFTBackend::Fermat),
+            FFTConfig::with_threshold(0).with_backend(FFTBackend::Ntt),
+            FFTConfig::with_threshold(0)
+                .with_backend(FFTBackend::Fermat)
+                .with_memory_limit(1),
+            FFTConfig::default(),
+        ] {
+            let terms = [(&a, &b), (&c, &a), (&b, &b), (&a, &BigUint::ZERO)];
+            assert_eq!(sum_of_products_with(&terms, &config), expected);
+            assert_eq!(
+                sqr_add_sqr_with(&a, &b, &config),
+                &a * &a + &b * &b,
+                "{config:?}"
+            );
+        }
+        assert_eq!(sum_of_products(&[]), BigUint::ZERO);
+        assert_eq!(sqr_add_sqr(&c, &c), &c * &c * 2u32);
+    }
+
+    #[test]
+    fn product_sums_split_at_max_terms() {
+        // 2 bits of headroom: at most 4 products per inverse transform.
+        let plan = FFTPlan {
+            piece_bits: 64,
+            len: 8,
+            fermat_shift: 132,
+        };
+        assert_eq!(plan.max_terms(), 4);
+        assert!(FFTPlan::for_product(1 << 20, 1 << 20).max_terms() >= 4);
+
+        let config = FFTConfig::with_threshold(0);
+        let ones = (BigUint::one() << 256) - BigUint::one();
+        let t = TransformedOperand::new(&ones, plan, &config);
+        for count in [1, 4, 9] {
+            let terms = vec![(&t, &t); count];
+            assert_eq!(
+                TransformedOperand::sum_of_products(&terms, &config),
+                &ones * &ones * BigUint::from(count)
+            );
+        }
+    }
+
+    #[test]
+    fn destination_products_overwrite_stale_values() {
+        let a = (BigUint::one() << 60_000) / BigUint::from(7u64);
+        let b = (BigUint::one() << 45_001) - BigUint::from(99u64);
+        let configs = [
+            FFTConfig::with_threshold(0).with_backend(FFTBackend::Fermat),
+            FFTConfig::with_threshold(0).with_backend(FFTBackend::Ntt),
+            FFTConfig::with_threshold(0)
+                .with_backend(FFTBackend::Fermat)
+                .with_memory_limit(1),
+            FFTConfig::default(),
+        ];
+        for config in &configs {
+            // Start from a larger value than any result, then from smaller ones.
+            let mut dst = BigUint::one() << 200_000;
+            mul_to_with(&mut dst, &a, &b, config);
+            assert_eq!(dst, &a * &b);
+            sqr_to_with(&mut dst, &b, config);
+            assert_eq!(dst, &b * &b);
+            sqr_add_sqr_to_with(&mut dst, &a, &b, config);
+            assert_eq!(dst, &a * &a + &b * &b);
+            sum_of_products_to_with(&mut dst, &[(&a, &b), (&b, &b)], config);
+            assert_eq!(dst, &a * &b + &b * &b);
+            mul_to_with(&mut dst, &a, &BigUint::ZERO, config);
+            assert!(dst.is_zero());
+            sqr_to_with(&mut dst, &a, config);
+            assert_eq!(dst, &a * &a);
+        }
+    }
+
     #[test]
     fn pool_allocator_is_used_in_fft() {
         use crate::allocator::{PoolAllocator, TempAllocator};
@@ -236,7 +1225,37 @@ mod tests {
         // Verify pool starts empty
         let val = alloc.alloc(1000);
         alloc.free(val);
-        // After free, the pool should have at least 1 item
-        // This test verifies the allocator infrastructure is functional
+        // A released value is handed out again from its size class
+        alloc.free(BigUint::one() << 900);
+        let _ = alloc.alloc(1000);
+        assert_eq!(alloc.stats().hits, 1);
+
+        let before = pool_stats();
+        let a = (BigUint::one() << 20_000) - BigUint::from(3u64);
+        let plan = FFTPlan::for_product(20_000, 20_000);
+        let mut square = BigUint::ZERO;
+        fft_square(&mut square, &a, plan, &FFTConfig::default());
+        assert_eq!(square, &a * &a);
+        assert!(pool_stats().hits > before.hits);
+    }
+
+    #[test]
+    fn coefficients_come_from_the_arena() {
+        let a = (BigUint::one() << 20_000) - BigUint::from(3u64);
+        let b = (BigUint::one() << 18_000) + BigUint::from(5u64);
+        let plan = FFTPlan::for_product(20_000, 18_000);
+        let mut product = BigUint::ZERO;
+        fft_multiply(&mut product, &a, &b, plan, &FFTConfig::default());
+        assert_eq!(product, &a * &b);
+        // After the reset the arena keeps a chunk at least as large as one
+        // polynomial's limbs.
+        let poly_bytes = plan.len * (plan.fermat_shift.div_ceil(64) + 1) * 8;
+        let retained = || with_coefficients(usize::MAX, crate::bump::FFTBumpAllocator::allocated_bytes);
+        assert!(retained() >= poly_bytes);
+        // Under a memory budget nothing outlives the product.
+        let budget = FFTConfig::default().with_memory_limit(usize::MAX - 1);
+        fft_multiply(&mut product, &a, &b, plan, &budget);
+        assert_eq!(product, &a * &b);
+        assert_eq!(retained(), 0);
     }
 }
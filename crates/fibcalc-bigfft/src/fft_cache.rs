@@ -1,20 +1,69 @@
 //! Thread-safe LRU cache for FFT transforms.
-#![allow(dead_code)] // Infrastructure: will be wired up for FFT transform caching
+//!
+//! Maps an operand and an [`FFTPlan`] to its [`TransformedOperand`], so an
+//! operand used by several products is transformed only once.
 
 use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::Arc;
 
+use num_bigint::BigUint;
 use parking_lot::Mutex;
 
-/// Cache key for FFT transforms.
-#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+use crate::fft::{FFTConfig, FFTPlan, TransformedOperand};
+
+/// Cache key for FFT transforms: the plan plus a fingerprint of the operand.
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
 pub struct CacheKey {
-    pub size: usize,
-    pub shift: usize,
+    /// Plan the operand is transformed under.
+    pub plan: FFTPlan,
+    /// Bit length of the operand.
+    pub bits: u64,
+    /// Hash of the operand's digits.
+    pub fingerprint: u64,
+}
+
+impl CacheKey {
+    /// Key for `value` transformed under `plan`.
+    #[must_use]
+    pub fn new(value: &BigUint, plan: FFTPlan) -> Self {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        Self {
+            plan,
+            bits: value.bits(),
+            fingerprint: hasher.finish(),
+        }
+    }
+}
+
+/// Hit and miss counters of an [`FFTCache`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Lookups served from the cache.
+    pub hits: u64,
+    /// Lookups that had to run a forward transform.
+    pub misses: u64,
+}
+
+struct Entry {
+    /// The operand, compared on lookup so fingerprint collisions never
+    /// return the wrong transform.
+    source: BigUint,
+    transform: Arc<TransformedOperand>,
+    last_used: u64,
 }
 
-/// Thread-safe LRU cache for FFT root tables.
+#[derive(Default)]
+struct Inner {
+    entries: HashMap<CacheKey, Entry>,
+    tick: u64,
+    stats: CacheStats,
+}
+
+/// Thread-safe LRU cache of forward transforms, bounded by entry count.
 pub struct FFTCache {
-    cache: Mutex<HashMap<CacheKey, Vec<Vec<u64>>>>,
+    inner: Mutex<Inner>,
     max_entries: usize,
 }
 
@@ -23,41 +72,102 @@ impl FFTCache {
     #[must_use]
     pub fn new(max_entries: usize) -> Self {
         Self {
-            cache: Mutex::new(HashMap::new()),
-            max_entries,
+            inner: Mutex::new(Inner::default()),
+            max_entries: max_entries.max(1),
+        }
+    }
+
+    /// Get the cached transform of `value` under `plan`, if available.
+    pub fn get(&self, value: &BigUint, plan: FFTPlan) -> Option<Arc<TransformedOperand>> {
+        let key = CacheKey::new(value, plan);
+        let mut inner = self.inner.lock();
+        inner.tick += 1;
+        let tick = inner.tick;
+        let found = inner
+            .entries
+            .get_mut(&key)
+            .filter(|entry| entry.source == *value)
+            .map(|entry| {
+                entry.last_used = tick;
+                Arc::clone(&entry.transform)
+            });
+        if found.is_some() {
+            inner.stats.hits += 1;
+        } else {
+            inner.stats.misses += 1;
         }
+        found
     }
 
-    /// Get a cached transform, if available.
-    pub fn get(&self, key: &CacheKey) -> Option<Vec<Vec<u64>>> {
-        self.cache.lock().get(key).cloned()
+    /// Store the transform of `value`, evicting the least recently used
+    /// entry when full.
+    pub fn put(&self, value: &BigUint, transform: Arc<TransformedOperand>) {
+        let key = CacheKey::new(value, transform.plan());
+        let mut inner = self.inner.lock();
+        if !inner.entries.contains_key(&key) && inner.entries.len() >= self.max_entries {
+            if let Some(oldest) = inner
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| *key)
+            {
+                inner.entries.remove(&oldest);
+            }
+        }
+        inner.tick += 1;
+        let last_used = inner.tick;
+        inner.entries.insert(
+            key,
+            Entry {
+                source: value.clone(),
+                transform,
+                last_used,
+            },
+        );
     }
 
-    /// Store a transform in the cache.
-    pub fn put(&self, key: CacheKey, value: Vec<Vec<u64>>) {
-        let mut cache = self.cache.lock();
-        if cache.len() >= self.max_entries {
-            // Simple eviction: clear all (LRU would be more sophisticated)
-            cache.clear();
+    /// Return the transform of `value` under `plan`, computing and caching
+    /// it on a miss.
+    pub fn transform(
+        &self,
+        value: &BigUint,
+        plan: FFTPlan,
+        config: &FFTConfig,
+    ) -> Arc<TransformedOperand> {
+        if let Some(hit) = self.get(value, plan) {
+            return hit;
         }
-        cache.insert(key, value);
+        let transform = Arc::new(TransformedOperand::new(value, plan, config));
+        self.put(value, Arc::clone(&transform));
+        transform
+    }
+
+    /// Hit and miss counts since creation or the last [`Self::reset_stats`].
+    #[must_use]
+    pub fn stats(&self) -> CacheStats {
+        self.inner.lock().stats
+    }
+
+    /// Reset the hit and miss counters.
+    pub fn reset_stats(&self) {
+        self.inner.lock().stats = CacheStats::default();
     }
 
     /// Get the number of cached entries.
     #[must_use]
     pub fn len(&self) -> usize {
-        self.cache.lock().len()
+        self.inner.lock().entries.len()
     }
 
     /// Check if the cache is empty.
     #[must_use]
     pub fn is_empty(&self) -> bool {
-        self.cache.lock().is_empty()
+        self.inner.lock().entries.is_empty()
     }
 
     /// Clear the cache.
     pub fn clear(&self) {
-        self.cache.lock().clear();
+        self.inner.lock().entries.clear();
     }
 }
 
@@ -71,32 +181,50 @@ impl Default for FFTCache {
 mod tests {
     use super::*;
 
+    const PLAN: FFTPlan = FFTPlan {
+        piece_bits: 64,
+        len: 8,
+        fermat_shift: 256,
+    };
+
+    fn transformed(value: u64) -> (BigUint, Arc<TransformedOperand>) {
+        let value = BigUint::from(value);
+        let t = Arc::new(TransformedOperand::new(&value, PLAN, &FFTConfig::default()));
+        (value, t)
+    }
+
     #[test]
     fn cache_put_get() {
         let cache = FFTCache::new(10);
-        let key = CacheKey { size: 8, shift: 64 };
-        cache.put(key.clone(), vec![vec![1, 2, 3]]);
-        assert!(cache.get(&key).is_some());
+        let (value, t) = transformed(42);
+        cache.put(&value, t);
+        assert!(cache.get(&value, PLAN).is_some());
     }
 
     #[test]
     fn cache_eviction() {
         let cache = FFTCache::new(2);
         for i in 0..3 {
-            cache.put(CacheKey { size: i, shift: 64 }, vec![]);
+            let (value, t) = transformed(i);
+            cache.put(&value, t);
         }
-        // After exceeding max, cache should have been cleared + 1 new entry
-        assert!(cache.len() <= 2);
+        assert_eq!(cache.len(), 2);
     }
 
     #[test]
     fn cache_miss() {
         let cache = FFTCache::new(10);
-        let key = CacheKey {
-            size: 99,
-            shift: 128,
-        };
-        assert!(cache.get(&key).is_none());
+        assert!(cache.get(&BigUint::from(99u64), PLAN).is_none());
+        assert_eq!(cache.stats(), CacheStats { hits: 0, misses: 1 });
+    }
+
+    #[test]
+    fn cache_miss_for_other_plan() {
+        let cache = FFTCache::new(10);
+        let (value, t) = transformed(7);
+        cache.put(&value, t);
+        let other = FFTPlan::for_product(10_000, 10_000);
+        assert!(cache.get(&value, other).is_none());
     }
 
     #[test]
@@ -115,7 +243,8 @@ mod tests {
     #[test]
     fn cache_not_empty_after_put() {
         let cache = FFTCache::new(5);
-        cache.put(CacheKey { size: 1, shift: 1 }, vec![vec![1]]);
+        let (value, t) = transformed(1);
+        cache.put(&value, t);
         assert!(!cache.is_empty());
         assert_eq!(cache.len(), 1);
     }
@@ -123,8 +252,10 @@ mod tests {
     #[test]
     fn cache_clear() {
         let cache = FFTCache::new(10);
-        cache.put(CacheKey { size: 1, shift: 1 }, vec![vec![1]]);
-        cache.put(CacheKey { size: 2, shift: 2 }, vec![vec![2]]);
+        for i in 1..=2 {
+            let (value, t) = transformed(i);
+            cache.put(&value, t);
+        }
         assert_eq!(cache.len(), 2);
 
         cache.clear();
@@ -133,103 +264,83 @@ mod tests {
     }
 
     #[test]
-    fn cache_get_returns_correct_value() {
+    fn cache_get_returns_same_transform() {
         let cache = FFTCache::new(10);
-        let key = CacheKey { size: 4, shift: 32 };
-        let value = vec![vec![10, 20], vec![30, 40]];
-        cache.put(key.clone(), value.clone());
-
-        let retrieved = cache.get(&key).unwrap();
-        assert_eq!(retrieved, value);
+        let (value, t) = transformed(1234);
+        cache.put(&value, Arc::clone(&t));
+        let retrieved = cache.get(&value, PLAN).unwrap();
+        assert!(Arc::ptr_eq(&retrieved, &t));
     }
 
     #[test]
     fn cache_overwrite_same_key() {
         let cache = FFTCache::new(10);
-        let key = CacheKey { size: 4, shift: 32 };
-        cache.put(key.clone(), vec![vec![1]]);
-        cache.put(key.clone(), vec![vec![2]]);
-
-        let retrieved = cache.get(&key).unwrap();
-        assert_eq!(retrieved, vec![vec![2]]);
+        let (value, first) = transformed(5);
+        let (_, second) = transformed(5);
+        cache.put(&value, first);
+        cache.put(&value, Arc::clone(&second));
+        assert!(Arc::ptr_eq(&cache.get(&value, PLAN).unwrap(), &second));
         assert_eq!(cache.len(), 1);
     }
 
     #[test]
-    fn cache_eviction_clears_all_then_inserts() {
+    fn cache_evicts_least_recently_used() {
         let cache = FFTCache::new(2);
-        let key1 = CacheKey { size: 1, shift: 1 };
-        let key2 = CacheKey { size: 2, shift: 2 };
-        cache.put(key1.clone(), vec![vec![1]]);
-        cache.put(key2.clone(), vec![vec![2]]);
+        let (v1, t1) = transformed(1);
+        let (v2, t2) = transformed(2);
+        let (v3, t3) = transformed(3);
+        cache.put(&v1, t1);
+        cache.put(&v2, t2);
+        // Touch v1 so v2 becomes the eviction candidate.
+        assert!(cache.get(&v1, PLAN).is_some());
+        cache.put(&v3, t3);
+
+        assert!(cache.get(&v1, PLAN).is_some());
+        assert!(cache.get(&v2, PLAN).is_none());
+        assert!(cache.get(&v3, PLAN).is_some());
         assert_eq!(cache.len(), 2);
-
-        // This triggers eviction (clear) then insert
-        let key3 = CacheKey { size: 3, shift: 3 };
-        cache.put(key3.clone(), vec![vec![3]]);
-
-        // Old entries should be gone
-        assert!(cache.get(&key1).is_none());
-        assert!(cache.get(&key2).is_none());
-        // New entry should be present
-        assert!(cache.get(&key3).is_some());
-        assert_eq!(cache.len(), 1);
     }
 
     #[test]
-    fn cache_multiple_different_keys() {
-        let cache = FFTCache::new(100);
-        for i in 0..50 {
-            cache.put(
-                CacheKey {
-                    size: i,
-                    shift: i * 2,
-                },
-                vec![vec![i as u64]],
-            );
-        }
-        assert_eq!(cache.len(), 50);
-
-        for i in 0..50 {
-            let val = cache.get(&CacheKey {
-                size: i,
-                shift: i * 2,
-            });
-            assert!(val.is_some());
-            assert_eq!(val.unwrap(), vec![vec![i as u64]]);
-        }
+    fn cache_transform_counts_hits_and_misses() {
+        let cache = FFTCache::new(4);
+        let config = FFTConfig::default();
+        let value = BigUint::from(987_654_321u64);
+        let first = cache.transform(&value, PLAN, &config);
+        let second = cache.transform(&value, PLAN, &config);
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(cache.stats(), CacheStats { hits: 1, misses: 1 });
+        assert_eq!(first.sqr(&config), &value * &value);
+
+        cache.reset_stats();
+        assert_eq!(cache.stats(), CacheStats::default());
     }
 
     #[test]
     fn cache_key_equality() {
-        let k1 = CacheKey { size: 8, shift: 64 };
-        let k2 = CacheKey { size: 8, shift: 64 };
-        let k3 = CacheKey {
-            size: 8,
-            shift: 128,
-        };
+        let v = BigUint::from(8u64);
+        let k1 = CacheKey::new(&v, PLAN);
+        let k2 = CacheKey::new(&v, PLAN);
+        let k3 = CacheKey::new(&BigUint::from(9u64), PLAN);
         assert_eq!(k1, k2);
         assert_ne!(k1, k3);
     }
 
     #[test]
     fn cache_concurrent_access() {
-        use std::sync::Arc;
         use std::thread;
 
         let cache = Arc::new(FFTCache::new(1000));
 
         let mut handles = vec![];
-        for t in 0..4 {
+        for t in 0..4u64 {
             let cache = Arc::clone(&cache);
             handles.push(thread::spawn(move || {
+                let config = FFTConfig::default();
                 for i in 0..50 {
-                    let key = CacheKey {
-                        size: t * 100 + i,
-                        shift: 64,
-                    };
-                    cache.put(key.clone(), vec![vec![i as u64]]);
-                    let _ = cache.get(&key);
+                    let value = BigUint::from(t * 100 + i);
+                    let transform = cache.transform(&value, PLAN, &config);
+                    assert_eq!(transform.bits(), value.bits());
                 }
             }));
         }
@@ -238,18 +349,20 @@ mod tests {
             h.join().unwrap();
         }
 
-        // Cache should have entries and not have panicked
-        assert!(cache.len() > 0);
+        assert_eq!(cache.len(), 200);
     }
 
     #[test]
     fn cache_max_entries_one() {
         let cache = FFTCache::new(1);
-        cache.put(CacheKey { size: 1, shift: 1 }, vec![]);
+        let (v1, t1) = transformed(1);
+        let (v2, t2) = transformed(2);
+        cache.put(&v1, t1);
         assert_eq!(cache.len(), 1);
 
         // Adding second entry should trigger eviction
-        cache.put(CacheKey { size: 2, shift: 2 }, vec![]);
+        cache.put(&v2, t2);
         assert_eq!(cache.len(), 1);
+        assert!(cache.get(&v2, PLAN).is_some());
     }
 }
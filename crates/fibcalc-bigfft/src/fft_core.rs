@@ -1,21 +1,37 @@
 //! Core FFT transform: forward and inverse (NTT over Fermat ring).
 
-use crate::fermat::FermatNum;
+use crate::fermat::{FermatNum, Limbs};
 
 /// Perform forward NTT transform in-place over Z/(2^shift + 1).
 ///
 /// Uses the Cooley-Tukey butterfly with twiddle factors that are powers of 2,
-/// exploiting the fact that 2 is a root of unity modulo Fermat numbers.
-pub fn fft_forward(data: &mut [FermatNum], shift: usize) {
+/// exploiting the fact that 2 is a root of unity modulo Fermat numbers:
+/// the `n`-th root used is `2^(2*shift/n)`.
+///
+/// # Panics
+///
+/// Panics if `data.len()` is not a power of two, if `2 * shift` is not a
+/// multiple of `data.len()`, or if any coefficient has a different shift.
+pub fn fft_forward<S: Limbs>(data: &mut [FermatNum<S>], shift: usize) {
     let n = data.len();
     if n <= 1 {
         return;
     }
+    check_transform(data, shift);
 
     // Bit-reversal permutation
     bit_reverse_permutation(data);
 
-    // Iterative Cooley-Tukey FFT
+    butterflies(data, shift);
+}
+
+/// Iterative Cooley-Tukey butterflies over bit-reversed input.
+///
+/// `data.len()` is the transform size; the root of unity is
+/// `2^(2*shift/len)`. Used directly by [`fft_forward`] and as the
+/// sequential leaf of [`crate::fft_recursion::fft_recursive`].
+pub(crate) fn butterflies<S: Limbs>(data: &mut [FermatNum<S>], shift: usize) {
+    let n = data.len();
     let mut size = 2;
     while size <= n {
         let half = size / 2;
@@ -28,14 +44,8 @@ pub fn fft_forward(data: &mut [FermatNum], shift: usize) {
                 let s = step * j;
                 // Split to get simultaneous mutable access to indices [start+j] and [start+j+half]
                 let (lo, hi) = data.split_at_mut(start + j + half);
-                let upper = &lo[start + j];
-                let lower = &mut hi[0];
-                lower.shift_left(s); // lower = data[start+j+half] * ω^j (in-place)
-
-                let sum = upper.add(lower); // u + ω^j * t
-                let diff = upper.sub(lower); // u - ω^j * t
-                lo[start + j] = sum;
-                hi[0] = diff;
+                // (u, t) -> (u + ω^j * t, u - ω^j * t), in place
+                FermatNum::butterfly(&mut lo[start + j], &mut hi[0], s);
             }
         }
         size *= 2;
@@ -46,7 +56,11 @@ pub fn fft_forward(data: &mut [FermatNum], shift: usize) {
 ///
 /// Standard technique: reverse elements (except first), apply forward FFT,
 /// then divide each element by n.
-pub fn fft_inverse(data: &mut [FermatNum], shift: usize) {
+///
+/// # Panics
+///
+/// Panics under the same conditions as [`fft_forward`].
+pub fn fft_inverse<S: Limbs>(data: &mut [FermatNum<S>], shift: usize) {
     let n = data.len();
     if n <= 1 {
         return;
@@ -65,8 +79,26 @@ pub fn fft_inverse(data: &mut [FermatNum], shift: usize) {
     }
 }
 
+/// Check the shape requirements of a length-`data.len()` transform.
+fn check_transform<S: Limbs>(data: &[FermatNum<S>], shift: usize) {
+    let n = data.len();
+    assert!(
+        n.is_power_of_two(),
+        "transform length {n} is not a power of two"
+    );
+    assert!(
+        (2 * shift) % n == 0,
+        "2 * shift ({}) is not a multiple of the transform length {n}",
+        2 * shift
+    );
+    assert!(
+        data.iter().all(|c| c.shift == shift),
+        "coefficient shift differs from the transform shift {shift}"
+    );
+}
+
 /// Bit-reversal permutation.
-fn bit_reverse_permutation(data: &mut [FermatNum]) {
+pub(crate) fn bit_reverse_permutation<S: Limbs>(data: &mut [FermatNum<S>]) {
     let n = data.len();
     let mut j = 0;
     for i in 1..n {
@@ -1,20 +1,30 @@
 //! Polynomial operations for FFT multiplication.
 
 use num_bigint::BigUint;
-use num_traits::One;
+use num_traits::Zero;
+use rayon::prelude::*;
 
-use crate::fermat::FermatNum;
+use crate::allocator::{SimpleAllocator, TempAllocator};
+use crate::bump::{with_scratch, FFTBumpAllocator};
+use crate::fermat::{load_limbs, FermatNum, Limbs};
+use crate::fft_core::{fft_forward, fft_inverse};
 
 /// Polynomial representation for FFT multiplication.
+///
+/// An integer split into `len()` pieces of `piece_bits` bits, read as
+/// the coefficients of a polynomial evaluated at `x = 2^piece_bits`,
+/// with every coefficient in the ring mod `2^fermat_shift + 1`.
+///
+/// Invariants: every coefficient has shift `fermat_shift`, and
+/// `piece_bits < fermat_shift` so that each piece is stored unreduced.
+#[derive(Debug, Clone)]
 pub struct Poly {
     /// Coefficients of the polynomial (`FermatNum` values).
-    pub coeffs: Vec<FermatNum>,
+    pub(crate) coeffs: Vec<FermatNum>,
     /// Fermat modulus shift parameter.
-    #[allow(dead_code)] // Used in tests via to_biguint()
-    pub fermat_shift: usize,
+    pub(crate) fermat_shift: usize,
     /// Number of bits per piece used for splitting the input.
-    #[allow(dead_code)] // Used in tests via to_biguint(); also written by from_biguint()
-    pub piece_bits: usize,
+    pub(crate) piece_bits: usize,
 }
 
 impl Poly {
@@ -26,39 +36,55 @@ impl Poly {
     /// - `n`: number of coefficients (padded with zeros)
     /// - `piece_bits`: bits per piece
     /// - `fermat_shift`: Fermat modulus parameter for the NTT
+    ///
+    /// # Panics
+    ///
+    /// Panics if `piece_bits` is zero, if `piece_bits >= fermat_shift`, or
+    /// if `value` does not fit in `n` pieces.
     #[must_use]
     pub fn from_biguint(value: &BigUint, n: usize, piece_bits: usize, fermat_shift: usize) -> Self {
+        assert!(
+            piece_bits > 0 && piece_bits < fermat_shift,
+            "piece_bits ({piece_bits}) must be in 1..fermat_shift ({fermat_shift})"
+        );
+        assert!(
+            value.bits() <= (n * piece_bits) as u64,
+            "value of {} bits does not fit in {n} pieces of {piece_bits} bits",
+            value.bits()
+        );
         let digits = value.to_u64_digits();
-        let num_limbs_per_piece = piece_bits.div_ceil(64);
-        let mut coeffs = Vec::with_capacity(n);
-
-        if piece_bits % 64 == 0 {
-            // Fast path: pieces align on u64 boundaries, direct slice copy
-            for i in 0..n {
-                let start_limb = i * num_limbs_per_piece;
-                let fermat_limbs = fermat_shift.div_ceil(64) + 1;
-                let mut data = vec![0u64; fermat_limbs];
-                let copy_end = (start_limb + num_limbs_per_piece).min(digits.len());
-                if start_limb < digits.len() {
-                    let copy_len = (copy_end - start_limb).min(fermat_limbs);
-                    data[..copy_len].copy_from_slice(&digits[start_limb..start_limb + copy_len]);
-                }
-                coeffs.push(FermatNum {
-                    data,
-                    shift: fermat_shift,
-                });
-            }
-        } else {
-            // General path: extract arbitrary bit-aligned pieces
-            let mask = (BigUint::one() << piece_bits) - BigUint::one();
-            let mut remaining = value.clone();
-            for _ in 0..n {
-                let piece = &remaining & &mask;
-                remaining >>= piece_bits;
-                coeffs.push(FermatNum::from_biguint(&piece, fermat_shift));
-            }
+        let coeffs = (0..n)
+            .map(|i| {
+                let mut coeff = FermatNum::new(fermat_shift);
+                extract_piece(&digits, i * piece_bits, piece_bits, &mut coeff.data);
+                coeff
+            })
+            .collect();
+
+        Self {
+            coeffs,
+            fermat_shift,
+            piece_bits,
         }
+    }
 
+    /// Create a polynomial from existing coefficients.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `piece_bits` is zero, or if a coefficient's shift is not
+    /// greater than `piece_bits` or differs from the others.
+    #[must_use]
+    pub fn from_coeffs(coeffs: Vec<FermatNum>, piece_bits: usize) -> Self {
+        let fermat_shift = coeffs.first().map_or(piece_bits + 1, FermatNum::shift);
+        assert!(
+            piece_bits > 0 && piece_bits < fermat_shift,
+            "piece_bits ({piece_bits}) must be in 1..fermat_shift ({fermat_shift})"
+        );
+        assert!(
+            coeffs.iter().all(|c| c.shift == fermat_shift),
+            "all coefficients must share one Fermat shift"
+        );
         Self {
             coeffs,
             fermat_shift,
@@ -68,56 +94,299 @@ impl Poly {
 
     /// Convert polynomial back to `BigUint` by evaluating at x = `2^piece_bits`.
     #[must_use]
-    #[allow(dead_code)] // Used in tests
     pub fn to_biguint(&self) -> BigUint {
-        let mut result = BigUint::from(0u32);
-        for (i, coeff) in self.coeffs.iter().enumerate() {
-            result += coeff.to_biguint() << (i * self.piece_bits);
-        }
-        result
+        reassemble(&self.coeffs, self.piece_bits, &SimpleAllocator)
+    }
+
+    /// The coefficients, lowest degree first.
+    #[must_use]
+    pub fn coeffs(&self) -> &[FermatNum] {
+        &self.coeffs
+    }
+
+    /// Take the coefficients, lowest degree first.
+    #[must_use]
+    pub fn into_coeffs(self) -> Vec<FermatNum> {
+        self.coeffs
+    }
+
+    /// Fermat modulus shift: coefficients live mod `2^fermat_shift + 1`.
+    #[must_use]
+    pub fn fermat_shift(&self) -> usize {
+        self.fermat_shift
+    }
+
+    /// Bits per piece: the polynomial is evaluated at `x = 2^piece_bits`.
+    #[must_use]
+    pub fn piece_bits(&self) -> usize {
+        self.piece_bits
     }
 
     /// Get the number of coefficients.
     #[must_use]
-    #[allow(dead_code)] // Used in tests
     pub fn len(&self) -> usize {
         self.coeffs.len()
     }
 
     /// Check if the polynomial is empty.
     #[must_use]
-    #[allow(dead_code)] // Companion to len()
     pub fn is_empty(&self) -> bool {
         self.coeffs.is_empty()
     }
+
+    /// Forward transform of the coefficients, in place.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the transform shape is invalid; see [`fft_forward`].
+    pub fn forward(&mut self) {
+        fft_forward(&mut self.coeffs, self.fermat_shift);
+    }
+
+    /// Inverse transform of the coefficients, in place.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the transform shape is invalid; see [`fft_inverse`].
+    pub fn inverse(&mut self) {
+        fft_inverse(&mut self.coeffs, self.fermat_shift);
+    }
+
+    /// Multiply coefficient-wise by `other`, in place.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the polynomials differ in length or Fermat shift.
+    pub fn pointwise_mul(&mut self, other: &Self) {
+        pointwise_multiply(&mut self.coeffs, &other.coeffs, false, &SimpleAllocator);
+    }
+
+    /// Square every coefficient, in place.
+    pub fn pointwise_square(&mut self) {
+        pointwise_square(&mut self.coeffs, false, &SimpleAllocator);
+    }
+
+    /// Cyclic convolution: the product mod `x^len - 1`, with coefficients
+    /// mod `2^fermat_shift + 1`.
+    ///
+    /// Under an [`FFTPlan`](crate::FFTPlan) that fits both operands, no
+    /// coefficient wraps and `to_biguint` of the result is the integer
+    /// product.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the polynomials differ in length or Fermat shift, or if
+    /// the transform shape is invalid; see [`fft_forward`].
+    #[must_use]
+    pub fn cyclic_mul(&self, other: &Self) -> Self {
+        let mut a = self.clone();
+        let mut b = other.clone();
+        a.forward();
+        b.forward();
+        a.pointwise_mul(&b);
+        a.inverse();
+        a
+    }
 }
 
-/// Pointwise multiplication of two polynomial coefficient arrays mod Fermat.
-#[must_use]
-pub fn pointwise_multiply(a: &[FermatNum], b: &[FermatNum], _shift: usize) -> Vec<FermatNum> {
-    assert_eq!(a.len(), b.len());
-    a.iter()
-        .zip(b.iter())
-        .map(|(ai, bi)| ai.fermat_mul(bi))
-        .collect()
+/// Split `value` into `n` coefficients like [`Poly::from_biguint`], with
+/// their limbs carved from `arena`.
+pub(crate) fn split_in<'a>(
+    value: &BigUint,
+    n: usize,
+    piece_bits: usize,
+    fermat_shift: usize,
+    arena: &'a FFTBumpAllocator,
+) -> &'a mut [FermatNum<&'a mut [u64]>] {
+    let digits = arena.alloc_slice(value.iter_u64_digits().len());
+    for (d, digit) in digits.iter_mut().zip(value.iter_u64_digits()) {
+        *d = digit;
+    }
+    let limbs = fermat_shift.div_ceil(64) + 1;
+    let slab = arena.alloc_slice(n * limbs);
+    let coeffs = slab.chunks_exact_mut(limbs).enumerate().map(|(i, data)| {
+        extract_piece(digits, i * piece_bits, piece_bits, data);
+        FermatNum {
+            data,
+            shift: fermat_shift,
+        }
+    });
+    arena.bump().alloc_slice_fill_iter(coeffs)
+}
+
+/// Copy `coeffs` into coefficients with limbs carved from `arena`.
+pub(crate) fn copy_in<'a, S: Limbs>(
+    coeffs: &[FermatNum<S>],
+    arena: &'a FFTBumpAllocator,
+) -> &'a mut [FermatNum<&'a mut [u64]>] {
+    let limbs = coeffs.first().map_or(1, |c| c.data.len());
+    let slab = arena.alloc_slice(coeffs.len() * limbs);
+    let copies = slab.chunks_exact_mut(limbs).zip(coeffs).map(|(data, c)| {
+        data.copy_from_slice(&c.data);
+        FermatNum {
+            data,
+            shift: c.shift,
+        }
+    });
+    arena.bump().alloc_slice_fill_iter(copies)
+}
+
+/// Write bits `start..start + bits` of the little-endian `digits` into the
+/// zeroed limbs `out`.
+fn extract_piece(digits: &[u64], start: usize, bits: usize, out: &mut [u64]) {
+    let word = start / 64;
+    let bit = start % 64;
+    let words = bits.div_ceil(64);
+    for (k, limb) in out.iter_mut().take(words).enumerate() {
+        let lo = digits.get(word + k).copied().unwrap_or(0);
+        *limb = if bit == 0 {
+            lo
+        } else {
+            let hi = digits.get(word + k + 1).copied().unwrap_or(0);
+            (lo >> bit) | (hi << (64 - bit))
+        };
+    }
+    if bits % 64 != 0 {
+        out[words - 1] &= (1u64 << (bits % 64)) - 1;
+    }
+}
+
+/// Multiply `a` by `b` coefficient-wise mod Fermat, in place, in parallel
+/// when `parallel` is set. Product temporaries come from `alloc`.
+pub fn pointwise_multiply<S: Limbs, T: Limbs>(
+    a: &mut [FermatNum<S>],
+    b: &[FermatNum<T>],
+    parallel: bool,
+    alloc: &dyn TempAllocator,
+) {
+    assert_eq!(a.len(), b.len(), "pointwise operands differ in length");
+    let multiply = |(ai, bi): (&mut FermatNum<S>, &FermatNum<T>)| ai.mul_assign_with(bi, alloc);
+    if parallel {
+        a.par_iter_mut().zip(b.par_iter()).for_each(multiply);
+    } else {
+        a.iter_mut().zip(b.iter()).for_each(multiply);
+    }
+}
+
+/// Square every coefficient in place, in parallel when `parallel` is set.
+/// Product temporaries come from `alloc`.
+pub fn pointwise_square<S: Limbs>(
+    coeffs: &mut [FermatNum<S>],
+    parallel: bool,
+    alloc: &dyn TempAllocator,
+) {
+    let square = |coeff: &mut FermatNum<S>| coeff.square_with(alloc);
+    if parallel {
+        coeffs.par_iter_mut().for_each(square);
+    } else {
+        coeffs.iter_mut().for_each(square);
+    }
+}
+
+/// Add `b` to `a` coefficient-wise mod Fermat, in place, in parallel when
+/// `parallel` is set.
+pub fn pointwise_add<S: Limbs, T: Limbs>(
+    a: &mut [FermatNum<S>],
+    b: &[FermatNum<T>],
+    parallel: bool,
+) {
+    assert_eq!(a.len(), b.len(), "pointwise operands differ in length");
+    let add = |(ai, bi): (&mut FermatNum<S>, &FermatNum<T>)| ai.add_assign(bi);
+    if parallel {
+        a.par_iter_mut().zip(b.par_iter()).for_each(add);
+    } else {
+        a.iter_mut().zip(b.iter()).for_each(add);
+    }
 }
 
 /// Reassemble a `BigUint` from NTT result coefficients.
 ///
-/// Each coefficient c[i] is placed at bit position i * `piece_bits`.
-/// Carries are handled naturally by `BigUint` addition.
+/// Each coefficient c[i] is added at bit position i * `piece_bits` into a
+/// limb accumulator from the scratch arena; the result value comes from
+/// `alloc`.
 #[must_use]
-pub fn reassemble(coeffs: &[FermatNum], piece_bits: usize) -> BigUint {
-    let mut result = BigUint::from(0u32);
+pub fn reassemble<S: Limbs>(
+    coeffs: &[FermatNum<S>],
+    piece_bits: usize,
+    alloc: &dyn TempAllocator,
+) -> BigUint {
+    let mut result = alloc.alloc(reassembled_limbs(coeffs, piece_bits) * 64);
+    reassemble_into(coeffs, piece_bits, &mut result);
+    result
+}
+
+/// [`reassemble`] into `dst`, reusing its allocation when it is large
+/// enough.
+pub fn reassemble_into<S: Limbs>(coeffs: &[FermatNum<S>], piece_bits: usize, dst: &mut BigUint) {
+    let total_limbs = reassembled_limbs(coeffs, piece_bits);
+    if total_limbs == 0 {
+        dst.set_zero();
+        return;
+    }
+    with_scratch(|arena| {
+        let acc = arena.alloc_slice(total_limbs);
+        add_reassembled(acc, coeffs, piece_bits, 0);
+        load_limbs(acc, dst);
+    });
+}
+
+/// Add the value reassembled from `coeffs`, shifted left by `bit_offset`
+/// bits, into the limbs of `acc`.
+///
+/// `acc` must have `bit_offset / 64` limbs plus the reassembled length
+/// (`((len - 1) * piece_bits) / 64` plus two limbs more than one
+/// coefficient) to spare for the carries.
+pub(crate) fn add_reassembled<S: Limbs>(
+    acc: &mut [u64],
+    coeffs: &[FermatNum<S>],
+    piece_bits: usize,
+    bit_offset: usize,
+) {
     for (i, coeff) in coeffs.iter().enumerate() {
-        result += coeff.to_biguint() << (i * piece_bits);
+        add_shifted(acc, &coeff.data, bit_offset + i * piece_bits);
+    }
+}
+
+/// Limbs of the accumulator that [`reassemble`] sums `coeffs` into.
+fn reassembled_limbs<S: Limbs>(coeffs: &[FermatNum<S>], piece_bits: usize) -> usize {
+    coeffs.first().map_or(0, |c| {
+        ((coeffs.len() - 1) * piece_bits) / 64 + c.data.len() + 2
+    })
+}
+
+/// `acc += limbs << bit_offset`, on little-endian u64 limbs.
+fn add_shifted(acc: &mut [u64], limbs: &[u64], bit_offset: usize) {
+    let word = bit_offset / 64;
+    let bit = bit_offset % 64;
+    let mut carry = 0u64;
+    let mut prev = 0u64;
+    let mut i = word;
+    for &limb in limbs.iter().chain(std::iter::once(&0)) {
+        let shifted = if bit == 0 {
+            limb
+        } else {
+            (limb << bit) | (prev >> (64 - bit))
+        };
+        prev = limb;
+        let (s1, c1) = acc[i].overflowing_add(shifted);
+        let (s2, c2) = s1.overflowing_add(carry);
+        acc[i] = s2;
+        carry = u64::from(c1) + u64::from(c2);
+        i += 1;
+    }
+    while carry > 0 {
+        let (sum, c) = acc[i].overflowing_add(carry);
+        acc[i] = sum;
+        carry = u64::from(c);
+        i += 1;
     }
-    result
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::allocator::PoolAllocator;
+    use num_traits::One;
 
     #[test]
     fn poly_roundtrip_small() {
@@ -153,8 +422,63 @@ mod tests {
             FermatNum::from_biguint(&BigUint::from(7u64), shift),
             FermatNum::from_biguint(&BigUint::from(11u64), shift),
         ];
-        let c = pointwise_multiply(&a, &b, shift);
+        let mut c = a.clone();
+        pointwise_multiply(&mut c, &b, false, &SimpleAllocator);
         assert_eq!(c[0].to_biguint(), BigUint::from(21u64));
         assert_eq!(c[1].to_biguint(), BigUint::from(55u64));
     }
+
+    #[test]
+    fn pointwise_parallel_matches_sequential() {
+        let shift = 128;
+        let a: Vec<FermatNum> = (1..=16u64)
+            .map(|v| FermatNum::from_biguint(&BigUint::from(v << 60), shift))
+            .collect();
+        let b: Vec<FermatNum> = (1..=16u64)
+            .map(|v| FermatNum::from_biguint(&BigUint::from(v * 1_000_003), shift))
+            .collect();
+        let pool = PoolAllocator::new();
+        let mut seq = a.clone();
+        pointwise_multiply(&mut seq, &b, false, &pool);
+        let mut par = a.clone();
+        pointwise_multiply(&mut par, &b, true, &pool);
+        let mut sq = a.clone();
+        pointwise_square(&mut sq, true, &pool);
+        let mut sq_seq = a.clone();
+        pointwise_multiply(&mut sq_seq, &a, false, &SimpleAllocator);
+        for i in 0..a.len() {
+            assert_eq!(seq[i].to_biguint(), par[i].to_biguint());
+            assert_eq!(sq[i].to_biguint(), sq_seq[i].to_biguint());
+        }
+        assert!(pool.stats().hits > 0, "pooled temporaries should be reused");
+    }
+
+    #[test]
+    fn reassemble_matches_biguint_sum() {
+        let shift = 256;
+        let piece_bits = 100;
+        let coeffs: Vec<FermatNum> = (0..9usize)
+            .map(|v| {
+                let value = (BigUint::one() << (150 + v)) - BigUint::from(v + 1);
+                FermatNum::from_biguint(&value, shift)
+            })
+            .collect();
+        let expected = coeffs
+            .iter()
+            .enumerate()
+            .fold(BigUint::ZERO, |acc, (i, c)| {
+                acc + (c.to_biguint() << (i * piece_bits))
+            });
+        assert_eq!(reassemble(&coeffs, piece_bits, &SimpleAllocator), expected);
+        assert_eq!(
+            reassemble::<Vec<u64>>(&[], piece_bits, &SimpleAllocator),
+            BigUint::ZERO
+        );
+
+        let mut dst = BigUint::one() << 4_000;
+        reassemble_into(&coeffs, piece_bits, &mut dst);
+        assert_eq!(dst, expected);
+        reassemble_into::<Vec<u64>>(&[], piece_bits, &mut dst);
+        assert_eq!(dst, BigUint::ZERO);
+    }
 }
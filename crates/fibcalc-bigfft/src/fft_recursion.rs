@@ -1,30 +1,75 @@
 //! Recursive FFT implementation with parallelism.
-#![allow(dead_code)] // Infrastructure: alternative FFT implementation for parallel threshold tuning
 
-use crate::fermat::FermatNum;
+use rayon::prelude::*;
+
+use crate::fermat::{FermatNum, Limbs};
+use crate::fft_core::{bit_reverse_permutation, butterflies};
 
 /// Recursive FFT with threshold-based parallelism.
-pub fn fft_recursive(data: &mut [FermatNum], shift: usize, parallel_threshold: usize) {
+///
+/// Produces the same output as [`crate::fft_core::fft_forward`]. After the
+/// bit-reversal permutation the even- and odd-indexed sub-transforms occupy
+/// the two halves of `data`, so they are computed with `rayon::join` and
+/// combined by a parallel butterfly pass. Sub-transforms of at most
+/// `parallel_threshold` coefficients run sequentially.
+pub fn fft_recursive<S: Limbs>(data: &mut [FermatNum<S>], shift: usize, parallel_threshold: usize) {
     let n = data.len();
     if n <= 1 {
         return;
     }
 
-    if n <= parallel_threshold {
-        // Sequential base case
-        fft_sequential(data, shift);
-    } else {
-        // P2 optimization: parallel recursion via rayon::join on even/odd
-        // butterfly splits would improve throughput for very large transforms.
-        // Sequential fallback is correct and sufficient for current workloads.
+    bit_reverse_permutation(data);
+    fft_split(data, shift, parallel_threshold);
+}
+
+/// Inverse counterpart of [`fft_recursive`], matching
+/// [`crate::fft_core::fft_inverse`].
+pub fn fft_inverse_recursive<S: Limbs>(
+    data: &mut [FermatNum<S>],
+    shift: usize,
+    parallel_threshold: usize,
+) {
+    let n = data.len();
+    if n <= 1 {
+        return;
+    }
+
+    data[1..].reverse();
+    fft_recursive(data, shift, parallel_threshold);
+
+    let log_n = n.trailing_zeros() as usize;
+    data.par_iter_mut()
+        .with_min_len(parallel_threshold.max(1))
+        .for_each(|elem| elem.shift_right(log_n));
+}
+
+/// Decimation-in-time step on bit-reversed input.
+fn fft_split<S: Limbs>(data: &mut [FermatNum<S>], shift: usize, parallel_threshold: usize) {
+    let n = data.len();
+    if n <= parallel_threshold.max(1) {
         fft_sequential(data, shift);
+        return;
     }
+
+    let half = n / 2;
+    let (even, odd) = data.split_at_mut(half);
+    rayon::join(
+        || fft_split(even, shift, parallel_threshold),
+        || fft_split(odd, shift, parallel_threshold),
+    );
+
+    // Combine: X[j] = E[j] + w^j O[j], X[j + half] = E[j] - w^j O[j]
+    let step = 2 * shift / n;
+    even.par_iter_mut()
+        .zip(odd.par_iter_mut())
+        .enumerate()
+        .with_min_len(parallel_threshold.max(1))
+        .for_each(|(j, (upper, lower))| FermatNum::butterfly(upper, lower, step * j));
 }
 
-/// Sequential FFT (base case for recursion).
-fn fft_sequential(data: &mut [FermatNum], shift: usize) {
-    use crate::fft_core::fft_forward;
-    fft_forward(data, shift);
+/// Sequential FFT (base case for recursion), on bit-reversed input.
+fn fft_sequential<S: Limbs>(data: &mut [FermatNum<S>], shift: usize) {
+    butterflies(data, shift);
 }
 
 #[cfg(test)]
@@ -84,7 +129,7 @@ mod tests {
 
     #[test]
     fn fft_recursive_above_threshold_roundtrip() {
-        // n=4 elements, threshold=2 -> would go to parallel path (which currently falls back to sequential)
+        // n=4 elements, threshold=2 -> parallel split into two sequential halves
         let shift = 8;
         let vals = [5u64, 10, 15, 20];
         let mut data: Vec<FermatNum> = vals
@@ -180,4 +225,41 @@ mod tests {
         // The sum element (index 0 after forward FFT) should be 1+2+3+4 = 10
         assert_eq!(transformed[0], BigUint::from(10u64));
     }
+
+    #[test]
+    fn fft_recursive_matches_iterative_forward() {
+        use crate::fft_core::fft_forward;
+        let shift = 64;
+        let n = 64u64;
+        let input: Vec<FermatNum> = (0..n)
+            .map(|v| FermatNum::from_biguint(&BigUint::from(v * v + 3), shift))
+            .collect();
+        let mut expected = input.clone();
+        fft_forward(&mut expected, shift);
+        for threshold in [0, 1, 2, 8, 32, 64] {
+            let mut data = input.clone();
+            fft_recursive(&mut data, shift, threshold);
+            for (i, (got, want)) in data.iter().zip(&expected).enumerate() {
+                assert_eq!(
+                    got.to_biguint(),
+                    want.to_biguint(),
+                    "threshold {threshold}, index {i}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn fft_inverse_recursive_roundtrip() {
+        let shift = 64;
+        let original: Vec<BigUint> = (0..32u64).map(|v| BigUint::from(v * 7 + 1)).collect();
+        let mut data: Vec<FermatNum> = original
+            .iter()
+            .map(|v| FermatNum::from_biguint(v, shift))
+            .collect();
+        fft_recursive(&mut data, shift, 4);
+        fft_inverse_recursive(&mut data, shift, 4);
+        let got: Vec<BigUint> = data.iter().map(FermatNum::to_biguint).collect();
+        assert_eq!(got, original);
+    }
 }
@@ -2,6 +2,11 @@
 //!
 //! FFT-based big number multiplication using Fermat numbers.
 //! Port of the Go `internal/bigfft` package.
+//!
+//! The Fermat ring, its transforms and the polynomial type behind the
+//! multiplier are public in [`ring`]. [`mul_signed`] and
+//! [`BarrettModulus`] extend the multiplier to `BigInt` and modular
+//! products.
 #![warn(missing_docs)]
 // Crate-level #![allow(dead_code)] removed -- individual modules/items annotated instead
 
@@ -15,12 +20,24 @@ pub(crate) mod fft_core;
 pub(crate) mod fft_poly;
 pub(crate) mod fft_recursion;
 pub(crate) mod memory_est;
+pub(crate) mod modular;
+pub(crate) mod ntt;
 pub mod pool;
 pub(crate) mod pool_warming;
+pub mod ring;
 pub(crate) mod scan;
 
 // Re-exports
-pub use fft::{mul, mul_to, sqr, sqr_to};
+pub use fft::{
+    mul, mul_to, mul_to_with, mul_with, sqr, sqr_add_sqr,
+    sqr_add_sqr_to_with, sqr_add_sqr_with, sqr_to, sqr_to_with, sqr_with, sum_of_products,
+    sum_of_products_to_with, sum_of_products_with, FFTBackend, FFTConfig, FFTPlan,
+    TransformedOperand, DEFAULT_NTT_THRESHOLD_BITS,
+};
+pub use fft_cache::{CacheKey, CacheStats, FFTCache};
 pub use fft::pool_stats;
 pub use fft::warm_global_pool;
-pub use memory_est::estimate_fft_memory;
+pub use memory_est::{estimate_fft_memory, FFTLayout};
+pub use modular::{
+    barrett_reciprocal, mul_signed, mul_signed_with, mulmod, mulmod_with, BarrettModulus,
+};
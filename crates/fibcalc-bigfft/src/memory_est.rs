@@ -1,22 +1,185 @@
 //! Memory estimation for FFT operations.
+//!
+//! [`FFTLayout::for_product`] picks the parameters of one FFT product so
+//! that its working memory stays within a budget: the default Fermat plan,
+//! a plan with a different piece size, or a segmented product that
+//! multiplies the operands block by block.
 
-use crate::fermat::select_fft_params;
+use std::fmt;
+
+use crate::fermat::{fft_params_for_piece, select_fft_params};
+use crate::fft::{FFTBackend, FFTConfig, FFTPlan};
+use crate::ntt::ntt_len;
+
+/// Piece sizes tried when the default plan exceeds the budget.
+const CANDIDATE_PIECE_BITS: [usize; 11] = [
+    64, 128, 256, 512, 1024, 2048, 4096, 8192, 16_384, 32_768, 65_536,
+];
+
+/// Smallest block of a segmented product; below this, blocks are used
+/// even if their plan still exceeds the budget.
+const MIN_SEGMENT_BITS: usize = 1 << 16;
 
 /// Estimate memory usage for an FFT multiplication of two numbers.
 #[must_use]
 pub fn estimate_fft_memory(a_bits: usize, b_bits: usize) -> usize {
-    let (_piece_bits, n, shift) = select_fft_params(a_bits, b_bits);
-    let limbs_per_element = shift.div_ceil(64) + 1;
+    let (piece_bits, n, shift) = select_fft_params(a_bits, b_bits);
+    plan_memory(&FFTPlan {
+        piece_bits,
+        len: n,
+        fermat_shift: shift,
+    })
+}
+
+/// Working memory of one Fermat FFT product under `plan`.
+fn plan_memory(plan: &FFTPlan) -> usize {
+    let limbs_per_element = plan.fermat_shift.div_ceil(64) + 1;
     let bytes_per_element = limbs_per_element * 8;
 
     // Two input polynomials + output + temporaries
-    let poly_bytes = n * bytes_per_element * 4;
+    let poly_bytes = plan.len * bytes_per_element * 4;
     // Root tables
-    let root_bytes = n * 8 * 2;
+    let root_bytes = plan.len * 8 * 2;
 
     poly_bytes + root_bytes
 }
 
+/// Memory held by one forward transform under `plan`.
+fn transform_memory(plan: &FFTPlan) -> usize {
+    plan.len * (plan.fermat_shift.div_ceil(64) + 1) * 8
+}
+
+/// Whether `count` forward transforms under `plan` can be kept alongside
+/// one block product within the budget of `config`.
+pub(crate) fn transforms_fit(plan: &FFTPlan, count: usize, config: &FFTConfig) -> bool {
+    config
+        .memory_limit_bytes
+        .checked_sub(plan_memory(plan))
+        .is_some_and(|spare| count.saturating_mul(transform_memory(plan)) <= spare)
+}
+
+/// Working memory of one three-prime NTT product of length `len`: three
+/// residue vectors plus, with all primes in flight, two operand vectors
+/// per prime.
+const fn ntt_memory(len: usize) -> usize {
+    len * 8 * 9
+}
+
+/// The Fermat plan with the smallest working memory for this product.
+fn smallest_plan(a_bits: usize, b_bits: usize) -> FFTPlan {
+    CANDIDATE_PIECE_BITS
+        .iter()
+        .map(|&piece| {
+            let (piece_bits, len, fermat_shift) = fft_params_for_piece(a_bits, b_bits, piece);
+            FFTPlan {
+                piece_bits,
+                len,
+                fermat_shift,
+            }
+        })
+        .min_by_key(plan_memory)
+        .expect("at least one candidate piece size")
+}
+
+/// The default plan if it fits `limit`, else the smallest plan if that fits.
+fn fitting_plan(a_bits: usize, b_bits: usize, limit: usize) -> Option<FFTPlan> {
+    let default = FFTPlan::for_product(a_bits, b_bits);
+    if plan_memory(&default) <= limit {
+        return Some(default);
+    }
+    let smallest = smallest_plan(a_bits, b_bits);
+    (plan_memory(&smallest) <= limit).then_some(smallest)
+}
+
+/// How one FFT product is carried out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FFTLayout {
+    /// Three-prime NTT of `len` coefficients.
+    Ntt {
+        /// Transform length.
+        len: usize,
+    },
+    /// One Fermat transform of the whole product.
+    Whole(FFTPlan),
+    /// Operands cut into `segment_bits` blocks; every pair of blocks is
+    /// multiplied under `plan` and accumulated into the result.
+    Segmented {
+        /// Plan shared by all block products.
+        plan: FFTPlan,
+        /// Bits per operand block.
+        segment_bits: usize,
+    },
+}
+
+impl FFTLayout {
+    /// Layout for the product of an `a_bits`-bit and a `b_bits`-bit
+    /// operand within the memory budget of `config`.
+    ///
+    /// The configured backend is used when it fits the budget. Otherwise
+    /// Fermat plans are tried from the default one down to the smallest
+    /// piece-size variant, and as a last resort the product is segmented.
+    #[must_use]
+    pub fn for_product(a_bits: usize, b_bits: usize, config: &FFTConfig) -> Self {
+        let limit = config.memory_limit_bytes;
+        let max_bits = a_bits.max(b_bits);
+        if config.backend_for(max_bits) == FFTBackend::Ntt {
+            let len = ntt_len(a_bits.div_ceil(64), b_bits.div_ceil(64));
+            if ntt_memory(len) <= limit {
+                return Self::Ntt { len };
+            }
+        }
+
+        if let Some(plan) = fitting_plan(a_bits, b_bits, limit) {
+            return Self::Whole(plan);
+        }
+
+        let mut segment_bits = max_bits;
+        loop {
+            // Whole limbs, so blocks are sliced out of the operands' digits.
+            segment_bits = segment_bits
+                .div_ceil(2)
+                .next_multiple_of(64)
+                .max(MIN_SEGMENT_BITS);
+            if let Some(plan) = fitting_plan(segment_bits, segment_bits, limit) {
+                return Self::Segmented { plan, segment_bits };
+            }
+            if segment_bits == MIN_SEGMENT_BITS {
+                let plan = smallest_plan(segment_bits, segment_bits);
+                return Self::Segmented { plan, segment_bits };
+            }
+        }
+    }
+
+    /// Estimated working memory of one (block) product, in bytes.
+    #[must_use]
+    pub fn memory_bytes(&self) -> usize {
+        match self {
+            Self::Ntt { len } => ntt_memory(*len),
+            Self::Whole(plan) | Self::Segmented { plan, .. } => plan_memory(plan),
+        }
+    }
+}
+
+impl fmt::Display for FFTLayout {
+    #[allow(clippy::cast_precision_loss)]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mib = self.memory_bytes() as f64 / (1024.0 * 1024.0);
+        match self {
+            Self::Ntt { len } => write!(f, "NTT, {len} coefficients, {mib:.1} MiB"),
+            Self::Whole(plan) => write!(
+                f,
+                "Fermat, {} x {}-bit pieces, shift {}, {mib:.1} MiB",
+                plan.len, plan.piece_bits, plan.fermat_shift
+            ),
+            Self::Segmented { plan, segment_bits } => write!(
+                f,
+                "Fermat segmented into {segment_bits}-bit blocks, {} x {}-bit pieces, shift {}, {mib:.1} MiB per block",
+                plan.len, plan.piece_bits, plan.fermat_shift
+            ),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -33,4 +196,44 @@ mod tests {
         let large = estimate_fft_memory(1_000_000, 1_000_000);
         assert!(large > small);
     }
+
+    #[test]
+    fn layout_honors_memory_limit() {
+        let bits = 4_000_000;
+        let fermat = FFTConfig::with_threshold(0).with_backend(FFTBackend::Fermat);
+        let unlimited = FFTLayout::for_product(bits, bits, &fermat);
+        assert_eq!(
+            unlimited,
+            FFTLayout::Whole(FFTPlan::for_product(bits, bits))
+        );
+
+        let smallest = smallest_plan(bits, bits);
+        assert!(plan_memory(&smallest) < unlimited.memory_bytes());
+        let tight = fermat.with_memory_limit(plan_memory(&smallest));
+        assert_eq!(
+            FFTLayout::for_product(bits, bits, &tight),
+            FFTLayout::Whole(smallest)
+        );
+
+        let tiny = fermat.with_memory_limit(1);
+        match FFTLayout::for_product(bits, bits, &tiny) {
+            FFTLayout::Segmented { segment_bits, plan } => {
+                assert_eq!(segment_bits, MIN_SEGMENT_BITS);
+                assert!(plan.fits(MIN_SEGMENT_BITS as u64, MIN_SEGMENT_BITS as u64));
+            }
+            other => panic!("expected a segmented layout, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn ntt_over_budget_falls_back_to_fermat() {
+        let ntt = FFTConfig::with_threshold(0).with_backend(FFTBackend::Ntt);
+        let layout = FFTLayout::for_product(100_000, 100_000, &ntt);
+        assert!(matches!(layout, FFTLayout::Ntt { .. }));
+        let limited = ntt.with_memory_limit(layout.memory_bytes() - 1);
+        assert!(!matches!(
+            FFTLayout::for_product(100_000, 100_000, &limited),
+            FFTLayout::Ntt { .. }
+        ));
+    }
 }
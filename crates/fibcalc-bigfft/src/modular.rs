@@ -0,0 +1,265 @@
+//! Signed and modular products on top of the FFT multiplier.
+//!
+//! [`mul_signed`] multiplies `BigInt`s through [`mul_with`] on their
+//! magnitudes. [`BarrettModulus`] precomputes `floor(4^k / m)` for a
+//! `k`-bit modulus `m` by Newton iteration; after that, reducing a product
+//! below `m^2` costs two more multiplications of about `k` bits and at most
+//! two subtractions. Every multiplication goes through the FFT pipeline
+//! once its operands reach the configured threshold.
+//!
+//! # Example
+//! ```
+//! use fibcalc_bigfft::{mulmod, BarrettModulus};
+//! use num_bigint::BigUint;
+//!
+//! let m = BigUint::from(10u32).pow(50) + 151u32;
+//! let a = BigUint::from(3u32).pow(100) % &m;
+//! let b = BigUint::from(7u32).pow(57) % &m;
+//!
+//! let barrett = BarrettModulus::new(m.clone());
+//! assert_eq!(barrett.mulmod(&a, &b), &a * &b % &m);
+//! assert_eq!(mulmod(&a, &b, &m), &a * &b % &m);
+//! ```
+
+use num_bigint::{BigInt, BigUint};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+
+use crate::fft::{mul_with, sqr_with, FFTConfig};
+
+/// Reciprocals of values up to this many bits are computed by division.
+const NEWTON_CUTOFF_BITS: u64 = 4096;
+
+/// Multiply two `BigInt`s, using FFT for large operands.
+#[must_use]
+pub fn mul_signed(a: &BigInt, b: &BigInt) -> BigInt {
+    mul_signed_with(a, b, &FFTConfig::default())
+}
+
+/// Multiply two `BigInt`s, using FFT once `config.threshold_bits` is
+/// reached. The magnitudes are multiplied by [`mul_with`].
+#[must_use]
+pub fn mul_signed_with(a: &BigInt, b: &BigInt, config: &FFTConfig) -> BigInt {
+    let magnitude = mul_with(a.magnitude(), b.magnitude(), config);
+    BigInt::from_biguint(a.sign() * b.sign(), magnitude)
+}
+
+/// `floor(2^(2b) / p)` where `b = p.bits()`, by Newton iteration.
+///
+/// Each step recurses on the top half of `p` and doubles the precision,
+/// then corrects the last few units with a small-quotient division.
+///
+/// # Panics
+///
+/// Panics if `p` is zero.
+#[must_use]
+pub fn barrett_reciprocal(p: &BigUint, config: &FFTConfig) -> BigUint {
+    assert!(!p.is_zero(), "reciprocal of zero");
+    let b = p.bits();
+    if b <= NEWTON_CUTOFF_BITS {
+        return (BigUint::one() << (2 * b)) / p;
+    }
+
+    let h = b / 2 + 1;
+    let shift = b - h;
+    let x0 = barrett_reciprocal(&(p >> shift), config) << shift;
+    // x1 = 2*x0 - p*x0^2 / 2^(2b)
+    let correction = mul_with(p, &sqr_with(&x0, config), config) >> (2 * b);
+    let x1 = (x0 << 1u32) - correction;
+
+    let target = BigUint::one() << (2 * b);
+    let product = mul_with(p, &x1, config);
+    if product > target {
+        x1 - (product - target).div_ceil(p)
+    } else {
+        x1 + (target - product) / p
+    }
+}
+
+/// A modulus with its precomputed Barrett reciprocal.
+///
+/// Building one costs a few multiplications of the modulus size; it pays
+/// off as soon as the same modulus reduces more than a handful of products.
+#[derive(Debug, Clone)]
+pub struct BarrettModulus {
+    modulus: BigUint,
+    /// `k`, the bit length of the modulus.
+    bits: u64,
+    /// `floor(4^k / modulus)`.
+    reciprocal: BigUint,
+    config: FFTConfig,
+}
+
+impl BarrettModulus {
+    /// Precompute the reciprocal of `modulus`, multiplying under the
+    /// default [`FFTConfig`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modulus` is zero.
+    #[must_use]
+    pub fn new(modulus: BigUint) -> Self {
+        Self::with_config(modulus, FFTConfig::default())
+    }
+
+    /// Precompute the reciprocal of `modulus`, multiplying under `config`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modulus` is zero.
+    #[must_use]
+    pub fn with_config(modulus: BigUint, config: FFTConfig) -> Self {
+        assert!(!modulus.is_zero(), "modulus cannot be zero");
+        let reciprocal = barrett_reciprocal(&modulus, &config);
+        Self {
+            bits: modulus.bits(),
+            modulus,
+            reciprocal,
+            config,
+        }
+    }
+
+    /// The modulus.
+    #[must_use]
+    pub const fn modulus(&self) -> &BigUint {
+        &self.modulus
+    }
+
+    /// `x mod m`.
+    ///
+    /// Values below `4^k` for a `k`-bit modulus, which includes every
+    /// product of two reduced values, use Barrett reduction; larger values
+    /// fall back to division.
+    #[must_use]
+    pub fn reduce(&self, x: &BigUint) -> BigUint {
+        if x < &self.modulus {
+            return x.clone();
+        }
+        if x.bits() > 2 * self.bits {
+            return x % &self.modulus;
+        }
+        // HAC 14.42 in base 2: the estimate is at most 2 below the quotient.
+        let top = x >> (self.bits - 1);
+        let q = mul_with(&top, &self.reciprocal, &self.config) >> (self.bits + 1);
+        let mut r = x - mul_with(&q, &self.modulus, &self.config);
+        while r >= self.modulus {
+            r -= &self.modulus;
+        }
+        r
+    }
+
+    /// `a * b mod m`; fastest when `a` and `b` are reduced.
+    #[must_use]
+    pub fn mulmod(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        self.reduce(&mul_with(a, b, &self.config))
+    }
+
+    /// `a^2 mod m`; fastest when `a` is reduced.
+    #[must_use]
+    pub fn sqrmod(&self, a: &BigUint) -> BigUint {
+        self.reduce(&sqr_with(a, &self.config))
+    }
+}
+
+/// `a * b mod m`, using FFT for large operands.
+///
+/// # Panics
+///
+/// Panics if `m` is zero.
+#[must_use]
+pub fn mulmod(a: &BigUint, b: &BigUint, m: &BigUint) -> BigUint {
+    mulmod_with(a, b, m, &FFTConfig::default())
+}
+
+/// `a * b mod m`, using FFT once `config.threshold_bits` is reached.
+///
+/// Computes the Barrett reciprocal of `m` for this one product; build a
+/// [`BarrettModulus`] to reuse it across products.
+///
+/// # Panics
+///
+/// Panics if `m` is zero.
+#[must_use]
+pub fn mulmod_with(a: &BigUint, b: &BigUint, m: &BigUint, config: &FFTConfig) -> BigUint {
+    BarrettModulus::with_config(m.clone(), *config).mulmod(a, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fft::FFTBackend;
+    use num_bigint::Sign;
+
+    fn configs() -> [FFTConfig; 3] {
+        [
+            FFTConfig::default(),
+            FFTConfig::with_threshold(0).with_backend(FFTBackend::Fermat),
+            FFTConfig::with_threshold(0).with_backend(FFTBackend::Ntt),
+        ]
+    }
+
+    #[test]
+    fn signed_products_match_bigint() {
+        let a = BigInt::from((BigUint::one() << 30_000u32) / BigUint::from(7u64));
+        let b = BigInt::from((BigUint::one() << 20_011u32) - BigUint::from(3u64));
+        for config in &configs() {
+            for (x, y) in [(&a, &b), (&-&a, &b), (&a, &-&b), (&-&a, &-&b)] {
+                assert_eq!(mul_signed_with(x, y, config), x * y);
+            }
+            let zero = BigInt::zero();
+            let product = mul_signed_with(&-&a, &zero, config);
+            assert_eq!(product.sign(), Sign::NoSign);
+        }
+        assert_eq!(
+            mul_signed(&BigInt::from(-12), &BigInt::from(5)),
+            BigInt::from(-60)
+        );
+    }
+
+    #[test]
+    fn reciprocal_is_exact_floor() {
+        for p in [
+            BigUint::from(10u32).pow(4000) + 12_345u32,
+            BigUint::one() << 9_000u32,
+            (BigUint::one() << 20_000u32) - 1u32,
+        ] {
+            let r = barrett_reciprocal(&p, &FFTConfig::with_threshold(0));
+            let target = BigUint::one() << (2 * p.bits());
+            assert!(&r * &p <= target);
+            assert!((&r + 1u32) * &p > target);
+        }
+    }
+
+    #[test]
+    fn mulmod_matches_remainder() {
+        let moduli = [
+            BigUint::one(),
+            BigUint::from(1_000_000_007u64),
+            BigUint::from(10u32).pow(1_000),
+            (BigUint::one() << 12_000u32) + 1u32,
+            (BigUint::one() << 30_001u32) / BigUint::from(13u64),
+        ];
+        for config in &configs() {
+            for m in &moduli {
+                let barrett = BarrettModulus::with_config(m.clone(), *config);
+                let a = (BigUint::one() << (m.bits() + 5)) / BigUint::from(11u64) % m;
+                let b = (m - 1u32) / 3u32;
+                let max = m - 1u32;
+                assert_eq!(barrett.mulmod(&a, &b), &a * &b % m);
+                assert_eq!(barrett.sqrmod(&a), &a * &a % m);
+                assert_eq!(barrett.mulmod(&max, &max), &max * &max % m);
+                assert_eq!(barrett.reduce(&(m * 3u32 + 2u32)), BigUint::from(2u32) % m);
+                // Inputs beyond 4^k fall back to division.
+                let huge = (&a + 1u32) << (3 * m.bits());
+                assert_eq!(barrett.reduce(&huge), &huge % m);
+                assert_eq!(mulmod_with(&a, &b, m, config), &a * &b % m);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "modulus cannot be zero")]
+    fn zero_modulus_panics() {
+        let _ = BarrettModulus::new(BigUint::zero());
+    }
+}
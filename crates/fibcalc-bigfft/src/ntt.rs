@@ -0,0 +1,496 @@
+//! Three-prime number-theoretic transform multiplication.
+//!
+//! Operands are split into 64-bit limbs and convolved modulo three
+//! ~62-bit primes of the form `c * 2^k + 1`, using Montgomery arithmetic
+//! on `u64`. Each convolution coefficient is below
+//! `min(len_a, len_b) * 2^128 < p1 * p2 * p3 ≈ 2^183.7`, so Garner's CRT
+//! recovers it exactly for any transform length the primes support.
+//! Sums of products are added in the transform domain, as long as the
+//! overlaps `min(len_a, len_b)` of all terms add up to at most `2^55`.
+
+use num_bigint::BigUint;
+use num_traits::Zero;
+use rayon::prelude::*;
+
+use crate::bump::with_scratch;
+use crate::fermat::load_limbs;
+
+/// A word-sized NTT prime with its Montgomery constants (`R = 2^64`).
+struct Prime {
+    p: u64,
+    /// `-p^{-1} mod 2^64`.
+    neg_inv: u64,
+    /// `R^2 mod p`, for converting into Montgomery form.
+    r2: u64,
+    /// A primitive root modulo `p`.
+    generator: u64,
+    /// Largest `k` with `2^k | p - 1`.
+    two_adicity: u32,
+}
+
+impl Prime {
+    const fn new(p: u64, generator: u64) -> Self {
+        // Newton iteration for p^{-1} mod 2^64 (p is odd).
+        let mut inv = p;
+        let mut i = 0;
+        while i < 6 {
+            inv = inv.wrapping_mul(2u64.wrapping_sub(p.wrapping_mul(inv)));
+            i += 1;
+        }
+        let r = (u64::MAX as u128 % p as u128 + 1) % p as u128;
+        #[allow(clippy::cast_possible_truncation)]
+        let r2 = (r * r % p as u128) as u64;
+        Self {
+            p,
+            neg_inv: inv.wrapping_neg(),
+            r2,
+            generator,
+            two_adicity: (p - 1).trailing_zeros(),
+        }
+    }
+
+    /// Montgomery reduction of `t < p * 2^64`.
+    #[inline]
+    #[allow(clippy::cast_possible_truncation)]
+    const fn reduce(&self, t: u128) -> u64 {
+        let m = (t as u64).wrapping_mul(self.neg_inv);
+        let u = ((t + m as u128 * self.p as u128) >> 64) as u64;
+        if u >= self.p {
+            u - self.p
+        } else {
+            u
+        }
+    }
+
+    /// Montgomery product `a * b * R^{-1} mod p`.
+    #[inline]
+    const fn mul(&self, a: u64, b: u64) -> u64 {
+        self.reduce(a as u128 * b as u128)
+    }
+
+    #[inline]
+    const fn add(&self, a: u64, b: u64) -> u64 {
+        let s = a + b;
+        if s >= self.p {
+            s - self.p
+        } else {
+            s
+        }
+    }
+
+    #[inline]
+    const fn sub(&self, a: u64, b: u64) -> u64 {
+        if a >= b {
+            a - b
+        } else {
+            a + self.p - b
+        }
+    }
+
+    /// Convert `x < 2^64` into Montgomery form.
+    #[inline]
+    const fn to_mont(&self, x: u64) -> u64 {
+        self.mul(x % self.p, self.r2)
+    }
+
+    /// Convert out of Montgomery form.
+    #[inline]
+    const fn to_normal(&self, x: u64) -> u64 {
+        self.reduce(x as u128)
+    }
+
+    /// `base^exp` with `base` in Montgomery form.
+    const fn pow(&self, mut base: u64, mut exp: u64) -> u64 {
+        let mut acc = self.to_mont(1);
+        while exp > 0 {
+            if exp & 1 == 1 {
+                acc = self.mul(acc, base);
+            }
+            base = self.mul(base, base);
+            exp >>= 1;
+        }
+        acc
+    }
+
+    /// Montgomery-form primitive `n`-th root of unity (`n` a power of two).
+    const fn root_of_unity(&self, n: usize) -> u64 {
+        self.pow(self.to_mont(self.generator), (self.p - 1) / n as u64)
+    }
+
+    /// Montgomery-form modular inverse (`x` in Montgomery form, nonzero).
+    const fn inv(&self, x: u64) -> u64 {
+        self.pow(x, self.p - 2)
+    }
+}
+
+/// The three NTT primes: `29·2^57+1`, `69·2^55+1` and `27·2^56+1`.
+static PRIMES: [Prime; 3] = [
+    Prime::new(4_179_340_454_199_820_289, 3),
+    Prime::new(2_485_986_994_308_513_793, 5),
+    Prime::new(1_945_555_039_024_054_273, 5),
+];
+
+/// Largest supported transform length (`2^55` coefficients).
+pub const MAX_NTT_LEN: usize = 1 << 55;
+
+/// Transform length for a product of `a_limbs` and `b_limbs` 64-bit limbs.
+#[must_use]
+pub fn ntt_len(a_limbs: usize, b_limbs: usize) -> usize {
+    let len = (a_limbs + b_limbs).next_power_of_two();
+    assert!(len <= MAX_NTT_LEN, "operands too large for the NTT backend");
+    len
+}
+
+/// Twiddle factors `w^j` for `j < n/2`, `w` a primitive `n`-th root.
+fn twiddles(prime: &Prime, n: usize, inverse: bool) -> Vec<u64> {
+    let mut w = prime.root_of_unity(n);
+    if inverse {
+        w = prime.inv(w);
+    }
+    let mut table = Vec::with_capacity(n / 2);
+    let mut acc = prime.to_mont(1);
+    for _ in 0..n / 2 {
+        table.push(acc);
+        acc = prime.mul(acc, w);
+    }
+    table
+}
+
+/// Decimation-in-frequency forward NTT: natural order in, bit-reversed out.
+fn forward(prime: &Prime, data: &mut [u64], roots: &[u64]) {
+    let n = data.len();
+    let mut len = n;
+    while len >= 2 {
+        let half = len / 2;
+        let stride = n / len;
+        for block in data.chunks_exact_mut(len) {
+            let (lo, hi) = block.split_at_mut(half);
+            for (j, (u, v)) in lo.iter_mut().zip(hi.iter_mut()).enumerate() {
+                let (a, b) = (*u, *v);
+                *u = prime.add(a, b);
+                *v = prime.mul(prime.sub(a, b), roots[j * stride]);
+            }
+        }
+        len = half;
+    }
+}
+
+/// Decimation-in-time inverse NTT: bit-reversed in, natural order out,
+/// scaled by `1/n`.
+fn inverse(prime: &Prime, data: &mut [u64], inv_roots: &[u64]) {
+    let n = data.len();
+    let mut len = 2;
+    while len <= n {
+        let half = len / 2;
+        let stride = n / len;
+        for block in data.chunks_exact_mut(len) {
+            let (lo, hi) = block.split_at_mut(half);
+            for (j, (u, v)) in lo.iter_mut().zip(hi.iter_mut()).enumerate() {
+                let a = *u;
+                let b = prime.mul(*v, inv_roots[j * stride]);
+                *u = prime.add(a, b);
+                *v = prime.sub(a, b);
+            }
+        }
+        len *= 2;
+    }
+    let n_inv = prime.inv(prime.to_mont(n as u64));
+    for x in data.iter_mut() {
+        *x = prime.mul(*x, n_inv);
+    }
+}
+
+/// Load `limbs` into Montgomery form, zero-padded to `len`.
+fn load(prime: &Prime, limbs: &[u64], len: usize) -> Vec<u64> {
+    let mut data = Vec::with_capacity(len);
+    data.extend(limbs.iter().map(|&x| prime.to_mont(x)));
+    data.resize(len, 0);
+    data
+}
+
+/// One term of an NTT sum as 64-bit limbs: `a * b`, or `a^2` when `b`
+/// is `None`.
+type Term<'a> = (&'a [u64], Option<&'a [u64]>);
+
+/// Cyclic convolution of the sum of products of `terms` modulo `prime`,
+/// with one inverse transform, returned in plain (non-Montgomery) form.
+fn convolve(prime: &Prime, terms: &[Term<'_>], len: usize) -> Vec<u64> {
+    let roots = twiddles(prime, len, false);
+    let mut acc: Option<Vec<u64>> = None;
+    for &(a, b) in terms {
+        let mut fa = load(prime, a, len);
+        forward(prime, &mut fa, &roots);
+        if let Some(b) = b {
+            let mut fb = load(prime, b, len);
+            forward(prime, &mut fb, &roots);
+            for (x, y) in fa.iter_mut().zip(&fb) {
+                *x = prime.mul(*x, *y);
+            }
+        } else {
+            for x in &mut fa {
+                *x = prime.mul(*x, *x);
+            }
+        }
+        match &mut acc {
+            Some(acc) => {
+                for (x, y) in acc.iter_mut().zip(&fa) {
+                    *x = prime.add(*x, *y);
+                }
+            }
+            None => acc = Some(fa),
+        }
+    }
+    let mut fa = acc.expect("at least one term");
+    inverse(prime, &mut fa, &twiddles(prime, len, true));
+    for x in &mut fa {
+        *x = prime.to_normal(*x);
+    }
+    fa
+}
+
+/// Garner constants: `p1^{-1} mod p2`, `(p1 p2)^{-1} mod p3` and
+/// `p1 mod p3`, all in Montgomery form.
+struct Garner {
+    p1_inv_mod_p2: u64,
+    p1p2_inv_mod_p3: u64,
+    p1_mod_p3: u64,
+}
+
+impl Garner {
+    fn new() -> Self {
+        let [p1, p2, p3] = &PRIMES;
+        let p1_mod_p3 = p3.to_mont(p1.p);
+        Self {
+            p1_inv_mod_p2: p2.inv(p2.to_mont(p1.p)),
+            p1p2_inv_mod_p3: p3.inv(p3.mul(p1_mod_p3, p3.to_mont(p2.p))),
+            p1_mod_p3,
+        }
+    }
+
+    /// Reconstruct `x < p1 p2 p3` from its residues as three 64-bit limbs.
+    #[allow(clippy::cast_possible_truncation)]
+    fn combine(&self, r1: u64, r2: u64, r3: u64) -> [u64; 3] {
+        let [p1, p2, p3] = &PRIMES;
+        // x = v1 + v2 p1 + v3 p1 p2
+        let v1 = r1;
+        let v2 = p2.to_normal(p2.mul(p2.sub(p2.to_mont(r2), p2.to_mont(v1)), self.p1_inv_mod_p2));
+        let v1_v2p1 = p3.add(p3.to_mont(v1), p3.mul(p3.to_mont(v2), self.p1_mod_p3));
+        let v3 = p3.to_normal(p3.mul(p3.sub(p3.to_mont(r3), v1_v2p1), self.p1p2_inv_mod_p3));
+
+        // low = v1 + v2 p1 < 2^126
+        let low = u128::from(v1) + u128::from(v2) * u128::from(p1.p);
+        // v3 p1 p2 as 192 bits: (p1 p2) is a 124-bit constant.
+        let p1p2 = u128::from(p1.p) * u128::from(p2.p);
+        let p1p2_lo = u128::from(p1p2 as u64);
+        let p1p2_hi = p1p2 >> 64;
+        let t0 = u128::from(v3) * p1p2_lo;
+        let t1 = u128::from(v3) * p1p2_hi + (t0 >> 64);
+
+        let w0 = u128::from(t0 as u64) + u128::from(low as u64);
+        let w1 = u128::from(t1 as u64) + (low >> 64) + (w0 >> 64);
+        let w2 = (t1 >> 64) + (w1 >> 64);
+        [w0 as u64, w1 as u64, w2 as u64]
+    }
+}
+
+/// Write the sum of the products of `terms` (squares where `b` is `None`)
+/// into `dst` using the three-prime NTT, with one inverse transform per
+/// prime.
+fn ntt_sum(dst: &mut BigUint, terms: &[(&BigUint, Option<&BigUint>)], parallel: bool) {
+    let digits: Vec<(Vec<u64>, Option<Vec<u64>>)> = terms
+        .iter()
+        .map(|&(a, b)| (a.to_u64_digits(), b.map(BigUint::to_u64_digits)))
+        .collect();
+    let terms: Vec<Term<'_>> = digits
+        .iter()
+        .map(|(a, b)| (a.as_slice(), b.as_deref()))
+        .filter(|(a, b)| !a.is_empty() && !b.is_some_and(<[u64]>::is_empty))
+        .collect();
+    let operand_lens = |&(a, b): &Term<'_>| (a.len(), b.map_or(a.len(), <[u64]>::len));
+    let Some(len) = terms
+        .iter()
+        .map(|term| {
+            let (a_len, b_len) = operand_lens(term);
+            ntt_len(a_len, b_len)
+        })
+        .max()
+    else {
+        dst.set_zero();
+        return;
+    };
+    assert!(
+        PRIMES.iter().all(|p| len.trailing_zeros() <= p.two_adicity),
+        "transform length exceeds the NTT primes' 2-adicity"
+    );
+    let overlap: usize = terms
+        .iter()
+        .map(|term| {
+            let (a_len, b_len) = operand_lens(term);
+            a_len.min(b_len)
+        })
+        .sum();
+    assert!(
+        overlap <= MAX_NTT_LEN,
+        "sum of products too large for the NTT backend"
+    );
+
+    let residues: Vec<Vec<u64>> = if parallel {
+        PRIMES
+            .par_iter()
+            .map(|prime| convolve(prime, &terms, len))
+            .collect()
+    } else {
+        PRIMES
+            .iter()
+            .map(|prime| convolve(prime, &terms, len))
+            .collect()
+    };
+
+    let garner = Garner::new();
+    let out_len = terms
+        .iter()
+        .map(|term| {
+            let (a_len, b_len) = operand_lens(term);
+            a_len + b_len
+        })
+        .max()
+        .unwrap_or(0);
+    with_scratch(|arena| {
+        let out = arena.alloc_slice(out_len + 3);
+        // Running 192-bit accumulator (carry into the next limb).
+        let (mut c0, mut c1, mut c2) = (0u64, 0u64, 0u64);
+        let limbs = residues[0].iter().zip(&residues[1]).zip(&residues[2]);
+        for (limb, ((&r1, &r2), &r3)) in out.iter_mut().zip(limbs.take(out_len)) {
+            let [x0, x1, x2] = garner.combine(r1, r2, r3);
+            let (s0, k0) = c0.overflowing_add(x0);
+            let (s1, k1a) = c1.overflowing_add(x1);
+            let (s1, k1b) = s1.overflowing_add(u64::from(k0));
+            let s2 = c2 + x2 + u64::from(k1a) + u64::from(k1b);
+            *limb = s0;
+            (c0, c1, c2) = (s1, s2, 0);
+        }
+        out[out_len..].copy_from_slice(&[c0, c1, c2]);
+        load_limbs(out, dst);
+    });
+}
+
+/// Multiply two `BigUint`s with the three-prime NTT, writing the product
+/// into `dst`.
+pub fn ntt_multiply_to(dst: &mut BigUint, a: &BigUint, b: &BigUint, parallel: bool) {
+    ntt_sum(dst, &[(a, Some(b))], parallel);
+}
+
+/// Square a `BigUint` with the three-prime NTT (one forward transform per
+/// prime), writing the square into `dst`.
+pub fn ntt_square_to(dst: &mut BigUint, a: &BigUint, parallel: bool) {
+    ntt_sum(dst, &[(a, None)], parallel);
+}
+
+/// Write the sum of the products of `terms` into `dst` with the
+/// three-prime NTT, with one inverse transform per prime. A term whose two
+/// sides are the same value is squared.
+pub fn ntt_sum_of_products_to(dst: &mut BigUint, terms: &[(&BigUint, &BigUint)], parallel: bool) {
+    let terms: Vec<(&BigUint, Option<&BigUint>)> = terms
+        .iter()
+        .map(|&(a, b)| (a, (!std::ptr::eq(a, b)).then_some(b)))
+        .collect();
+    ntt_sum(dst, &terms, parallel);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_traits::One;
+
+    #[test]
+    fn montgomery_roundtrip_and_mul() {
+        for prime in &PRIMES {
+            for x in [0u64, 1, 2, 12_345, prime.p - 1, u64::MAX] {
+                assert_eq!(prime.to_normal(prime.to_mont(x)), x % prime.p);
+            }
+            let (a, b) = (prime.p - 2, 987_654_321u64);
+            let expected =
+                u64::try_from(u128::from(a) * u128::from(b) % u128::from(prime.p)).unwrap();
+            let got = prime.to_normal(prime.mul(prime.to_mont(a), prime.to_mont(b)));
+            assert_eq!(got, expected);
+        }
+    }
+
+    #[test]
+    fn roots_have_exact_order() {
+        for prime in &PRIMES {
+            let one = prime.to_mont(1);
+            let w = prime.root_of_unity(1 << 20);
+            assert_eq!(prime.pow(w, 1 << 20), one);
+            assert_ne!(prime.pow(w, 1 << 19), one);
+            assert_eq!(prime.mul(w, prime.inv(w)), one);
+        }
+    }
+
+    #[test]
+    fn garner_reconstructs_large_values() {
+        let garner = Garner::new();
+        let values = [
+            BigUint::ZERO,
+            BigUint::from(u64::MAX),
+            (BigUint::one() << 183) - BigUint::from(12_345u64),
+            (BigUint::one() << 150) + BigUint::from(99u64),
+        ];
+        for x in &values {
+            let residue = |p: u64| (x % p).iter_u64_digits().next().unwrap_or(0);
+            let limbs = garner.combine(
+                residue(PRIMES[0].p),
+                residue(PRIMES[1].p),
+                residue(PRIMES[2].p),
+            );
+            let got = BigUint::from(limbs[0])
+                + (BigUint::from(limbs[1]) << 64)
+                + (BigUint::from(limbs[2]) << 128);
+            assert_eq!(&got, x);
+        }
+    }
+
+    #[test]
+    fn ntt_multiply_matches_schoolbook() {
+        let cases = [
+            (BigUint::from(3u64), BigUint::from(5u64)),
+            (BigUint::from(u64::MAX), BigUint::from(u64::MAX)),
+            (
+                (BigUint::one() << 4096) - BigUint::one(),
+                (BigUint::one() << 3000) - BigUint::from(3u64),
+            ),
+            (
+                (BigUint::one() << 100_000) / BigUint::from(7u64),
+                BigUint::from(0xdead_beef_u64),
+            ),
+        ];
+        // One destination across all cases: results shrink and grow in it.
+        let mut dst = BigUint::ZERO;
+        for parallel in [false, true] {
+            for (a, b) in cases.iter().rev() {
+                ntt_multiply_to(&mut dst, a, b, parallel);
+                assert_eq!(dst, a * b);
+                ntt_square_to(&mut dst, a, parallel);
+                assert_eq!(dst, a * a);
+            }
+        }
+        ntt_multiply_to(&mut dst, &BigUint::ZERO, &cases[2].0, false);
+        assert_eq!(dst, BigUint::ZERO);
+    }
+
+    #[test]
+    fn ntt_sum_of_products_matches_schoolbook() {
+        let a = (BigUint::one() << 40_000) / BigUint::from(7u64);
+        let b = (BigUint::one() << 9_000) - BigUint::from(3u64);
+        let c = BigUint::from(u64::MAX);
+        let terms = [(&a, &b), (&a, &a), (&c, &b), (&c, &BigUint::ZERO)];
+        let expected = &a * &b + &a * &a + &c * &b;
+        let mut dst = BigUint::ZERO;
+        for parallel in [false, true] {
+            ntt_sum_of_products_to(&mut dst, &terms, parallel);
+            assert_eq!(dst, expected);
+        }
+        ntt_sum_of_products_to(&mut dst, &[], false);
+        assert_eq!(dst, BigUint::ZERO);
+    }
+}
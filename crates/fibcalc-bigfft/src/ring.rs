@@ -0,0 +1,37 @@
+//! Fermat-ring and polynomial arithmetic.
+//!
+//! The building blocks of the Schönhage-Strassen multiplier, for callers
+//! running their own convolutions:
+//!
+//! - [`FermatNum`]: a reduced residue mod `2^shift + 1`, over owned limbs
+//!   or any other [`Limbs`] storage.
+//! - [`fft_forward`] / [`fft_inverse`]: length-`n` transforms over that
+//!   ring, with `2^(2*shift/n)` as the `n`-th root of unity. `n` must be a
+//!   power of two dividing `2 * shift`.
+//! - [`Poly`]: an integer split into `piece_bits`-bit coefficients, with
+//!   transforms, pointwise products and cyclic convolution
+//!   (products mod `x^n - 1`).
+//!
+//! [`FFTPlan::for_product`](crate::FFTPlan::for_product) picks parameters
+//! under which the cyclic convolution of two operands is their integer
+//! product:
+//!
+//! ```
+//! use fibcalc_bigfft::ring::Poly;
+//! use fibcalc_bigfft::FFTPlan;
+//! use num_bigint::BigUint;
+//!
+//! let a = BigUint::from(u64::MAX).pow(40);
+//! let b = BigUint::from(12_345_678_901u64).pow(50);
+//! let plan = FFTPlan::for_product(a.bits() as usize, b.bits() as usize);
+//! let pa = Poly::from_biguint(&a, plan.len, plan.piece_bits, plan.fermat_shift);
+//! let pb = Poly::from_biguint(&b, plan.len, plan.piece_bits, plan.fermat_shift);
+//! assert_eq!(pa.cyclic_mul(&pb).to_biguint(), &a * &b);
+//! ```
+//!
+//! Constructors and transforms check these invariants and panic on
+//! violation; see each item's `# Panics` section.
+
+pub use crate::fermat::{FermatNum, Limbs};
+pub use crate::fft_core::{fft_forward, fft_inverse};
+pub use crate::fft_poly::Poly;
@@ -0,0 +1,80 @@
+//! Allocation counts of the destination-reusing products.
+//!
+//! A counting global allocator measures the bytes requested by
+//! `mul_to_with`, `sqr_to_with` and `sqr_add_sqr_to_with` into a warm
+//! destination. Value-returning products allocate a fresh result every
+//! call; the `_to` forms must save at least the size of that result.
+//!
+//! Everything runs in one test so that no other test thread allocates
+//! while a region is being measured.
+
+use std::alloc::System;
+
+use num_bigint::BigUint;
+use num_traits::One;
+use stats_alloc::{Region, StatsAlloc, INSTRUMENTED_SYSTEM};
+
+use fibcalc_bigfft::{
+    mul_to_with, mul_with, sqr_add_sqr_to_with, sqr_add_sqr_with, sqr_to_with, sqr_with,
+    FFTBackend, FFTConfig,
+};
+
+#[global_allocator]
+static GLOBAL: &StatsAlloc<System> = &INSTRUMENTED_SYSTEM;
+
+/// Bytes requested from the allocator while `f` runs, growth by
+/// reallocation included.
+fn bytes_allocated(f: impl FnOnce()) -> usize {
+    let region = Region::new(GLOBAL);
+    f();
+    let stats = region.change();
+    stats.bytes_allocated + usize::try_from(stats.bytes_reallocated.max(0)).unwrap_or(0)
+}
+
+/// Check that writing into a warm `dst` saves at least the size of the
+/// result over the value-returning `fresh` product, and that both agree.
+fn assert_reuses_destination(label: &str, to: impl Fn(&mut BigUint), fresh: impl Fn() -> BigUint) {
+    let mut dst = BigUint::ZERO;
+    // Warm the destination, the pool and the scratch arena.
+    to(&mut dst);
+    let expected = fresh();
+    let result_bytes = usize::try_from(expected.bits() / 8).unwrap();
+
+    let reused = bytes_allocated(|| to(&mut dst));
+    let mut value = BigUint::ZERO;
+    let allocated = bytes_allocated(|| value = fresh());
+
+    assert_eq!(dst, expected, "{label}: wrong result");
+    assert_eq!(value, expected, "{label}: wrong result");
+    assert!(
+        reused + result_bytes <= allocated,
+        "{label}: {reused} bytes into a warm destination vs {allocated} for a fresh result \
+         of {result_bytes} bytes"
+    );
+}
+
+#[test]
+fn destination_products_do_not_allocate_their_result() {
+    let a: BigUint = (BigUint::one() << 300_000) / BigUint::from(7u64);
+    let b: BigUint = (BigUint::one() << 250_000) - BigUint::from(12_345u64);
+
+    for backend in [FFTBackend::Fermat, FFTBackend::Ntt] {
+        let config = FFTConfig::with_threshold(0).with_backend(backend);
+        let name = backend.name();
+        assert_reuses_destination(
+            &format!("{name} mul"),
+            |dst| mul_to_with(dst, &a, &b, &config),
+            || mul_with(&a, &b, &config),
+        );
+        assert_reuses_destination(
+            &format!("{name} sqr"),
+            |dst| sqr_to_with(dst, &a, &config),
+            || sqr_with(&a, &config),
+        );
+        assert_reuses_destination(
+            &format!("{name} sqr_add_sqr"),
+            |dst| sqr_add_sqr_to_with(dst, &a, &b, &config),
+            || sqr_add_sqr_with(&a, &b, &config),
+        );
+    }
+}
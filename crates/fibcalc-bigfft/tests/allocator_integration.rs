@@ -0,0 +1,49 @@
+//! Integration tests for the allocators behind the FFT pipeline.
+//!
+//! Fermat products draw their `BigUint` temporaries from the global pool,
+//! so repeated products must show up as pool hits in `pool_stats()`.
+
+use num_bigint::BigUint;
+use num_traits::One;
+
+use fibcalc_bigfft::{
+    mul_with, pool_stats, sqr_with, FFTBackend, FFTConfig, FFTPlan, TransformedOperand,
+};
+
+#[test]
+fn fft_products_reuse_pooled_temporaries() {
+    let config = FFTConfig::with_threshold(0).with_backend(FFTBackend::Fermat);
+    let a = (BigUint::one() << 50_000) / BigUint::from(7u64);
+    let b = (BigUint::one() << 40_000) - BigUint::from(12_345u64);
+
+    let before = pool_stats();
+    for _ in 0..3 {
+        assert_eq!(mul_with(&a, &b, &config), &a * &b);
+        assert_eq!(sqr_with(&a, &config), &a * &a);
+    }
+    let after = pool_stats();
+
+    assert!(
+        after.hits > before.hits,
+        "FFT products should reuse pooled temporaries: {before:?} -> {after:?}"
+    );
+    let hits = after.hits - before.hits;
+    let misses = after.misses - before.misses;
+    assert!(
+        hits > misses,
+        "pool hit rate should dominate: {hits} hits, {misses} misses"
+    );
+}
+
+#[test]
+fn transformed_operands_use_the_pool() {
+    let config = FFTConfig::default();
+    let a = (BigUint::one() << 30_000) - BigUint::one();
+    let plan = FFTPlan::for_product(30_000, 30_000);
+    let ta = TransformedOperand::new(&a, plan, &config);
+
+    let before = pool_stats();
+    assert_eq!(ta.sqr(&config), &a * &a);
+    assert_eq!(ta.mul(&ta, &config), &a * &a);
+    assert!(pool_stats().hits > before.hits);
+}
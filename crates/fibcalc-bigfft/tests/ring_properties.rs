@@ -0,0 +1,183 @@
+//! Property tests for the public Fermat-ring and polynomial API.
+
+use num_bigint::BigUint;
+use num_traits::One;
+use proptest::prelude::*;
+
+use fibcalc_bigfft::ring::{fft_forward, fft_inverse, FermatNum, Poly};
+use fibcalc_bigfft::FFTPlan;
+
+fn biguint(limbs: &[u64]) -> BigUint {
+    limbs
+        .iter()
+        .rev()
+        .fold(BigUint::ZERO, |acc, &limb| (acc << 64u32) + limb)
+}
+
+fn modulus(shift: usize) -> BigUint {
+    (BigUint::one() << shift) + 1u32
+}
+
+fn limbs(max: usize) -> impl Strategy<Value = Vec<u64>> {
+    prop::collection::vec(any::<u64>(), 0..max)
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    /// A Fermat number holds its value reduced mod 2^shift + 1.
+    #[test]
+    fn fermat_roundtrip_reduces(
+        value in limbs(8),
+        shift in prop::sample::select(vec![1usize, 8, 63, 64, 100, 128, 256]),
+    ) {
+        let value = biguint(&value);
+        let num = FermatNum::from_biguint(&value, shift);
+        prop_assert_eq!(num.shift(), shift);
+        prop_assert_eq!(num.limbs().len(), shift.div_ceil(64) + 1);
+        prop_assert_eq!(num.to_biguint(), &value % modulus(shift));
+    }
+
+    /// Splitting into pieces and evaluating at 2^piece_bits is lossless.
+    #[test]
+    fn poly_roundtrip(
+        value in limbs(12),
+        piece_bits in prop::sample::select(vec![16usize, 60, 64, 100, 128]),
+    ) {
+        let value = biguint(&value);
+        let bits = usize::try_from(value.bits()).unwrap();
+        let n = bits.div_ceil(piece_bits).max(1).next_power_of_two();
+        let poly = Poly::from_biguint(&value, n, piece_bits, 2 * piece_bits);
+        prop_assert_eq!(poly.len(), n);
+        prop_assert_eq!(poly.to_biguint(), value);
+    }
+
+    /// The inverse transform undoes the forward transform.
+    #[test]
+    fn forward_inverse_is_identity(
+        coeffs in prop::collection::vec(limbs(3), 16),
+        log_n in 1u32..=4,
+        shift in prop::sample::select(vec![64usize, 128]),
+    ) {
+        let n = 1usize << log_n;
+        let mut data: Vec<FermatNum> = coeffs[..n]
+            .iter()
+            .map(|c| FermatNum::from_biguint(&biguint(c), shift))
+            .collect();
+        let original: Vec<BigUint> = data.iter().map(FermatNum::to_biguint).collect();
+        fft_forward(&mut data, shift);
+        fft_inverse(&mut data, shift);
+        let restored: Vec<BigUint> = data.iter().map(FermatNum::to_biguint).collect();
+        prop_assert_eq!(restored, original);
+    }
+
+    /// Transform-based products equal the naive cyclic convolution
+    /// mod (x^n - 1, 2^shift + 1).
+    #[test]
+    fn cyclic_mul_matches_naive_convolution(
+        a in prop::collection::vec(any::<u64>(), 8),
+        b in prop::collection::vec(any::<u64>(), 8),
+    ) {
+        let (n, shift) = (8, 64);
+        let m = modulus(shift);
+        let to_poly = |c: &[u64]| {
+            let coeffs = c
+                .iter()
+                .map(|&v| FermatNum::from_biguint(&BigUint::from(v), shift))
+                .collect();
+            Poly::from_coeffs(coeffs, 32)
+        };
+        let product = to_poly(&a).cyclic_mul(&to_poly(&b));
+        for k in 0..n {
+            let expected = (0..n).fold(BigUint::ZERO, |acc, i| {
+                acc + BigUint::from(a[i]) * BigUint::from(b[(n + k - i) % n])
+            }) % &m;
+            prop_assert_eq!(product.coeffs()[k].to_biguint(), expected, "coefficient {}", k);
+        }
+    }
+
+    /// Under a fitting plan, cyclic convolution is the integer product and
+    /// pointwise squaring gives the integer square.
+    #[test]
+    fn cyclic_mul_matches_biguint(a in limbs(40), b in limbs(40)) {
+        let (a, b) = (biguint(&a), biguint(&b));
+        let a_bits = usize::try_from(a.bits()).unwrap();
+        let b_bits = usize::try_from(b.bits()).unwrap();
+        let plan = FFTPlan::for_product(a_bits, b_bits);
+        let pa = Poly::from_biguint(&a, plan.len, plan.piece_bits, plan.fermat_shift);
+        let pb = Poly::from_biguint(&b, plan.len, plan.piece_bits, plan.fermat_shift);
+        prop_assert_eq!(pa.cyclic_mul(&pb).to_biguint(), &a * &b);
+
+        let plan = FFTPlan::for_product(a_bits, a_bits);
+        let mut sq = Poly::from_biguint(&a, plan.len, plan.piece_bits, plan.fermat_shift);
+        sq.forward();
+        sq.pointwise_square();
+        sq.inverse();
+        prop_assert_eq!(sq.to_biguint(), &a * &a);
+    }
+}
+
+/// Case shrunk from a `cyclic_mul_matches_biguint` failure: a 17-limb
+/// operand with three zero low limbs times a 5-limb operand.
+#[test]
+fn cyclic_mul_regression_17_by_5_limbs() {
+    let a = biguint(&[
+        0,
+        0,
+        0,
+        176_031_436,
+        3_467_350_836_359_577_528,
+        16_329_144_300_273_438_309,
+        12_732_236_719_338_635_658,
+        14_456_693_111_446_409_507,
+        10_510_434_753_970_282_326,
+        6_934_381_071_359_362_030,
+        18_051_515_792_011_772_198,
+        13_124_592_723_297_671_528,
+        6_283_418_608_017_584_550,
+        3_985_896_896_468_193_707,
+        13_683_494_648_945_922_609,
+        17_444_829_544_451_771_268,
+        13_379_746_571_111_768_191,
+    ]);
+    let b = biguint(&[
+        6_665_920_375_537_717_048,
+        5_482_657_780_573_119_500,
+        15_288_832_765_702_465_079,
+        9_747_202_621_422_954_928,
+        18_024_656_526_999_074_774,
+    ]);
+    let a_bits = usize::try_from(a.bits()).unwrap();
+    let b_bits = usize::try_from(b.bits()).unwrap();
+    let plan = FFTPlan::for_product(a_bits, b_bits);
+    let pa = Poly::from_biguint(&a, plan.len, plan.piece_bits, plan.fermat_shift);
+    let pb = Poly::from_biguint(&b, plan.len, plan.piece_bits, plan.fermat_shift);
+    assert_eq!(pa.cyclic_mul(&pb).to_biguint(), &a * &b);
+
+    let plan = FFTPlan::for_product(a_bits, a_bits);
+    let mut sq = Poly::from_biguint(&a, plan.len, plan.piece_bits, plan.fermat_shift);
+    sq.forward();
+    sq.pointwise_square();
+    sq.inverse();
+    assert_eq!(sq.to_biguint(), &a * &a);
+}
+
+#[test]
+#[should_panic(expected = "not a power of two")]
+fn transform_rejects_non_power_of_two_length() {
+    let mut data = vec![FermatNum::new(64); 3];
+    fft_forward(&mut data, 64);
+}
+
+#[test]
+#[should_panic(expected = "does not fit")]
+fn poly_rejects_oversized_value() {
+    let value = BigUint::one() << 100;
+    let _ = Poly::from_biguint(&value, 2, 32, 64);
+}
+
+#[test]
+#[should_panic(expected = "share one Fermat shift")]
+fn poly_rejects_mixed_shifts() {
+    let _ = Poly::from_coeffs(vec![FermatNum::new(64), FermatNum::new(128)], 32);
+}
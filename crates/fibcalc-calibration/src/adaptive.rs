@@ -1,7 +1,9 @@
 //! Adaptive threshold estimation.
 
+use fibcalc_bigfft::DEFAULT_NTT_THRESHOLD_BITS;
 use fibcalc_core::constants::{
     DEFAULT_FFT_THRESHOLD, DEFAULT_PARALLEL_THRESHOLD, DEFAULT_STRASSEN_THRESHOLD,
+    DEFAULT_TOOM3_THRESHOLD, DEFAULT_TOOM4_THRESHOLD,
 };
 
 use crate::microbench;
@@ -12,6 +14,9 @@ pub struct EstimatedThresholds {
     pub parallel_threshold: usize,
     pub fft_threshold: usize,
     pub strassen_threshold: usize,
+    pub ntt_threshold: usize,
+    pub toom3_threshold: usize,
+    pub toom4_threshold: usize,
 }
 
 impl Default for EstimatedThresholds {
@@ -20,6 +25,9 @@ impl Default for EstimatedThresholds {
             parallel_threshold: DEFAULT_PARALLEL_THRESHOLD,
             fft_threshold: DEFAULT_FFT_THRESHOLD,
             strassen_threshold: DEFAULT_STRASSEN_THRESHOLD,
+            ntt_threshold: DEFAULT_NTT_THRESHOLD_BITS,
+            toom3_threshold: DEFAULT_TOOM3_THRESHOLD,
+            toom4_threshold: DEFAULT_TOOM4_THRESHOLD,
         }
     }
 }
@@ -32,17 +40,22 @@ pub fn estimate_thresholds() -> (usize, usize) {
     (est.parallel_threshold, est.fft_threshold)
 }
 
-/// Estimate all thresholds (parallel, FFT, Strassen).
+/// Estimate all thresholds (parallel, FFT, Strassen, NTT, Toom).
 #[must_use]
 pub fn estimate_all_thresholds() -> EstimatedThresholds {
     let fft_threshold = find_fft_crossover_threshold();
     let parallel_threshold = find_parallel_crossover_threshold();
     let strassen_threshold = find_strassen_threshold(fft_threshold);
+    let ntt_threshold = find_ntt_crossover_threshold();
+    let (toom3_threshold, toom4_threshold) = find_toom_thresholds();
 
     EstimatedThresholds {
         parallel_threshold,
         fft_threshold,
         strassen_threshold,
+        ntt_threshold,
+        toom3_threshold,
+        toom4_threshold,
     }
 }
 
@@ -98,6 +111,18 @@ fn binary_search_crossover(mut low: usize, mut high: usize) -> usize {
     (low + high) / 2
 }
 
+/// Find the operand size from which the NTT backend beats Fermat FFT.
+fn find_ntt_crossover_threshold() -> usize {
+    let sample_points: Vec<usize> = (10..=18).step_by(2).map(|exp| 1 << exp).collect(); // 1K to 256K
+    microbench::ntt_threshold_from(&microbench::find_backend_crossover(&sample_points))
+}
+
+/// Find the operand sizes from which Toom-3 and Toom-4 beat the tier below.
+fn find_toom_thresholds() -> (usize, usize) {
+    let sample_points: Vec<usize> = (12..=18).step_by(2).map(|exp| 1 << exp).collect(); // 4K to 256K
+    microbench::toom_thresholds_from(&microbench::find_toom_crossover(&sample_points))
+}
+
 /// Find the threshold where parallel execution becomes beneficial.
 fn find_parallel_crossover_threshold() -> usize {
     let test_sizes: Vec<usize> = vec![512, 1024, 2048, 4096, 8192, 16384];
@@ -73,7 +73,7 @@ impl CalibrationEngine {
     }
 
     fn full_calibration(&self) -> CalibrationProfile {
-        let total_steps = 4;
+        let total_steps = 6;
 
         // Step 1: Benchmark multiplication at various bit lengths
         self.report_progress("Benchmarking Karatsuba multiplication", 1, total_steps);
@@ -107,6 +107,18 @@ impl CalibrationEngine {
             .max(fibcalc_core::constants::DEFAULT_STRASSEN_THRESHOLD)
             .min(fft_threshold);
 
+        // Step 5: Pick the FFT backend per operand size
+        self.report_progress("Comparing FFT backends", 5, total_steps);
+        let backend_points: Vec<usize> = (10..=20).step_by(2).map(|exp| 1 << exp).collect();
+        let ntt_threshold =
+            microbench::ntt_threshold_from(&microbench::find_backend_crossover(&backend_points));
+
+        // Step 6: Find the Toom-3 and Toom-4 tiers
+        self.report_progress("Finding Toom-Cook thresholds", 6, total_steps);
+        let toom_points: Vec<usize> = (12..=20).map(|exp| 1 << exp).collect();
+        let (toom3_threshold, toom4_threshold) =
+            microbench::toom_thresholds_from(&microbench::find_toom_crossover(&toom_points));
+
         let cpu = profile::cpu_model();
         let fingerprint = profile::cpu_fingerprint();
         let timestamp = profile::current_timestamp();
@@ -116,6 +128,9 @@ impl CalibrationEngine {
             parallel_threshold,
             fft_threshold,
             strassen_threshold,
+            ntt_threshold,
+            toom3_threshold,
+            toom4_threshold,
             cpu_model: cpu,
             num_cores: std::thread::available_parallelism()
                 .map(std::num::NonZero::get)
@@ -148,6 +163,9 @@ impl CalibrationEngine {
             parallel_threshold: est.parallel_threshold,
             fft_threshold: est.fft_threshold,
             strassen_threshold: est.strassen_threshold,
+            ntt_threshold: est.ntt_threshold,
+            toom3_threshold: est.toom3_threshold,
+            toom4_threshold: est.toom4_threshold,
             cpu_model: cpu,
             num_cores: std::thread::available_parallelism()
                 .map(std::num::NonZero::get)
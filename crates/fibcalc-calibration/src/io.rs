@@ -32,8 +32,19 @@ pub fn load_profile() -> Option<CalibrationProfile> {
 /// Load a profile and validate it against the current environment.
 /// Returns `None` if the profile is incompatible, invalid, or for a different CPU.
 pub fn load_validated_profile() -> Option<CalibrationProfile> {
-    let p = load_profile()?;
+    validate(load_profile()?)
+}
 
+/// Load a profile from an explicit path and validate it against the current
+/// environment.
+/// Returns `None` if the file is missing, unreadable, incompatible, invalid,
+/// or for a different CPU.
+#[must_use]
+pub fn load_validated_profile_from(path: &std::path::Path) -> Option<CalibrationProfile> {
+    validate(load_from_path(path)?)
+}
+
+fn validate(p: CalibrationProfile) -> Option<CalibrationProfile> {
     if !p.is_compatible() {
         tracing::info!("Profile version mismatch, ignoring cached profile");
         return None;
@@ -232,6 +243,9 @@ mod tests {
             parallel_threshold: 8192,
             fft_threshold: 600_000,
             strassen_threshold: 4096,
+            ntt_threshold: 65_536,
+            toom3_threshold: 32_768,
+            toom4_threshold: 131_072,
             cpu_model: "TestCPU".to_string(),
             num_cores: 16,
             cpu_fingerprint: "cores=16".to_string(),
@@ -241,6 +255,9 @@ mod tests {
         let loaded = load_from_path(&path).unwrap();
         assert_eq!(loaded.version, p.version);
         assert_eq!(loaded.parallel_threshold, 8192);
+        assert_eq!(loaded.ntt_threshold, 65_536);
+        assert_eq!(loaded.toom3_threshold, 32_768);
+        assert_eq!(loaded.toom4_threshold, 131_072);
         assert_eq!(loaded.fft_threshold, 600_000);
         assert_eq!(loaded.strassen_threshold, 4096);
         assert_eq!(loaded.cpu_model, "TestCPU");
@@ -370,4 +387,32 @@ mod tests {
         let loaded = load_from_path(&path);
         assert!(loaded.is_some());
     }
+
+    #[test]
+    fn load_validated_profile_from_accepts_valid_profile() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("custom.json");
+        let p = CalibrationProfile {
+            parallel_threshold: 1234,
+            cpu_fingerprint: profile::cpu_fingerprint(),
+            ..Default::default()
+        };
+        save_to_path(&p, &path).unwrap();
+        let loaded = load_validated_profile_from(&path).unwrap();
+        assert_eq!(loaded.parallel_threshold, 1234);
+    }
+
+    #[test]
+    fn load_validated_profile_from_rejects_missing_and_invalid() {
+        let dir = TempDir::new().unwrap();
+        assert!(load_validated_profile_from(&dir.path().join("missing.json")).is_none());
+
+        let path = dir.path().join("invalid.json");
+        let p = CalibrationProfile {
+            fft_threshold: 0,
+            ..Default::default()
+        };
+        save_to_path(&p, &path).unwrap();
+        assert!(load_validated_profile_from(&path).is_none());
+    }
 }
@@ -2,10 +2,21 @@
 
 use std::time::Duration;
 
+use fibcalc_bigfft::{FFTBackend, FFTConfig};
+use fibcalc_core::strategy::{Multiplier, Toom3Strategy, Toom4Strategy};
 use num_bigint::BigUint;
 
 use crate::runner::{benchmark, benchmark_detailed, BenchmarkResult};
 
+/// Force the FFT pipeline so crossover measurements compare real FFT cost
+/// against Karatsuba instead of the library's built-in routing.
+const ALWAYS_FFT: FFTConfig = FFTConfig::with_threshold(0);
+
+/// Run a product under the given backend, bypassing the NTT crossover.
+const fn backend_config(backend: FFTBackend) -> FFTConfig {
+    ALWAYS_FFT.with_backend(backend)
+}
+
 /// Benchmark Karatsuba multiplication at a given bit length.
 #[must_use]
 pub fn bench_karatsuba(bit_length: usize) -> Duration {
@@ -22,7 +33,7 @@ pub fn bench_fft(bit_length: usize) -> Duration {
     let a = make_number(bit_length);
     let b = make_number(bit_length);
     benchmark(10, || {
-        let _ = fibcalc_bigfft::mul(&a, &b);
+        let _ = fibcalc_bigfft::mul_with(&a, &b, &ALWAYS_FFT);
     })
 }
 
@@ -45,6 +56,85 @@ pub fn find_fft_crossover(bit_lengths: &[usize]) -> Vec<CrossoverPoint> {
         .collect()
 }
 
+/// Benchmark the Fermat and NTT backends at various bit lengths.
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub fn find_backend_crossover(bit_lengths: &[usize]) -> Vec<BackendPoint> {
+    bit_lengths
+        .iter()
+        .map(|&bits| {
+            let fermat = bench_backend_detailed(bits, FFTBackend::Fermat);
+            let ntt = bench_backend_detailed(bits, FFTBackend::Ntt);
+            BackendPoint {
+                bit_length: bits,
+                fermat_ns: fermat.median.as_nanos() as u64,
+                ntt_ns: ntt.median.as_nanos() as u64,
+                ntt_is_faster: ntt.median < fermat.median,
+            }
+        })
+        .collect()
+}
+
+/// Smallest sampled size from which NTT stays faster than Fermat.
+///
+/// Returns 0 when NTT wins at every point, and `usize::MAX` when it loses
+/// at the largest one or nothing was sampled, so Auto keeps using Fermat.
+#[must_use]
+pub fn ntt_threshold_from(points: &[BackendPoint]) -> usize {
+    if points.is_empty() {
+        return usize::MAX;
+    }
+    let losing = points.iter().rposition(|p| !p.ntt_is_faster);
+    match losing {
+        None => 0,
+        Some(idx) => points.get(idx + 1).map_or(usize::MAX, |p| p.bit_length),
+    }
+}
+
+/// Benchmark Karatsuba, Toom-3 and Toom-4 at various bit lengths.
+///
+/// Each Toom product splits once at the top level and multiplies the
+/// pieces with num-bigint, so a win means one Toom level pays off there.
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub fn find_toom_crossover(bit_lengths: &[usize]) -> Vec<ToomPoint> {
+    bit_lengths
+        .iter()
+        .map(|&bits| {
+            let karatsuba = bench_karatsuba_detailed(bits);
+            let toom3 = bench_multiplier_detailed(bits, &Toom3Strategy::new(bits));
+            let toom4 = bench_multiplier_detailed(bits, &Toom4Strategy::new(bits));
+            ToomPoint {
+                bit_length: bits,
+                karatsuba_ns: karatsuba.median.as_nanos() as u64,
+                toom3_ns: toom3.median.as_nanos() as u64,
+                toom4_ns: toom4.median.as_nanos() as u64,
+            }
+        })
+        .collect()
+}
+
+/// Toom-3 and Toom-4 thresholds from sampled points.
+///
+/// Toom-3 starts at the smallest sampled size from which it stays faster
+/// than Karatsuba; Toom-4 where it stays faster than both. A tier that
+/// loses at the largest size gets `usize::MAX` (never used).
+#[must_use]
+pub fn toom_thresholds_from(points: &[ToomPoint]) -> (usize, usize) {
+    let toom3 = stays_faster_from(points, |p| p.toom3_ns < p.karatsuba_ns);
+    let toom4 = stays_faster_from(points, |p| p.toom4_ns < p.karatsuba_ns.min(p.toom3_ns));
+    (toom3, toom4)
+}
+
+/// Smallest sampled size from which `wins` holds at every larger point.
+fn stays_faster_from(points: &[ToomPoint], wins: impl Fn(&ToomPoint) -> bool) -> usize {
+    let start = points
+        .iter()
+        .rposition(|p| !wins(p))
+        .map_or(0, |idx| idx + 1);
+    points.get(start).map_or(usize::MAX, |p| p.bit_length)
+}
+
 /// Measure parallel overhead by comparing sequential vs parallel work.
 #[must_use]
 #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
@@ -90,16 +180,37 @@ fn bench_karatsuba_detailed(bit_length: usize) -> BenchmarkResult {
     .with_name(format!("karatsuba_{bit_length}"))
 }
 
+/// Detailed benchmark of a [`Multiplier`] strategy.
+fn bench_multiplier_detailed(bit_length: usize, multiplier: &dyn Multiplier) -> BenchmarkResult {
+    let a = make_number(bit_length);
+    let b = make_number(bit_length);
+    benchmark_detailed(2, 5, || {
+        let _ = multiplier.multiply(&a, &b);
+    })
+    .with_name(format!("{}_{bit_length}", multiplier.name().to_lowercase()))
+}
+
 /// Detailed FFT benchmark.
 fn bench_fft_detailed(bit_length: usize) -> BenchmarkResult {
     let a = make_number(bit_length);
     let b = make_number(bit_length);
     benchmark_detailed(3, 10, || {
-        let _ = fibcalc_bigfft::mul(&a, &b);
+        let _ = fibcalc_bigfft::mul_with(&a, &b, &ALWAYS_FFT);
     })
     .with_name(format!("fft_{bit_length}"))
 }
 
+/// Detailed benchmark of one FFT backend.
+fn bench_backend_detailed(bit_length: usize, backend: FFTBackend) -> BenchmarkResult {
+    let a = make_number(bit_length);
+    let b = make_number(bit_length);
+    let config = backend_config(backend);
+    benchmark_detailed(2, 5, || {
+        let _ = fibcalc_bigfft::mul_with(&a, &b, &config);
+    })
+    .with_name(format!("{}_{bit_length}", backend.name()))
+}
+
 /// Create a `BigUint` with approximately the given number of bits.
 fn make_number(bit_length: usize) -> BigUint {
     if bit_length == 0 {
@@ -120,6 +231,24 @@ pub struct CrossoverPoint {
     pub fft_is_faster: bool,
 }
 
+/// Result of comparing the Fermat and NTT backends at a specific bit length.
+#[derive(Debug, Clone)]
+pub struct BackendPoint {
+    pub bit_length: usize,
+    pub fermat_ns: u64,
+    pub ntt_ns: u64,
+    pub ntt_is_faster: bool,
+}
+
+/// Result of comparing Karatsuba, Toom-3 and Toom-4 at a specific bit length.
+#[derive(Debug, Clone)]
+pub struct ToomPoint {
+    pub bit_length: usize,
+    pub karatsuba_ns: u64,
+    pub toom3_ns: u64,
+    pub toom4_ns: u64,
+}
+
 /// Result of measuring parallel execution overhead.
 #[derive(Debug, Clone)]
 pub struct ParallelOverhead {
@@ -169,6 +298,70 @@ mod tests {
         }
     }
 
+    #[test]
+    fn find_backend_crossover_runs() {
+        let points = find_backend_crossover(&[1024, 4096]);
+        assert_eq!(points.len(), 2);
+        for p in &points {
+            assert!(p.fermat_ns > 0);
+            assert!(p.ntt_ns > 0);
+        }
+    }
+
+    #[test]
+    fn ntt_threshold_from_picks_trailing_wins() {
+        let point = |bit_length, ntt_is_faster| BackendPoint {
+            bit_length,
+            fermat_ns: 1,
+            ntt_ns: 1,
+            ntt_is_faster,
+        };
+        assert_eq!(ntt_threshold_from(&[point(1, true), point(2, true)]), 0);
+        assert_eq!(
+            ntt_threshold_from(&[point(1, true), point(2, false), point(4, true)]),
+            4
+        );
+        assert_eq!(
+            ntt_threshold_from(&[point(1, true), point(2, false)]),
+            usize::MAX
+        );
+        assert_eq!(ntt_threshold_from(&[]), usize::MAX);
+    }
+
+    #[test]
+    fn find_toom_crossover_runs() {
+        let points = find_toom_crossover(&[4096, 16384]);
+        assert_eq!(points.len(), 2);
+        for p in &points {
+            assert!(p.karatsuba_ns > 0);
+            assert!(p.toom3_ns > 0);
+            assert!(p.toom4_ns > 0);
+        }
+    }
+
+    #[test]
+    fn toom_thresholds_from_picks_trailing_wins() {
+        let point = |bit_length, toom3_ns, toom4_ns| ToomPoint {
+            bit_length,
+            karatsuba_ns: 10,
+            toom3_ns,
+            toom4_ns,
+        };
+        assert_eq!(
+            toom_thresholds_from(&[point(1, 5, 4), point(2, 5, 4)]),
+            (1, 1)
+        );
+        assert_eq!(
+            toom_thresholds_from(&[point(1, 20, 30), point(2, 8, 9), point(4, 8, 7)]),
+            (2, 4)
+        );
+        assert_eq!(
+            toom_thresholds_from(&[point(1, 5, 20), point(2, 20, 20)]),
+            (usize::MAX, usize::MAX)
+        );
+        assert_eq!(toom_thresholds_from(&[]), (usize::MAX, usize::MAX));
+    }
+
     #[test]
     fn measure_parallel_overhead_runs() {
         let overhead = measure_parallel_overhead(2048);
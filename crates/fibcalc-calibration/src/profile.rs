@@ -2,8 +2,10 @@
 
 use serde::{Deserialize, Serialize};
 
+use fibcalc_bigfft::DEFAULT_NTT_THRESHOLD_BITS;
 use fibcalc_core::constants::{
     DEFAULT_FFT_THRESHOLD, DEFAULT_PARALLEL_THRESHOLD, DEFAULT_STRASSEN_THRESHOLD,
+    DEFAULT_TOOM3_THRESHOLD, DEFAULT_TOOM4_THRESHOLD,
 };
 
 /// Current profile format version.
@@ -20,6 +22,15 @@ pub struct CalibrationProfile {
     pub fft_threshold: usize,
     /// Optimized Strassen threshold.
     pub strassen_threshold: usize,
+    /// Operand size at which the NTT backend beats Fermat FFT.
+    #[serde(default = "default_ntt_threshold")]
+    pub ntt_threshold: usize,
+    /// Operand size at which Toom-3 beats Karatsuba (`usize::MAX` = never).
+    #[serde(default = "default_toom3_threshold")]
+    pub toom3_threshold: usize,
+    /// Operand size at which Toom-4 beats Toom-3 (`usize::MAX` = never).
+    #[serde(default = "default_toom4_threshold")]
+    pub toom4_threshold: usize,
     /// CPU model used for calibration.
     pub cpu_model: String,
     /// Number of CPU cores.
@@ -37,6 +48,9 @@ impl Default for CalibrationProfile {
             parallel_threshold: DEFAULT_PARALLEL_THRESHOLD,
             fft_threshold: DEFAULT_FFT_THRESHOLD,
             strassen_threshold: DEFAULT_STRASSEN_THRESHOLD,
+            ntt_threshold: DEFAULT_NTT_THRESHOLD_BITS,
+            toom3_threshold: DEFAULT_TOOM3_THRESHOLD,
+            toom4_threshold: DEFAULT_TOOM4_THRESHOLD,
             cpu_model: String::new(),
             num_cores: num_cpus(),
             cpu_fingerprint: String::new(),
@@ -71,6 +85,18 @@ impl CalibrationProfile {
     }
 }
 
+fn default_ntt_threshold() -> usize {
+    DEFAULT_NTT_THRESHOLD_BITS
+}
+
+fn default_toom3_threshold() -> usize {
+    DEFAULT_TOOM3_THRESHOLD
+}
+
+fn default_toom4_threshold() -> usize {
+    DEFAULT_TOOM4_THRESHOLD
+}
+
 fn num_cpus() -> usize {
     std::thread::available_parallelism()
         .map(std::num::NonZero::get)
@@ -118,6 +144,34 @@ mod tests {
         assert_eq!(deserialized.version, PROFILE_VERSION);
     }
 
+    #[test]
+    fn profile_without_ntt_threshold_uses_default() {
+        let mut json = serde_json::to_value(CalibrationProfile {
+            ntt_threshold: 123_456,
+            ..Default::default()
+        })
+        .unwrap();
+        json.as_object_mut().unwrap().remove("ntt_threshold");
+        let profile: CalibrationProfile = serde_json::from_value(json).unwrap();
+        assert_eq!(profile.ntt_threshold, DEFAULT_NTT_THRESHOLD_BITS);
+    }
+
+    #[test]
+    fn profile_without_toom_thresholds_uses_defaults() {
+        let mut json = serde_json::to_value(CalibrationProfile {
+            toom3_threshold: 40_000,
+            toom4_threshold: 90_000,
+            ..Default::default()
+        })
+        .unwrap();
+        let fields = json.as_object_mut().unwrap();
+        fields.remove("toom3_threshold");
+        fields.remove("toom4_threshold");
+        let profile: CalibrationProfile = serde_json::from_value(json).unwrap();
+        assert_eq!(profile.toom3_threshold, DEFAULT_TOOM3_THRESHOLD);
+        assert_eq!(profile.toom4_threshold, DEFAULT_TOOM4_THRESHOLD);
+    }
+
     #[test]
     fn profile_compatibility() {
         let profile = CalibrationProfile::default();
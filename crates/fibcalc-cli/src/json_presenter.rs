@@ -0,0 +1,654 @@
+//! Machine-readable JSON and NDJSON result presenter.
+//!
+//! Every `CalculationResult` becomes one [`CalculationRecord`]; runs with
+//! several algorithms add a [`ComparisonRecord`] summary. With
+//! [`ResultFormat::Ndjson`] each record is one line of JSON, with
+//! [`ResultFormat::Json`] the whole run is a single document.
+
+use std::io::{self, Write};
+use std::time::Duration;
+
+use num_bigint::BigUint;
+use parking_lot::Mutex;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use fibcalc_core::calculator::FibError;
+use fibcalc_core::decimal::DecimalConverter;
+use fibcalc_core::lucas::Sequence;
+use fibcalc_orchestration::interfaces::{CalculationResult, ResultPresenter};
+
+use crate::output::format_last_digits;
+use crate::presenter::ResultFormat;
+
+/// Which representation of the value each record carries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ValueField {
+    /// No value, only its metadata.
+    ///
+    /// The digit count and hash still cost one radix conversion of F(n),
+    /// streamed into SHA-256 without building the decimal string.
+    #[default]
+    None,
+    /// The full decimal value.
+    Full,
+    /// The last K decimal digits, zero-padded.
+    LastDigits(u32),
+    /// F(n) mod M of a `--modulus` run.
+    Residue,
+}
+
+/// Serializable form of a single `CalculationResult`.
+#[derive(Debug, Clone, Serialize)]
+pub struct CalculationRecord {
+    /// Record discriminator, always `"result"`.
+    #[serde(rename = "type")]
+    pub record_type: &'static str,
+    /// Algorithm name.
+    pub algorithm: String,
+    /// Symbol of the sequence (`L`, `U` or `V`); absent for Fibonacci runs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence: Option<&'static str>,
+    /// Index, negative for terms at −n.
+    pub n: i128,
+    /// Wall-clock duration in nanoseconds.
+    pub duration_ns: u64,
+    /// Bit length of F(n) (absent on error and in modular runs).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bits: Option<u64>,
+    /// Decimal digit count of F(n) (absent on error and in modular runs).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub digits: Option<usize>,
+    /// `sha256:<hex>` of the decimal representation, with its sign, whatever
+    /// `--output-format` writes; of the zero-padded last K digits or the
+    /// decimal residue in modular runs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+    /// Full decimal value with its sign, when requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// Last K decimal digits, when requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_digits: Option<String>,
+    /// F(n) mod M in decimal, with `--modulus M`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub residue: Option<String>,
+    /// Error kind and message if the calculation failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<FibError>,
+}
+
+impl CalculationRecord {
+    /// Build the record for `result`, computing F(n)'s metadata once with
+    /// `conv`.
+    #[must_use]
+    pub fn from_result(
+        n: u64,
+        result: &CalculationResult,
+        field: ValueField,
+        conv: &mut DecimalConverter,
+    ) -> Self {
+        Self::new(
+            &result.algorithm,
+            n.into(),
+            result.outcome.as_ref(),
+            result.duration,
+            field,
+            false,
+            conv,
+        )
+    }
+
+    /// `negated` prefixes full values with `-`; residues and last digits
+    /// are always non-negative.
+    fn new(
+        algorithm: &str,
+        n: i128,
+        outcome: Result<&BigUint, &FibError>,
+        duration: Duration,
+        field: ValueField,
+        negated: bool,
+        conv: &mut DecimalConverter,
+    ) -> Self {
+        let mut record = Self {
+            record_type: "result",
+            algorithm: algorithm.to_string(),
+            sequence: None,
+            n,
+            duration_ns: u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX),
+            bits: None,
+            digits: None,
+            hash: None,
+            value: None,
+            last_digits: None,
+            residue: None,
+            error: None,
+        };
+        match (outcome, field) {
+            // Modular runs only know F(n) mod M.
+            (Ok(value), ValueField::LastDigits(k)) => {
+                let digits = format_last_digits(value, k);
+                record.hash = Some(format!("sha256:{:x}", Sha256::digest(digits.as_bytes())));
+                record.last_digits = Some(digits);
+            }
+            (Ok(value), ValueField::Residue) => {
+                let residue = conv.to_string(value);
+                record.hash = Some(format!("sha256:{:x}", Sha256::digest(residue.as_bytes())));
+                record.residue = Some(residue);
+            }
+            (Ok(value), ValueField::Full) => {
+                let mut decimal = conv.to_string(value);
+                record.bits = Some(value.bits());
+                record.digits = Some(decimal.len());
+                if negated {
+                    decimal.insert(0, '-');
+                }
+                record.hash = Some(format!("sha256:{:x}", Sha256::digest(decimal.as_bytes())));
+                record.value = Some(decimal);
+            }
+            (Ok(value), ValueField::None) => {
+                let mut digest = DecimalDigest::default();
+                if negated {
+                    digest.hasher.update(b"-");
+                }
+                conv.write(value, &mut digest).expect("hashing cannot fail");
+                record.bits = Some(value.bits());
+                record.digits = Some(digest.digits);
+                record.hash = Some(format!("sha256:{:x}", digest.hasher.finalize()));
+            }
+            (Err(error), _) => record.error = Some(error.clone()),
+        }
+        record
+    }
+}
+
+/// SHA-256 and length of decimal digits written through it.
+#[derive(Default)]
+struct DecimalDigest {
+    hasher: Sha256,
+    digits: usize,
+}
+
+impl Write for DecimalDigest {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.hasher.update(buf);
+        self.digits += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Summary of a multi-algorithm run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ComparisonRecord {
+    /// Record discriminator, always `"comparison"`.
+    #[serde(rename = "type")]
+    pub record_type: &'static str,
+    /// Index, negative for terms at −n.
+    pub n: i128,
+    /// Number of algorithms run.
+    pub algorithms: usize,
+    /// Number of algorithms that succeeded.
+    pub succeeded: usize,
+    /// Fastest successful algorithm.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fastest: Option<String>,
+    /// Duration of the fastest algorithm in nanoseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fastest_duration_ns: Option<u64>,
+    /// Whether every successful algorithm produced the same hash.
+    pub consistent: bool,
+}
+
+impl ComparisonRecord {
+    /// Summarize the records of one run.
+    #[must_use]
+    pub fn from_records(n: i128, records: &[CalculationRecord]) -> Self {
+        let ok: Vec<&CalculationRecord> = records.iter().filter(|r| r.error.is_none()).collect();
+        let fastest = ok.iter().min_by_key(|r| r.duration_ns);
+        Self {
+            record_type: "comparison",
+            n,
+            algorithms: records.len(),
+            succeeded: ok.len(),
+            fastest: fastest.map(|r| r.algorithm.clone()),
+            fastest_duration_ns: fastest.map(|r| r.duration_ns),
+            consistent: ok.windows(2).all(|w| w[0].hash == w[1].hash),
+        }
+    }
+}
+
+/// Whole-run document emitted by `--format json`.
+#[derive(Debug, Serialize)]
+struct RunDocument<'a> {
+    results: &'a [CalculationRecord],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    comparison: Option<ComparisonRecord>,
+}
+
+/// Render a run as JSON or NDJSON.
+///
+/// The comparison record is included only when more than one algorithm ran.
+/// Returns an empty string for [`ResultFormat::Text`].
+#[must_use]
+pub fn render_run(
+    format: ResultFormat,
+    n: u64,
+    results: &[CalculationResult],
+    field: ValueField,
+    conv: &mut DecimalConverter,
+) -> String {
+    let records: Vec<CalculationRecord> = results
+        .iter()
+        .map(|r| CalculationRecord::from_result(n, r, field, conv))
+        .collect();
+    render_records(format, n.into(), &records)
+}
+
+fn render_records(format: ResultFormat, n: i128, records: &[CalculationRecord]) -> String {
+    let comparison = (records.len() > 1).then(|| ComparisonRecord::from_records(n, records));
+
+    match format {
+        ResultFormat::Text => String::new(),
+        ResultFormat::Json => {
+            let doc = RunDocument {
+                results: records,
+                comparison,
+            };
+            to_json(&doc, true)
+        }
+        ResultFormat::Ndjson => {
+            let mut out = String::new();
+            for record in records {
+                out.push_str(&to_json(record, false));
+                out.push('\n');
+            }
+            if let Some(comparison) = comparison {
+                out.push_str(&to_json(&comparison, false));
+                out.push('\n');
+            }
+            out
+        }
+    }
+}
+
+fn to_json(value: &impl Serialize, pretty: bool) -> String {
+    let rendered = if pretty {
+        serde_json::to_string_pretty(value)
+    } else {
+        serde_json::to_string(value)
+    };
+    // Records contain only strings, integers, and booleans.
+    rendered.expect("result records always serialize")
+}
+
+/// Presenter emitting JSON or NDJSON on stdout.
+pub struct JsonResultPresenter {
+    format: ResultFormat,
+    n: u64,
+    field: ValueField,
+    sequence: Option<&'static str>,
+    negative_index: bool,
+    negated: bool,
+    /// Records printed by `present_result`, reused by `present_comparison`
+    /// instead of converting the same values again.
+    emitted: Mutex<Vec<CalculationRecord>>,
+    /// Shared by every record, so powers of ten are built once per run.
+    converter: Mutex<DecimalConverter>,
+}
+
+impl JsonResultPresenter {
+    /// Create a presenter for `format` (`Json` or `Ndjson`) reporting on F(n).
+    #[must_use]
+    pub fn new(format: ResultFormat, n: u64, field: ValueField) -> Self {
+        Self {
+            format,
+            n,
+            field,
+            sequence: None,
+            negative_index: false,
+            negated: false,
+            emitted: Mutex::new(Vec::new()),
+            converter: Mutex::new(DecimalConverter::new()),
+        }
+    }
+
+    /// Convert values to decimal with `converter`, e.g. one built for the
+    /// run's FFT configuration.
+    #[must_use]
+    pub fn with_converter(mut self, converter: DecimalConverter) -> Self {
+        self.converter = Mutex::new(converter);
+        self
+    }
+
+    /// Tag every record with the symbol of `sequence`, unless it is Fibonacci.
+    #[must_use]
+    pub fn with_sequence(mut self, sequence: Sequence) -> Self {
+        self.sequence = (sequence != Sequence::Fibonacci).then(|| sequence.symbol());
+        self
+    }
+
+    /// Report the index as −n when `negative`, and full values with a minus
+    /// sign when `negated` as well.
+    #[must_use]
+    pub fn with_negative_index(mut self, negative: bool, negated: bool) -> Self {
+        self.negative_index = negative;
+        self.negated = negative && negated;
+        self
+    }
+
+    /// The signed index reported for `n`.
+    fn index(&self, n: u64) -> i128 {
+        if self.negative_index {
+            -i128::from(n)
+        } else {
+            i128::from(n)
+        }
+    }
+
+    fn outcome_record(
+        &self,
+        n: u64,
+        algorithm: &str,
+        outcome: Result<&BigUint, &FibError>,
+        duration: Duration,
+        field: ValueField,
+    ) -> CalculationRecord {
+        let mut record = CalculationRecord::new(
+            algorithm,
+            self.index(n),
+            outcome,
+            duration,
+            field,
+            self.negated,
+            &mut self.converter.lock(),
+        );
+        record.sequence = self.sequence;
+        record
+    }
+
+    fn record(&self, n: u64, result: &CalculationResult, field: ValueField) -> CalculationRecord {
+        self.outcome_record(
+            n,
+            &result.algorithm,
+            result.outcome.as_ref(),
+            result.duration,
+            field,
+        )
+    }
+}
+
+impl ResultPresenter for JsonResultPresenter {
+    fn present_result(
+        &self,
+        algorithm: &str,
+        n: u64,
+        result: &BigUint,
+        duration: Duration,
+        _details: bool,
+    ) {
+        let record = self.outcome_record(n, algorithm, Ok(result), duration, self.field);
+        println!("{}", to_json(&record, self.format == ResultFormat::Json));
+        self.emitted.lock().push(record);
+    }
+
+    fn present_comparison(&self, results: &[CalculationResult]) {
+        let mut emitted = std::mem::take(&mut *self.emitted.lock());
+        let records: Vec<CalculationRecord> = results
+            .iter()
+            .map(|r| {
+                let printed = r
+                    .outcome
+                    .is_ok()
+                    .then(|| emitted.iter().position(|e| e.algorithm == r.algorithm))
+                    .flatten();
+                match printed {
+                    Some(i) => emitted.swap_remove(i),
+                    None => self.record(self.n, r, self.field),
+                }
+            })
+            .collect();
+        let comparison = ComparisonRecord::from_records(self.index(self.n), &records);
+        println!(
+            "{}",
+            to_json(&comparison, self.format == ResultFormat::Json)
+        );
+    }
+
+    fn present_error(&self, error: &str) {
+        let record = serde_json::json!({ "type": "error", "message": error });
+        println!("{record}");
+    }
+
+    fn present_all(&self, n: u64, results: &[CalculationResult], _details: bool) {
+        let records: Vec<CalculationRecord> = results
+            .iter()
+            .map(|r| self.record(n, r, self.field))
+            .collect();
+        print!("{}", render_records(self.format, self.index(n), &records));
+        if self.format == ResultFormat::Json {
+            println!();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conv() -> DecimalConverter {
+        DecimalConverter::new()
+    }
+
+    fn ok_result(algorithm: &str, value: u64, millis: u64) -> CalculationResult {
+        CalculationResult {
+            algorithm: algorithm.into(),
+            outcome: Ok(BigUint::from(value)),
+            duration: Duration::from_millis(millis),
+        }
+    }
+
+    #[test]
+    fn record_carries_metadata() {
+        let record = CalculationRecord::from_result(
+            10,
+            &ok_result("FastDoubling", 55, 2),
+            ValueField::None,
+            &mut conv(),
+        );
+        assert_eq!(record.n, 10);
+        assert_eq!(record.duration_ns, 2_000_000);
+        assert_eq!(record.bits, Some(6));
+        assert_eq!(record.digits, Some(2));
+        // sha256("55")
+        assert_eq!(
+            record.hash.as_deref(),
+            Some("sha256:02d20bbd7e394ad5999a4cebabac9619732c343a4cac99470c03e23ba2bdc2bc")
+        );
+        assert!(record.value.is_none());
+        assert!(record.error.is_none());
+    }
+
+    #[test]
+    fn record_value_fields() {
+        let result = ok_result("FastDoubling", 832_040, 1);
+        let full = CalculationRecord::from_result(30, &result, ValueField::Full, &mut conv());
+        assert_eq!(full.value.as_deref(), Some("832040"));
+        let last =
+            CalculationRecord::from_result(30, &result, ValueField::LastDigits(3), &mut conv());
+        assert_eq!(last.last_digits.as_deref(), Some("040"));
+        let padded =
+            CalculationRecord::from_result(30, &result, ValueField::LastDigits(8), &mut conv());
+        assert_eq!(padded.last_digits.as_deref(), Some("00832040"));
+        assert!(padded.bits.is_none() && padded.digits.is_none());
+        let unreduced = ok_result("FastDoubling", 700_832_040, 2);
+        let unreduced =
+            CalculationRecord::from_result(30, &unreduced, ValueField::LastDigits(8), &mut conv());
+        assert_eq!(unreduced.last_digits, padded.last_digits);
+        assert_eq!(unreduced.hash, padded.hash);
+        let residue = CalculationRecord::from_result(30, &result, ValueField::Residue, &mut conv());
+        assert_eq!(residue.residue.as_deref(), Some("832040"));
+        assert!(residue.bits.is_none() && residue.value.is_none());
+        assert_eq!(residue.hash, full.hash);
+    }
+
+    #[test]
+    fn metadata_only_record_matches_full_record() {
+        let value = BigUint::from(7u32).pow(40_000);
+        for negated in [false, true] {
+            let outcome = Ok(&value);
+            let none = CalculationRecord::new(
+                "A",
+                1,
+                outcome,
+                Duration::ZERO,
+                ValueField::None,
+                negated,
+                &mut conv(),
+            );
+            let full = CalculationRecord::new(
+                "A",
+                1,
+                outcome,
+                Duration::ZERO,
+                ValueField::Full,
+                negated,
+                &mut conv(),
+            );
+            assert_eq!(none.digits, full.digits);
+            assert_eq!(none.bits, full.bits);
+            assert_eq!(none.hash, full.hash);
+            assert!(none.value.is_none());
+        }
+    }
+
+    #[test]
+    fn record_error_kind() {
+        let result = CalculationResult {
+            algorithm: "FFTBased".into(),
+            outcome: Err(FibError::Cancelled),
+            duration: Duration::ZERO,
+        };
+        let json = serde_json::to_value(CalculationRecord::from_result(
+            5,
+            &result,
+            ValueField::Full,
+            &mut conv(),
+        ))
+        .unwrap();
+        assert_eq!(json["error"]["kind"], "cancelled");
+        assert!(json.get("hash").is_none());
+        assert!(json.get("value").is_none());
+    }
+
+    #[test]
+    fn ndjson_one_line_per_record_plus_summary() {
+        let results = vec![ok_result("FastDoubling", 55, 3), ok_result("Matrix", 55, 1)];
+        let out = render_run(
+            ResultFormat::Ndjson,
+            10,
+            &results,
+            ValueField::None,
+            &mut conv(),
+        );
+        let lines: Vec<serde_json::Value> = out
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0]["type"], "result");
+        assert_eq!(lines[2]["type"], "comparison");
+        assert_eq!(lines[2]["fastest"], "Matrix");
+        assert_eq!(lines[2]["consistent"], true);
+    }
+
+    #[test]
+    fn json_document_single_result_has_no_comparison() {
+        let out = render_run(
+            ResultFormat::Json,
+            10,
+            &[ok_result("FastDoubling", 55, 3)],
+            ValueField::Full,
+            &mut conv(),
+        );
+        let doc: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(doc["results"][0]["value"], "55");
+        assert!(doc.get("comparison").is_none());
+    }
+
+    #[test]
+    fn comparison_detects_inconsistent_hashes() {
+        let results = [ok_result("A", 55, 1), ok_result("B", 56, 2)];
+        let records: Vec<_> = results
+            .iter()
+            .map(|r| CalculationRecord::from_result(10, r, ValueField::None, &mut conv()))
+            .collect();
+        let summary = ComparisonRecord::from_records(10, &records);
+        assert!(!summary.consistent);
+        assert_eq!(summary.succeeded, 2);
+    }
+
+    #[test]
+    fn comparison_reuses_printed_records() {
+        let presenter = JsonResultPresenter::new(ResultFormat::Ndjson, 10, ValueField::None);
+        let results = vec![ok_result("A", 55, 2), ok_result("B", 55, 1)];
+        for r in &results {
+            let value = r.outcome.as_ref().unwrap();
+            presenter.present_result(&r.algorithm, 10, value, r.duration, false);
+        }
+        assert_eq!(presenter.emitted.lock().len(), 2);
+        presenter.present_comparison(&results);
+        assert!(presenter.emitted.lock().is_empty());
+    }
+
+    #[test]
+    fn negative_index_records() {
+        let result = ok_result("FastDoubling", 55, 1);
+        let presenter = JsonResultPresenter::new(ResultFormat::Ndjson, 10, ValueField::Full)
+            .with_negative_index(true, true);
+        let json = serde_json::to_value(presenter.record(10, &result, ValueField::Full)).unwrap();
+        assert_eq!(json["n"], -10);
+        assert_eq!(json["value"], "-55");
+        assert_eq!(json["digits"], 2);
+        let expected = format!("sha256:{:x}", Sha256::digest(b"-55"));
+        assert_eq!(json["hash"], expected.as_str());
+
+        // F(−9) = F(9): only the index carries the sign.
+        let result = ok_result("FastDoubling", 34, 1);
+        let presenter = JsonResultPresenter::new(ResultFormat::Ndjson, 9, ValueField::Full)
+            .with_negative_index(true, false);
+        let json = serde_json::to_value(presenter.record(9, &result, ValueField::Full)).unwrap();
+        assert_eq!(json["n"], -9);
+        assert_eq!(json["value"], "34");
+    }
+
+    #[test]
+    fn records_carry_sequence_symbol() {
+        let result = ok_result("LucasFastDoubling", 123, 1);
+        let lucas = JsonResultPresenter::new(ResultFormat::Ndjson, 10, ValueField::Full)
+            .with_sequence(Sequence::Lucas);
+        let json = serde_json::to_value(lucas.record(10, &result, ValueField::Full)).unwrap();
+        assert_eq!(json["sequence"], "L");
+        assert_eq!(json["value"], "123");
+
+        let fib = JsonResultPresenter::new(ResultFormat::Ndjson, 10, ValueField::None)
+            .with_sequence(Sequence::Fibonacci);
+        let json = serde_json::to_value(fib.record(10, &result, ValueField::None)).unwrap();
+        assert!(json.get("sequence").is_none());
+    }
+
+    #[test]
+    fn text_format_renders_nothing() {
+        let out = render_run(
+            ResultFormat::Text,
+            10,
+            &[ok_result("A", 55, 1)],
+            ValueField::None,
+            &mut conv(),
+        );
+        assert!(out.is_empty());
+    }
+}
@@ -3,9 +3,11 @@
 //! CLI output, progress display, and shell completion.
 
 pub mod completion;
+pub mod json_presenter;
 pub mod output;
 pub mod presenter;
 pub mod progress_eta;
 pub mod ui;
 
-pub use presenter::CLIResultPresenter;
+pub use json_presenter::JsonResultPresenter;
+pub use presenter::{CLIResultPresenter, ResultFormat};
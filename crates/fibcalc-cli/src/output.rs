@@ -1,21 +1,43 @@
 //! CLI output formatting.
 
-use std::io::{self, Write};
+use std::io::{self, BufWriter, Read, Write};
+use std::path::Path;
 use std::time::Duration;
 
-use num_bigint::BigUint;
+use fibcalc_core::decimal::DecimalConverter;
+use num_bigint::{BigInt, BigUint, Sign};
+use sha2::{Digest, Sha256};
 
 /// Format a `BigUint` for display, potentially truncating.
+///
+/// The truncated form only converts the first and last 50 digits, so it
+/// stays cheap even for results with hundreds of millions of digits.
 #[must_use]
-pub fn format_result(value: &BigUint, verbose: bool) -> String {
-    let s = value.to_string();
-    if !verbose && s.len() > 100 {
-        format!("{}...{} ({} digits)", &s[..50], &s[s.len() - 50..], s.len())
+pub fn format_result(value: &BigUint, verbose: bool, conv: &mut DecimalConverter) -> String {
+    if verbose {
+        return conv.to_string(value);
+    }
+    let digits = conv.digit_count(value);
+    if digits > 100 {
+        format!(
+            "{}...{} ({digits} digits)",
+            conv.leading_digits(value, 50),
+            conv.trailing_digits(value, 50)
+        )
     } else {
-        s
+        value.to_string()
     }
 }
 
+/// Last `k` decimal digits of `value`, left-padded with zeros to exactly
+/// `k` characters.
+#[must_use]
+pub fn format_last_digits(value: &BigUint, k: u32) -> String {
+    let decimal = (value % BigUint::from(10u32).pow(k)).to_string();
+    let k = k as usize;
+    format!("{decimal:0>k$}")
+}
+
 /// Format a duration for display.
 #[must_use]
 #[allow(
@@ -52,21 +74,435 @@ pub fn format_number(n: u64) -> String {
     result.chars().rev().collect()
 }
 
-/// Write result to a file.
+/// Write result to a file, streaming its decimal digits in chunks.
 ///
 /// # Errors
 ///
 /// Returns an I/O error if the file cannot be created or written.
-pub fn write_to_file(path: &str, value: &BigUint) -> io::Result<()> {
+pub fn write_to_file(path: &str, value: &BigUint, conv: &mut DecimalConverter) -> io::Result<()> {
     let mut file = std::fs::File::create(path)?;
-    write!(file, "{value}")?;
-    Ok(())
+    conv.write(value, &mut file)?;
+    file.sync_all()
+}
+
+/// Write the decimal digits of `value` and a newline to stdout.
+///
+/// # Errors
+///
+/// Returns an I/O error if stdout cannot be written.
+pub fn write_to_stdout(value: &BigUint, conv: &mut DecimalConverter) -> io::Result<()> {
+    let mut out = io::stdout().lock();
+    conv.write(value, &mut out)?;
+    writeln!(out)
+}
+
+/// Write one `index value` line of a streamed sequence.
+///
+/// Pass the same `conv` for every term so its powers of ten are reused.
+///
+/// # Errors
+///
+/// Returns any I/O error from the writer.
+pub fn write_sequence_term(
+    writer: &mut impl Write,
+    index: u64,
+    value: &BigUint,
+    conv: &mut DecimalConverter,
+) -> io::Result<()> {
+    write!(writer, "{index} ")?;
+    conv.write(value, writer)?;
+    writeln!(writer)
+}
+
+/// Write one `index value` line of a sequence over signed indices.
+///
+/// # Errors
+///
+/// Returns any I/O error from the writer.
+pub fn write_signed_sequence_term(
+    writer: &mut impl Write,
+    index: i64,
+    value: &BigInt,
+    conv: &mut DecimalConverter,
+) -> io::Result<()> {
+    write!(writer, "{index} ")?;
+    if value.sign() == Sign::Minus {
+        writer.write_all(b"-")?;
+    }
+    conv.write(value.magnitude(), writer)?;
+    writeln!(writer)
+}
+
+/// Encoding of a value written with `--output`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum ValueFormat {
+    /// Decimal digits.
+    #[default]
+    Dec,
+    /// Lowercase hexadecimal digits, without a `0x` prefix.
+    Hex,
+    /// Raw little-endian bytes.
+    BinLe,
+    /// Raw big-endian bytes.
+    BinBe,
+    /// Standard padded base64 of the big-endian bytes.
+    Base64,
+}
+
+impl ValueFormat {
+    /// Whether this format is raw binary (and may carry a [`BinaryHeader`]).
+    #[must_use]
+    pub const fn is_binary(self) -> bool {
+        matches!(self, Self::BinLe | Self::BinBe)
+    }
+}
+
+/// Magic bytes opening a binary result header.
+pub const HEADER_MAGIC: &[u8; 8] = b"FIBVAL01";
+
+/// Size in bytes of an encoded [`BinaryHeader`].
+pub const HEADER_LEN: usize = 8 + 1 + 8 + 8 + 8 + 32;
+
+/// Optional header in front of `bin-le`/`bin-be` payloads.
+///
+/// Layout (integers little-endian): magic, byte order (0 = LE, 1 = BE),
+/// `n` u64, bit length u64, payload length u64, SHA-256 of the payload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BinaryHeader {
+    /// Index of the Fibonacci number stored in the payload.
+    pub n: u64,
+    /// Bit length of the value.
+    pub bits: u64,
+    /// Number of payload bytes following the header.
+    pub payload_len: u64,
+    /// SHA-256 of the payload bytes.
+    pub checksum: [u8; 32],
+}
+
+impl BinaryHeader {
+    fn encode(&self, format: ValueFormat) -> [u8; HEADER_LEN] {
+        let mut out = [0u8; HEADER_LEN];
+        out[..8].copy_from_slice(HEADER_MAGIC);
+        out[8] = u8::from(format == ValueFormat::BinBe);
+        out[9..17].copy_from_slice(&self.n.to_le_bytes());
+        out[17..25].copy_from_slice(&self.bits.to_le_bytes());
+        out[25..33].copy_from_slice(&self.payload_len.to_le_bytes());
+        out[33..].copy_from_slice(&self.checksum);
+        out
+    }
+
+    fn decode(bytes: &[u8], format: ValueFormat) -> io::Result<Self> {
+        if bytes.len() < HEADER_LEN || &bytes[..8] != HEADER_MAGIC {
+            return Err(invalid_data("missing binary result header"));
+        }
+        if bytes[8] != u8::from(format == ValueFormat::BinBe) {
+            return Err(invalid_data("header byte order does not match format"));
+        }
+        let u64_at = |at: usize| {
+            let mut buf = [0u8; 8];
+            buf.copy_from_slice(&bytes[at..at + 8]);
+            u64::from_le_bytes(buf)
+        };
+        let mut checksum = [0u8; 32];
+        checksum.copy_from_slice(&bytes[33..HEADER_LEN]);
+        Ok(Self {
+            n: u64_at(9),
+            bits: u64_at(17),
+            payload_len: u64_at(25),
+            checksum,
+        })
+    }
+}
+
+/// A value read back by [`read_value_file`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadedValue {
+    /// The decoded value; only `dec` and `hex` can hold a negative one.
+    pub value: BigInt,
+    /// The verified header, if one was read.
+    pub header: Option<BinaryHeader>,
+}
+
+fn invalid_data(msg: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.into())
+}
+
+/// Write `value` to `writer` in the given format.
+///
+/// `header` holds the index `n` to record in a [`BinaryHeader`]; it is
+/// only honored for binary formats. `conv` converts the `dec` format.
+///
+/// # Errors
+///
+/// Returns any I/O error from the writer, or `InvalidInput` when a header
+/// is requested for a text format.
+pub fn write_value(
+    writer: &mut impl Write,
+    value: &BigUint,
+    format: ValueFormat,
+    header: Option<u64>,
+    conv: &mut DecimalConverter,
+) -> io::Result<()> {
+    if header.is_some() && !format.is_binary() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "a result header is only supported for bin-le and bin-be",
+        ));
+    }
+    match format {
+        ValueFormat::Dec => conv.write(value, writer),
+        ValueFormat::Hex => writer.write_all(value.to_str_radix(16).as_bytes()),
+        ValueFormat::Base64 => writer.write_all(encode_base64(&value.to_bytes_be()).as_bytes()),
+        ValueFormat::BinLe | ValueFormat::BinBe => {
+            let payload = if format == ValueFormat::BinLe {
+                value.to_bytes_le()
+            } else {
+                value.to_bytes_be()
+            };
+            if let Some(n) = header {
+                let header = BinaryHeader {
+                    n,
+                    bits: value.bits(),
+                    payload_len: payload.len() as u64,
+                    checksum: Sha256::digest(&payload).into(),
+                };
+                writer.write_all(&header.encode(format))?;
+            }
+            writer.write_all(&payload)
+        }
+    }
+}
+
+/// Write `value` with a leading `-` when `negative`, as for the terms of
+/// negative indices.
+///
+/// Only `dec` and `hex` can carry the sign; the other formats write
+/// magnitudes, so a negative value is rejected for them.
+///
+/// # Errors
+///
+/// Returns the errors of [`write_value`], or `InvalidInput` for a negative
+/// value in a binary or base64 format.
+pub fn write_signed_value(
+    writer: &mut impl Write,
+    value: &BigUint,
+    negative: bool,
+    format: ValueFormat,
+    header: Option<u64>,
+    conv: &mut DecimalConverter,
+) -> io::Result<()> {
+    if negative {
+        if !matches!(format, ValueFormat::Dec | ValueFormat::Hex) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "negative values can only be written as dec or hex",
+            ));
+        }
+        writer.write_all(b"-")?;
+    }
+    write_value(writer, value, format, header, conv)
+}
+
+/// Write `value` to the file at `path` in the given format.
+///
+/// See [`write_value`] for the meaning of `header`.
+///
+/// # Errors
+///
+/// Returns an I/O error if the file cannot be created or written.
+pub fn write_value_file(
+    path: impl AsRef<Path>,
+    value: &BigUint,
+    format: ValueFormat,
+    header: Option<u64>,
+    conv: &mut DecimalConverter,
+) -> io::Result<()> {
+    write_signed_value_file(path, value, false, format, header, conv)
+}
+
+/// Write `value` to the file at `path`, with a leading `-` when `negative`.
+///
+/// See [`write_signed_value`].
+///
+/// # Errors
+///
+/// Returns an I/O error if the file cannot be created or written, or
+/// `InvalidInput` for a negative value in a binary or base64 format.
+pub fn write_signed_value_file(
+    path: impl AsRef<Path>,
+    value: &BigUint,
+    negative: bool,
+    format: ValueFormat,
+    header: Option<u64>,
+    conv: &mut DecimalConverter,
+) -> io::Result<()> {
+    if negative && !matches!(format, ValueFormat::Dec | ValueFormat::Hex) {
+        // Fail before creating the file.
+        return write_signed_value(&mut io::sink(), value, negative, format, header, conv);
+    }
+    let file = std::fs::File::create(path)?;
+    let mut writer = BufWriter::new(file);
+    write_signed_value(&mut writer, value, negative, format, header, conv)?;
+    writer
+        .into_inner()
+        .map_err(io::IntoInnerError::into_error)?
+        .sync_all()
+}
+
+/// Read a value written by [`write_value`] or [`write_signed_value`] back
+/// from `reader`.
+///
+/// `dec` and `hex` take an optional leading `-`. With `header` set, a [`BinaryHeader`] is expected in front of the
+/// payload and its length, bit length and checksum are verified.
+///
+/// # Errors
+///
+/// Returns `InvalidData` if the contents do not parse in `format` or fail
+/// header verification, or any I/O error from the reader.
+pub fn read_value(
+    reader: &mut impl Read,
+    format: ValueFormat,
+    header: bool,
+) -> io::Result<LoadedValue> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    if format.is_binary() {
+        return decode_binary(&bytes, format, header);
+    }
+    if header {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "a result header is only supported for bin-le and bin-be",
+        ));
+    }
+    let text = std::str::from_utf8(&bytes)
+        .map_err(|e| invalid_data(e.to_string()))?
+        .trim_end();
+    let (sign, digits) = match text.strip_prefix('-') {
+        Some(digits) if format != ValueFormat::Base64 => (Sign::Minus, digits),
+        _ => (Sign::Plus, text),
+    };
+    let magnitude = match format {
+        ValueFormat::Dec => BigUint::parse_bytes(digits.as_bytes(), 10),
+        ValueFormat::Hex => BigUint::parse_bytes(digits.as_bytes(), 16),
+        _ => decode_base64(digits).map(|b| BigUint::from_bytes_be(&b)),
+    }
+    .ok_or_else(|| invalid_data(format!("not a valid {format:?} value")))?;
+    let value = BigInt::from_biguint(sign, magnitude);
+    Ok(LoadedValue {
+        value,
+        header: None,
+    })
+}
+
+/// Read a value written by [`write_value_file`] back from `path`.
+///
+/// # Errors
+///
+/// See [`read_value`]; also fails if the file cannot be opened.
+pub fn read_value_file(
+    path: impl AsRef<Path>,
+    format: ValueFormat,
+    header: bool,
+) -> io::Result<LoadedValue> {
+    read_value(&mut std::fs::File::open(path)?, format, header)
+}
+
+fn decode_binary(bytes: &[u8], format: ValueFormat, header: bool) -> io::Result<LoadedValue> {
+    let (header, payload) = if header {
+        let header = BinaryHeader::decode(bytes, format)?;
+        let payload = &bytes[HEADER_LEN..];
+        if payload.len() as u64 != header.payload_len {
+            return Err(invalid_data(format!(
+                "payload is {} bytes, header says {}",
+                payload.len(),
+                header.payload_len
+            )));
+        }
+        let checksum: [u8; 32] = Sha256::digest(payload).into();
+        if checksum != header.checksum {
+            return Err(invalid_data("payload checksum mismatch"));
+        }
+        (Some(header), payload)
+    } else {
+        (None, bytes)
+    };
+    let value = if format == ValueFormat::BinLe {
+        BigUint::from_bytes_le(payload)
+    } else {
+        BigUint::from_bytes_be(payload)
+    };
+    if let Some(ref h) = header {
+        if value.bits() != h.bits {
+            return Err(invalid_data(format!(
+                "value has {} bits, header says {}",
+                value.bits(),
+                h.bits
+            )));
+        }
+    }
+    Ok(LoadedValue {
+        value: value.into(),
+        header,
+    })
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn encode_base64(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let word = u32::from(b[0]) << 16 | u32::from(b[1]) << 8 | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(char::from(
+                    BASE64_ALPHABET[(word >> (18 - 6 * i)) as usize & 63],
+                ));
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    let text = text.as_bytes();
+    if text.len() % 4 != 0 {
+        return None;
+    }
+    let mut out = Vec::with_capacity(text.len() / 4 * 3);
+    for (index, chunk) in text.chunks(4).enumerate() {
+        let last = index + 1 == text.len() / 4;
+        let pad = chunk.iter().rev().take_while(|&&c| c == b'=').count();
+        if pad > 2 || (pad > 0 && !last) {
+            return None;
+        }
+        let mut word = 0u32;
+        for &c in &chunk[..4 - pad] {
+            let v = BASE64_ALPHABET.iter().position(|&a| a == c)?;
+            word = word << 6 | u32::try_from(v).ok()?;
+        }
+        word <<= 6 * pad;
+        out.extend_from_slice(&word.to_be_bytes()[1..4 - pad]);
+    }
+    Some(out)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn conv() -> DecimalConverter {
+        DecimalConverter::new()
+    }
+
     #[test]
     fn format_duration_micro() {
         let s = format_duration(Duration::from_nanos(500));
@@ -101,7 +537,202 @@ mod tests {
     #[test]
     fn format_result_short() {
         let value = BigUint::from(12345u64);
-        let s = format_result(&value, false);
+        let s = format_result(&value, false, &mut conv());
         assert_eq!(s, "12345");
     }
+
+    #[test]
+    fn format_last_digits_pads_to_width() {
+        let value = BigUint::from(832_040_u64);
+        assert_eq!(format_last_digits(&value, 3), "040");
+        assert_eq!(format_last_digits(&value, 6), "832040");
+        assert_eq!(format_last_digits(&value, 8), "00832040");
+        assert_eq!(format_last_digits(&BigUint::from(0u32), 4), "0000");
+    }
+
+    #[test]
+    fn format_result_truncates_long_values() {
+        let value = BigUint::from(7u32).pow(500);
+        let s = value.to_string();
+        let formatted = format_result(&value, false, &mut conv());
+        assert_eq!(
+            formatted,
+            format!("{}...{} ({} digits)", &s[..50], &s[s.len() - 50..], s.len())
+        );
+        assert_eq!(format_result(&value, true, &mut conv()), s);
+    }
+
+    #[test]
+    fn write_to_file_matches_display() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("value.txt");
+        let value = BigUint::from(3u32).pow(20_000);
+        write_to_file(path.to_str().unwrap(), &value, &mut conv()).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), value.to_string());
+    }
+
+    #[test]
+    fn write_sequence_term_line() {
+        let mut conv = conv();
+        let mut buf = Vec::new();
+        write_sequence_term(&mut buf, 10, &BigUint::from(55u32), &mut conv).unwrap();
+        write_sequence_term(&mut buf, 11, &BigUint::from(89u32), &mut conv).unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), "10 55\n11 89\n");
+
+        let mut buf = Vec::new();
+        write_signed_sequence_term(&mut buf, -10, &BigInt::from(-55), &mut conv).unwrap();
+        write_signed_sequence_term(&mut buf, -11, &BigInt::from(89), &mut conv).unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), "-10 -55\n-11 89\n");
+    }
+
+    fn round_trip(value: &BigUint, format: ValueFormat, header: Option<u64>) -> LoadedValue {
+        let mut buf = Vec::new();
+        write_value(&mut buf, value, format, header, &mut conv()).unwrap();
+        read_value(&mut buf.as_slice(), format, header.is_some()).unwrap()
+    }
+
+    #[test]
+    fn value_formats_round_trip() {
+        let values = [
+            BigUint::from(0u32),
+            BigUint::from(255u32),
+            BigUint::from(65_536u32),
+            BigUint::from(3u32).pow(5_000),
+        ];
+        for value in &values {
+            for format in [
+                ValueFormat::Dec,
+                ValueFormat::Hex,
+                ValueFormat::BinLe,
+                ValueFormat::BinBe,
+                ValueFormat::Base64,
+            ] {
+                let loaded = round_trip(value, format, None);
+                assert_eq!(loaded.value, BigInt::from(value.clone()), "{format:?}");
+                assert!(loaded.header.is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn hex_and_base64_encodings() {
+        let mut buf = Vec::new();
+        write_value(
+            &mut buf,
+            &BigUint::from(0xabc_def_u32),
+            ValueFormat::Hex,
+            None,
+            &mut conv(),
+        )
+        .unwrap();
+        assert_eq!(buf, b"abcdef");
+        assert_eq!(encode_base64(b"f"), "Zg==");
+        assert_eq!(encode_base64(b"fo"), "Zm8=");
+        assert_eq!(encode_base64(b"foobar"), "Zm9vYmFy");
+        assert_eq!(decode_base64("Zm9vYg==").unwrap(), b"foob");
+        assert!(decode_base64("Zm9=Yg==").is_none());
+        assert!(decode_base64("Zm9").is_none());
+    }
+
+    #[test]
+    fn binary_header_round_trip() {
+        let value = BigUint::from(7u32).pow(1_000);
+        for format in [ValueFormat::BinLe, ValueFormat::BinBe] {
+            let loaded = round_trip(&value, format, Some(1234));
+            assert_eq!(loaded.value, value.clone().into());
+            let header = loaded.header.unwrap();
+            assert_eq!(header.n, 1234);
+            assert_eq!(header.bits, value.bits());
+        }
+    }
+
+    #[test]
+    fn binary_header_detects_corruption() {
+        let value = BigUint::from(7u32).pow(1_000);
+        let mut buf = Vec::new();
+        write_value(&mut buf, &value, ValueFormat::BinBe, Some(5), &mut conv()).unwrap();
+        assert_eq!(buf.len(), HEADER_LEN + value.to_bytes_be().len());
+
+        let mut corrupted = buf.clone();
+        *corrupted.last_mut().unwrap() ^= 1;
+        let err = read_value(&mut corrupted.as_slice(), ValueFormat::BinBe, true).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let truncated = &buf[..buf.len() - 1];
+        assert!(read_value(&mut &truncated[..], ValueFormat::BinBe, true).is_err());
+        assert!(read_value(&mut buf.as_slice(), ValueFormat::BinLe, true).is_err());
+    }
+
+    #[test]
+    fn header_rejected_for_text_formats() {
+        let mut buf = Vec::new();
+        let err = write_value(
+            &mut buf,
+            &BigUint::from(1u32),
+            ValueFormat::Hex,
+            Some(1),
+            &mut conv(),
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn write_value_file_round_trip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("value.bin");
+        let value = BigUint::from(5u32).pow(3_000);
+        write_value_file(&path, &value, ValueFormat::BinLe, Some(42), &mut conv()).unwrap();
+        let loaded = read_value_file(&path, ValueFormat::BinLe, true).unwrap();
+        assert_eq!(loaded.value, value.into());
+        assert_eq!(loaded.header.unwrap().n, 42);
+    }
+
+    #[test]
+    fn signed_values() {
+        let value = BigUint::from(55u32);
+        for (format, expected) in [(ValueFormat::Dec, "-55"), (ValueFormat::Hex, "-37")] {
+            let mut buf = Vec::new();
+            write_signed_value(&mut buf, &value, true, format, None, &mut conv()).unwrap();
+            assert_eq!(std::str::from_utf8(&buf).unwrap(), expected);
+            let loaded = read_value(&mut buf.as_slice(), format, false).unwrap();
+            assert_eq!(loaded.value, BigInt::from(-55), "{format:?}");
+        }
+        for (text, format) in [("--55", ValueFormat::Dec), ("-", ValueFormat::Hex)] {
+            assert!(
+                read_value(&mut text.as_bytes(), format, false).is_err(),
+                "{text:?}"
+            );
+        }
+        assert!(read_value(&mut "-Nw==".as_bytes(), ValueFormat::Base64, false).is_err());
+        let mut buf = Vec::new();
+        write_signed_value(
+            &mut buf,
+            &value,
+            false,
+            ValueFormat::BinLe,
+            None,
+            &mut conv(),
+        )
+        .unwrap();
+        assert_eq!(buf, [55]);
+        for format in [ValueFormat::BinLe, ValueFormat::BinBe, ValueFormat::Base64] {
+            let err = write_signed_value(&mut Vec::new(), &value, true, format, None, &mut conv())
+                .unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        }
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("value.bin");
+        assert!(write_signed_value_file(
+            &path,
+            &value,
+            true,
+            ValueFormat::BinBe,
+            None,
+            &mut conv()
+        )
+        .is_err());
+        assert!(!path.exists());
+    }
 }
@@ -1,57 +1,192 @@
 //! CLI result presenter.
 
+use std::io::Write;
 use std::time::Duration;
 
 use num_bigint::BigUint;
+use parking_lot::Mutex;
 
+use fibcalc_core::decimal::DecimalConverter;
+use fibcalc_core::lucas::Sequence;
 use fibcalc_orchestration::interfaces::{CalculationResult, ResultPresenter};
 
-use crate::output::{format_duration, format_number, format_result};
+use crate::output::{
+    format_duration, format_last_digits, format_number, format_result, write_to_stdout,
+};
+
+/// Result output format selected by `--format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum ResultFormat {
+    /// Human-readable text.
+    #[default]
+    Text,
+    /// One JSON document for the whole run.
+    Json,
+    /// One JSON object per line.
+    Ndjson,
+}
 
 /// CLI result presenter.
 pub struct CLIResultPresenter {
     verbose: bool,
     quiet: bool,
+    last_digits: Option<u32>,
+    modulus: Option<String>,
+    sequence: Sequence,
+    negative_index: bool,
+    negated: bool,
+    /// Shared by every result, so powers of ten are built once per run.
+    converter: Mutex<DecimalConverter>,
 }
 
 impl CLIResultPresenter {
     #[must_use]
     pub fn new(verbose: bool, quiet: bool) -> Self {
-        Self { verbose, quiet }
+        Self {
+            verbose,
+            quiet,
+            last_digits: None,
+            modulus: None,
+            sequence: Sequence::Fibonacci,
+            negative_index: false,
+            negated: false,
+            converter: Mutex::new(DecimalConverter::new()),
+        }
     }
-}
 
-impl ResultPresenter for CLIResultPresenter {
-    fn present_result(
+    /// Convert results to decimal with `converter`, e.g. one built for the
+    /// run's FFT configuration.
+    #[must_use]
+    pub fn with_converter(mut self, converter: DecimalConverter) -> Self {
+        self.converter = Mutex::new(converter);
+        self
+    }
+
+    /// Present results as F(n) mod 10^k, zero-padded to exactly `k`
+    /// digits, when `last_digits` is `Some(k)` with `k > 0`.
+    #[must_use]
+    pub fn with_last_digits(mut self, last_digits: Option<u32>) -> Self {
+        self.last_digits = last_digits.filter(|&k| k > 0);
+        self
+    }
+
+    /// Present results as F(n) mod m, labelling m as the user wrote it.
+    #[must_use]
+    pub fn with_modulus(mut self, modulus: Option<String>) -> Self {
+        self.modulus = modulus.map(|m| m.trim().to_string());
+        self
+    }
+
+    /// Label results with the symbol of `sequence`, e.g. `L(n) = ...`.
+    #[must_use]
+    pub fn with_sequence(mut self, sequence: Sequence) -> Self {
+        self.sequence = sequence;
+        self
+    }
+
+    /// Label the index as −n when `negative`, and print values with a
+    /// minus sign when `negated` as well.
+    ///
+    /// Residues of modular runs are printed as given, so pass `negated`
+    /// only for exact values.
+    #[must_use]
+    pub fn with_negative_index(mut self, negative: bool, negated: bool) -> Self {
+        self.negative_index = negative;
+        self.negated = negative && negated;
+        self
+    }
+
+    /// Present F(n), or F(n) mod m in a modular run, for an index too large
+    /// for [`ResultPresenter::present_result`].
+    pub fn present_big_index(
         &self,
         algorithm: &str,
-        n: u64,
+        n: &BigUint,
+        result: &BigUint,
+        duration: Duration,
+    ) {
+        let n = format_result(n, self.verbose, &mut self.converter.lock());
+        self.present(algorithm, &n, result, duration, false);
+    }
+
+    /// The modulus label and the residue, in a modular run.
+    fn residue(&self, result: &BigUint) -> Option<(String, String)> {
+        if let Some(k) = self.last_digits {
+            return Some((format!("10^{k}"), format_last_digits(result, k)));
+        }
+        self.modulus
+            .as_ref()
+            .map(|modulus| (modulus.clone(), result.to_string()))
+    }
+
+    fn present(
+        &self,
+        algorithm: &str,
+        n: &str,
         result: &BigUint,
         duration: Duration,
         details: bool,
     ) {
+        let residue = self.residue(result);
+        let mut conv = self.converter.lock();
+        let sign = if self.negated && residue.is_none() {
+            "-"
+        } else {
+            ""
+        };
         if self.quiet {
-            println!("{result}");
+            let written = match &residue {
+                Some((_, residue)) => writeln!(std::io::stdout(), "{residue}"),
+                None => write!(std::io::stdout(), "{sign}")
+                    .and_then(|()| write_to_stdout(result, &mut conv)),
+            };
+            if let Err(e) = written {
+                eprintln!("Error: failed to write result: {e}");
+            }
             return;
         }
 
+        let n = if self.negative_index {
+            format!("-{n}")
+        } else {
+            n.to_string()
+        };
         println!("Algorithm: {algorithm}");
-        println!("N: {}", format_number(n));
+        println!("N: {n}");
         println!("Duration: {}", format_duration(duration));
 
+        // Only F(n) mod m is known in a modular run.
+        let symbol = self.sequence.symbol();
+        if let Some((modulus, residue)) = residue {
+            println!("{symbol}({n}) mod {modulus} = {residue}");
+            return;
+        }
+
         if details {
             let bits = result.bits();
-            let digits = result.to_string().len();
+            let digits = conv.digit_count(result);
             println!("Result bits: {bits}");
             println!("Result digits: {digits}");
         }
 
         println!(
-            "F({}) = {}",
-            format_number(n),
-            format_result(result, self.verbose)
+            "{symbol}({n}) = {sign}{}",
+            format_result(result, self.verbose, &mut conv)
         );
     }
+}
+
+impl ResultPresenter for CLIResultPresenter {
+    fn present_result(
+        &self,
+        algorithm: &str,
+        n: u64,
+        result: &BigUint,
+        duration: Duration,
+        details: bool,
+    ) {
+        self.present(algorithm, &format_number(n), result, duration, details);
+    }
 
     fn present_comparison(&self, results: &[CalculationResult]) {
         if self.quiet {
@@ -122,6 +257,60 @@ mod tests {
         presenter.present_result("Matrix", 30, &result, Duration::from_millis(10), true);
     }
 
+    #[test]
+    fn presenter_last_digits() {
+        let presenter = CLIResultPresenter::new(false, false).with_last_digits(Some(8));
+        assert_eq!(presenter.last_digits, Some(8));
+        let result = BigUint::from(832_040_u64);
+        presenter.present_result("FastDoublingMod", 30, &result, Duration::ZERO, true);
+        let presenter = CLIResultPresenter::new(false, true).with_last_digits(Some(0));
+        assert_eq!(presenter.last_digits, None);
+    }
+
+    #[test]
+    fn presenter_modulus() {
+        let presenter = CLIResultPresenter::new(false, false).with_modulus(Some(" 10^9+7 ".into()));
+        let result = BigUint::from(21u32);
+        assert_eq!(
+            presenter.residue(&result),
+            Some(("10^9+7".to_string(), "21".to_string()))
+        );
+        assert_eq!(CLIResultPresenter::new(false, false).residue(&result), None);
+        let n = BigUint::from(10u32).pow(30);
+        presenter.present_big_index("FastDoublingMod", &n, &result, Duration::ZERO);
+        let quiet = CLIResultPresenter::new(false, true).with_last_digits(Some(4));
+        assert_eq!(
+            quiet.residue(&result),
+            Some(("10^4".to_string(), "0021".to_string()))
+        );
+        quiet.present_big_index("FastDoublingMod", &n, &result, Duration::ZERO);
+    }
+
+    #[test]
+    fn presenter_negative_index() {
+        let presenter = CLIResultPresenter::new(false, false);
+        assert!(!presenter.negative_index && !presenter.negated);
+        let presenter = presenter.with_negative_index(false, true);
+        assert!(!presenter.negated, "non-negative indices are never negated");
+        let presenter = presenter.with_negative_index(true, true);
+        assert!(presenter.negative_index && presenter.negated);
+        let result = BigUint::from(55u32);
+        presenter.present_result("FastDoubling", 10, &result, Duration::ZERO, true);
+        CLIResultPresenter::new(false, true)
+            .with_negative_index(true, true)
+            .present_result("FastDoubling", 10, &result, Duration::ZERO, false);
+    }
+
+    #[test]
+    fn presenter_sequence() {
+        let presenter = CLIResultPresenter::new(false, false);
+        assert_eq!(presenter.sequence, Sequence::Fibonacci);
+        let presenter = presenter.with_sequence(Sequence::LucasV { p: 3, q: 2 });
+        assert_eq!(presenter.sequence.symbol(), "V");
+        let result = BigUint::from(1025u32);
+        presenter.present_result("LucasMatrix", 10, &result, Duration::ZERO, true);
+    }
+
     #[test]
     fn presenter_present_result_verbose() {
         let presenter = CLIResultPresenter::new(true, false);
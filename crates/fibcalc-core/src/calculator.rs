@@ -6,12 +6,14 @@
 
 use std::sync::Arc;
 
-use num_bigint::BigUint;
+use num_bigint::{BigInt, BigUint};
 
 use crate::constants::{FIB_TABLE, MAX_FIB_U64};
+use crate::lucas::Sequence;
 use crate::observer::ProgressObserver;
 use crate::options::Options;
 use crate::progress::{CancellationToken, ProgressUpdate};
+use crate::threshold_types::ThresholdSnapshot;
 
 /// Error type for Fibonacci calculations.
 #[derive(Debug, Clone, thiserror::Error)]
@@ -45,6 +47,34 @@ pub enum FibError {
     InvalidInput(String),
 }
 
+impl FibError {
+    /// Stable machine-readable name of the error variant.
+    #[must_use]
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Calculation(_) => "calculation",
+            Self::Config(_) => "config",
+            Self::Cancelled => "cancelled",
+            Self::Timeout(_) => "timeout",
+            Self::Mismatch => "mismatch",
+            Self::Overflow(..) => "overflow",
+            Self::InvalidInput(_) => "invalid_input",
+        }
+    }
+}
+
+/// Serializes as `{"kind": ..., "message": ...}` for machine-readable output.
+impl serde::Serialize for FibError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("FibError", 2)?;
+        state.serialize_field("kind", self.kind())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
+}
+
 /// Public trait for Fibonacci calculators, consumed by orchestration.
 ///
 /// # Example
@@ -78,8 +108,87 @@ pub trait Calculator: Send + Sync {
         opts: &Options,
     ) -> Result<BigUint, FibError>;
 
+    /// Calculate the term at a signed index, e.g. `F(−n) = (−1)^{n+1}·F(n)`.
+    ///
+    /// Computes the term at `|n|` with [`Calculator::calculate`] and applies
+    /// [`Sequence::negated_at`]; modular runs return the residue in `[0, m)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FibError::InvalidInput` for a negative `n` when the sequence
+    /// has no integer terms there, or any error of `calculate`.
+    fn calculate_signed(
+        &self,
+        cancel: &CancellationToken,
+        observer: &dyn ProgressObserver,
+        calc_index: usize,
+        n: i64,
+        opts: &Options,
+    ) -> Result<BigInt, FibError> {
+        let magnitude = n.unsigned_abs();
+        let negated = n < 0 && negated_at(opts.sequence, magnitude)?;
+        let value = self.calculate(cancel, observer, calc_index, magnitude, opts)?;
+        Ok(signed_term(value, negated, opts.result_modulus().as_ref()))
+    }
+
+    /// Calculate F(n) mod m, for the modulus of `opts`, at an index beyond
+    /// `u64`, reducing `n` by `Options::pisano_period` when it is set.
+    ///
+    /// Only calculators that work modulo m support it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FibError::InvalidInput` for calculators that need a `u64`
+    /// index, or any error of the calculation.
+    fn calculate_big(
+        &self,
+        _cancel: &CancellationToken,
+        _observer: &dyn ProgressObserver,
+        _calc_index: usize,
+        _n: &BigUint,
+        _opts: &Options,
+    ) -> Result<BigUint, FibError> {
+        Err(FibError::InvalidInput(format!(
+            "{} needs |n| below 2^63",
+            self.name()
+        )))
+    }
+
     /// Get the name of this calculator.
     fn name(&self) -> &'static str;
+
+    /// Final thresholds and adjustment history from the most recent run
+    /// with `Options::dynamic_thresholds` enabled, if any.
+    fn threshold_snapshot(&self) -> Option<ThresholdSnapshot> {
+        None
+    }
+}
+
+/// [`Sequence::negated_at`], as an error for sequences without integer
+/// terms at negative indices.
+///
+/// # Errors
+///
+/// Returns `FibError::InvalidInput` when Q is not ±1.
+pub fn negated_at(sequence: Sequence, n: u64) -> Result<bool, FibError> {
+    sequence.negated_at(n).ok_or_else(|| {
+        let (p, q) = sequence.parameters();
+        FibError::InvalidInput(format!(
+            "{}_n({p}, {q}) has integer terms at negative indices only for Q = ±1",
+            sequence.symbol()
+        ))
+    })
+}
+
+/// `value`, or its negation when `negated`; modulo `modulus` the negation
+/// is taken in `[0, m)`.
+#[must_use]
+pub fn signed_term(value: BigUint, negated: bool, modulus: Option<&BigUint>) -> BigInt {
+    match modulus {
+        Some(m) if negated => BigInt::from((m - value % m) % m),
+        None if negated => -BigInt::from(value),
+        _ => BigInt::from(value),
+    }
 }
 
 /// Internal trait for algorithm implementations.
@@ -99,8 +208,33 @@ pub trait CoreCalculator: Send + Sync {
         opts: &Options,
     ) -> Result<BigUint, FibError>;
 
+    /// See [`Calculator::calculate_big`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `FibError::InvalidInput` unless overridden.
+    fn calculate_big(
+        &self,
+        _cancel: &CancellationToken,
+        _observer: &dyn ProgressObserver,
+        _calc_index: usize,
+        _n: &BigUint,
+        _opts: &Options,
+    ) -> Result<BigUint, FibError> {
+        Err(FibError::InvalidInput(format!(
+            "{} needs |n| below 2^63",
+            self.name()
+        )))
+    }
+
     /// Get the name of this algorithm.
     fn name(&self) -> &'static str;
+
+    /// Final thresholds and adjustment history from the most recent run
+    /// with `Options::dynamic_thresholds` enabled, if any.
+    fn threshold_snapshot(&self) -> Option<ThresholdSnapshot> {
+        None
+    }
 }
 
 /// Decorator that wraps a `CoreCalculator` with fast path and progress reporting.
@@ -156,8 +290,8 @@ impl Calculator for FibCalculator {
         n: u64,
         opts: &Options,
     ) -> Result<BigUint, FibError> {
-        // Fast path for small n
-        if n <= MAX_FIB_U64 {
+        // Fast path for small n; modular runs and other sequences go to the core
+        if n <= MAX_FIB_U64 && !opts.is_modular() && opts.sequence == Sequence::Fibonacci {
             observer.on_progress(&ProgressUpdate::done(calc_index, self.inner.name()));
             return Ok(Self::calculate_small(n));
         }
@@ -172,9 +306,28 @@ impl Calculator for FibCalculator {
             .calculate_core(cancel, observer, calc_index, n, opts)
     }
 
+    fn calculate_big(
+        &self,
+        cancel: &CancellationToken,
+        observer: &dyn ProgressObserver,
+        calc_index: usize,
+        n: &BigUint,
+        opts: &Options,
+    ) -> Result<BigUint, FibError> {
+        if cancel.is_cancelled() {
+            return Err(FibError::Cancelled);
+        }
+        self.inner
+            .calculate_big(cancel, observer, calc_index, n, opts)
+    }
+
     fn name(&self) -> &'static str {
         self.inner.name()
     }
+
+    fn threshold_snapshot(&self) -> Option<ThresholdSnapshot> {
+        self.inner.threshold_snapshot()
+    }
 }
 
 #[cfg(test)]
@@ -209,6 +362,66 @@ mod tests {
         let _ = FibCalculator::calculate_small(94);
     }
 
+    #[test]
+    fn negative_indices() {
+        use crate::fastdoubling::OptimizedFastDoubling;
+        use crate::lucas::LucasFastDoubling;
+        use crate::observers::NoOpObserver;
+
+        let calc = FibCalculator::new(Arc::new(OptimizedFastDoubling::new()));
+        let cancel = CancellationToken::new();
+        let observer = NoOpObserver::new();
+        let signed = |n: i64, opts: &Options| {
+            calc.calculate_signed(&cancel, &observer, 0, n, opts)
+                .unwrap()
+                .to_string()
+        };
+        let opts = Options::default();
+        let terms: Vec<String> = (-8..=8).map(|n| signed(n, &opts)).collect();
+        assert_eq!(
+            terms,
+            [
+                "-21", "13", "-8", "5", "-3", "2", "-1", "1", "0", "1", "1", "2", "3", "5", "8",
+                "13", "21"
+            ]
+        );
+        assert_eq!(
+            signed(-200, &opts),
+            "-280571172992510140037611932413038677189525"
+        );
+
+        // Residues of negative terms stay in [0, m).
+        let opts = Options {
+            modulus: Some(Arc::new(BigUint::from(1000u32))),
+            ..Options::default()
+        };
+        assert_eq!(signed(-8, &opts), "979");
+        assert_eq!(signed(-7, &opts), "13");
+
+        let opts = Options {
+            sequence: Sequence::LucasU { p: 3, q: 2 },
+            ..Options::default()
+        };
+        let err = calc.calculate_signed(&cancel, &observer, 0, -3, &opts);
+        assert!(matches!(err, Err(FibError::InvalidInput(_))));
+
+        // L(−n) = (−1)^n·L(n).
+        let lucas = FibCalculator::new(Arc::new(LucasFastDoubling::new()));
+        let opts = Options {
+            sequence: Sequence::Lucas,
+            ..Options::default()
+        };
+        let terms: Vec<String> = (-4..=0)
+            .map(|n| {
+                lucas
+                    .calculate_signed(&cancel, &observer, 0, n, &opts)
+                    .unwrap()
+                    .to_string()
+            })
+            .collect();
+        assert_eq!(terms, ["7", "-4", "3", "-1", "2"]);
+    }
+
     #[test]
     fn fib_error_display() {
         let err = FibError::Calculation("test".into());
@@ -217,4 +430,14 @@ mod tests {
         let err = FibError::Cancelled;
         assert_eq!(err.to_string(), "calculation cancelled");
     }
+
+    #[test]
+    fn fib_error_serializes_kind_and_message() {
+        let json = serde_json::to_value(FibError::Timeout("5m".into())).unwrap();
+        assert_eq!(json["kind"], "timeout");
+        assert_eq!(json["message"], "calculation timed out after 5m");
+
+        let json = serde_json::to_value(FibError::Overflow(94, "u64")).unwrap();
+        assert_eq!(json["kind"], "overflow");
+    }
 }
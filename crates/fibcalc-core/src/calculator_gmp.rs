@@ -60,6 +60,7 @@ mod inner {
             let frozen = observer.freeze();
 
             for i in (0..num_bits).rev() {
+                cancel.wait_while_paused();
                 if cancel.is_cancelled() {
                     return Err(FibError::Cancelled);
                 }
@@ -0,0 +1,471 @@
+//! Checkpoint files for resuming long Fast Doubling runs.
+//!
+//! The doubling loops scan the bits of `n` from MSB to LSB, so the full
+//! state after each iteration is the pair `(F(k), F(k+1))` plus the number
+//! of bits still to process. A checkpoint stores exactly that, together
+//! with `n` and the algorithm name so a resume can be validated. The pair
+//! itself is checked against Cassini's identity before a run resumes.
+//!
+//! File layout (all integers little-endian):
+//!
+//! ```text
+//! magic "FIBCKPT1" | n: u64 | remaining_bits: u32 | name_len: u16 | name
+//! | fk_len: u64 | fk bytes | fk1_len: u64 | fk1 bytes
+//! ```
+
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use fibcalc_bigfft::FFTConfig;
+use num_bigint::BigUint;
+use num_traits::CheckedSub;
+
+use crate::calculator::FibError;
+use crate::fft_wrappers::{smart_multiply, smart_square};
+use crate::options::Options;
+
+/// Magic bytes identifying a checkpoint file (format version 1).
+const MAGIC: &[u8; 8] = b"FIBCKPT1";
+
+/// Algorithms whose loops can write and resume checkpoints.
+pub const SUPPORTED_ALGORITHMS: &[&str] = &["FastDoubling", "FFTBased"];
+
+/// Whether the named algorithm supports checkpoint and resume.
+#[must_use]
+pub fn supports(algorithm: &str) -> bool {
+    SUPPORTED_ALGORITHMS.contains(&algorithm)
+}
+
+/// Saved state of a Fast Doubling run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checkpoint {
+    /// Index being computed.
+    pub n: u64,
+    /// Name of the algorithm that wrote the checkpoint.
+    pub algorithm: String,
+    /// Bits of `n` still to process; the loop resumes at bit `remaining_bits - 1`.
+    pub remaining_bits: u32,
+    /// F(k) where `k = n >> remaining_bits`.
+    pub fk: BigUint,
+    /// F(k+1).
+    pub fk1: BigUint,
+}
+
+impl Checkpoint {
+    /// Read a checkpoint from `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the file cannot be read or is not a valid checkpoint.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        Self::read_from(&mut BufReader::new(File::open(path)?))
+    }
+
+    /// Decode a checkpoint from a reader.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidData` if the magic bytes or field lengths are wrong.
+    pub fn read_from(reader: &mut impl Read) -> io::Result<Self> {
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid_data("not a fibcalc checkpoint file"));
+        }
+
+        let n = u64::from_le_bytes(read_array(reader)?);
+        let remaining_bits = u32::from_le_bytes(read_array(reader)?);
+        let name_len = u16::from_le_bytes(read_array(reader)?);
+        let mut name = vec![0u8; usize::from(name_len)];
+        reader.read_exact(&mut name)?;
+        let algorithm =
+            String::from_utf8(name).map_err(|_| invalid_data("algorithm name is not UTF-8"))?;
+        let fk = read_biguint(reader)?;
+        let fk1 = read_biguint(reader)?;
+
+        if remaining_bits > 64 - n.leading_zeros() {
+            return Err(invalid_data("bit index exceeds the bit length of n"));
+        }
+
+        Ok(Self {
+            n,
+            algorithm,
+            remaining_bits,
+            fk,
+            fk1,
+        })
+    }
+
+    /// Encode this checkpoint to a writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns any I/O error from the writer.
+    pub fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        write_state(
+            writer,
+            self.n,
+            &self.algorithm,
+            self.remaining_bits,
+            &self.fk,
+            &self.fk1,
+        )
+    }
+
+    /// Check that this checkpoint belongs to a run of `algorithm` computing F(n).
+    ///
+    /// The Cassini check multiplies under the run's `fft` configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FibError::Config` if `n` or the algorithm differ, if the
+    /// bit index lies outside `n`, or if `fk` and `fk1` are not consecutive
+    /// Fibonacci numbers of the right parity.
+    pub fn validate(&self, n: u64, algorithm: &str, fft: &FFTConfig) -> Result<(), FibError> {
+        if self.n != n {
+            return Err(FibError::Config(format!(
+                "checkpoint is for n={}, not n={n}",
+                self.n
+            )));
+        }
+        if self.algorithm != algorithm {
+            return Err(FibError::Config(format!(
+                "checkpoint was written by {}, not {algorithm}",
+                self.algorithm
+            )));
+        }
+        if self.remaining_bits > 64 - n.leading_zeros() {
+            return Err(FibError::Config(format!(
+                "checkpoint bit index {} exceeds the bit length of n={n}",
+                self.remaining_bits
+            )));
+        }
+        let k = n.checked_shr(self.remaining_bits).unwrap_or(0);
+        if !self.satisfies_cassini(k, fft) {
+            return Err(FibError::Config(format!(
+                "checkpoint values are not F({k}) and F({}); the file is corrupt",
+                k + 1
+            )));
+        }
+        Ok(())
+    }
+
+    /// Cassini's identity `F(k+1)·F(k−1) − F(k)² = (−1)^k`, with
+    /// `F(k−1) = F(k+1) − F(k)`.
+    ///
+    /// Any other pair of consecutive Fibonacci numbers of the same parity
+    /// also passes, but a corrupted value almost never does.
+    fn satisfies_cassini(&self, k: u64, fft: &FFTConfig) -> bool {
+        let Some(fk_prev) = self.fk1.checked_sub(&self.fk) else {
+            return false;
+        };
+        let product = smart_multiply(&self.fk1, &fk_prev, fft);
+        let fk_squared = smart_square(&self.fk, fft);
+        if k % 2 == 0 {
+            product == fk_squared + 1u32
+        } else {
+            product + 1u32 == fk_squared
+        }
+    }
+}
+
+/// Starting `(remaining_bits, F(k), F(k+1))` for a doubling loop: the
+/// resume checkpoint from `opts` if any, otherwise `(bits(n), F(0), F(1))`.
+pub(crate) fn initial_state(
+    opts: &Options,
+    n: u64,
+    algorithm: &str,
+) -> Result<(u32, BigUint, BigUint), FibError> {
+    match opts.resume.as_deref() {
+        Some(cp) => {
+            cp.validate(n, algorithm, &opts.fft_config())?;
+            Ok((cp.remaining_bits, cp.fk.clone(), cp.fk1.clone()))
+        }
+        None => Ok((64 - n.leading_zeros(), BigUint::ZERO, BigUint::from(1u32))),
+    }
+}
+
+/// When and where a calculation writes checkpoints.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckpointConfig {
+    /// File the checkpoint is written to (replaced atomically).
+    pub path: PathBuf,
+    /// Save after this many loop iterations (`0` disables the count trigger).
+    pub every_iterations: u32,
+    /// Save once this much time has passed since the last save.
+    pub interval: Option<Duration>,
+}
+
+/// Tracks when the next checkpoint is due inside a doubling loop.
+pub(crate) struct CheckpointWriter<'a> {
+    config: &'a CheckpointConfig,
+    n: u64,
+    algorithm: &'static str,
+    iterations_since_save: u32,
+    last_save: Instant,
+}
+
+impl<'a> CheckpointWriter<'a> {
+    pub(crate) fn new(config: &'a CheckpointConfig, n: u64, algorithm: &'static str) -> Self {
+        Self {
+            config,
+            n,
+            algorithm,
+            iterations_since_save: 0,
+            last_save: Instant::now(),
+        }
+    }
+
+    /// Record a finished iteration and save if a trigger fired.
+    pub(crate) fn after_iteration(
+        &mut self,
+        remaining_bits: u32,
+        fk: &BigUint,
+        fk1: &BigUint,
+    ) -> Result<(), FibError> {
+        self.iterations_since_save += 1;
+        let by_count = self.config.every_iterations > 0
+            && self.iterations_since_save >= self.config.every_iterations;
+        let by_time = self
+            .config
+            .interval
+            .is_some_and(|interval| self.last_save.elapsed() >= interval);
+        if by_count || by_time {
+            self.save(remaining_bits, fk, fk1)?;
+        }
+        Ok(())
+    }
+
+    /// Save the given state now.
+    pub(crate) fn save(
+        &mut self,
+        remaining_bits: u32,
+        fk: &BigUint,
+        fk1: &BigUint,
+    ) -> Result<(), FibError> {
+        save_atomically(
+            &self.config.path,
+            self.n,
+            self.algorithm,
+            remaining_bits,
+            fk,
+            fk1,
+        )
+        .map_err(|e| {
+            FibError::Calculation(format!(
+                "failed to write checkpoint '{}': {e}",
+                self.config.path.display()
+            ))
+        })?;
+        self.iterations_since_save = 0;
+        self.last_save = Instant::now();
+        Ok(())
+    }
+}
+
+/// Write to a sibling temporary file, then rename over `path`, so a crash
+/// mid-write never destroys the previous checkpoint.
+fn save_atomically(
+    path: &Path,
+    n: u64,
+    algorithm: &str,
+    remaining_bits: u32,
+    fk: &BigUint,
+    fk1: &BigUint,
+) -> io::Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+
+    let mut writer = BufWriter::new(File::create(&tmp)?);
+    write_state(&mut writer, n, algorithm, remaining_bits, fk, fk1)?;
+    writer
+        .into_inner()
+        .map_err(io::IntoInnerError::into_error)?
+        .sync_all()?;
+    std::fs::rename(&tmp, path)
+}
+
+fn write_state(
+    writer: &mut impl Write,
+    n: u64,
+    algorithm: &str,
+    remaining_bits: u32,
+    fk: &BigUint,
+    fk1: &BigUint,
+) -> io::Result<()> {
+    let name_len =
+        u16::try_from(algorithm.len()).map_err(|_| invalid_data("algorithm name too long"))?;
+    writer.write_all(MAGIC)?;
+    writer.write_all(&n.to_le_bytes())?;
+    writer.write_all(&remaining_bits.to_le_bytes())?;
+    writer.write_all(&name_len.to_le_bytes())?;
+    writer.write_all(algorithm.as_bytes())?;
+    write_biguint(writer, fk)?;
+    write_biguint(writer, fk1)?;
+    writer.flush()
+}
+
+fn write_biguint(writer: &mut impl Write, value: &BigUint) -> io::Result<()> {
+    let bytes = value.to_bytes_le();
+    writer.write_all(&(bytes.len() as u64).to_le_bytes())?;
+    writer.write_all(&bytes)
+}
+
+fn read_biguint(reader: &mut impl Read) -> io::Result<BigUint> {
+    let len = usize::try_from(u64::from_le_bytes(read_array(reader)?))
+        .map_err(|_| invalid_data("integer length does not fit in memory"))?;
+    let mut bytes = Vec::new();
+    reader.take(len as u64).read_to_end(&mut bytes)?;
+    if bytes.len() != len {
+        return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+    }
+    Ok(BigUint::from_bytes_le(&bytes))
+}
+
+fn read_array<const N: usize>(reader: &mut impl Read) -> io::Result<[u8; N]> {
+    let mut buf = [0u8; N];
+    reader.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::DEFAULT_FFT_THRESHOLD;
+
+    const FFT: FFTConfig = FFTConfig::with_threshold(DEFAULT_FFT_THRESHOLD);
+
+    fn sample() -> Checkpoint {
+        Checkpoint {
+            n: 1_000,
+            algorithm: "FastDoubling".to_string(),
+            remaining_bits: 4,
+            fk: BigUint::from(4_052_739_537_881u64),
+            fk1: BigUint::from(6_557_470_319_842u64),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let cp = sample();
+        let mut buf = Vec::new();
+        cp.write_to(&mut buf).unwrap();
+        let decoded = Checkpoint::read_from(&mut buf.as_slice()).unwrap();
+        assert_eq!(decoded, cp);
+    }
+
+    #[test]
+    fn rejects_bad_magic() {
+        let err = Checkpoint::read_from(&mut &b"NOTACKPT________"[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn rejects_truncated_file() {
+        let mut buf = Vec::new();
+        sample().write_to(&mut buf).unwrap();
+        buf.truncate(buf.len() - 3);
+        assert!(Checkpoint::read_from(&mut buf.as_slice()).is_err());
+    }
+
+    #[test]
+    fn rejects_bit_index_beyond_n() {
+        let cp = Checkpoint {
+            remaining_bits: 40,
+            ..sample()
+        };
+        let mut buf = Vec::new();
+        cp.write_to(&mut buf).unwrap();
+        let err = Checkpoint::read_from(&mut buf.as_slice()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn validate_checks_n_and_algorithm() {
+        let cp = sample();
+        assert!(cp.validate(1_000, "FastDoubling", &FFT).is_ok());
+        assert!(matches!(
+            cp.validate(1_001, "FastDoubling", &FFT),
+            Err(FibError::Config(_))
+        ));
+        assert!(matches!(
+            cp.validate(1_000, "FFTBased", &FFT),
+            Err(FibError::Config(_))
+        ));
+        let beyond = Checkpoint {
+            remaining_bits: 11,
+            ..sample()
+        };
+        assert!(matches!(
+            beyond.validate(1_000, "FastDoubling", &FFT),
+            Err(FibError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn validate_checks_values_with_cassini() {
+        let corrupt = Checkpoint {
+            fk: sample().fk + 1u32,
+            ..sample()
+        };
+        assert!(matches!(
+            corrupt.validate(1_000, "FastDoubling", &FFT),
+            Err(FibError::Config(_))
+        ));
+        // F(61), F(62): consecutive, but k = 62 is even.
+        let shifted = Checkpoint {
+            fk: BigUint::from(2_504_730_781_961u64),
+            fk1: BigUint::from(4_052_739_537_881u64),
+            ..sample()
+        };
+        assert!(shifted.validate(1_000, "FastDoubling", &FFT).is_err());
+        let swapped = Checkpoint {
+            fk: sample().fk1,
+            fk1: sample().fk,
+            ..sample()
+        };
+        assert!(swapped.validate(1_000, "FastDoubling", &FFT).is_err());
+        let start = Checkpoint {
+            remaining_bits: 10,
+            fk: BigUint::ZERO,
+            fk1: BigUint::from(1u32),
+            ..sample()
+        };
+        assert!(start.validate(1_000, "FastDoubling", &FFT).is_ok());
+    }
+
+    #[test]
+    fn writer_saves_by_iteration_count() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("count.ckpt");
+        let config = CheckpointConfig {
+            path: path.clone(),
+            every_iterations: 2,
+            interval: None,
+        };
+        let mut writer = CheckpointWriter::new(&config, 1_000, "FastDoubling");
+        let (fk, fk1) = (BigUint::from(3u32), BigUint::from(5u32));
+
+        writer.after_iteration(9, &fk, &fk1).unwrap();
+        assert!(!path.exists());
+        writer.after_iteration(8, &fk, &fk1).unwrap();
+        let cp = Checkpoint::load(&path).unwrap();
+        assert_eq!(cp.remaining_bits, 8);
+        assert_eq!(cp.fk, fk);
+        assert_eq!(cp.fk1, fk1);
+    }
+
+    #[test]
+    fn supported_algorithms() {
+        assert!(supports("FastDoubling"));
+        assert!(supports("FFTBased"));
+        assert!(!supports("MatrixExponentiation"));
+    }
+}
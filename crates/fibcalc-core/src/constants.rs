@@ -9,8 +9,16 @@ pub const DEFAULT_FFT_THRESHOLD: usize = 20_000_000;
 /// Default threshold (in bits) for Strassen multiplication.
 pub const DEFAULT_STRASSEN_THRESHOLD: usize = 3072;
 
-/// Threshold (in bits) for parallel FFT execution.
-pub const PARALLEL_FFT_THRESHOLD: usize = 5_000_000;
+/// Default threshold (in bits) for Toom-3 multiplication.
+pub const DEFAULT_TOOM3_THRESHOLD: usize = usize::MAX;
+
+/// Default threshold (in bits) for Toom-4 multiplication.
+pub const DEFAULT_TOOM4_THRESHOLD: usize = usize::MAX;
+
+/// Threshold (in bits) for parallel FFT execution; the default of
+/// `fibcalc_bigfft::FFTConfig`.
+pub const PARALLEL_FFT_THRESHOLD: usize =
+    fibcalc_bigfft::FFTConfig::DEFAULT_PARALLEL_THRESHOLD_BITS;
 
 /// Default N value for calibration benchmarks.
 pub const CALIBRATION_N: u64 = 10_000_000;
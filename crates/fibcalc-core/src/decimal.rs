@@ -0,0 +1,459 @@
+//! Subquadratic decimal conversion for very large integers.
+//!
+//! `BigUint::to_string` repeatedly divides by a single-limb power of ten,
+//! which is quadratic in the number of digits and materializes the whole
+//! string. [`DecimalConverter`] instead splits the value recursively around
+//! cached powers `10^(LEAF_DIGITS * 2^i)`, dividing with Barrett reduction
+//! against Newton reciprocals so every step costs a few
+//! `fibcalc_bigfft::mul_with` calls, which switch to FFT multiplication
+//! above the configured threshold. Digits are produced most-significant first
+//! and written to any `io::Write` in fixed-size chunks.
+//!
+//! # Example
+//! ```
+//! use fibcalc_core::constants::DEFAULT_FFT_THRESHOLD;
+//! use fibcalc_core::decimal::{to_decimal_string, DecimalConverter};
+//! use num_bigint::BigUint;
+//!
+//! let value = BigUint::from(10u32).pow(1000) - 1u32;
+//! assert_eq!(to_decimal_string(&value, DEFAULT_FFT_THRESHOLD), value.to_string());
+//!
+//! // One converter reuses its powers of ten across values.
+//! let mut conv = DecimalConverter::with_fft_threshold(DEFAULT_FFT_THRESHOLD);
+//! let mut out = Vec::new();
+//! conv.write(&value, &mut out).unwrap();
+//! conv.write(&(value + 1u32), &mut out).unwrap();
+//! assert_eq!(out.len(), 2001);
+//! ```
+
+use std::io::{self, Write};
+
+use fibcalc_bigfft::{barrett_reciprocal, mul_with, sqr_with, FFTConfig};
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::{One, Zero};
+
+use crate::constants::DEFAULT_FFT_THRESHOLD;
+
+/// Digits per leaf; leaves are converted with `BigUint::to_string`.
+const LEAF_DIGITS: u32 = 512;
+
+/// Values below this many bits have fewer than `LEAF_DIGITS` digits and are
+/// converted directly.
+const DIRECT_BITS: u64 = 1700;
+
+/// `floor(log10(2) * 2^32)`.
+const LOG10_2_FIXED: u128 = 1_292_913_986;
+
+/// Bytes buffered before each write to the underlying writer.
+const CHUNK_BYTES: usize = 64 * 1024;
+
+/// One cached power `10^(LEAF_DIGITS * 2^i)` with its Barrett reciprocal.
+struct PowerLevel {
+    power: BigUint,
+    bits: u64,
+    /// `floor(2^(2 * bits) / power)`, computed on first use.
+    reciprocal: Option<BigUint>,
+}
+
+/// Decimal converter with a cache of powers of ten.
+///
+/// Reusing one converter for several values of similar size avoids
+/// recomputing the powers and their reciprocals.
+pub struct DecimalConverter {
+    levels: Vec<PowerLevel>,
+    fft: FFTConfig,
+}
+
+impl DecimalConverter {
+    /// Create a converter with an empty power cache, using FFT
+    /// multiplication above `DEFAULT_FFT_THRESHOLD` bits.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_fft_threshold(DEFAULT_FFT_THRESHOLD)
+    }
+
+    /// Create a converter that uses FFT multiplication for operands of at
+    /// least `fft_threshold` bits.
+    #[must_use]
+    pub fn with_fft_threshold(fft_threshold: usize) -> Self {
+        Self::with_fft_config(FFTConfig::with_threshold(fft_threshold))
+    }
+
+    /// Create a converter whose products and reciprocals run under `fft`.
+    #[must_use]
+    pub fn with_fft_config(fft: FFTConfig) -> Self {
+        Self {
+            levels: Vec::new(),
+            fft,
+        }
+    }
+
+    /// Write the decimal digits of `value` to `writer`.
+    ///
+    /// # Errors
+    ///
+    /// Returns any I/O error from `writer`.
+    pub fn write<W: Write + ?Sized>(&mut self, value: &BigUint, writer: &mut W) -> io::Result<()> {
+        if value.bits() <= DIRECT_BITS {
+            return writer.write_all(value.to_string().as_bytes());
+        }
+        let mut sink = ChunkWriter::new(writer);
+        let top = self.ensure_levels(value);
+        self.convert(value, top, false, &mut sink)?;
+        sink.finish()
+    }
+
+    /// Convert `value` to a decimal `String`.
+    #[must_use]
+    pub fn to_string(&mut self, value: &BigUint) -> String {
+        let mut out = Vec::new();
+        self.write(value, &mut out)
+            .expect("writing to a Vec cannot fail");
+        String::from_utf8(out).expect("decimal digits are ASCII")
+    }
+
+    /// Number of decimal digits of `value` (1 for zero).
+    #[must_use]
+    pub fn digit_count(&mut self, value: &BigUint) -> u64 {
+        if value.is_zero() {
+            return 1;
+        }
+        // floor((bits - 1) * log10(2)) + 1 with log10(2) in 32-bit fixed
+        // point; the true count is within one of it, either way.
+        let product = u128::from(value.bits() - 1) * LOG10_2_FIXED;
+        let mut digits = u64::try_from(product >> 32).expect("digit count fits in u64") + 1;
+        let mut low = pow10(digits - 1, self.fft);
+        while *value < low {
+            digits -= 1;
+            low /= 10u32;
+        }
+        loop {
+            let high = &low * 10u32;
+            if *value < high {
+                return digits;
+            }
+            digits += 1;
+            low = high;
+        }
+    }
+
+    /// The first `count` digits of `value` (all of them if it is shorter).
+    #[must_use]
+    pub fn leading_digits(&mut self, value: &BigUint, count: u64) -> String {
+        let digits = self.digit_count(value);
+        if digits <= count {
+            return self.to_string(value);
+        }
+        // Small quotient, so the schoolbook division here is linear.
+        (value / pow10(digits - count, self.fft)).to_string()
+    }
+
+    /// The last `count` digits of `value`, left-padded with zeros.
+    #[must_use]
+    pub fn trailing_digits(&mut self, value: &BigUint, count: u64) -> String {
+        let tail = value % pow10(count, self.fft);
+        let digits = self.to_string(&tail);
+        let width = usize::try_from(count).expect("digit count fits in memory");
+        format!("{digits:0>width$}")
+    }
+
+    /// Smallest level `t` with `value < 10^(LEAF_DIGITS * 2^t)`, building
+    /// powers as needed.
+    fn ensure_levels(&mut self, value: &BigUint) -> usize {
+        if self.levels.is_empty() {
+            self.push_level(BigUint::from(10u32).pow(LEAF_DIGITS));
+        }
+        let mut top = 0;
+        loop {
+            if *value < self.levels[top].power {
+                return top;
+            }
+            top += 1;
+            if top == self.levels.len() {
+                let next = sqr_with(&self.levels[top - 1].power, &self.fft);
+                self.push_level(next);
+            }
+        }
+    }
+
+    fn push_level(&mut self, power: BigUint) {
+        self.levels.push(PowerLevel {
+            bits: power.bits(),
+            power,
+            reciprocal: None,
+        });
+    }
+
+    /// Write `x < 10^(LEAF_DIGITS * 2^level)`, left-padded to the full
+    /// width of the level when `pad` is set.
+    fn convert<W: Write + ?Sized>(
+        &mut self,
+        x: &BigUint,
+        level: usize,
+        pad: bool,
+        sink: &mut ChunkWriter<'_, W>,
+    ) -> io::Result<()> {
+        if level == 0 {
+            let digits = x.to_string();
+            if pad {
+                sink.zeros(LEAF_DIGITS as usize - digits.len());
+            }
+            return sink.write(digits.as_bytes());
+        }
+
+        let half = level - 1;
+        if !pad && *x < self.levels[half].power {
+            return self.convert(x, half, false, sink);
+        }
+        let (high, low) = self.div_rem(x, half);
+        self.convert(&high, half, pad, sink)?;
+        drop(high);
+        self.convert(&low, half, true, sink)
+    }
+
+    /// Divide `x < power^2` by the power at `level` using Barrett reduction.
+    fn div_rem(&mut self, x: &BigUint, level: usize) -> (BigUint, BigUint) {
+        let fft = &self.fft;
+        let entry = &mut self.levels[level];
+        let reciprocal = entry
+            .reciprocal
+            .get_or_insert_with(|| barrett_reciprocal(&entry.power, fft));
+
+        // The reciprocal is a floor, so this quotient never overshoots.
+        let mut q = mul_with(x, reciprocal, fft) >> (2 * entry.bits);
+        let mut r = x - mul_with(&q, &entry.power, fft);
+        if r >= entry.power {
+            let (dq, dr) = r.div_rem(&entry.power);
+            q += dq;
+            r = dr;
+        }
+        (q, r)
+    }
+}
+
+/// `10^k`.
+fn pow10(k: u64, fft: FFTConfig) -> BigUint {
+    let mut result = BigUint::one();
+    let mut base = BigUint::from(10u32);
+    let mut k = k;
+    while k > 0 {
+        if k & 1 == 1 {
+            result = mul_with(&result, &base, &fft);
+        }
+        k >>= 1;
+        if k > 0 {
+            base = sqr_with(&base, &fft);
+        }
+    }
+    result
+}
+
+impl Default for DecimalConverter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Buffers digits and forwards them in `CHUNK_BYTES` pieces.
+struct ChunkWriter<'a, W: Write + ?Sized> {
+    inner: &'a mut W,
+    buf: Vec<u8>,
+}
+
+impl<'a, W: Write + ?Sized> ChunkWriter<'a, W> {
+    fn new(inner: &'a mut W) -> Self {
+        Self {
+            inner,
+            buf: Vec::with_capacity(CHUNK_BYTES),
+        }
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.buf.extend_from_slice(bytes);
+        if self.buf.len() >= CHUNK_BYTES {
+            self.inner.write_all(&self.buf)?;
+            self.buf.clear();
+        }
+        Ok(())
+    }
+
+    fn zeros(&mut self, count: usize) {
+        self.buf.resize(self.buf.len() + count, b'0');
+    }
+
+    fn finish(self) -> io::Result<()> {
+        self.inner.write_all(&self.buf)
+    }
+}
+
+/// Write the decimal digits of `value` to `writer` in chunks, using FFT
+/// multiplication for operands of at least `fft_threshold` bits.
+///
+/// Each call builds its powers of ten from scratch; keep one
+/// [`DecimalConverter`] to reuse them across values.
+///
+/// # Errors
+///
+/// Returns any I/O error from `writer`.
+pub fn write_decimal<W: Write + ?Sized>(
+    value: &BigUint,
+    writer: &mut W,
+    fft_threshold: usize,
+) -> io::Result<()> {
+    DecimalConverter::with_fft_threshold(fft_threshold).write(value, writer)
+}
+
+/// Convert `value` to a decimal `String` in subquadratic time, using FFT
+/// multiplication for operands of at least `fft_threshold` bits.
+///
+/// Each call builds its powers of ten from scratch; keep one
+/// [`DecimalConverter`] to reuse them across values.
+#[must_use]
+pub fn to_decimal_string(value: &BigUint, fft_threshold: usize) -> String {
+    DecimalConverter::with_fft_threshold(fft_threshold).to_string(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::iterator::FibIterator;
+
+    #[test]
+    fn small_values() {
+        for v in [0u64, 1, 9, 10, 12345, u64::MAX] {
+            assert_eq!(
+                to_decimal_string(&BigUint::from(v), DEFAULT_FFT_THRESHOLD),
+                v.to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn powers_of_ten_boundaries() {
+        for k in [511, 512, 513, 1023, 1024, 1025, 4096, 5000] {
+            let p = BigUint::from(10u32).pow(k);
+            assert_eq!(
+                to_decimal_string(&p, DEFAULT_FFT_THRESHOLD),
+                p.to_string(),
+                "10^{k}"
+            );
+            let below = &p - 1u32;
+            assert_eq!(
+                to_decimal_string(&below, DEFAULT_FFT_THRESHOLD),
+                below.to_string(),
+                "10^{k}-1"
+            );
+            let above = &p + 1u32;
+            assert_eq!(
+                to_decimal_string(&above, DEFAULT_FFT_THRESHOLD),
+                above.to_string(),
+                "10^{k}+1"
+            );
+        }
+    }
+
+    #[test]
+    fn internal_zero_chunks_are_padded() {
+        // 1 followed by 3000 zeros then 7: whole leaves of zeros in the middle.
+        let value = BigUint::from(10u32).pow(3001) + 7u32;
+        let s = to_decimal_string(&value, DEFAULT_FFT_THRESHOLD);
+        assert_eq!(s.len(), 3002);
+        assert_eq!(s, value.to_string());
+    }
+
+    #[test]
+    fn large_fibonacci_matches_to_string() {
+        // F(200_000) has ~41,800 digits: several recursion levels and
+        // reciprocals above the Newton cutoff.
+        let (_, value) = FibIterator::from_index(200_000).next().unwrap();
+        assert_eq!(
+            to_decimal_string(&value, DEFAULT_FFT_THRESHOLD),
+            value.to_string()
+        );
+    }
+
+    #[test]
+    fn forced_fft_matches_to_string() {
+        let mut conv = DecimalConverter::with_fft_threshold(0);
+        let value = BigUint::from(3u32).pow(30_000);
+        assert_eq!(conv.to_string(&value), value.to_string());
+    }
+
+    #[test]
+    fn converter_reuses_cache() {
+        let mut conv = DecimalConverter::new();
+        let a = BigUint::from(3u32).pow(20_000);
+        let b = BigUint::from(7u32).pow(9_000);
+        assert_eq!(conv.to_string(&a), a.to_string());
+        assert_eq!(conv.to_string(&b), b.to_string());
+    }
+
+    #[test]
+    fn writes_in_chunks() {
+        struct Counting {
+            writes: usize,
+            bytes: usize,
+        }
+        impl Write for Counting {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.writes += 1;
+                self.bytes += buf.len();
+                Ok(buf.len())
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let value = BigUint::from(10u32).pow(200_000);
+        let mut sink = Counting {
+            writes: 0,
+            bytes: 0,
+        };
+        write_decimal(&value, &mut sink, DEFAULT_FFT_THRESHOLD).unwrap();
+        assert_eq!(sink.bytes, 200_001);
+        assert!(sink.writes >= 3);
+    }
+
+    #[test]
+    fn leading_and_trailing_digits() {
+        let mut conv = DecimalConverter::new();
+        let value = BigUint::from(3u32).pow(10_000);
+        let s = value.to_string();
+        assert_eq!(conv.leading_digits(&value, 50), s[..50]);
+        assert_eq!(conv.trailing_digits(&value, 50), s[s.len() - 50..]);
+        assert_eq!(conv.leading_digits(&BigUint::from(1234u32), 50), "1234");
+        assert_eq!(conv.trailing_digits(&BigUint::from(1234u32), 6), "001234");
+        assert_eq!(conv.trailing_digits(&BigUint::from(10u32).pow(9), 3), "000");
+    }
+
+    #[test]
+    fn digit_count_matches_length() {
+        let mut conv = DecimalConverter::new();
+        for v in [
+            BigUint::zero(),
+            BigUint::from(9u32),
+            BigUint::from(10u32),
+            BigUint::from(10u32).pow(700) - 1u32,
+            BigUint::from(10u32).pow(700),
+            BigUint::from(3u32).pow(5_000),
+        ] {
+            assert_eq!(conv.digit_count(&v), v.to_string().len() as u64);
+        }
+    }
+
+    #[test]
+    fn digit_count_where_decimal_log_overestimates() {
+        // 13301 * 0.30103 = 4004.0003 but 13301 * log10(2) = 4003.99995,
+        // so a 0.30103 estimate claims 4005 digits for 2^13301.
+        let mut conv = DecimalConverter::new();
+        let value = BigUint::one() << 13_301u32;
+        let s = value.to_string();
+        assert_eq!(s.len(), 4004);
+        assert_eq!(conv.digit_count(&value), 4004);
+        assert_eq!(conv.leading_digits(&value, 50), s[..50]);
+        let below = &value - 1u32;
+        assert_eq!(conv.digit_count(&below), below.to_string().len() as u64);
+    }
+}
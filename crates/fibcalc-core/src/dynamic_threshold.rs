@@ -1,14 +1,56 @@
 //! Dynamic threshold management with ring buffer and hysteresis.
 
 use std::collections::VecDeque;
+use std::time::Duration;
 
 use crate::constants::{
     DEFAULT_FFT_THRESHOLD, DEFAULT_PARALLEL_THRESHOLD, DEFAULT_STRASSEN_THRESHOLD,
 };
+use crate::options::Options;
 use crate::threshold_types::{
-    DynamicThresholdConfig, IterationMetric, ThresholdAdjustment, ThresholdSnapshot, ThresholdStats,
+    DynamicThresholdConfig, IterationMetric, MultiplicationMethod, ThresholdAdjustment,
+    ThresholdSnapshot, ThresholdStats,
 };
 
+/// Last observed `(bit_length, duration_ns)` for each multiplication mode.
+///
+/// Used to estimate what the mode *not* taken in an iteration would have
+/// cost at the current operand size.
+#[derive(Debug, Default, Clone, Copy)]
+struct ModeTimings {
+    karatsuba: Option<(usize, u64)>,
+    fft: Option<(usize, u64)>,
+    sequential: Option<(usize, u64)>,
+    parallel: Option<(usize, u64)>,
+}
+
+/// Relative Karatsuba cost: `b^log2(3)`.
+#[allow(clippy::cast_precision_loss)]
+fn karatsuba_work(bits: usize) -> f64 {
+    (bits.max(1) as f64).powf(1.585)
+}
+
+/// Relative FFT cost: `b * log2(b)`.
+#[allow(clippy::cast_precision_loss)]
+fn fft_work(bits: usize) -> f64 {
+    let b = bits.max(2) as f64;
+    b * b.log2()
+}
+
+/// Scale an observed `(bits, ns)` sample to `to_bits` under a cost model.
+#[allow(clippy::cast_precision_loss)]
+fn extrapolate(observed: (usize, u64), to_bits: usize, work: fn(usize) -> f64) -> f64 {
+    observed.1 as f64 * work(to_bits) / work(observed.0)
+}
+
+/// Fraction of `baseline` saved by `candidate`, clamped to `[-1, 1]`.
+fn speedup(baseline: f64, candidate: f64) -> f64 {
+    if baseline <= 0.0 || candidate <= 0.0 {
+        return 0.0;
+    }
+    ((baseline - candidate) / baseline.max(candidate)).clamp(-1.0, 1.0)
+}
+
 /// Manager for dynamically adjusting multiplication thresholds.
 pub struct DynamicThresholdManager {
     config: DynamicThresholdConfig,
@@ -20,6 +62,7 @@ pub struct DynamicThresholdManager {
     current_strassen: usize,
     adjustment_count: usize,
     adjustment_history: VecDeque<ThresholdAdjustment>,
+    timings: ModeTimings,
 }
 
 impl DynamicThresholdManager {
@@ -37,9 +80,22 @@ impl DynamicThresholdManager {
             current_strassen: DEFAULT_STRASSEN_THRESHOLD,
             adjustment_count: 0,
             adjustment_history: VecDeque::new(),
+            timings: ModeTimings::default(),
         }
     }
 
+    /// Create a manager seeded with the thresholds from `opts`.
+    #[must_use]
+    pub fn from_options(opts: &Options) -> Self {
+        let mut mgr = Self::default();
+        mgr.set_thresholds(
+            opts.parallel_threshold,
+            opts.fft_threshold,
+            opts.strassen_threshold,
+        );
+        mgr
+    }
+
     /// Record a metric from an iteration.
     pub fn record(&mut self, metric: IterationMetric) {
         let ring_size = self.config.ring_buffer_size;
@@ -52,6 +108,82 @@ impl DynamicThresholdManager {
         self.ring_pos = (self.ring_pos + 1) % ring_size;
     }
 
+    /// Choose the multiplication method and whether to parallelize the
+    /// products for operands of `bit_length` bits, using current thresholds.
+    #[must_use]
+    pub fn select(&self, bit_length: usize) -> (MultiplicationMethod, bool) {
+        let method = if bit_length >= self.current_fft {
+            MultiplicationMethod::Fft
+        } else {
+            MultiplicationMethod::Karatsuba
+        };
+        (method, bit_length >= self.current_parallel)
+    }
+
+    /// Record a timed iteration.
+    ///
+    /// The FFT and parallel speedups are estimated by extrapolating the last
+    /// observed timing of the alternative mode to `bit_length`, so they stay
+    /// at zero until both sides of a threshold have been seen.
+    pub fn record_iteration(
+        &mut self,
+        bit_length: usize,
+        duration: Duration,
+        method: MultiplicationMethod,
+        parallel: bool,
+    ) {
+        let ns = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+        #[allow(clippy::cast_precision_loss)]
+        let observed = ns as f64;
+        let t = self.timings;
+
+        let fft_speedup = if method == MultiplicationMethod::Fft {
+            t.karatsuba.map_or(0.0, |k| {
+                speedup(extrapolate(k, bit_length, karatsuba_work), observed)
+            })
+        } else {
+            t.fft.map_or(0.0, |f| {
+                speedup(observed, extrapolate(f, bit_length, fft_work))
+            })
+        };
+
+        let work = if method == MultiplicationMethod::Fft {
+            fft_work
+        } else {
+            karatsuba_work
+        };
+        let parallel_speedup = if parallel {
+            t.sequential
+                .map_or(0.0, |s| speedup(extrapolate(s, bit_length, work), observed))
+        } else {
+            t.parallel
+                .map_or(0.0, |p| speedup(observed, extrapolate(p, bit_length, work)))
+        };
+
+        let sample = Some((bit_length, ns));
+        if method == MultiplicationMethod::Fft {
+            self.timings.fft = sample;
+        } else {
+            self.timings.karatsuba = sample;
+        }
+        if parallel {
+            self.timings.parallel = sample;
+        } else {
+            self.timings.sequential = sample;
+        }
+
+        self.record(IterationMetric {
+            bit_length,
+            fft_speedup,
+            parallel_speedup,
+            duration_ns: ns,
+            method,
+            parallel,
+            bits_processed: bit_length as u64,
+            cache_hit: false,
+        });
+    }
+
     /// Get the number of recorded metrics.
     #[must_use]
     pub fn metric_count(&self) -> usize {
@@ -198,6 +330,7 @@ impl DynamicThresholdManager {
         self.current_strassen = DEFAULT_STRASSEN_THRESHOLD;
         self.adjustment_count = 0;
         self.adjustment_history.clear();
+        self.timings = ModeTimings::default();
     }
 
     /// Whether the ring buffer is full (has wrapped around at least once).
@@ -425,4 +558,105 @@ mod tests {
         assert!(mgr.parallel_threshold() >= 512);
         assert!(mgr.strassen_threshold() >= 512);
     }
+
+    #[test]
+    fn from_options_seeds_thresholds() {
+        let opts = Options {
+            parallel_threshold: 1000,
+            fft_threshold: 50_000,
+            strassen_threshold: 2000,
+            ..Options::default()
+        };
+        let mgr = DynamicThresholdManager::from_options(&opts);
+        assert_eq!(mgr.parallel_threshold(), 1000);
+        assert_eq!(mgr.fft_threshold(), 50_000);
+        assert_eq!(mgr.strassen_threshold(), 2000);
+    }
+
+    #[test]
+    fn select_follows_thresholds() {
+        let mut mgr = DynamicThresholdManager::default();
+        mgr.set_thresholds(1000, 50_000, 2000);
+        assert_eq!(mgr.select(500), (MultiplicationMethod::Karatsuba, false));
+        assert_eq!(mgr.select(1000), (MultiplicationMethod::Karatsuba, true));
+        assert_eq!(mgr.select(50_000), (MultiplicationMethod::Fft, true));
+    }
+
+    #[test]
+    fn record_iteration_without_alternative_is_neutral() {
+        let mut mgr = DynamicThresholdManager::default();
+        mgr.record_iteration(
+            1000,
+            Duration::from_micros(10),
+            MultiplicationMethod::Karatsuba,
+            false,
+        );
+        let stats = mgr.stats().unwrap();
+        assert!(stats.fft_benefit.abs() < f64::EPSILON);
+        assert!(stats.parallel_benefit.abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn record_iteration_fast_fft_lowers_threshold() {
+        let mut mgr = DynamicThresholdManager::default();
+        mgr.set_thresholds(4096, 100_000, 3072);
+        // Karatsuba at 50k bits, then FFT at 100k bits taking the same time:
+        // extrapolated Karatsuba would have been ~3x slower.
+        mgr.record_iteration(
+            50_000,
+            Duration::from_millis(10),
+            MultiplicationMethod::Karatsuba,
+            true,
+        );
+        mgr.record_iteration(
+            100_000,
+            Duration::from_millis(10),
+            MultiplicationMethod::Fft,
+            true,
+        );
+        mgr.adjust();
+        assert!(mgr.fft_threshold() < 100_000);
+        assert_eq!(mgr.snapshot().adjustment_history[0].threshold_name, "fft");
+    }
+
+    #[test]
+    fn record_iteration_slow_parallel_raises_threshold() {
+        let mut mgr = DynamicThresholdManager::default();
+        mgr.set_thresholds(4096, 20_000_000, 3072);
+        mgr.record_iteration(
+            2048,
+            Duration::from_micros(10),
+            MultiplicationMethod::Karatsuba,
+            false,
+        );
+        // Parallel at double the size taking 10x as long is a clear loss.
+        mgr.record_iteration(
+            4096,
+            Duration::from_micros(100),
+            MultiplicationMethod::Karatsuba,
+            true,
+        );
+        mgr.adjust();
+        assert!(mgr.parallel_threshold() > 4096);
+    }
+
+    #[test]
+    fn reset_clears_mode_timings() {
+        let mut mgr = DynamicThresholdManager::default();
+        mgr.record_iteration(
+            1000,
+            Duration::from_micros(10),
+            MultiplicationMethod::Karatsuba,
+            false,
+        );
+        mgr.reset();
+        mgr.record_iteration(
+            2000,
+            Duration::from_micros(10),
+            MultiplicationMethod::Fft,
+            false,
+        );
+        let stats = mgr.stats().unwrap();
+        assert!(stats.fft_benefit.abs() < f64::EPSILON);
+    }
 }
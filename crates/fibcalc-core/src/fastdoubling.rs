@@ -8,16 +8,21 @@
 //! Includes thread-local pooling of `CalculationState` objects.
 
 use std::cell::RefCell;
+use std::time::Instant;
 
-use fibcalc_bigfft::{mul, sqr};
 use num_bigint::BigUint;
 use num_traits::{One, Zero};
+use parking_lot::Mutex;
 
 use crate::calculator::{CoreCalculator, FibError};
+use crate::checkpoint::{self, CheckpointWriter};
+use crate::dynamic_threshold::DynamicThresholdManager;
+use crate::fft_wrappers::{mul_fft_to, sqr_add_sqr_fft_to};
 use crate::observer::ProgressObserver;
 use crate::options::Options;
 use crate::pool;
 use crate::progress::{CancellationToken, ProgressUpdate};
+use crate::threshold_types::{MultiplicationMethod, ThresholdSnapshot};
 
 /// State for the Fast Doubling computation, enabling pool reuse.
 pub struct CalculationState {
@@ -70,13 +75,7 @@ const THREAD_LOCAL_POOL_MAX: usize = 4;
 
 /// Acquire a `CalculationState` from the thread-local pool.
 fn tl_acquire_state() -> CalculationState {
-    CALC_STATE_POOL.with(|p| {
-        pool::tl_acquire(
-            p,
-            CalculationState::new,
-            CalculationState::reset,
-        )
-    })
+    CALC_STATE_POOL.with(|p| pool::tl_acquire(p, CalculationState::new, CalculationState::reset))
 }
 
 /// Return a `CalculationState` to the thread-local pool.
@@ -101,17 +100,22 @@ fn tl_release_state(state: CalculationState) {
 /// let result = calc.calculate_core(&cancel, &observer, 0, 100, &opts).unwrap();
 /// assert_eq!(result.to_string(), "354224848179261915075");
 /// ```
-pub struct OptimizedFastDoubling;
+pub struct OptimizedFastDoubling {
+    /// Threshold state from the last run with dynamic thresholds.
+    last_snapshot: Mutex<Option<ThresholdSnapshot>>,
+}
 
 impl OptimizedFastDoubling {
     /// Create a new `OptimizedFastDoubling` calculator.
     #[must_use]
     pub fn new() -> Self {
-        Self
+        Self {
+            last_snapshot: Mutex::new(None),
+        }
     }
 
     /// Execute the doubling loop.
-    #[allow(clippy::cast_possible_truncation, clippy::unused_self)]
+    #[allow(clippy::cast_possible_truncation, clippy::too_many_lines)]
     fn execute_doubling_loop(
         &self,
         n: u64,
@@ -121,15 +125,31 @@ impl OptimizedFastDoubling {
         opts: &Options,
     ) -> Result<BigUint, FibError> {
         let num_bits = 64 - n.leading_zeros();
+        let (start_bits, fk, fk1) = checkpoint::initial_state(opts, n, "FastDoubling")?;
         let mut state = tl_acquire_state();
+        state.fk = fk;
+        state.fk1 = fk1;
 
         let frozen = observer.freeze();
+        let fft = opts.fft_config();
+        let mut dynamic = opts
+            .dynamic_thresholds
+            .then(|| DynamicThresholdManager::from_options(opts));
+        let mut writer = opts
+            .checkpoint
+            .as_ref()
+            .map(|config| CheckpointWriter::new(config, n, "FastDoubling"));
         // Inline strategy logic to reuse CalculationState buffers
 
         let result = (|| {
-            for i in (0..num_bits).rev() {
-                // Cancellation checkpoint
+            for i in (0..start_bits).rev() {
+                // Pause and cancellation checkpoint
+                cancel.wait_while_paused();
                 if cancel.is_cancelled() {
+                    // Bit i is still unprocessed, so save the state before it
+                    if let Some(writer) = writer.as_mut() {
+                        writer.save(i + 1, &state.fk, &state.fk1)?;
+                    }
                     return Err(FibError::Cancelled);
                 }
 
@@ -141,60 +161,72 @@ impl OptimizedFastDoubling {
                 state.t1 -= &state.fk;
 
                 let max_bits = state.fk.bits().max(state.fk1.bits()) as usize;
-                let use_fft = max_bits >= opts.fft_threshold;
-
-                let (f2k, f2k1) = if max_bits >= opts.parallel_threshold {
-                    // Parallel: multiply and 2 squarings concurrently
-                    let ((fk_sq, fk1_sq), f2k) = rayon::join(
-                        || {
-                            rayon::join(
-                                || {
-                                    if use_fft {
-                                        sqr(&state.fk)
-                                    } else {
-                                        &state.fk * &state.fk
-                                    }
-                                },
-                                || {
-                                    if use_fft {
-                                        sqr(&state.fk1)
-                                    } else {
-                                        &state.fk1 * &state.fk1
-                                    }
-                                },
-                            )
-                        },
+                let (use_fft, use_parallel) = match &dynamic {
+                    Some(mgr) => {
+                        let (method, parallel) = mgr.select(max_bits);
+                        (method == MultiplicationMethod::Fft, parallel)
+                    }
+                    None => (
+                        max_bits >= opts.fft_threshold,
+                        max_bits >= opts.parallel_threshold,
+                    ),
+                };
+                let step_start = Instant::now();
+
+                // F(2k) = F(k)*T goes into t2 and F(2k+1) = F(k)^2 + F(k+1)^2
+                // into t3. FFT products are written into the registers'
+                // existing allocations; F(k)^2 + F(k+1)^2 is one fused FFT
+                // sum (one inverse transform) or two plain squarings.
+                let sqr_sum = |dst: &mut BigUint, fk: &BigUint, fk1: &BigUint| {
+                    if use_fft {
+                        sqr_add_sqr_fft_to(dst, fk, fk1, &fft);
+                    } else {
+                        *dst = fk * fk + fk1 * fk1;
+                    }
+                };
+                let product = |dst: &mut BigUint, fk: &BigUint, t: &BigUint| {
+                    if use_fft {
+                        mul_fft_to(dst, fk, t, &fft);
+                    } else {
+                        *dst = fk * t;
+                    }
+                };
+                if use_parallel {
+                    // Parallel: multiply and squarings concurrently
+                    rayon::join(
+                        || product(&mut state.t2, &state.fk, &state.t1),
                         || {
                             if use_fft {
-                                mul(&state.fk, &state.t1)
+                                sqr_sum(&mut state.t3, &state.fk, &state.fk1);
                             } else {
-                                &state.fk * &state.t1
+                                let (fk_sq, fk1_sq) = rayon::join(
+                                    || &state.fk * &state.fk,
+                                    || &state.fk1 * &state.fk1,
+                                );
+                                state.t3 = fk_sq + fk1_sq;
                             }
                         },
                     );
-                    (f2k, fk_sq + fk1_sq)
                 } else {
                     // Sequential for small operands
-                    let f2k = if use_fft {
-                        mul(&state.fk, &state.t1)
-                    } else {
-                        &state.fk * &state.t1
-                    };
-                    let fk_sq = if use_fft {
-                        sqr(&state.fk)
-                    } else {
-                        &state.fk * &state.fk
-                    };
-                    let fk1_sq = if use_fft {
-                        sqr(&state.fk1)
+                    product(&mut state.t2, &state.fk, &state.t1);
+                    sqr_sum(&mut state.t3, &state.fk, &state.fk1);
+                }
+
+                // Rotate: the old F(k) and F(k+1) buffers become the next
+                // step's destinations.
+                std::mem::swap(&mut state.fk, &mut state.t2);
+                std::mem::swap(&mut state.fk1, &mut state.t3);
+
+                if let Some(mgr) = dynamic.as_mut() {
+                    let method = if use_fft {
+                        MultiplicationMethod::Fft
                     } else {
-                        &state.fk1 * &state.fk1
+                        MultiplicationMethod::Karatsuba
                     };
-                    (f2k, fk_sq + fk1_sq)
-                };
-
-                state.fk = f2k;
-                state.fk1 = f2k1;
+                    mgr.record_iteration(max_bits, step_start.elapsed(), method, use_parallel);
+                    mgr.adjust();
+                }
 
                 // Conditional addition step
                 if (n >> i) & 1 == 1 {
@@ -219,6 +251,10 @@ impl OptimizedFastDoubling {
                         u64::from(num_bits),
                     ));
                 }
+
+                if let Some(writer) = writer.as_mut() {
+                    writer.after_iteration(i, &state.fk, &state.fk1)?;
+                }
             }
 
             // Zero-copy result extraction
@@ -227,6 +263,7 @@ impl OptimizedFastDoubling {
 
         // Return state to pool regardless of success/failure
         tl_release_state(state);
+        *self.last_snapshot.lock() = dynamic.map(|mgr| mgr.snapshot());
 
         result
     }
@@ -258,6 +295,10 @@ impl CoreCalculator for OptimizedFastDoubling {
     fn name(&self) -> &'static str {
         "FastDoubling"
     }
+
+    fn threshold_snapshot(&self) -> Option<ThresholdSnapshot> {
+        self.last_snapshot.lock().clone()
+    }
 }
 
 #[cfg(test)]
@@ -316,6 +357,60 @@ mod tests {
         assert!(matches!(result, Err(FibError::Cancelled)));
     }
 
+    #[test]
+    fn fast_doubling_pause_blocks_until_resume() {
+        let cancel = CancellationToken::new();
+        cancel.pause_token().pause();
+        let worker = {
+            let cancel = cancel.clone();
+            std::thread::spawn(move || {
+                let calc = OptimizedFastDoubling::new();
+                let observer = NoOpObserver::new();
+                calc.calculate_core(&cancel, &observer, 0, 1000, &Options::default())
+            })
+        };
+
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        assert!(!worker.is_finished(), "calculation ran while paused");
+
+        cancel.pause_token().resume();
+        let result = worker.join().unwrap().unwrap();
+        assert_eq!(result, compute_fib(1000));
+    }
+
+    #[test]
+    fn dynamic_thresholds_match_static() {
+        let calc = OptimizedFastDoubling::new();
+        let cancel = CancellationToken::new();
+        let observer = NoOpObserver::new();
+        let opts = Options {
+            dynamic_thresholds: true,
+            parallel_threshold: 512,
+            fft_threshold: 2048,
+            ..Options::default()
+        };
+        let result = calc
+            .calculate_core(&cancel, &observer, 0, 50_000, &opts)
+            .unwrap();
+        assert_eq!(result, compute_fib(50_000));
+
+        let snapshot = calc
+            .threshold_snapshot()
+            .expect("snapshot after dynamic run");
+        assert!(snapshot.fft_threshold >= 1024);
+        assert!(snapshot.parallel_threshold >= 512);
+    }
+
+    #[test]
+    fn static_run_has_no_snapshot() {
+        let calc = OptimizedFastDoubling::new();
+        let cancel = CancellationToken::new();
+        let observer = NoOpObserver::new();
+        calc.calculate_core(&cancel, &observer, 0, 1000, &Options::default())
+            .unwrap();
+        assert!(calc.threshold_snapshot().is_none());
+    }
+
     #[test]
     fn calculation_state_reset() {
         let mut state = CalculationState::new();
@@ -346,4 +441,79 @@ mod tests {
         assert_eq!(state2.fk1, BigUint::from(1u32));
         tl_release_state(state2);
     }
+
+    #[test]
+    fn resume_from_checkpoint_matches_full_run() {
+        let n = 10_000u64;
+        let remaining_bits = 6;
+        let k = n >> remaining_bits;
+        let checkpoint = crate::checkpoint::Checkpoint {
+            n,
+            algorithm: "FastDoubling".to_string(),
+            remaining_bits,
+            fk: compute_fib(k),
+            fk1: compute_fib(k + 1),
+        };
+        let opts = Options {
+            resume: Some(std::sync::Arc::new(checkpoint)),
+            ..Options::default()
+        };
+        let result = OptimizedFastDoubling::new()
+            .calculate_core(&CancellationToken::new(), &NoOpObserver::new(), 0, n, &opts)
+            .unwrap();
+        assert_eq!(result, compute_fib(n));
+    }
+
+    #[test]
+    fn resume_rejects_checkpoint_for_other_n() {
+        let checkpoint = crate::checkpoint::Checkpoint {
+            n: 999,
+            algorithm: "FastDoubling".to_string(),
+            remaining_bits: 2,
+            fk: BigUint::ZERO,
+            fk1: BigUint::from(1u32),
+        };
+        let opts = Options {
+            resume: Some(std::sync::Arc::new(checkpoint)),
+            ..Options::default()
+        };
+        let result = OptimizedFastDoubling::new().calculate_core(
+            &CancellationToken::new(),
+            &NoOpObserver::new(),
+            0,
+            1_000,
+            &opts,
+        );
+        assert!(matches!(result, Err(FibError::Config(_))));
+    }
+
+    #[test]
+    fn cancellation_writes_checkpoint() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("cancel.ckpt");
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        let opts = Options {
+            checkpoint: Some(crate::checkpoint::CheckpointConfig {
+                path: path.clone(),
+                every_iterations: 0,
+                interval: None,
+            }),
+            ..Options::default()
+        };
+        let result = OptimizedFastDoubling::new().calculate_core(
+            &cancel,
+            &NoOpObserver::new(),
+            0,
+            10_000,
+            &opts,
+        );
+        assert!(matches!(result, Err(FibError::Cancelled)));
+
+        // Nothing was processed, so the checkpoint holds the initial state.
+        let cp = crate::checkpoint::Checkpoint::load(&path).unwrap();
+        assert_eq!(cp.remaining_bits, 14);
+        assert_eq!(cp.fk, BigUint::ZERO);
+        assert_eq!(cp.fk1, BigUint::from(1u32));
+    }
 }
@@ -2,26 +2,39 @@
 //!
 //! Uses Fast Doubling with FFT multiplication for very large numbers.
 
+use std::time::Instant;
+
 use num_bigint::BigUint;
+use parking_lot::Mutex;
 
 use crate::calculator::{CoreCalculator, FibError};
-use crate::constants::DEFAULT_FFT_THRESHOLD;
+use crate::checkpoint::{self, CheckpointWriter};
+use crate::dynamic_threshold::DynamicThresholdManager;
 use crate::observer::ProgressObserver;
 use crate::options::Options;
 use crate::progress::{CancellationToken, ProgressUpdate};
-use crate::strategy::{AdaptiveStrategy, DoublingStepExecutor};
+use crate::strategy::{
+    AdaptiveStrategy, DoublingStepExecutor, FFTOnlyStrategy, KaratsubaStrategy,
+    ParallelKaratsubaStrategy,
+};
+use crate::threshold_types::{MultiplicationMethod, ThresholdSnapshot};
 
 /// FFT-based Fibonacci calculator.
 ///
 /// Uses the Fast Doubling framework but with FFT multiplication
 /// for operands exceeding the FFT threshold.
-pub struct FFTBasedCalculator;
+pub struct FFTBasedCalculator {
+    /// Threshold state from the last run with dynamic thresholds.
+    last_snapshot: Mutex<Option<ThresholdSnapshot>>,
+}
 
 impl FFTBasedCalculator {
     /// Create a new FFT-based Fibonacci calculator.
     #[must_use]
     pub fn new() -> Self {
-        Self
+        Self {
+            last_snapshot: Mutex::new(None),
+        }
     }
 }
 
@@ -38,26 +51,62 @@ impl CoreCalculator for FFTBasedCalculator {
         observer: &dyn ProgressObserver,
         calc_index: usize,
         n: u64,
-        _opts: &Options,
+        opts: &Options,
     ) -> Result<BigUint, FibError> {
         // Use the same doubling loop structure as FastDoubling,
         // but with FFT multiplication for large operands.
         let num_bits = 64 - n.leading_zeros();
-        let mut fk = BigUint::ZERO;
-        let mut fk1 = BigUint::from(1u32);
+        let (start_bits, mut fk, mut fk1) = checkpoint::initial_state(opts, n, "FFTBased")?;
 
         let frozen = observer.freeze();
-        let strategy = AdaptiveStrategy::new(DEFAULT_FFT_THRESHOLD);
+        let strategy = AdaptiveStrategy::from_options(opts);
+        let fft_only = FFTOnlyStrategy::new().with_fft_config(opts.fft_config());
+        let mut dynamic = opts
+            .dynamic_thresholds
+            .then(|| DynamicThresholdManager::from_options(opts));
+        let mut writer = opts
+            .checkpoint
+            .as_ref()
+            .map(|config| CheckpointWriter::new(config, n, "FFTBased"));
+        *self.last_snapshot.lock() = None;
+        // Destinations of each step, swapped with F(k) and F(k+1) afterwards.
+        let (mut t2, mut t3) = (BigUint::ZERO, BigUint::ZERO);
 
-        for i in (0..num_bits).rev() {
+        for i in (0..start_bits).rev() {
+            cancel.wait_while_paused();
             if cancel.is_cancelled() {
+                if let Some(writer) = writer.as_mut() {
+                    writer.save(i + 1, &fk, &fk1)?;
+                }
                 return Err(FibError::Cancelled);
             }
 
-            // Doubling step with FFT multiplication for large operands
-            let (f2k, f2k1) = strategy.execute_doubling_step(&fk, &fk1);
-            fk = f2k;
-            fk1 = f2k1;
+            // Doubling step with FFT multiplication for large operands:
+            // F(2k) goes into t2 and F(2k+1) into t3, reusing their
+            // allocations on FFT steps.
+            if let Some(mgr) = dynamic.as_mut() {
+                #[allow(clippy::cast_possible_truncation)]
+                let max_bits = fk.bits().max(fk1.bits()) as usize;
+                let (method, parallel) = mgr.select(max_bits);
+                let step_start = Instant::now();
+                if method == MultiplicationMethod::Fft {
+                    fft_only.execute_doubling_step_to(&mut t2, &mut t3, &fk, &fk1);
+                } else if parallel {
+                    ParallelKaratsubaStrategy::new(0)
+                        .execute_doubling_step_to(&mut t2, &mut t3, &fk, &fk1);
+                } else {
+                    KaratsubaStrategy::new().execute_doubling_step_to(&mut t2, &mut t3, &fk, &fk1);
+                }
+                mgr.record_iteration(max_bits, step_start.elapsed(), method, parallel);
+                mgr.adjust();
+            } else {
+                strategy.execute_doubling_step_to(&mut t2, &mut t3, &fk, &fk1);
+            }
+
+            // Rotate: the old F(k) and F(k+1) buffers become the next
+            // step's destinations.
+            std::mem::swap(&mut fk, &mut t2);
+            std::mem::swap(&mut fk1, &mut t3);
 
             // Conditional addition
             if (n >> i) & 1 == 1 {
@@ -77,8 +126,13 @@ impl CoreCalculator for FFTBasedCalculator {
                     u64::from(num_bits),
                 ));
             }
+
+            if let Some(writer) = writer.as_mut() {
+                writer.after_iteration(i, &fk, &fk1)?;
+            }
         }
 
+        *self.last_snapshot.lock() = dynamic.map(|mgr| mgr.snapshot());
         observer.on_progress(&ProgressUpdate::done(calc_index, "FFTBased"));
         Ok(fk)
     }
@@ -86,6 +140,10 @@ impl CoreCalculator for FFTBasedCalculator {
     fn name(&self) -> &'static str {
         "FFTBased"
     }
+
+    fn threshold_snapshot(&self) -> Option<ThresholdSnapshot> {
+        self.last_snapshot.lock().clone()
+    }
 }
 
 #[cfg(test)]
@@ -133,4 +191,118 @@ mod tests {
             assert_eq!(fd_result, fft_result, "Mismatch at n={n}");
         }
     }
+
+    #[test]
+    fn fft_dynamic_thresholds_match_static() {
+        let calc = FFTBasedCalculator::new();
+        let cancel = CancellationToken::new();
+        let observer = NoOpObserver::new();
+        let opts = Options {
+            dynamic_thresholds: true,
+            parallel_threshold: 512,
+            fft_threshold: 2048,
+            ..Options::default()
+        };
+        let result = calc
+            .calculate_core(&cancel, &observer, 0, 20_000, &opts)
+            .unwrap();
+        assert_eq!(result, compute_fib(20_000));
+        assert!(calc.threshold_snapshot().is_some());
+    }
+
+    #[test]
+    fn fft_honors_configured_threshold() {
+        let calc = FFTBasedCalculator::new();
+        let cancel = CancellationToken::new();
+        let observer = NoOpObserver::new();
+        for fft_threshold in [1, 1024, usize::MAX] {
+            let opts = Options {
+                fft_threshold,
+                ..Options::default()
+            };
+            let result = calc
+                .calculate_core(&cancel, &observer, 0, 20_000, &opts)
+                .unwrap();
+            assert_eq!(
+                result,
+                compute_fib(20_000),
+                "Mismatch with fft_threshold={fft_threshold}"
+            );
+        }
+    }
+
+    #[test]
+    fn resume_from_checkpoint_matches_full_run() {
+        let n = 10_000u64;
+        let remaining_bits = 6;
+        let k = n >> remaining_bits;
+        let checkpoint = crate::checkpoint::Checkpoint {
+            n,
+            algorithm: "FFTBased".to_string(),
+            remaining_bits,
+            fk: compute_fib(k),
+            fk1: compute_fib(k + 1),
+        };
+        let opts = Options {
+            resume: Some(std::sync::Arc::new(checkpoint)),
+            ..Options::default()
+        };
+        let result = FFTBasedCalculator::new()
+            .calculate_core(&CancellationToken::new(), &NoOpObserver::new(), 0, n, &opts)
+            .unwrap();
+        assert_eq!(result, compute_fib(n));
+    }
+
+    #[test]
+    fn resume_rejects_checkpoint_for_other_n() {
+        let checkpoint = crate::checkpoint::Checkpoint {
+            n: 999,
+            algorithm: "FFTBased".to_string(),
+            remaining_bits: 2,
+            fk: BigUint::ZERO,
+            fk1: BigUint::from(1u32),
+        };
+        let opts = Options {
+            resume: Some(std::sync::Arc::new(checkpoint)),
+            ..Options::default()
+        };
+        let result = FFTBasedCalculator::new().calculate_core(
+            &CancellationToken::new(),
+            &NoOpObserver::new(),
+            0,
+            1_000,
+            &opts,
+        );
+        assert!(matches!(result, Err(FibError::Config(_))));
+    }
+
+    #[test]
+    fn cancellation_writes_checkpoint() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("cancel.ckpt");
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        let opts = Options {
+            checkpoint: Some(crate::checkpoint::CheckpointConfig {
+                path: path.clone(),
+                every_iterations: 0,
+                interval: None,
+            }),
+            ..Options::default()
+        };
+        let result = FFTBasedCalculator::new().calculate_core(
+            &cancel,
+            &NoOpObserver::new(),
+            0,
+            10_000,
+            &opts,
+        );
+        assert!(matches!(result, Err(FibError::Cancelled)));
+
+        // Nothing was processed, so the checkpoint holds the initial state.
+        let cp = crate::checkpoint::Checkpoint::load(&path).unwrap();
+        assert_eq!(cp.remaining_bits, 14);
+        assert_eq!(cp.fk, BigUint::ZERO);
+        assert_eq!(cp.fk1, BigUint::from(1u32));
+    }
 }
@@ -1,63 +1,172 @@
 //! FFT multiplication wrappers.
 //!
 //! Provides `mul_fft`, `sqr_fft`, `smart_multiply`, and `smart_square`
-//! that route to FFT or Karatsuba based on operand size.
+//! that route to FFT or Karatsuba based on operand size, plus fused
+//! sums of products that share one inverse transform.
 
+use fibcalc_bigfft::{FFTBackend, FFTCache, FFTConfig, FFTLayout, FFTPlan, TransformedOperand};
 use num_bigint::BigUint;
 
 #[cfg(test)]
 use crate::constants::DEFAULT_FFT_THRESHOLD;
 
-/// Multiply using FFT if operands are large enough, otherwise use default.
-#[must_use]
-#[allow(clippy::cast_possible_truncation)]
-#[allow(dead_code)] // TODO: Phase 2 — FFT strategy integration
-pub fn smart_multiply(a: &BigUint, b: &BigUint, fft_threshold: usize) -> BigUint {
-    let max_bits = a.bits().max(b.bits()) as usize;
-    if max_bits >= fft_threshold {
-        mul_fft(a, b)
-    } else {
-        a * b
+/// Capacity of a doubling step's transform cache: F(k), F(k+1) and T.
+pub(crate) const STEP_CACHE_ENTRIES: usize = 3;
+
+/// `fft` with every product routed through the FFT pipeline.
+pub(crate) fn always_fft(fft: &FFTConfig) -> FFTConfig {
+    FFTConfig {
+        threshold_bits: 0,
+        ..*fft
     }
 }
 
-/// Square using FFT if operand is large enough.
+/// Multiply using FFT if operands reach `fft`'s threshold, otherwise use default.
 #[must_use]
-#[allow(clippy::cast_possible_truncation)]
-#[allow(dead_code)] // TODO: Phase 2 — FFT strategy integration
-pub fn smart_square(a: &BigUint, fft_threshold: usize) -> BigUint {
-    let bits = a.bits() as usize;
-    if bits >= fft_threshold {
-        sqr_fft(a)
-    } else {
-        a * a
-    }
+pub fn smart_multiply(a: &BigUint, b: &BigUint, fft: &FFTConfig) -> BigUint {
+    fibcalc_bigfft::mul_with(a, b, fft)
+}
+
+/// Square using FFT if the operand reaches `fft`'s threshold.
+#[must_use]
+pub fn smart_square(a: &BigUint, fft: &FFTConfig) -> BigUint {
+    fibcalc_bigfft::sqr_with(a, fft)
+}
+
+/// FFT multiplication via fibcalc-bigfft under `fft`'s backend, regardless
+/// of operand size.
+#[must_use]
+pub fn mul_fft(a: &BigUint, b: &BigUint, fft: &FFTConfig) -> BigUint {
+    fibcalc_bigfft::mul_with(a, b, &always_fft(fft))
+}
+
+/// FFT squaring via fibcalc-bigfft under `fft`'s backend, regardless of
+/// operand size.
+#[must_use]
+pub fn sqr_fft(a: &BigUint, fft: &FFTConfig) -> BigUint {
+    fibcalc_bigfft::sqr_with(a, &always_fft(fft))
+}
+
+/// [`mul_fft`] into `dst` under `fft`'s backend, reusing its allocation.
+pub fn mul_fft_to(dst: &mut BigUint, a: &BigUint, b: &BigUint, fft: &FFTConfig) {
+    fibcalc_bigfft::mul_to_with(dst, a, b, &always_fft(fft));
+}
+
+/// `a^2 + b^2`, fused in the FFT domain once an operand reaches `fft`'s
+/// threshold.
+#[must_use]
+pub fn smart_sqr_add_sqr(a: &BigUint, b: &BigUint, fft: &FFTConfig) -> BigUint {
+    fibcalc_bigfft::sqr_add_sqr_with(a, b, fft)
+}
+
+/// Sum of products, fused in the FFT domain once an operand reaches
+/// `fft`'s threshold.
+#[must_use]
+pub fn smart_sum_of_products(terms: &[(&BigUint, &BigUint)], fft: &FFTConfig) -> BigUint {
+    fibcalc_bigfft::sum_of_products_with(terms, fft)
 }
 
-/// FFT multiplication via fibcalc-bigfft.
+/// Fused FFT `a^2 + b^2` under `fft`'s backend, regardless of operand size.
 #[must_use]
-#[allow(dead_code)] // TODO: Phase 2 — FFT strategy integration
-pub fn mul_fft(a: &BigUint, b: &BigUint) -> BigUint {
-    fibcalc_bigfft::mul(a, b)
+pub fn sqr_add_sqr_fft(a: &BigUint, b: &BigUint, fft: &FFTConfig) -> BigUint {
+    fibcalc_bigfft::sqr_add_sqr_with(a, b, &always_fft(fft))
+}
+
+/// [`sqr_add_sqr_fft`] into `dst` under `fft`'s backend, reusing its
+/// allocation.
+pub fn sqr_add_sqr_fft_to(dst: &mut BigUint, a: &BigUint, b: &BigUint, fft: &FFTConfig) {
+    fibcalc_bigfft::sqr_add_sqr_to_with(dst, a, b, &always_fft(fft));
 }
 
-/// FFT squaring via fibcalc-bigfft.
+/// Fused FFT sum of products under `fft`'s backend, regardless of operand
+/// size.
 #[must_use]
-#[allow(dead_code)] // TODO: Phase 2 — FFT strategy integration
-pub fn sqr_fft(a: &BigUint) -> BigUint {
-    fibcalc_bigfft::sqr(a)
+pub fn sum_of_products_fft(terms: &[(&BigUint, &BigUint)], fft: &FFTConfig) -> BigUint {
+    fibcalc_bigfft::sum_of_products_with(terms, &always_fft(fft))
+}
+
+/// FFT doubling step that runs one forward transform per operand.
+///
+/// F(k), F(k+1) and T = 2F(k+1) - F(k) are transformed under a shared plan
+/// through `cache`, so F(k)'s transform serves both F(k)*T and F(k)^2:
+/// three forward transforms instead of four. F(k)^2 + F(k+1)^2 is summed in
+/// the transform domain, so the step runs two inverse transforms instead of
+/// three. The step clears `cache` before returning; only its hit and miss
+/// counters carry over to the next step.
+///
+/// Only whole Fermat transforms are reusable; steps that `fft` lays out as
+/// NTT or as a memory-bounded segmented product run three independent
+/// products.
+pub(crate) fn fft_doubling_step(
+    fk: &BigUint,
+    fk1: &BigUint,
+    fft: &FFTConfig,
+    cache: &FFTCache,
+) -> (BigUint, BigUint) {
+    let (mut f2k, mut f2k1) = (BigUint::ZERO, BigUint::ZERO);
+    fft_doubling_step_to(&mut f2k, &mut f2k1, fk, fk1, fft, cache);
+    (f2k, f2k1)
+}
+
+/// [`fft_doubling_step`] into `f2k` and `f2k1`, reusing their allocations.
+#[allow(clippy::cast_possible_truncation)]
+pub(crate) fn fft_doubling_step_to(
+    f2k: &mut BigUint,
+    f2k1: &mut BigUint,
+    fk: &BigUint,
+    fk1: &BigUint,
+    fft: &FFTConfig,
+    cache: &FFTCache,
+) {
+    let t = (fk1 << 1u32) - fk;
+    let bits = fk.bits().max(fk1.bits()).max(t.bits()) as usize;
+    match FFTLayout::for_product(bits, bits, fft) {
+        FFTLayout::Whole(plan) => fermat_doubling_step_to(f2k, f2k1, fk, fk1, &t, plan, cache),
+        FFTLayout::Ntt { .. } | FFTLayout::Segmented { .. } => {
+            mul_fft_to(f2k, fk, &t, fft);
+            sqr_add_sqr_fft_to(f2k1, fk, fk1, fft);
+        }
+    }
+}
+
+fn fermat_doubling_step_to(
+    f2k: &mut BigUint,
+    f2k1: &mut BigUint,
+    fk: &BigUint,
+    fk1: &BigUint,
+    t: &BigUint,
+    plan: FFTPlan,
+    cache: &FFTCache,
+) {
+    let config = FFTConfig::with_threshold(0).with_backend(FFTBackend::Fermat);
+
+    let t_hat = cache.transform(t, plan, &config);
+    cache
+        .transform(fk, plan, &config)
+        .mul_to(f2k, &t_hat, &config);
+    let fk_hat = cache.transform(fk, plan, &config);
+    let fk1_hat = cache.transform(fk1, plan, &config);
+    TransformedOperand::sum_of_products_to(
+        f2k1,
+        &[(&*fk_hat, &*fk_hat), (&*fk1_hat, &*fk1_hat)],
+        &config,
+    );
+    cache.clear();
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// The default FFT configuration.
+    const DEFAULT_FFT: FFTConfig = FFTConfig::with_threshold(DEFAULT_FFT_THRESHOLD);
+
     #[test]
     fn smart_multiply_small() {
         let a = BigUint::from(12345u64);
         let b = BigUint::from(67890u64);
         assert_eq!(
-            smart_multiply(&a, &b, DEFAULT_FFT_THRESHOLD),
+            smart_multiply(&a, &b, &DEFAULT_FFT),
             BigUint::from(838_102_050u64)
         );
     }
@@ -65,22 +174,74 @@ mod tests {
     #[test]
     fn smart_square_small() {
         let a = BigUint::from(1000u64);
-        assert_eq!(
-            smart_square(&a, DEFAULT_FFT_THRESHOLD),
-            BigUint::from(1_000_000u64)
-        );
+        assert_eq!(smart_square(&a, &DEFAULT_FFT), BigUint::from(1_000_000u64));
     }
 
     #[test]
     fn mul_fft_correctness() {
         let a = BigUint::from(999u64);
         let b = BigUint::from(1001u64);
-        assert_eq!(mul_fft(&a, &b), BigUint::from(999_999u64));
+        assert_eq!(mul_fft(&a, &b, &DEFAULT_FFT), BigUint::from(999_999u64));
     }
 
     #[test]
     fn sqr_fft_correctness() {
         let a = BigUint::from(1234u64);
-        assert_eq!(sqr_fft(&a), BigUint::from(1_522_756u64));
+        assert_eq!(sqr_fft(&a, &DEFAULT_FFT), BigUint::from(1_522_756u64));
+    }
+
+    #[test]
+    fn fused_sums_match_separate_products() {
+        let a = BigUint::from(u64::MAX).pow(300);
+        let b = BigUint::from(987_654_321u64).pow(400);
+        let expected = &a * &a + &b * &b;
+        assert_eq!(sqr_add_sqr_fft(&a, &b, &DEFAULT_FFT), expected);
+        assert_eq!(smart_sqr_add_sqr(&a, &b, &DEFAULT_FFT), expected);
+        let terms = [(&a, &b), (&b, &b)];
+        let expected = &a * &b + &b * &b;
+        assert_eq!(sum_of_products_fft(&terms, &DEFAULT_FFT), expected);
+        assert_eq!(smart_sum_of_products(&terms, &DEFAULT_FFT), expected);
+    }
+
+    #[test]
+    fn fermat_doubling_step_small_operands() {
+        let fk = BigUint::from(5u64);
+        let fk1 = BigUint::from(8u64);
+        let t = BigUint::from(11u64);
+        let plan = FFTPlan::for_product(4, 4);
+        let (mut f2k, mut f2k1) = (BigUint::from(u64::MAX), BigUint::from(7u64));
+        let cache = FFTCache::new(STEP_CACHE_ENTRIES);
+        fermat_doubling_step_to(&mut f2k, &mut f2k1, &fk, &fk1, &t, plan, &cache);
+        assert_eq!(f2k, BigUint::from(55u64));
+        assert_eq!(f2k1, BigUint::from(89u64));
+    }
+
+    #[test]
+    fn fermat_doubling_step_reuses_fk_transform() {
+        let fk = BigUint::from(987_654_321u64).pow(300);
+        let fk1 = BigUint::from(u64::MAX).pow(200);
+        let t = (&fk1 << 1u32) - &fk;
+        let plan = FFTPlan::for_product(t.bits() as usize, t.bits() as usize);
+        let cache = FFTCache::new(STEP_CACHE_ENTRIES);
+        let (mut f2k, mut f2k1) = (BigUint::ZERO, BigUint::ZERO);
+        for _ in 0..2 {
+            fermat_doubling_step_to(&mut f2k, &mut f2k1, &fk, &fk1, &t, plan, &cache);
+            assert_eq!(f2k, &fk * &t);
+            assert_eq!(f2k1, &fk * &fk + &fk1 * &fk1);
+            assert!(cache.is_empty());
+        }
+        // Per step: T, F(k) and F(k+1) miss, F(k) hits for the square.
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses), (2, 6));
+    }
+
+    #[test]
+    fn fft_doubling_step_matches_schoolbook() {
+        let fk = BigUint::from(832_040u64);
+        let fk1 = BigUint::from(1_346_269u64);
+        let (f2k, f2k1) =
+            fft_doubling_step(&fk, &fk1, &DEFAULT_FFT, &FFTCache::new(STEP_CACHE_ENTRIES));
+        assert_eq!(f2k, BigUint::from(1_548_008_755_920u64));
+        assert_eq!(f2k1, BigUint::from(2_504_730_781_961u64));
     }
 }
@@ -1,21 +1,79 @@
 //! Sequence generator trait.
 
-use num_bigint::BigUint;
+use num_bigint::{BigInt, BigUint};
 
 use crate::calculator::FibError;
+use crate::observer::ProgressObserver;
+use crate::observers::NoOpObserver;
 use crate::progress::CancellationToken;
 
-/// Trait for generating sequences of Fibonacci numbers.
-#[allow(dead_code)] // TODO: Phase 3 — PRD §2.15.1 SequenceGenerator
+/// Receives each `(index, F(index))` term as it is generated.
+///
+/// Returning an error stops generation and propagates the error.
+pub type SequenceSink<'a> = dyn FnMut(u64, &BigUint) -> Result<(), FibError> + 'a;
+
+/// Receives each `(index, F(index))` term of a range over signed indices.
+pub type SignedSequenceSink<'a> = dyn FnMut(i64, &BigInt) -> Result<(), FibError> + 'a;
+
+/// Trait for streaming ranges of Fibonacci numbers.
+///
+/// Terms are handed to a sink one at a time, so a generator can emit
+/// millions of terms while holding only a constant number of values.
+///
+/// # Example
+/// ```
+/// use fibcalc_core::generator::SequenceGenerator;
+/// use fibcalc_core::generator_iterative::IterativeGenerator;
+/// use fibcalc_core::observers::NoOpObserver;
+/// use fibcalc_core::progress::CancellationToken;
+///
+/// let mut lines = Vec::new();
+/// IterativeGenerator::new()
+///     .generate(10, 12, &CancellationToken::new(), &NoOpObserver::new(), &mut |i, v| {
+///         lines.push(format!("{i} {v}"));
+///         Ok(())
+///     })
+///     .unwrap();
+/// assert_eq!(lines, ["10 55", "11 89", "12 144"]);
+/// ```
 pub trait SequenceGenerator: Send + Sync {
-    /// Generate Fibonacci numbers from start to end.
+    /// Stream F(start)..=F(end) to `sink` in index order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FibError::Config` if `start > end`, `FibError::Cancelled` if
+    /// `cancel` fires, or any error returned by `sink`.
     fn generate(
         &self,
         start: u64,
         end: u64,
         cancel: &CancellationToken,
-    ) -> Result<Vec<(u64, BigUint)>, FibError>;
+        observer: &dyn ProgressObserver,
+        sink: &mut SequenceSink<'_>,
+    ) -> Result<(), FibError>;
+
+    /// Generate F(start)..=F(end) into a `Vec`.
+    ///
+    /// Convenience for short ranges; prefer [`generate`](Self::generate)
+    /// for anything large.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`generate`](Self::generate).
+    fn generate_vec(
+        &self,
+        start: u64,
+        end: u64,
+        cancel: &CancellationToken,
+    ) -> Result<Vec<(u64, BigUint)>, FibError> {
+        let mut results = Vec::new();
+        self.generate(start, end, cancel, &NoOpObserver::new(), &mut |i, v| {
+            results.push((i, v.clone()));
+            Ok(())
+        })?;
+        Ok(results)
+    }
 
     /// Get the name of this generator.
-    fn name(&self) -> &str;
+    fn name(&self) -> &'static str;
 }
@@ -1,21 +1,96 @@
 //! Iterative Fibonacci sequence generator.
 
-use num_bigint::BigUint;
-
 use crate::calculator::FibError;
-use crate::generator::SequenceGenerator;
-use crate::progress::CancellationToken;
+use crate::generator::{SequenceGenerator, SequenceSink, SignedSequenceSink};
+use crate::iterator::FibIterator;
+use crate::observer::ProgressObserver;
+use crate::progress::{CancellationToken, ProgressUpdate};
 
 /// Iterative generator that computes sequential Fibonacci numbers.
-#[allow(dead_code)] // TODO: Phase 3 — PRD §2.15.1 IterativeGenerator
+///
+/// Jumps to the first index with [`FibIterator::from_index`] in O(log n)
+/// multiplications, then walks the additive recurrence, keeping only the
+/// current pair in memory.
 pub struct IterativeGenerator;
 
-#[allow(dead_code)] // TODO: Phase 3 — PRD §2.15.1 IterativeGenerator
 impl IterativeGenerator {
+    /// Create a new iterative generator.
     #[must_use]
     pub fn new() -> Self {
         Self
     }
+
+    /// Stream the terms from F(start) to F(end) over signed indices, with
+    /// `F(−n) = (−1)^{n+1}·F(n)`.
+    ///
+    /// Walks up from `start`, or down with `descending`, using
+    /// [`FibIterator::from_signed_index`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `FibError::Config` if `end` lies before `start` in the
+    /// walking direction, `FibError::Cancelled` if `cancel` fires, or any
+    /// error returned by `sink`.
+    pub fn generate_signed(
+        &self,
+        start: i64,
+        end: i64,
+        descending: bool,
+        cancel: &CancellationToken,
+        observer: &dyn ProgressObserver,
+        sink: &mut SignedSequenceSink<'_>,
+    ) -> Result<(), FibError> {
+        if descending && start < end {
+            return Err(FibError::Config(
+                "start must be >= end when descending".into(),
+            ));
+        }
+        if !descending && start > end {
+            return Err(FibError::Config("start must be <= end".into()));
+        }
+
+        let terms = FibIterator::from_signed_index(start);
+        let terms = if descending { terms.backward() } else { terms };
+        let total = start.abs_diff(end).saturating_add(1);
+        self.stream(terms, total, cancel, observer, sink)
+    }
+
+    /// Hand the first `total` of `terms` to `sink`, reporting progress.
+    #[allow(clippy::cast_precision_loss)]
+    fn stream<I, V>(
+        &self,
+        terms: impl Iterator<Item = (I, V)>,
+        total: u64,
+        cancel: &CancellationToken,
+        observer: &dyn ProgressObserver,
+        sink: &mut dyn FnMut(I, &V) -> Result<(), FibError>,
+    ) -> Result<(), FibError> {
+        let frozen = observer.freeze();
+
+        for (emitted, (index, value)) in (1..=total).zip(terms) {
+            cancel.wait_while_paused();
+            if cancel.is_cancelled() {
+                return Err(FibError::Cancelled);
+            }
+
+            sink(index, &value)?;
+
+            let progress = emitted as f64 / total as f64;
+            if frozen.should_report(progress) {
+                frozen.update(progress);
+                observer.on_progress(&ProgressUpdate::new(
+                    0,
+                    self.name(),
+                    progress,
+                    emitted,
+                    total,
+                ));
+            }
+        }
+
+        observer.on_progress(&ProgressUpdate::done(0, self.name()));
+        Ok(())
+    }
 }
 
 impl Default for IterativeGenerator {
@@ -25,35 +100,26 @@ impl Default for IterativeGenerator {
 }
 
 impl SequenceGenerator for IterativeGenerator {
-    #[allow(clippy::cast_possible_truncation)]
     fn generate(
         &self,
         start: u64,
         end: u64,
         cancel: &CancellationToken,
-    ) -> Result<Vec<(u64, BigUint)>, FibError> {
+        observer: &dyn ProgressObserver,
+        sink: &mut SequenceSink<'_>,
+    ) -> Result<(), FibError> {
         if start > end {
             return Err(FibError::Config("start must be <= end".into()));
         }
 
-        let mut results = Vec::with_capacity((end - start + 1) as usize);
-        let mut a = BigUint::from(0u32);
-        let mut b = BigUint::from(1u32);
-
-        for i in 0..=end {
-            if cancel.is_cancelled() {
-                return Err(FibError::Cancelled);
-            }
-
-            if i >= start {
-                results.push((i, a.clone()));
-            }
-
-            let next = &a + &b;
-            a = std::mem::replace(&mut b, next);
-        }
-
-        Ok(results)
+        let total = (end - start).saturating_add(1);
+        self.stream(
+            FibIterator::from_index(start),
+            total,
+            cancel,
+            observer,
+            sink,
+        )
     }
 
     fn name(&self) -> &'static str {
@@ -64,12 +130,14 @@ impl SequenceGenerator for IterativeGenerator {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::observers::NoOpObserver;
+    use num_bigint::{BigInt, BigUint};
 
     #[test]
     fn generate_first_ten() {
         let gen = IterativeGenerator::new();
         let cancel = CancellationToken::new();
-        let results = gen.generate(0, 9, &cancel).unwrap();
+        let results = gen.generate_vec(0, 9, &cancel).unwrap();
         assert_eq!(results.len(), 10);
         assert_eq!(results[0], (0, BigUint::from(0u32)));
         assert_eq!(results[1], (1, BigUint::from(1u32)));
@@ -80,7 +148,7 @@ mod tests {
     fn generate_range() {
         let gen = IterativeGenerator::new();
         let cancel = CancellationToken::new();
-        let results = gen.generate(5, 7, &cancel).unwrap();
+        let results = gen.generate_vec(5, 7, &cancel).unwrap();
         assert_eq!(results.len(), 3);
         assert_eq!(results[0], (5, BigUint::from(5u32)));
         assert_eq!(results[1], (6, BigUint::from(8u32)));
@@ -91,7 +159,7 @@ mod tests {
     fn generate_single_element() {
         let gen = IterativeGenerator::new();
         let cancel = CancellationToken::new();
-        let results = gen.generate(0, 0, &cancel).unwrap();
+        let results = gen.generate_vec(0, 0, &cancel).unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0], (0, BigUint::from(0u32)));
     }
@@ -100,7 +168,7 @@ mod tests {
     fn generate_single_element_nonzero() {
         let gen = IterativeGenerator::new();
         let cancel = CancellationToken::new();
-        let results = gen.generate(10, 10, &cancel).unwrap();
+        let results = gen.generate_vec(10, 10, &cancel).unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0], (10, BigUint::from(55u32)));
     }
@@ -109,7 +177,7 @@ mod tests {
     fn generate_start_greater_than_end_errors() {
         let gen = IterativeGenerator::new();
         let cancel = CancellationToken::new();
-        let result = gen.generate(10, 5, &cancel);
+        let result = gen.generate_vec(10, 5, &cancel);
         assert!(matches!(result, Err(FibError::Config(_))));
     }
 
@@ -118,7 +186,7 @@ mod tests {
         let gen = IterativeGenerator::new();
         let cancel = CancellationToken::new();
         cancel.cancel();
-        let result = gen.generate(0, 100, &cancel);
+        let result = gen.generate_vec(0, 100, &cancel);
         assert!(matches!(result, Err(FibError::Cancelled)));
     }
 
@@ -126,7 +194,7 @@ mod tests {
     fn generate_known_values() {
         let gen = IterativeGenerator::new();
         let cancel = CancellationToken::new();
-        let results = gen.generate(0, 20, &cancel).unwrap();
+        let results = gen.generate_vec(0, 20, &cancel).unwrap();
 
         let expected: Vec<u64> = vec![
             0, 1, 1, 2, 3, 5, 8, 13, 21, 34, 55, 89, 144, 233, 377, 610, 987, 1597, 2584, 4181,
@@ -159,8 +227,111 @@ mod tests {
         let gen = IterativeGenerator::new();
         let cancel = CancellationToken::new();
         // Start from a later position
-        let results = gen.generate(20, 20, &cancel).unwrap();
+        let results = gen.generate_vec(20, 20, &cancel).unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0], (20, BigUint::from(6765u32)));
     }
+
+    #[test]
+    fn generate_streams_without_collecting() {
+        let gen = IterativeGenerator::new();
+        let cancel = CancellationToken::new();
+        let mut count = 0u64;
+        let mut last = BigUint::ZERO;
+        gen.generate(1_000, 1_999, &cancel, &NoOpObserver::new(), &mut |i, v| {
+            assert_eq!(i, 1_000 + count);
+            count += 1;
+            last.clone_from(v);
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(count, 1_000);
+        assert_eq!(last, FibIterator::from_index(1_999).next().unwrap().1);
+    }
+
+    #[test]
+    fn generate_signed_both_directions() {
+        let gen = IterativeGenerator::new();
+        let cancel = CancellationToken::new();
+        let mut terms = Vec::new();
+        let mut collect = |i: i64, v: &BigInt| {
+            terms.push(format!("{i} {v}"));
+            Ok(())
+        };
+        gen.generate_signed(-3, 1, false, &cancel, &NoOpObserver::new(), &mut collect)
+            .unwrap();
+        gen.generate_signed(2, -2, true, &cancel, &NoOpObserver::new(), &mut collect)
+            .unwrap();
+        assert_eq!(
+            terms,
+            ["-3 2", "-2 -1", "-1 1", "0 0", "1 1", "2 1", "1 1", "0 0", "-1 1", "-2 -1"]
+        );
+    }
+
+    #[test]
+    fn generate_signed_rejects_range_against_direction() {
+        let gen = IterativeGenerator::new();
+        let cancel = CancellationToken::new();
+        let observer = NoOpObserver::new();
+        for (start, end, descending) in [(1, -1, false), (-1, 1, true)] {
+            let result =
+                gen.generate_signed(start, end, descending, &cancel, &observer, &mut |_, _| {
+                    Ok(())
+                });
+            assert!(matches!(result, Err(FibError::Config(_))));
+        }
+    }
+
+    #[test]
+    fn generate_mid_sequence_matches_from_zero() {
+        let gen = IterativeGenerator::new();
+        let cancel = CancellationToken::new();
+        let tail = gen.generate_vec(300, 310, &cancel).unwrap();
+        let full = gen.generate_vec(0, 310, &cancel).unwrap();
+        assert_eq!(tail, full[300..]);
+    }
+
+    #[test]
+    fn generate_sink_error_stops() {
+        let gen = IterativeGenerator::new();
+        let cancel = CancellationToken::new();
+        let mut seen = 0;
+        let result = gen.generate(0, 100, &cancel, &NoOpObserver::new(), &mut |i, _| {
+            seen += 1;
+            if i == 4 {
+                Err(FibError::Calculation("sink full".into()))
+            } else {
+                Ok(())
+            }
+        });
+        assert!(matches!(result, Err(FibError::Calculation(_))));
+        assert_eq!(seen, 5);
+    }
+
+    #[test]
+    fn generate_reports_progress() {
+        use crate::observer::FrozenObserver;
+        use std::sync::Mutex;
+
+        struct Recorder(Mutex<Vec<ProgressUpdate>>);
+        impl ProgressObserver for Recorder {
+            fn on_progress(&self, update: &ProgressUpdate) {
+                self.0.lock().unwrap().push(update.clone());
+            }
+            fn freeze(&self) -> FrozenObserver {
+                FrozenObserver::new(0.25)
+            }
+        }
+
+        let recorder = Recorder(Mutex::new(Vec::new()));
+        IterativeGenerator::new()
+            .generate(0, 99, &CancellationToken::new(), &recorder, &mut |_, _| {
+                Ok(())
+            })
+            .unwrap();
+        let updates = recorder.0.into_inner().unwrap();
+        assert!(updates.len() >= 4);
+        assert!(updates.last().unwrap().done);
+        assert_eq!(updates[0].total_steps, 100);
+    }
 }
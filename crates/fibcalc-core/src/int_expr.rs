@@ -0,0 +1,276 @@
+//! Parsing of integer arguments such as `10^9+7` or `0xFFFF_FFFF`.
+//!
+//! Numbers are decimal or `0x` hexadecimal, with optional `_` separators.
+//! They combine with `+`, `-`, `*`, right-associative `^` and parentheses,
+//! under the usual precedence. Intermediate results may not go negative;
+//! [`parse_int_expr`] accepts a single leading `-` for the whole expression.
+//!
+//! # Example
+//! ```
+//! use fibcalc_core::int_expr::parse_uint_expr;
+//! use num_bigint::BigUint;
+//!
+//! assert_eq!(parse_uint_expr("2^61-1").unwrap(), BigUint::from((1u64 << 61) - 1));
+//! assert_eq!(parse_uint_expr("10^9 + 7").unwrap(), BigUint::from(1_000_000_007u64));
+//! assert_eq!(parse_uint_expr("0xff").unwrap(), BigUint::from(255u32));
+//! assert!(parse_uint_expr("1-2").is_err());
+//! ```
+
+use num_bigint::{BigInt, BigUint};
+use num_traits::{Num, ToPrimitive, Zero};
+
+/// Largest value an expression may produce, in bits.
+const MAX_EXPR_BITS: u64 = 1 << 26;
+
+/// Deepest nesting of parentheses and `^` an expression may use, so that
+/// input like `((((…` fails instead of overflowing the stack.
+const MAX_EXPR_DEPTH: usize = 256;
+
+/// Parse a non-negative integer expression.
+///
+/// # Errors
+///
+/// Returns an error string if the expression is malformed, goes negative,
+/// exceeds 2^26 bits, or nests more than 256 levels deep.
+pub fn parse_uint_expr(s: &str) -> Result<BigUint, String> {
+    let mut parser = Parser {
+        input: s.as_bytes(),
+        pos: 0,
+        depth: 0,
+    };
+    let value = parser.expr()?;
+    parser.skip_spaces();
+    if parser.pos < parser.input.len() {
+        return Err(format!(
+            "unexpected '{}' at position {}",
+            char::from(parser.input[parser.pos]),
+            parser.pos + 1
+        ));
+    }
+    Ok(value)
+}
+
+/// Parse an integer expression, negated as a whole when it starts with `-`.
+///
+/// `-10^12` is −(10^12); the expression after the sign follows
+/// [`parse_uint_expr`].
+///
+/// # Errors
+///
+/// Returns an error string if the expression after the sign does not parse.
+pub fn parse_int_expr(s: &str) -> Result<BigInt, String> {
+    match s.trim_start().strip_prefix('-') {
+        Some(rest) => parse_uint_expr(rest).map(|v| -BigInt::from(v)),
+        None => parse_uint_expr(s).map(BigInt::from),
+    }
+}
+
+/// Recursive-descent parser over the ASCII bytes of an expression.
+struct Parser<'a> {
+    input: &'a [u8],
+    pos: usize,
+    /// Parentheses and `^` currently open.
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn skip_spaces(&mut self) {
+        while self
+            .input
+            .get(self.pos)
+            .is_some_and(u8::is_ascii_whitespace)
+        {
+            self.pos += 1;
+        }
+    }
+
+    /// Consume `op` if it is the next non-space byte.
+    fn eat(&mut self, op: u8) -> bool {
+        self.skip_spaces();
+        if self.input.get(self.pos) == Some(&op) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Run `parse` one nesting level deeper, failing past
+    /// [`MAX_EXPR_DEPTH`].
+    fn nested(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<BigUint, String>,
+    ) -> Result<BigUint, String> {
+        if self.depth == MAX_EXPR_DEPTH {
+            return Err(format!(
+                "expression nests deeper than {MAX_EXPR_DEPTH} levels at position {}",
+                self.pos + 1
+            ));
+        }
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    /// `term (('+' | '-') term)*`
+    fn expr(&mut self) -> Result<BigUint, String> {
+        let mut value = self.term()?;
+        loop {
+            if self.eat(b'+') {
+                value += self.term()?;
+            } else if self.eat(b'-') {
+                let rhs = self.term()?;
+                if rhs > value {
+                    return Err("expression is negative".into());
+                }
+                value -= rhs;
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    /// `power ('*' power)*`
+    fn term(&mut self) -> Result<BigUint, String> {
+        let mut value = self.power()?;
+        while self.eat(b'*') {
+            let rhs = self.power()?;
+            if value.bits() + rhs.bits() > MAX_EXPR_BITS {
+                return Err(too_large());
+            }
+            value *= rhs;
+        }
+        Ok(value)
+    }
+
+    /// `atom ('^' power)?`
+    fn power(&mut self) -> Result<BigUint, String> {
+        let base = self.atom()?;
+        if !self.eat(b'^') {
+            return Ok(base);
+        }
+        let exponent = self.nested(Self::power)?;
+        if base.bits() <= 1 || exponent.is_zero() {
+            // 0^e, 1^e and b^0 never grow.
+            return Ok(if exponent.is_zero() {
+                BigUint::from(1u32)
+            } else {
+                base
+            });
+        }
+        let exponent = exponent
+            .to_u32()
+            .filter(|&e| base.bits() * u64::from(e) <= MAX_EXPR_BITS)
+            .ok_or_else(too_large)?;
+        Ok(base.pow(exponent))
+    }
+
+    /// A number or a parenthesized expression.
+    fn atom(&mut self) -> Result<BigUint, String> {
+        if self.eat(b'(') {
+            let value = self.nested(Self::expr)?;
+            if !self.eat(b')') {
+                return Err(format!("missing ')' at position {}", self.pos + 1));
+            }
+            return Ok(value);
+        }
+        self.skip_spaces();
+        let rest = &self.input[self.pos..];
+        let hex = rest.starts_with(b"0x") || rest.starts_with(b"0X");
+        let (radix, prefix) = if hex { (16, 2) } else { (10, 0) };
+        let is_digit = |b: &u8| {
+            *b == b'_'
+                || if radix == 16 {
+                    b.is_ascii_hexdigit()
+                } else {
+                    b.is_ascii_digit()
+                }
+        };
+        let digits = rest[prefix..].iter().take_while(|b| is_digit(b)).count();
+        let text = std::str::from_utf8(&rest[prefix..prefix + digits])
+            .expect("ASCII digits")
+            .replace('_', "");
+        if text.is_empty() {
+            return Err(format!("expected a number at position {}", self.pos + 1));
+        }
+        self.pos += prefix + digits;
+        BigUint::from_str_radix(&text, radix).map_err(|e| e.to_string())
+    }
+}
+
+fn too_large() -> String {
+    format!("value exceeds {MAX_EXPR_BITS} bits")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> BigUint {
+        parse_uint_expr(s).unwrap()
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(parse("0"), BigUint::zero());
+        assert_eq!(parse(" 1_000_000 "), BigUint::from(1_000_000u32));
+        assert_eq!(parse("0xDEAD_beef"), BigUint::from(0xDEAD_BEEFu32));
+        assert_eq!(
+            parse("123456789012345678901234567890").to_string(),
+            "123456789012345678901234567890"
+        );
+    }
+
+    #[test]
+    fn operators_and_precedence() {
+        assert_eq!(parse("2^61-1"), BigUint::from((1u64 << 61) - 1));
+        assert_eq!(parse("10^9+7"), BigUint::from(1_000_000_007u64));
+        assert_eq!(parse("2*3^2"), BigUint::from(18u32));
+        assert_eq!(parse("2^3^2"), BigUint::from(512u32));
+        assert_eq!(parse("(2+3)*4 - 1"), BigUint::from(19u32));
+        assert_eq!(parse("7^0 + 0^5 + 1^100000000000"), BigUint::from(2u32));
+        assert_eq!(parse("10^100").to_string().len(), 101);
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        for bad in [
+            "", "abc", "1+", "(2", "2)", "1 2", "0x", "1-2", "-1", "12abc",
+        ] {
+            assert!(parse_uint_expr(bad).is_err(), "{bad:?} should not parse");
+        }
+    }
+
+    #[test]
+    fn rejects_huge_values() {
+        assert!(parse_uint_expr("10^100000000").is_err());
+        assert!(parse_uint_expr("2^(2^40)").is_err());
+        assert!(parse_uint_expr("(2^40000000)*(2^40000000)").is_err());
+        assert!(parse_uint_expr("2^1000000").is_ok());
+    }
+
+    #[test]
+    fn rejects_deep_nesting() {
+        let deep = |n: usize| format!("{}1{}", "(".repeat(n), ")".repeat(n));
+        assert_eq!(parse(&deep(MAX_EXPR_DEPTH)), BigUint::from(1u32));
+        let err = parse_uint_expr(&deep(MAX_EXPR_DEPTH + 1)).unwrap_err();
+        assert!(err.contains("nests deeper"), "{err}");
+        assert!(parse_uint_expr(&"(".repeat(1_000_000)).is_err());
+        let tower = |n: usize| format!("{}1", "1^".repeat(n));
+        assert_eq!(parse(&tower(MAX_EXPR_DEPTH)), BigUint::from(1u32));
+        assert!(parse_uint_expr(&tower(100_000)).is_err());
+    }
+
+    #[test]
+    fn signed_expressions() {
+        let parse = |s: &str| parse_int_expr(s).unwrap().to_string();
+        assert_eq!(parse("-10^12"), "-1000000000000");
+        assert_eq!(parse(" -(2^3 - 1)"), "-7");
+        assert_eq!(parse("42"), "42");
+        assert_eq!(parse("-0"), "0");
+        for bad in ["-", "--1", "- -1", "1-2", "-1-2"] {
+            assert!(parse_int_expr(bad).is_err(), "{bad:?} should not parse");
+        }
+    }
+}
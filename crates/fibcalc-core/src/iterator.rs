@@ -1,6 +1,6 @@
 //! Lazy Fibonacci iterator using the standard additive recurrence.
 
-use num_bigint::BigUint;
+use num_bigint::{BigInt, BigUint};
 use num_traits::{One, Zero};
 
 /// Lazy iterator over the Fibonacci sequence.
@@ -39,6 +39,26 @@ impl FibIterator {
         let (a, b) = fib_pair(n);
         Self { a, b, index: n }
     }
+
+    /// Start iteration from a signed index, with `F(−n) = (−1)^{n+1}·F(n)`.
+    ///
+    /// The returned iterator runs forward, or backward after
+    /// [`SignedFibIterator::backward`].
+    ///
+    /// # Example
+    /// ```
+    /// use fibcalc_core::iterator::FibIterator;
+    /// let terms: Vec<_> = FibIterator::from_signed_index(2)
+    ///     .backward()
+    ///     .take(6)
+    ///     .map(|(i, v)| format!("F({i})={v}"))
+    ///     .collect();
+    /// assert_eq!(terms, ["F(2)=1", "F(1)=1", "F(0)=0", "F(-1)=1", "F(-2)=-1", "F(-3)=2"]);
+    /// ```
+    #[must_use]
+    pub fn from_signed_index(n: i64) -> SignedFibIterator {
+        SignedFibIterator::new(n)
+    }
 }
 
 impl Default for FibIterator {
@@ -60,6 +80,74 @@ impl Iterator for FibIterator {
     }
 }
 
+/// Lazy iterator over the Fibonacci sequence extended to negative indices.
+///
+/// Yields `(index, F(index))` pairs with signed values, forward by default
+/// and toward −∞ after [`backward`](Self::backward). Stops at the ends of
+/// the `i64` range.
+pub struct SignedFibIterator {
+    /// F(index).
+    a: BigInt,
+    /// F(index + 1).
+    b: BigInt,
+    index: Option<i64>,
+    backward: bool,
+}
+
+impl SignedFibIterator {
+    /// Create a forward iterator starting from F(n), computed in O(log |n|).
+    #[must_use]
+    pub fn new(n: i64) -> Self {
+        let magnitude = n.unsigned_abs();
+        let (fm, fm1) = fib_pair(magnitude);
+        let (a, b) = if n >= 0 {
+            (BigInt::from(fm), BigInt::from(fm1))
+        } else {
+            // F(−m) = (−1)^{m+1}·F(m) and F(−m+1) = (−1)^m·F(m−1).
+            let fm_prev = BigInt::from(&fm1 - &fm);
+            let (fm, even) = (BigInt::from(fm), magnitude % 2 == 0);
+            if even {
+                (-fm, fm_prev)
+            } else {
+                (fm, -fm_prev)
+            }
+        };
+        Self {
+            a,
+            b,
+            index: Some(n),
+            backward: false,
+        }
+    }
+
+    /// Iterate toward −∞ instead, starting from the current index.
+    #[must_use]
+    pub fn backward(mut self) -> Self {
+        self.backward = true;
+        self
+    }
+}
+
+impl Iterator for SignedFibIterator {
+    type Item = (i64, BigInt);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let idx = self.index?;
+        let val = self.a.clone();
+        if self.backward {
+            // F(k−1) = F(k+1) − F(k).
+            let prev = &self.b - &self.a;
+            self.b = std::mem::replace(&mut self.a, prev);
+            self.index = idx.checked_sub(1);
+        } else {
+            let next = &self.a + &self.b;
+            self.a = std::mem::replace(&mut self.b, next);
+            self.index = idx.checked_add(1);
+        }
+        Some((idx, val))
+    }
+}
+
 /// Compute (F(n), F(n+1)) in O(log n) using fast doubling.
 ///
 /// Identities: F(2k) = F(k)*(2*F(k+1) - F(k)), F(2k+1) = F(k)^2 + F(k+1)^2.
@@ -131,4 +219,51 @@ mod tests {
             assert_eq!(fast_val, linear_val, "mismatch at n={n}");
         }
     }
+
+    #[test]
+    fn signed_iterator_matches_negafibonacci() {
+        let forward: Vec<i64> = FibIterator::from_signed_index(-6)
+            .take(13)
+            .map(|(_, v)| i64::try_from(v).unwrap())
+            .collect();
+        assert_eq!(forward, [-8, 5, -3, 2, -1, 1, 0, 1, 1, 2, 3, 5, 8]);
+
+        let mut backward = FibIterator::from_signed_index(6).backward();
+        let values: Vec<(i64, i64)> = backward
+            .by_ref()
+            .take(13)
+            .map(|(i, v)| (i, i64::try_from(v).unwrap()))
+            .collect();
+        assert_eq!(values[0], (6, 8));
+        assert_eq!(values[12], (-6, -8));
+        assert_eq!(backward.next().unwrap().0, -7);
+    }
+
+    #[test]
+    fn signed_iterator_from_large_negative_index() {
+        for n in [-1000i64, -1001] {
+            let (idx, value) = FibIterator::from_signed_index(n).next().unwrap();
+            let (_, magnitude) = FibIterator::from_index(n.unsigned_abs()).next().unwrap();
+            assert_eq!(idx, n);
+            assert_eq!(value.magnitude(), &magnitude);
+            assert_eq!(value < BigInt::ZERO, n % 2 == 0);
+        }
+        // Walking back from F(−999) reaches F(−1000).
+        let mut iter = FibIterator::from_signed_index(-999).backward();
+        iter.next();
+        let (_, f) = iter.next().unwrap();
+        assert_eq!(f, FibIterator::from_signed_index(-1000).next().unwrap().1);
+    }
+
+    #[test]
+    fn signed_iterator_stops_at_range_end() {
+        let mut iter = SignedFibIterator {
+            a: BigInt::from(0),
+            b: BigInt::from(1),
+            index: Some(i64::MIN),
+            backward: true,
+        };
+        assert_eq!(iter.next().unwrap().0, i64::MIN);
+        assert!(iter.next().is_none());
+    }
 }
@@ -7,15 +7,19 @@
 
 pub(crate) mod arena;
 pub mod calculator;
+pub mod checkpoint;
 pub(crate) mod common;
 pub mod constants;
+pub mod decimal;
 pub mod dynamic_threshold;
 pub mod fastdoubling;
 pub mod fft_based;
 pub(crate) mod fft_wrappers;
-pub(crate) mod generator;
-pub(crate) mod generator_iterative;
+pub mod generator;
+pub mod generator_iterative;
+pub mod int_expr;
 pub mod iterator;
+pub mod lucas;
 pub mod matrix;
 pub(crate) mod matrix_ops;
 pub(crate) mod matrix_types;
@@ -24,11 +28,13 @@ pub mod modular;
 pub mod observer;
 pub mod observers;
 pub mod options;
+pub mod pisano;
 pub(crate) mod pool;
 pub mod progress;
 pub mod registry;
 pub mod strategy;
-pub(crate) mod threshold_types;
+pub mod threshold_types;
+pub(crate) mod toom;
 
 #[cfg_attr(docsrs, doc(cfg(feature = "gmp")))]
 #[cfg(feature = "gmp")]
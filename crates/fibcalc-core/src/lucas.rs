@@ -0,0 +1,683 @@
+//! Lucas numbers L(n) and the Lucas sequences `U_n(P, Q)`, `V_n(P, Q)`.
+//!
+//! `U_0 = 0`, `U_1 = 1`, `V_0 = 2`, `V_1 = P`, and both follow
+//! `X_{n+1} = P·X_n − Q·X_{n−1}`, so `F(n) = U_n(1, −1)` and
+//! `L(n) = V_n(1, −1)`. The doubling loop carries `(U_k, U_{k+1})`:
+//!
+//! ```text
+//! U_{2k}   = U_k · (2·U_{k+1} − P·U_k)
+//! U_{2k+1} = U_{k+1}^2 − Q·U_k^2
+//! ```
+//!
+//! and finishes with `V_n = 2·U_{n+1} − P·U_n`. For P = 1, Q = −1 these are
+//! the Fibonacci identities, so exact Lucas numbers run
+//! [`DoublingStepExecutor::execute_doubling_step`] unchanged.
+//! [`LucasMatrix`] raises `[[P, −Q], [1, 0]]` to the n-th power instead and
+//! cross-validates the doubling loop.
+//!
+//! Both calculators read the sequence from `Options::sequence` and work
+//! modulo `Options::result_modulus` in modular runs. Exact results are
+//! `BigUint`s, so they need every term to be non-negative, which holds
+//! exactly when P ≥ 0 and P^2 ≥ 4Q.
+
+use fibcalc_bigfft::FFTConfig;
+use num_bigint::{BigInt, BigUint, Sign};
+use num_traits::Zero;
+
+use crate::calculator::{CoreCalculator, FibError};
+use crate::modular::ModProducts;
+use crate::observer::{FrozenObserver, ProgressObserver};
+use crate::options::Options;
+use crate::progress::{CancellationToken, ProgressUpdate};
+use crate::strategy::{AdaptiveStrategy, DoublingStepExecutor};
+
+/// Sequence a calculation produces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Sequence {
+    /// Fibonacci numbers `F(n) = U_n(1, −1)`.
+    #[default]
+    Fibonacci,
+    /// Lucas numbers `L(n) = V_n(1, −1)`.
+    Lucas,
+    /// The Lucas sequence `U_n(P, Q)`.
+    LucasU {
+        /// Parameter P.
+        p: i64,
+        /// Parameter Q.
+        q: i64,
+    },
+    /// The companion Lucas sequence `V_n(P, Q)`.
+    LucasV {
+        /// Parameter P.
+        p: i64,
+        /// Parameter Q.
+        q: i64,
+    },
+}
+
+impl Sequence {
+    /// Symbol of the sequence in output: `F`, `L`, `U` or `V`.
+    #[must_use]
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Self::Fibonacci => "F",
+            Self::Lucas => "L",
+            Self::LucasU { .. } => "U",
+            Self::LucasV { .. } => "V",
+        }
+    }
+
+    /// The parameters (P, Q); Fibonacci and Lucas numbers use (1, −1).
+    #[must_use]
+    pub fn parameters(self) -> (i64, i64) {
+        match self {
+            Self::Fibonacci | Self::Lucas => (1, -1),
+            Self::LucasU { p, q } | Self::LucasV { p, q } => (p, q),
+        }
+    }
+
+    /// Whether every term is non-negative: P ≥ 0 and P^2 ≥ 4Q.
+    #[must_use]
+    pub fn is_non_negative(self) -> bool {
+        let (p, q) = self.parameters();
+        p >= 0 && i128::from(p) * i128::from(p) >= 4 * i128::from(q)
+    }
+
+    /// Whether the term at index −n is the negative of the term at n.
+    ///
+    /// With `Q = ±1`, `U_{−n} = −Q^n·U_n` and `V_{−n} = Q^n·V_n`, so
+    /// `F(−n) = (−1)^{n+1}·F(n)` and `L(−n) = (−1)^n·L(n)`. Other values of Q
+    /// give fractions at negative indices, and `None`.
+    #[must_use]
+    pub fn negated_at(self, n: u64) -> Option<bool> {
+        let (_, q) = self.parameters();
+        if q.unsigned_abs() != 1 {
+            return None;
+        }
+        let odd_power = q == -1 && n % 2 == 1;
+        Some(self.is_companion() == odd_power)
+    }
+
+    /// Whether the result is `V_n` rather than `U_n`.
+    fn is_companion(self) -> bool {
+        matches!(self, Self::Lucas | Self::LucasV { .. })
+    }
+}
+
+/// Lucas numbers and sequences by fast doubling.
+///
+/// # Example
+/// ```
+/// use fibcalc_core::calculator::CoreCalculator;
+/// use fibcalc_core::lucas::{LucasFastDoubling, Sequence};
+/// use fibcalc_core::observers::NoOpObserver;
+/// use fibcalc_core::options::Options;
+/// use fibcalc_core::progress::CancellationToken;
+///
+/// let opts = Options { sequence: Sequence::Lucas, ..Options::default() };
+/// let cancel = CancellationToken::new();
+/// let result = LucasFastDoubling::new()
+///     .calculate_core(&cancel, &NoOpObserver::new(), 0, 10, &opts)
+///     .unwrap();
+/// assert_eq!(result.to_string(), "123");
+/// ```
+pub struct LucasFastDoubling;
+
+/// Lucas numbers and sequences by powers of `[[P, −Q], [1, 0]]`.
+///
+/// Cross-checks [`LucasFastDoubling`] when `--algo all` runs.
+pub struct LucasMatrix;
+
+impl LucasFastDoubling {
+    /// Create a new Lucas fast doubling calculator.
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for LucasFastDoubling {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CoreCalculator for LucasFastDoubling {
+    fn calculate_core(
+        &self,
+        cancel: &CancellationToken,
+        observer: &dyn ProgressObserver,
+        calc_index: usize,
+        n: u64,
+        opts: &Options,
+    ) -> Result<BigUint, FibError> {
+        let sequence = opts.sequence;
+        let steps = Steps::new(cancel, observer, calc_index, self.name(), n);
+        let result = if let Some(modulus) = opts.result_modulus() {
+            let ring = Residues::new(&modulus, &opts.fft_config());
+            let (un, un1) = doubling(&ring, n, sequence, &steps)?;
+            finish(&ring, sequence, &un, &un1)
+        } else {
+            check_non_negative(sequence)?;
+            let strategy = AdaptiveStrategy::from_options(opts);
+            if sequence.parameters() == (1, -1) {
+                let (fk, fk1) = fibonacci_pair(&strategy, n, &steps)?;
+                if sequence.is_companion() {
+                    (fk1 << 1u32) - fk
+                } else {
+                    fk
+                }
+            } else {
+                let ring = Integers(&strategy);
+                let (un, un1) = doubling(&ring, n, sequence, &steps)?;
+                into_biguint(finish(&ring, sequence, &un, &un1))
+            }
+        };
+        observer.on_progress(&ProgressUpdate::done(calc_index, self.name()));
+        Ok(result)
+    }
+
+    fn name(&self) -> &'static str {
+        "LucasFastDoubling"
+    }
+}
+
+impl LucasMatrix {
+    /// Create a new Lucas matrix calculator.
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for LucasMatrix {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CoreCalculator for LucasMatrix {
+    fn calculate_core(
+        &self,
+        cancel: &CancellationToken,
+        observer: &dyn ProgressObserver,
+        calc_index: usize,
+        n: u64,
+        opts: &Options,
+    ) -> Result<BigUint, FibError> {
+        let sequence = opts.sequence;
+        let steps = Steps::new(cancel, observer, calc_index, self.name(), n);
+        let result = if let Some(modulus) = opts.result_modulus() {
+            let ring = Residues::new(&modulus, &opts.fft_config());
+            let (un, un1) = matrix_power(&ring, n, sequence, &steps)?;
+            finish(&ring, sequence, &un, &un1)
+        } else {
+            check_non_negative(sequence)?;
+            let strategy = AdaptiveStrategy::from_options(opts);
+            let ring = Integers(&strategy);
+            let (un, un1) = matrix_power(&ring, n, sequence, &steps)?;
+            into_biguint(finish(&ring, sequence, &un, &un1))
+        };
+        observer.on_progress(&ProgressUpdate::done(calc_index, self.name()));
+        Ok(result)
+    }
+
+    fn name(&self) -> &'static str {
+        "LucasMatrix"
+    }
+}
+
+fn check_non_negative(sequence: Sequence) -> Result<(), FibError> {
+    if sequence.is_non_negative() {
+        return Ok(());
+    }
+    let (p, q) = sequence.parameters();
+    Err(FibError::Config(format!(
+        "{}_n({p}, {q}) takes negative values (P < 0 or P^2 < 4Q); use a modulus",
+        sequence.symbol()
+    )))
+}
+
+fn into_biguint(value: BigInt) -> BigUint {
+    let (sign, magnitude) = value.into_parts();
+    assert!(
+        sign != Sign::Minus,
+        "P >= 0 and P^2 >= 4Q give non-negative terms"
+    );
+    magnitude
+}
+
+/// Pause, cancellation and progress reporting for one loop over the bits of n.
+struct Steps<'a> {
+    cancel: &'a CancellationToken,
+    observer: &'a dyn ProgressObserver,
+    frozen: FrozenObserver,
+    calc_index: usize,
+    name: &'static str,
+    num_bits: u32,
+}
+
+impl<'a> Steps<'a> {
+    fn new(
+        cancel: &'a CancellationToken,
+        observer: &'a dyn ProgressObserver,
+        calc_index: usize,
+        name: &'static str,
+        n: u64,
+    ) -> Self {
+        Self {
+            cancel,
+            observer,
+            frozen: observer.freeze(),
+            calc_index,
+            name,
+            num_bits: 64 - n.leading_zeros(),
+        }
+    }
+
+    /// Bit indices from the most significant down.
+    fn bits(&self) -> impl Iterator<Item = u32> {
+        (0..self.num_bits).rev()
+    }
+
+    fn begin(&self) -> Result<(), FibError> {
+        self.cancel.wait_while_paused();
+        if self.cancel.is_cancelled() {
+            return Err(FibError::Cancelled);
+        }
+        Ok(())
+    }
+
+    fn end(&self, i: u32) {
+        let progress = 1.0 - (f64::from(i) / f64::from(self.num_bits));
+        if self.frozen.should_report(progress) {
+            self.frozen.update(progress);
+            self.observer.on_progress(&ProgressUpdate::new(
+                self.calc_index,
+                self.name,
+                progress,
+                u64::from(self.num_bits - i),
+                u64::from(self.num_bits),
+            ));
+        }
+    }
+}
+
+/// Arithmetic the Lucas loops run on.
+trait Ring {
+    type Elem: Clone;
+
+    fn int(&self, v: i128) -> Self::Elem;
+    fn add(&self, a: &Self::Elem, b: &Self::Elem) -> Self::Elem;
+    fn sub(&self, a: &Self::Elem, b: &Self::Elem) -> Self::Elem;
+    fn mul(&self, a: &Self::Elem, b: &Self::Elem) -> Self::Elem;
+    fn sqr(&self, a: &Self::Elem) -> Self::Elem;
+    /// `a·k` for a small `k`.
+    fn scale(&self, a: &Self::Elem, k: i128) -> Self::Elem;
+}
+
+/// Exact integers, multiplying magnitudes with a doubling strategy.
+struct Integers<'a>(&'a dyn DoublingStepExecutor);
+
+impl Ring for Integers<'_> {
+    type Elem = BigInt;
+
+    fn int(&self, v: i128) -> BigInt {
+        BigInt::from(v)
+    }
+
+    fn add(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        a + b
+    }
+
+    fn sub(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        a - b
+    }
+
+    fn mul(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        let product = self.0.multiply(a.magnitude(), b.magnitude());
+        BigInt::from_biguint(a.sign() * b.sign(), product)
+    }
+
+    fn sqr(&self, a: &BigInt) -> BigInt {
+        BigInt::from(self.0.square(a.magnitude()))
+    }
+
+    fn scale(&self, a: &BigInt, k: i128) -> BigInt {
+        a * k
+    }
+}
+
+/// Residues modulo m, always kept reduced.
+struct Residues<'a> {
+    modulus: &'a BigUint,
+    products: ModProducts<'a>,
+}
+
+impl<'a> Residues<'a> {
+    fn new(modulus: &'a BigUint, fft: &FFTConfig) -> Self {
+        Self {
+            modulus,
+            products: ModProducts::new(modulus, fft),
+        }
+    }
+}
+
+impl Ring for Residues<'_> {
+    type Elem = BigUint;
+
+    fn int(&self, v: i128) -> BigUint {
+        let r = BigUint::from(v.unsigned_abs()) % self.modulus;
+        if v < 0 && !r.is_zero() {
+            self.modulus - r
+        } else {
+            r
+        }
+    }
+
+    fn add(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        let sum = a + b;
+        if &sum >= self.modulus {
+            sum - self.modulus
+        } else {
+            sum
+        }
+    }
+
+    fn sub(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        if a >= b {
+            a - b
+        } else {
+            a + self.modulus - b
+        }
+    }
+
+    fn mul(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        self.products.mul(a, b)
+    }
+
+    fn sqr(&self, a: &BigUint) -> BigUint {
+        self.products.sqr(a)
+    }
+
+    fn scale(&self, a: &BigUint, k: i128) -> BigUint {
+        (a * self.int(k)) % self.modulus
+    }
+}
+
+/// `U_n` of `sequence`, or `V_n = 2·U_{n+1} − P·U_n`.
+fn finish<R: Ring>(ring: &R, sequence: Sequence, un: &R::Elem, un1: &R::Elem) -> R::Elem {
+    if !sequence.is_companion() {
+        return un.clone();
+    }
+    let (p, _) = sequence.parameters();
+    ring.sub(&ring.add(un1, un1), &ring.scale(un, i128::from(p)))
+}
+
+/// `(U_n, U_{n+1})` by doubling.
+fn doubling<R: Ring>(
+    ring: &R,
+    n: u64,
+    sequence: Sequence,
+    steps: &Steps<'_>,
+) -> Result<(R::Elem, R::Elem), FibError> {
+    let (p, q) = sequence.parameters();
+    let (p, q) = (i128::from(p), i128::from(q));
+    let (mut uk, mut uk1) = (ring.int(0), ring.int(1));
+    for i in steps.bits() {
+        steps.begin()?;
+        // U_{2k} = U_k·(2·U_{k+1} − P·U_k), U_{2k+1} = U_{k+1}^2 − Q·U_k^2
+        let t = ring.sub(&ring.add(&uk1, &uk1), &ring.scale(&uk, p));
+        let u2k = ring.mul(&uk, &t);
+        uk1 = ring.sub(&ring.sqr(&uk1), &ring.scale(&ring.sqr(&uk), q));
+        uk = u2k;
+        if (n >> i) & 1 == 1 {
+            let next = ring.sub(&ring.scale(&uk1, p), &ring.scale(&uk, q));
+            uk = std::mem::replace(&mut uk1, next);
+        }
+        steps.end(i);
+    }
+    Ok((uk, uk1))
+}
+
+/// `(U_n, U_{n+1})` from `[[P, −Q], [1, 0]]^n = [[U_{n+1}, −Q·U_n], [U_n, −Q·U_{n−1}]]`.
+fn matrix_power<R: Ring>(
+    ring: &R,
+    n: u64,
+    sequence: Sequence,
+    steps: &Steps<'_>,
+) -> Result<(R::Elem, R::Elem), FibError> {
+    let (p, q) = sequence.parameters();
+    let (p, minus_q) = (i128::from(p), -i128::from(q));
+    let (mut m00, mut m01, mut m10, mut m11) = (ring.int(1), ring.int(0), ring.int(0), ring.int(1));
+    for i in steps.bits() {
+        steps.begin()?;
+        // Square the matrix with five products.
+        let off_diagonal = ring.mul(&m01, &m10);
+        let trace = ring.add(&m00, &m11);
+        (m00, m01, m10, m11) = (
+            ring.add(&ring.sqr(&m00), &off_diagonal),
+            ring.mul(&m01, &trace),
+            ring.mul(&m10, &trace),
+            ring.add(&ring.sqr(&m11), &off_diagonal),
+        );
+        if (n >> i) & 1 == 1 {
+            (m00, m01, m10, m11) = (
+                ring.add(&ring.scale(&m00, p), &m01),
+                ring.scale(&m00, minus_q),
+                ring.add(&ring.scale(&m10, p), &m11),
+                ring.scale(&m10, minus_q),
+            );
+        }
+        steps.end(i);
+    }
+    Ok((m10, m00))
+}
+
+/// (F(n), F(n+1)) with the Fibonacci doubling step of `strategy`.
+fn fibonacci_pair(
+    strategy: &dyn DoublingStepExecutor,
+    n: u64,
+    steps: &Steps<'_>,
+) -> Result<(BigUint, BigUint), FibError> {
+    let (mut fk, mut fk1) = (BigUint::ZERO, BigUint::from(1u32));
+    for i in steps.bits() {
+        steps.begin()?;
+        (fk, fk1) = strategy.execute_doubling_step(&fk, &fk1);
+        if (n >> i) & 1 == 1 {
+            let sum = &fk + &fk1;
+            fk = std::mem::replace(&mut fk1, sum);
+        }
+        steps.end(i);
+    }
+    Ok((fk, fk1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::observers::NoOpObserver;
+    use std::sync::Arc;
+
+    /// Terms 0..=count of U and V by the recurrence.
+    fn recurrence(p: i64, q: i64, count: usize) -> (Vec<BigInt>, Vec<BigInt>) {
+        let (p, q) = (BigInt::from(p), BigInt::from(q));
+        let mut u = vec![BigInt::from(0), BigInt::from(1)];
+        let mut v = vec![BigInt::from(2), p.clone()];
+        for i in 2..=count {
+            u.push(&p * &u[i - 1] - &q * &u[i - 2]);
+            v.push(&p * &v[i - 1] - &q * &v[i - 2]);
+        }
+        (u, v)
+    }
+
+    fn run(calc: &dyn CoreCalculator, n: u64, opts: &Options) -> Result<BigUint, FibError> {
+        calc.calculate_core(&CancellationToken::new(), &NoOpObserver::new(), 0, n, opts)
+    }
+
+    fn calculators() -> [Box<dyn CoreCalculator>; 2] {
+        [
+            Box::new(LucasFastDoubling::new()),
+            Box::new(LucasMatrix::new()),
+        ]
+    }
+
+    #[test]
+    fn lucas_numbers() {
+        let opts = Options {
+            sequence: Sequence::Lucas,
+            ..Options::default()
+        };
+        let (_, v) = recurrence(1, -1, 300);
+        for calc in calculators() {
+            for (n, term) in (0u64..).zip(&v) {
+                let expected = term.to_biguint().unwrap();
+                assert_eq!(run(calc.as_ref(), n, &opts).unwrap(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn exact_sequences_match_recurrence() {
+        for (p, q) in [(1, -1), (2, -1), (3, 2), (2, 1), (4, 3), (0, -5), (5, -7)] {
+            let (u, v) = recurrence(p, q, 120);
+            for (sequence, terms) in [
+                (Sequence::LucasU { p, q }, &u),
+                (Sequence::LucasV { p, q }, &v),
+            ] {
+                let opts = Options {
+                    sequence,
+                    ..Options::default()
+                };
+                for calc in calculators() {
+                    for (n, term) in (0u64..).zip(terms) {
+                        assert_eq!(
+                            BigInt::from(run(calc.as_ref(), n, &opts).unwrap()),
+                            *term,
+                            "{} {sequence:?} at n={n}",
+                            calc.name()
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn modular_sequences_match_recurrence() {
+        let modulus = BigUint::from(1_000_000_007u64);
+        let m = BigInt::from(modulus.clone());
+        for (p, q) in [(1, 1), (-3, 5), (7, 11), (i64::MAX, i64::MIN)] {
+            let (u, v) = recurrence(p, q, 80);
+            for (sequence, terms) in [
+                (Sequence::LucasU { p, q }, &u),
+                (Sequence::LucasV { p, q }, &v),
+            ] {
+                let opts = Options {
+                    sequence,
+                    modulus: Some(Arc::new(modulus.clone())),
+                    ..Options::default()
+                };
+                for calc in calculators() {
+                    for (n, term) in (0u64..).zip(terms) {
+                        let residue = ((term % &m) + &m) % &m;
+                        assert_eq!(
+                            BigInt::from(run(calc.as_ref(), n, &opts).unwrap()),
+                            residue,
+                            "{} {sequence:?} at n={n}",
+                            calc.name()
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn fast_doubling_and_matrix_agree() {
+        let modulus: BigUint = (BigUint::from(1u32) << 4000) - 1u32;
+        for sequence in [
+            Sequence::Lucas,
+            Sequence::LucasU { p: 3, q: -2 },
+            Sequence::LucasV { p: 6, q: 9 },
+        ] {
+            let exact = Options {
+                sequence,
+                ..Options::default()
+            };
+            let modular = Options {
+                modulus: Some(Arc::new(modulus.clone())),
+                fft_threshold: 1024,
+                ..exact.clone()
+            };
+            for opts in [&exact, &modular] {
+                let [fast, matrix] = calculators();
+                assert_eq!(
+                    run(fast.as_ref(), 20_000, opts).unwrap(),
+                    run(matrix.as_ref(), 20_000, opts).unwrap(),
+                    "{sequence:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn negated_at_matches_backward_recurrence() {
+        for (p, q) in [(1, -1), (3, -1), (-2, -1), (3, 1), (0, 1)] {
+            // x_{k} = (P·x_{k+1} − x_{k+2}) / Q, exact for Q = ±1.
+            let (u, v) = recurrence(p, q, 30);
+            let (p_big, q_big) = (BigInt::from(p), BigInt::from(q));
+            for (sequence, terms) in [
+                (Sequence::LucasU { p, q }, &u),
+                (Sequence::LucasV { p, q }, &v),
+            ] {
+                let (mut next, mut after) = (terms[0].clone(), terms[1].clone());
+                for (n, magnitude) in (1..=30u64).zip(&terms[1..]) {
+                    let term = (&p_big * &next - &after) / &q_big;
+                    let expected = if sequence.negated_at(n).unwrap() {
+                        -magnitude
+                    } else {
+                        magnitude.clone()
+                    };
+                    assert_eq!(term, expected, "{sequence:?} at −{n}");
+                    (next, after) = (term, next);
+                }
+            }
+        }
+        assert_eq!(Sequence::Fibonacci.negated_at(2), Some(true));
+        assert_eq!(Sequence::Lucas.negated_at(3), Some(true));
+        assert_eq!(Sequence::LucasU { p: 3, q: 2 }.negated_at(1), None);
+        assert_eq!(Sequence::LucasV { p: 1, q: i64::MIN }.negated_at(1), None);
+    }
+
+    #[test]
+    fn negative_terms_need_a_modulus() {
+        let opts = Options {
+            sequence: Sequence::LucasU { p: 1, q: 1 },
+            ..Options::default()
+        };
+        for calc in calculators() {
+            assert!(matches!(
+                run(calc.as_ref(), 10, &opts),
+                Err(FibError::Config(_))
+            ));
+        }
+        assert!(Sequence::LucasV { p: 2, q: 1 }.is_non_negative());
+        assert!(!Sequence::LucasV { p: -1, q: -1 }.is_non_negative());
+        assert!(!Sequence::LucasU { p: 0, q: 1 }.is_non_negative());
+    }
+
+    #[test]
+    fn cancellation() {
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        let opts = Options {
+            sequence: Sequence::Lucas,
+            ..Options::default()
+        };
+        for calc in calculators() {
+            let result = calc.calculate_core(&cancel, &NoOpObserver::new(), 0, 1000, &opts);
+            assert!(matches!(result, Err(FibError::Cancelled)));
+        }
+    }
+}
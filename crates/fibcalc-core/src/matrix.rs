@@ -55,26 +55,30 @@ impl MatrixExponentiation {
         cancel: &CancellationToken,
         observer: &dyn ProgressObserver,
         calc_index: usize,
+        opts: &Options,
     ) -> Result<BigUint, FibError> {
         let num_bits = 64 - n.leading_zeros();
         let mut state = tl_acquire_state();
+        let fft = opts.fft_config();
 
         let frozen = observer.freeze();
 
         let result = (|| {
             // Binary exponentiation: square-and-multiply
             for i in (0..num_bits).rev() {
+                cancel.wait_while_paused();
                 if cancel.is_cancelled() {
                     return Err(FibError::Cancelled);
                 }
 
-                // Square the result
-                state.result.square_symmetric_into();
+                // Square the result. Powers of Q are symmetric, so the
+                // 3-multiplication symmetric square always beats the
+                // 7-multiplication Strassen-Winograd product here.
+                state.result.square_symmetric_into(&fft);
 
-                // Multiply by base if bit is set
+                // Multiply by Q if bit is set; this only adds entries
                 if (n >> i) & 1 == 1 {
-                    let base_ref = &state.base;
-                    state.result.multiply_symmetric_into(base_ref);
+                    state.result.multiply_by_q_into();
                 }
 
                 // Progress reporting
@@ -115,9 +119,9 @@ impl CoreCalculator for MatrixExponentiation {
         observer: &dyn ProgressObserver,
         calc_index: usize,
         n: u64,
-        _opts: &Options,
+        opts: &Options,
     ) -> Result<BigUint, FibError> {
-        let result = self.execute_matrix_loop(n, cancel, observer, calc_index)?;
+        let result = self.execute_matrix_loop(n, cancel, observer, calc_index, opts)?;
         observer.on_progress(&ProgressUpdate::done(calc_index, "MatrixExponentiation"));
         Ok(result)
     }
@@ -180,6 +184,27 @@ mod tests {
         assert!(matches!(result, Err(FibError::Cancelled)));
     }
 
+    #[test]
+    fn matrix_pause_blocks_until_resume() {
+        let cancel = CancellationToken::new();
+        cancel.pause_token().pause();
+        let worker = {
+            let cancel = cancel.clone();
+            std::thread::spawn(move || {
+                let calc = MatrixExponentiation::new();
+                let observer = NoOpObserver::new();
+                calc.calculate_core(&cancel, &observer, 0, 1000, &Options::default())
+            })
+        };
+
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        assert!(!worker.is_finished(), "calculation ran while paused");
+
+        cancel.pause_token().resume();
+        let result = worker.join().unwrap().unwrap();
+        assert_eq!(result, compute_fib(1000));
+    }
+
     #[test]
     fn thread_local_pool_reuse() {
         // First computation populates the thread-local pool
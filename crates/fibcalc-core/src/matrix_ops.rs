@@ -1,5 +1,9 @@
 //! Matrix multiplication operations including Strassen.
 
+use fibcalc_bigfft::FFTConfig;
+use num_bigint::{BigInt, BigUint, Sign};
+
+use crate::fft_wrappers::smart_multiply;
 use crate::matrix_types::Matrix;
 
 /// Multiply two 2x2 matrices exploiting Fibonacci symmetry (b == c).
@@ -22,35 +26,98 @@ pub fn matrix_square(m: &Matrix) -> Matrix {
     m.square_symmetric()
 }
 
-/// Multiply two 2x2 matrices using Strassen-like optimization.
+/// Multiply two 2x2 matrices with the Strassen-Winograd scheme.
+///
+/// Below `threshold` bits the symmetric product (5 multiplications) is
+/// used. Above it, the Winograd variant of Strassen's algorithm computes
+/// the product with 7 multiplications and 15 additions, without assuming
+/// either operand is symmetric. Element products go through
+/// [`smart_multiply`] under `fft`.
 ///
-/// For 2x2 Fibonacci matrices with the symmetry property (b == c),
-/// we can reduce the number of multiplications.
+/// Squarings of Q-powers should stay on [`Matrix::square_symmetric_into`]:
+/// 3 squarings and 1 product beat any general 2x2 scheme.
 #[must_use]
+#[allow(dead_code)] // General 2x2 products; the exponentiation loop only squares Q-powers
 #[allow(clippy::cast_possible_truncation)]
-#[allow(dead_code)] // TODO: Phase 2 — PRD T2.4 Strassen optimization
-pub fn matrix_multiply_strassen(a: &Matrix, b: &Matrix, threshold: usize) -> Matrix {
+pub fn matrix_multiply_strassen(
+    a: &Matrix,
+    b: &Matrix,
+    threshold: usize,
+    fft: &FFTConfig,
+) -> Matrix {
     let max_bits = a.a.bits().max(b.a.bits()) as usize;
 
     if max_bits < threshold {
-        return matrix_multiply(a, b);
+        let mut result = a.clone();
+        result.multiply_symmetric_into(b, fft);
+        return result;
+    }
+
+    let [a11, a12, a21, a22] = signed_entries(a);
+    let [b11, b12, b21, b22] = signed_entries(b);
+
+    let s1 = &a21 + &a22;
+    let s2 = &s1 - &a11;
+    let s3 = &a11 - &a21;
+    let s4 = &a12 - &s2;
+    let t1 = &b12 - &b11;
+    let t2 = &b22 - &t1;
+    let t3 = &b22 - &b12;
+    let t4 = &t2 - &b21;
+
+    let m1 = signed_multiply(&a11, &b11, fft);
+    let m2 = signed_multiply(&a12, &b21, fft);
+    let m3 = signed_multiply(&s4, &b22, fft);
+    let m4 = signed_multiply(&a22, &t4, fft);
+    let m5 = signed_multiply(&s1, &t1, fft);
+    let m6 = signed_multiply(&s2, &t2, fft);
+    let m7 = signed_multiply(&s3, &t3, fft);
+
+    let u2 = &m1 + &m6;
+    let u3 = &u2 + &m7;
+    let u4 = &u2 + &m5;
+
+    Matrix {
+        a: into_unsigned(m1 + m2),
+        b: into_unsigned(u4 + m3),
+        c: into_unsigned(&u3 - m4),
+        d: into_unsigned(u3 + m5),
     }
+}
 
-    // Strassen-like optimization for symmetric Fibonacci matrices
-    // Standard 2x2 Strassen uses 7 multiplications instead of 8
-    // For our specific case, we can exploit b==c symmetry
+/// Entries of `m` as signed integers in row-major order.
+fn signed_entries(m: &Matrix) -> [BigInt; 4] {
+    [
+        BigInt::from(m.a.clone()),
+        BigInt::from(m.b.clone()),
+        BigInt::from(m.c.clone()),
+        BigInt::from(m.d.clone()),
+    ]
+}
 
-    // P2 optimization: full Strassen with recursive subdivision would reduce
-    // multiplications from 5 to ~4.7 for large operands, but the symmetric
-    // multiply already exploits Fibonacci Q-matrix structure (5 muls vs 8).
-    // Current fallback is correct and performant for all tested input sizes.
-    matrix_multiply(a, b)
+/// Signed product built on the unsigned multiplication dispatcher.
+fn signed_multiply(x: &BigInt, y: &BigInt, fft: &FFTConfig) -> BigInt {
+    let magnitude = smart_multiply(x.magnitude(), y.magnitude(), fft);
+    BigInt::from_biguint(x.sign() * y.sign(), magnitude)
+}
+
+/// Convert a product entry back to unsigned form.
+///
+/// Products of non-negative matrices are non-negative, so the Winograd
+/// intermediates always cancel out to a value that fits a `BigUint`.
+fn into_unsigned(x: BigInt) -> BigUint {
+    let (sign, magnitude) = x.into_parts();
+    assert!(
+        sign != Sign::Minus,
+        "Strassen-Winograd entry of non-negative matrices is non-negative"
+    );
+    magnitude
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use num_bigint::BigUint;
+    use crate::constants::DEFAULT_FFT_THRESHOLD;
 
     #[test]
     fn multiply_identity() {
@@ -141,7 +208,12 @@ mod tests {
         let q = Matrix::fibonacci_q();
         let q2_standard = matrix_multiply(&q, &q);
         // Threshold very high -> should fall through to standard multiply
-        let q2_strassen = matrix_multiply_strassen(&q, &q, 1_000_000);
+        let q2_strassen = matrix_multiply_strassen(
+            &q,
+            &q,
+            1_000_000,
+            &FFTConfig::with_threshold(DEFAULT_FFT_THRESHOLD),
+        );
         assert_eq!(q2_standard.a, q2_strassen.a);
         assert_eq!(q2_standard.b, q2_strassen.b);
         assert_eq!(q2_standard.c, q2_strassen.c);
@@ -151,10 +223,55 @@ mod tests {
     #[test]
     fn strassen_above_threshold() {
         let q = Matrix::fibonacci_q();
-        // Threshold 0 -> should take the "strassen" path (which currently falls back)
-        let q2 = matrix_multiply_strassen(&q, &q, 0);
+        // Threshold 0 -> should take the Strassen-Winograd path
+        let q2 =
+            matrix_multiply_strassen(&q, &q, 0, &FFTConfig::with_threshold(DEFAULT_FFT_THRESHOLD));
         assert_eq!(q2.a, BigUint::from(2u32));
         assert_eq!(q2.b, BigUint::from(1u32));
+        assert_eq!(q2.c, BigUint::from(1u32));
+        assert_eq!(q2.d, BigUint::from(1u32));
+    }
+
+    #[test]
+    fn strassen_matches_symmetric_on_large_entries() {
+        let q = Matrix::fibonacci_q();
+        let mut m = q.clone();
+        for _ in 0..10 {
+            m = matrix_square(&m);
+        }
+        m = matrix_multiply(&m, &q);
+        let expected = matrix_multiply(&m, &m);
+
+        for fft_threshold in [DEFAULT_FFT_THRESHOLD, 1] {
+            let got =
+                matrix_multiply_strassen(&m, &m, 0, &FFTConfig::with_threshold(fft_threshold));
+            assert_eq!(got.a, expected.a);
+            assert_eq!(got.b, expected.b);
+            assert_eq!(got.c, expected.c);
+            assert_eq!(got.d, expected.d);
+        }
+    }
+
+    #[test]
+    fn strassen_handles_non_symmetric_operands() {
+        let a = Matrix {
+            a: BigUint::from(3u32),
+            b: BigUint::from(7u32),
+            c: BigUint::from(2u32),
+            d: BigUint::from(5u32),
+        };
+        let b = Matrix {
+            a: BigUint::from(11u32),
+            b: BigUint::from(4u32),
+            c: BigUint::from(9u32),
+            d: BigUint::from(6u32),
+        };
+        let got =
+            matrix_multiply_strassen(&a, &b, 0, &FFTConfig::with_threshold(DEFAULT_FFT_THRESHOLD));
+        assert_eq!(got.a, BigUint::from(96u32));
+        assert_eq!(got.b, BigUint::from(54u32));
+        assert_eq!(got.c, BigUint::from(67u32));
+        assert_eq!(got.d, BigUint::from(38u32));
     }
 
     #[test]
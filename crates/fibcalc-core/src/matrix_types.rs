@@ -1,7 +1,10 @@
 //! Matrix types for the Matrix Exponentiation algorithm.
 
+use fibcalc_bigfft::FFTConfig;
 use num_bigint::BigUint;
 
+use crate::fft_wrappers::{smart_multiply, smart_square};
+
 /// 2x2 matrix of `BigUint` values.
 #[derive(Debug, Clone)]
 pub struct Matrix {
@@ -68,11 +71,12 @@ impl Matrix {
     /// In-place squaring for symmetric matrices.
     ///
     /// Mutates `self` to contain `self * self`, reusing buffer capacity.
-    pub fn square_symmetric_into(&mut self) {
-        let b_sq = &self.b * &self.b;
-        let new_a = &self.a * &self.a + &b_sq;
-        let new_b = &self.b * (&self.a + &self.d);
-        let new_d = &b_sq + &self.d * &self.d;
+    /// Element products run under `fft`.
+    pub fn square_symmetric_into(&mut self, fft: &FFTConfig) {
+        let b_sq = smart_square(&self.b, fft);
+        let new_a = smart_square(&self.a, fft) + &b_sq;
+        let new_b = smart_multiply(&self.b, &(&self.a + &self.d), fft);
+        let new_d = &b_sq + smart_square(&self.d, fft);
         self.a = new_a;
         self.c.clone_from(&new_b);
         self.b = new_b;
@@ -101,16 +105,28 @@ impl Matrix {
     /// In-place multiplication for symmetric matrices.
     ///
     /// Mutates `self` to contain `self * other`, reusing buffer capacity.
-    pub fn multiply_symmetric_into(&mut self, other: &Self) {
-        let b1_b2 = &self.b * &other.b;
-        let new_a = &self.a * &other.a + &b1_b2;
-        let new_b = &self.a * &other.b + &self.b * &other.d;
-        let new_d = &b1_b2 + &self.d * &other.d;
+    /// Element products run under `fft`.
+    pub fn multiply_symmetric_into(&mut self, other: &Self, fft: &FFTConfig) {
+        let b1_b2 = smart_multiply(&self.b, &other.b, fft);
+        let new_a = smart_multiply(&self.a, &other.a, fft) + &b1_b2;
+        let new_b = smart_multiply(&self.a, &other.b, fft) + smart_multiply(&self.b, &other.d, fft);
+        let new_d = &b1_b2 + smart_multiply(&self.d, &other.d, fft);
         self.a = new_a;
         self.c.clone_from(&new_b);
         self.b = new_b;
         self.d = new_d;
     }
+
+    /// In-place multiplication by the Fibonacci Q matrix.
+    ///
+    /// `[[a,b],[c,d]] * Q = [[a+b, a], [c+d, c]]`, so the step needs two
+    /// additions and no multiplications.
+    pub fn multiply_by_q_into(&mut self) {
+        self.b += &self.a;
+        std::mem::swap(&mut self.a, &mut self.b);
+        self.d += &self.c;
+        std::mem::swap(&mut self.c, &mut self.d);
+    }
 }
 
 /// State for matrix exponentiation computation.
@@ -145,6 +161,7 @@ impl Default for MatrixState {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::constants::DEFAULT_FFT_THRESHOLD;
 
     #[test]
     fn identity_matrix() {
@@ -166,7 +183,7 @@ mod tests {
         let q = Matrix::fibonacci_q();
         let expected = q.square_symmetric();
         let mut m = q.clone();
-        m.square_symmetric_into();
+        m.square_symmetric_into(&FFTConfig::with_threshold(DEFAULT_FFT_THRESHOLD));
         assert_eq!(m.a, expected.a);
         assert_eq!(m.b, expected.b);
         assert_eq!(m.d, expected.d);
@@ -178,12 +195,48 @@ mod tests {
         let q2 = q.square_symmetric();
         let expected = q2.multiply_symmetric(&q);
         let mut m = q2.clone();
-        m.multiply_symmetric_into(&q);
+        m.multiply_symmetric_into(&q, &FFTConfig::with_threshold(DEFAULT_FFT_THRESHOLD));
         assert_eq!(m.a, expected.a);
         assert_eq!(m.b, expected.b);
         assert_eq!(m.d, expected.d);
     }
 
+    #[test]
+    fn multiply_by_q_into_matches_product() {
+        let mut m = Matrix::fibonacci_q();
+        for _ in 0..6 {
+            m.square_symmetric_into(&FFTConfig::with_threshold(DEFAULT_FFT_THRESHOLD));
+        }
+        let expected = m.multiply_symmetric(&Matrix::fibonacci_q());
+        m.multiply_by_q_into();
+        assert_eq!(m.a, expected.a);
+        assert_eq!(m.b, expected.b);
+        assert_eq!(m.c, expected.c);
+        assert_eq!(m.d, expected.d);
+    }
+
+    #[test]
+    fn in_place_ops_match_with_fft_elements() {
+        let mut m = Matrix::fibonacci_q();
+        for _ in 0..8 {
+            m.square_symmetric_into(&FFTConfig::with_threshold(DEFAULT_FFT_THRESHOLD));
+        }
+        let expected_sq = m.square_symmetric();
+        let expected_mul = m.multiply_symmetric(&Matrix::fibonacci_q());
+
+        let mut sq = m.clone();
+        sq.square_symmetric_into(&FFTConfig::with_threshold(1));
+        assert_eq!(sq.a, expected_sq.a);
+        assert_eq!(sq.b, expected_sq.b);
+        assert_eq!(sq.d, expected_sq.d);
+
+        let mut mul = m.clone();
+        mul.multiply_symmetric_into(&Matrix::fibonacci_q(), &FFTConfig::with_threshold(1));
+        assert_eq!(mul.a, expected_mul.a);
+        assert_eq!(mul.b, expected_mul.b);
+        assert_eq!(mul.d, expected_mul.d);
+    }
+
     #[test]
     fn matrix_state_new() {
         let state = MatrixState::new();
@@ -7,23 +7,32 @@ pub struct MemoryEstimate {
     pub result_bytes: usize,
     /// Estimated memory for temporaries (in bytes).
     pub temp_bytes: usize,
+    /// Part of `temp_bytes` taken by FFT transforms (in bytes).
+    pub fft_bytes: usize,
     /// Total estimated memory (in bytes).
     pub total_bytes: usize,
 }
 
 impl MemoryEstimate {
-    /// Bit threshold above which FFT multiplication is used inside
-    /// `fibcalc-bigfft`. Kept in sync with `FFT_BIT_THRESHOLD` in `fft.rs`.
-    const FFT_BIT_THRESHOLD: usize = 10_000;
-
     /// Estimate memory usage for computing F(n).
+    ///
+    /// Assumes FFT multiplication kicks in at the `fibcalc-bigfft` default
+    /// threshold; use [`Self::estimate_with_fft_threshold`] when the run's
+    /// configured threshold is known.
+    #[must_use]
+    pub fn estimate(n: u64) -> Self {
+        Self::estimate_with_fft_threshold(n, fibcalc_bigfft::FFTConfig::DEFAULT_THRESHOLD_BITS)
+    }
+
+    /// Estimate memory usage for computing F(n) when FFT multiplication is
+    /// used for operands of at least `fft_threshold` bits.
     #[must_use]
     #[allow(
         clippy::cast_possible_truncation,
         clippy::cast_sign_loss,
         clippy::cast_precision_loss
     )]
-    pub fn estimate(n: u64) -> Self {
+    pub fn estimate_with_fft_threshold(n: u64, fft_threshold: usize) -> Self {
         // F(n) has approximately n * log2(phi) / 8 bytes
         // phi = (1 + sqrt(5)) / 2, log2(phi) ≈ 0.6942
         let result_bits = (n as f64 * 0.6942).ceil() as usize;
@@ -34,13 +43,17 @@ impl MemoryEstimate {
 
         // When the result is large enough, FFT multiplication is used and
         // requires additional working memory for polynomial transforms.
-        if result_bits >= Self::FFT_BIT_THRESHOLD {
-            temp_bytes += fibcalc_bigfft::estimate_fft_memory(result_bits, result_bits);
-        }
+        let fft_bytes = if result_bits >= fft_threshold {
+            fibcalc_bigfft::estimate_fft_memory(result_bits, result_bits)
+        } else {
+            0
+        };
+        temp_bytes += fft_bytes;
 
         Self {
             result_bytes,
             temp_bytes,
+            fft_bytes,
             total_bytes: result_bytes + temp_bytes,
         }
     }
@@ -55,6 +68,16 @@ impl MemoryEstimate {
             Some(l) => self.total_bytes <= l,
         }
     }
+
+    /// Memory left for FFT transforms under `limit`, or `None` if the
+    /// result and non-FFT temporaries alone exceed it.
+    ///
+    /// FFT products can be made to fit any remaining budget (smaller
+    /// plans, then segmented products), so only the rest must fit.
+    #[must_use]
+    pub fn fft_budget(&self, limit: usize) -> Option<usize> {
+        limit.checked_sub(self.total_bytes - self.fft_bytes)
+    }
 }
 
 /// Parse a memory limit string (e.g., "8G", "512M", "1024K").
@@ -127,7 +150,7 @@ mod tests {
 
     #[test]
     fn estimate_includes_fft_for_large_n() {
-        // n = 100_000 gives ~69_420 result bits, well above FFT_BIT_THRESHOLD.
+        // n = 100_000 gives ~69_420 result bits, well above the bigfft default threshold.
         let large = MemoryEstimate::estimate(100_000);
         // Without FFT overhead, temp_bytes would be exactly 5x result_bytes.
         let base_temp = large.result_bytes * 5;
@@ -138,7 +161,7 @@ mod tests {
             base_temp
         );
 
-        // n = 10 gives ~7 result bits, well below FFT_BIT_THRESHOLD.
+        // n = 10 gives ~7 result bits, well below the bigfft default threshold.
         let small = MemoryEstimate::estimate(10);
         let small_base_temp = small.result_bytes * 5;
         assert_eq!(
@@ -146,4 +169,23 @@ mod tests {
             "Small n should NOT include FFT memory overhead"
         );
     }
+
+    #[test]
+    fn estimate_honors_fft_threshold() {
+        let with_fft = MemoryEstimate::estimate_with_fft_threshold(100_000, 1_000);
+        let without_fft = MemoryEstimate::estimate_with_fft_threshold(100_000, usize::MAX);
+        assert_eq!(with_fft.result_bytes, without_fft.result_bytes);
+        assert_eq!(without_fft.temp_bytes, without_fft.result_bytes * 5);
+        assert!(with_fft.temp_bytes > without_fft.temp_bytes);
+    }
+
+    #[test]
+    fn fft_budget_excludes_fft_workspace() {
+        let est = MemoryEstimate::estimate_with_fft_threshold(100_000, 1_000);
+        assert!(est.fft_bytes > 0);
+        let base = est.total_bytes - est.fft_bytes;
+        assert_eq!(est.fft_budget(base - 1), None);
+        assert_eq!(est.fft_budget(base), Some(0));
+        assert_eq!(est.fft_budget(est.total_bytes), Some(est.fft_bytes));
+    }
 }
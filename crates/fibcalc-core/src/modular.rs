@@ -1,19 +1,137 @@
-//! Modular Fast Doubling for --last-digits mode.
+//! Modular Fast Doubling and matrix exponentiation for --last-digits mode.
 //!
 //! Computes F(n) mod 10^k using modular arithmetic throughout,
-//! avoiding the need to compute the full number.
+//! avoiding the need to compute the full number. Moduli of at least the
+//! FFT threshold are reduced with a precomputed Barrett reciprocal over
+//! FFT products instead of num-bigint division. Moduli below 2^64 use
+//! word-sized arithmetic with u128 products.
+//!
+//! The index may itself be a `BigUint`: the loops only walk its bits, so
+//! F(n) mod m stays cheap for indices far beyond `u64`. The calculators
+//! first reduce n modulo the Pisano period π(m) when m factors easily; see
+//! [`crate::pisano`].
 
+use fibcalc_bigfft::{BarrettModulus, FFTConfig};
 use num_bigint::BigUint;
-use num_traits::{One, Zero};
+use num_traits::{One, ToPrimitive, Zero};
 
 use crate::calculator::{CoreCalculator, FibError};
+use crate::constants::DEFAULT_FFT_THRESHOLD;
 use crate::observer::ProgressObserver;
 use crate::options::Options;
+use crate::pisano::reduce_index;
 use crate::progress::{CancellationToken, ProgressUpdate};
 
 /// Fast Doubling with modular arithmetic for computing last K digits.
 pub struct FastDoublingMod;
 
+/// Matrix exponentiation with modular arithmetic for computing last K digits.
+///
+/// Cross-checks [`FastDoublingMod`] when `--algo all` runs in last-digits
+/// mode.
+pub struct MatrixExponentiationMod;
+
+/// Modular products of the doubling loop.
+pub(crate) enum ModProducts<'a> {
+    /// num-bigint product, then `%`.
+    Remainder(&'a BigUint),
+    /// FFT product, then Barrett reduction.
+    Barrett(BarrettModulus),
+}
+
+impl<'a> ModProducts<'a> {
+    /// Barrett reduction under `fft` once `modulus` reaches its FFT
+    /// threshold.
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) fn new(modulus: &'a BigUint, fft: &FFTConfig) -> Self {
+        if fft.uses_fft(modulus.bits() as usize) {
+            Self::Barrett(BarrettModulus::with_config(modulus.clone(), *fft))
+        } else {
+            Self::Remainder(modulus)
+        }
+    }
+
+    pub(crate) fn mul(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        match self {
+            Self::Remainder(modulus) => (a * b) % *modulus,
+            Self::Barrett(barrett) => barrett.mulmod(a, b),
+        }
+    }
+
+    pub(crate) fn sqr(&self, a: &BigUint) -> BigUint {
+        match self {
+            Self::Remainder(modulus) => (a * a) % *modulus,
+            Self::Barrett(barrett) => barrett.sqrmod(a),
+        }
+    }
+}
+
+/// A modulus below 2^64, with products widened to u128.
+///
+/// Both loops keep their values reduced, so every sum fits in u128 before
+/// its reduction.
+#[derive(Debug, Clone, Copy)]
+struct WordModulus(u64);
+
+impl WordModulus {
+    #[allow(clippy::cast_possible_truncation)]
+    fn reduce(self, x: u128) -> u64 {
+        (x % u128::from(self.0)) as u64
+    }
+
+    fn mul(self, a: u64, b: u64) -> u64 {
+        self.reduce(u128::from(a) * u128::from(b))
+    }
+
+    /// `a*a + b*b mod m`.
+    fn sqr_add_sqr(self, a: u64, b: u64) -> u64 {
+        self.reduce(u128::from(a) * u128::from(a) + u128::from(self.mul(b, b)))
+    }
+
+    fn add(self, a: u64, b: u64) -> u64 {
+        self.reduce(u128::from(a) + u128::from(b))
+    }
+
+    /// F(n) mod m by fast doubling.
+    fn fast_doubling(self, n: &BigUint, cancel: &CancellationToken) -> Result<u64, FibError> {
+        let (mut fk, mut fk1) = (0, self.reduce(1));
+        for i in (0..n.bits()).rev() {
+            cancel.wait_while_paused();
+            if cancel.is_cancelled() {
+                return Err(FibError::Cancelled);
+            }
+            // F(2k) = F(k) * (2F(k+1) - F(k)), F(2k+1) = F(k)^2 + F(k+1)^2
+            let twice = self.reduce(2 * u128::from(fk1) + u128::from(self.0 - fk));
+            let f2k = self.mul(fk, twice);
+            fk1 = self.sqr_add_sqr(fk, fk1);
+            fk = f2k;
+            if n.bit(i) {
+                let sum = self.add(fk, fk1);
+                fk = std::mem::replace(&mut fk1, sum);
+            }
+        }
+        Ok(fk)
+    }
+
+    /// F(n) mod m as the off-diagonal entry of `[[1,1],[1,0]]^n`.
+    fn matrix_power(self, n: &BigUint, cancel: &CancellationToken) -> Result<u64, FibError> {
+        let (mut a, mut b, mut d) = (self.reduce(1), 0, self.reduce(1));
+        for i in (0..n.bits()).rev() {
+            cancel.wait_while_paused();
+            if cancel.is_cancelled() {
+                return Err(FibError::Cancelled);
+            }
+            let new_b = self.mul(b, self.add(a, d));
+            (a, d) = (self.sqr_add_sqr(a, b), self.sqr_add_sqr(d, b));
+            b = new_b;
+            if n.bit(i) {
+                (a, b, d) = (self.add(a, b), a, b);
+            }
+        }
+        Ok(b)
+    }
+}
+
 impl FastDoublingMod {
     /// Create a new modular Fast Doubling calculator.
     #[must_use]
@@ -32,26 +150,82 @@ impl FastDoublingMod {
         cancel: &CancellationToken,
         observer: &dyn ProgressObserver,
         calc_index: usize,
+    ) -> Result<BigUint, FibError> {
+        Self::fibonacci_mod_with(
+            n,
+            modulus,
+            &FFTConfig::with_threshold(DEFAULT_FFT_THRESHOLD),
+            cancel,
+            observer,
+            calc_index,
+        )
+    }
+
+    /// Compute F(n) mod m, with Barrett reduction over FFT products once
+    /// the modulus reaches the FFT threshold of `fft`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FibError::Config` if modulus is zero, or `FibError::Cancelled` on cancellation.
+    pub fn fibonacci_mod_with(
+        n: u64,
+        modulus: &BigUint,
+        fft: &FFTConfig,
+        cancel: &CancellationToken,
+        observer: &dyn ProgressObserver,
+        calc_index: usize,
+    ) -> Result<BigUint, FibError> {
+        Self::fibonacci_mod_big(
+            &BigUint::from(n),
+            modulus,
+            fft,
+            cancel,
+            observer,
+            calc_index,
+        )
+    }
+
+    /// Compute F(n) mod m for an index of any size.
+    ///
+    /// Moduli below 2^64 run on machine words; larger ones reduce like
+    /// [`FastDoublingMod::fibonacci_mod_with`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `FibError::Config` if modulus is zero, or `FibError::Cancelled` on cancellation.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn fibonacci_mod_big(
+        n: &BigUint,
+        modulus: &BigUint,
+        fft: &FFTConfig,
+        cancel: &CancellationToken,
+        observer: &dyn ProgressObserver,
+        calc_index: usize,
     ) -> Result<BigUint, FibError> {
         if modulus.is_zero() {
             return Err(FibError::Config("modulus cannot be zero".into()));
         }
+        if let Some(m) = modulus.to_u64() {
+            return WordModulus(m).fast_doubling(n, cancel).map(BigUint::from);
+        }
 
-        let num_bits = 64 - n.leading_zeros();
+        let products = ModProducts::new(modulus, fft);
+        let num_bits = n.bits();
         let mut fk = BigUint::zero();
         let mut fk1 = BigUint::one();
 
         let frozen = observer.freeze();
 
         for i in (0..num_bits).rev() {
+            cancel.wait_while_paused();
             if cancel.is_cancelled() {
                 return Err(FibError::Cancelled);
             }
 
             // Modular doubling step
-            let fk_sq = (&fk * &fk) % modulus;
-            let fk1_sq = (&fk1 * &fk1) % modulus;
-            let cross = (&fk * &fk1) % modulus;
+            let fk_sq = products.sqr(&fk);
+            let fk1_sq = products.sqr(&fk1);
+            let cross = products.mul(&fk, &fk1);
 
             // F(2k) = (2*cross - fk_sq) mod m
             let double_cross = (&cross << 1) % modulus;
@@ -68,21 +242,21 @@ impl FastDoublingMod {
             fk1 = f2k1;
 
             // Conditional addition (modular)
-            if (n >> i) & 1 == 1 {
+            if n.bit(i) {
                 let sum = (&fk + &fk1) % modulus;
                 fk = std::mem::replace(&mut fk1, sum);
             }
 
             // Progress
-            let progress = 1.0 - (f64::from(i) / f64::from(num_bits));
+            let progress = 1.0 - (i as f64 / num_bits as f64);
             if frozen.should_report(progress) {
                 frozen.update(progress);
                 observer.on_progress(&ProgressUpdate::new(
                     calc_index,
                     "FastDoublingMod",
                     progress,
-                    u64::from(num_bits - i),
-                    u64::from(num_bits),
+                    num_bits - i,
+                    num_bits,
                 ));
             }
         }
@@ -106,17 +280,38 @@ impl CoreCalculator for FastDoublingMod {
         n: u64,
         opts: &Options,
     ) -> Result<BigUint, FibError> {
-        let digits = match opts.last_digits {
-            Some(d) if d > 0 => d,
-            _ => {
-                return Err(FibError::Config(
-                    "FastDoublingMod requires last_digits > 0".into(),
-                ));
-            }
-        };
+        let modulus = result_modulus(opts, "FastDoublingMod")?;
+        let n = reduced_index(&BigUint::from(n), &modulus, opts, cancel)?;
+        let result = Self::fibonacci_mod_big(
+            &n,
+            &modulus,
+            &opts.fft_config(),
+            cancel,
+            observer,
+            calc_index,
+        )?;
+        observer.on_progress(&ProgressUpdate::done(calc_index, "FastDoublingMod"));
+        Ok(result)
+    }
 
-        let modulus = BigUint::from(10u32).pow(digits);
-        let result = Self::fibonacci_mod(n, &modulus, cancel, observer, calc_index)?;
+    fn calculate_big(
+        &self,
+        cancel: &CancellationToken,
+        observer: &dyn ProgressObserver,
+        calc_index: usize,
+        n: &BigUint,
+        opts: &Options,
+    ) -> Result<BigUint, FibError> {
+        let modulus = result_modulus(opts, "FastDoublingMod")?;
+        let n = reduced_index(n, &modulus, opts, cancel)?;
+        let result = Self::fibonacci_mod_big(
+            &n,
+            &modulus,
+            &opts.fft_config(),
+            cancel,
+            observer,
+            calc_index,
+        )?;
         observer.on_progress(&ProgressUpdate::done(calc_index, "FastDoublingMod"));
         Ok(result)
     }
@@ -126,6 +321,184 @@ impl CoreCalculator for FastDoublingMod {
     }
 }
 
+impl MatrixExponentiationMod {
+    /// Create a new modular matrix exponentiation calculator.
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Compute F(n) mod m by raising `[[1,1],[1,0]]` to the n-th power,
+    /// with Barrett reduction over FFT products once the modulus reaches
+    /// the FFT threshold of `fft`.
+    ///
+    /// The power is symmetric, so only `(a, b, d)` of `[[a,b],[b,d]]` are
+    /// kept; each squaring costs three modular squares and one product.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FibError::Config` if modulus is zero, or `FibError::Cancelled` on cancellation.
+    pub fn fibonacci_mod_with(
+        n: u64,
+        modulus: &BigUint,
+        fft: &FFTConfig,
+        cancel: &CancellationToken,
+        observer: &dyn ProgressObserver,
+        calc_index: usize,
+    ) -> Result<BigUint, FibError> {
+        Self::fibonacci_mod_big(
+            &BigUint::from(n),
+            modulus,
+            fft,
+            cancel,
+            observer,
+            calc_index,
+        )
+    }
+
+    /// Compute F(n) mod m for an index of any size, on machine words for
+    /// moduli below 2^64.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FibError::Config` if modulus is zero, or `FibError::Cancelled` on cancellation.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn fibonacci_mod_big(
+        n: &BigUint,
+        modulus: &BigUint,
+        fft: &FFTConfig,
+        cancel: &CancellationToken,
+        observer: &dyn ProgressObserver,
+        calc_index: usize,
+    ) -> Result<BigUint, FibError> {
+        if modulus.is_zero() {
+            return Err(FibError::Config("modulus cannot be zero".into()));
+        }
+        if let Some(m) = modulus.to_u64() {
+            return WordModulus(m).matrix_power(n, cancel).map(BigUint::from);
+        }
+
+        let products = ModProducts::new(modulus, fft);
+        let num_bits = n.bits();
+        // Identity matrix, reduced so that a modulus of 1 yields 0.
+        let mut a = BigUint::one() % modulus;
+        let mut b = BigUint::zero();
+        let mut d = a.clone();
+
+        let frozen = observer.freeze();
+
+        for i in (0..num_bits).rev() {
+            cancel.wait_while_paused();
+            if cancel.is_cancelled() {
+                return Err(FibError::Cancelled);
+            }
+
+            // [[a,b],[b,d]]^2 = [[a²+b², b(a+d)], [b(a+d), b²+d²]]
+            let b_sq = products.sqr(&b);
+            let new_b = products.mul(&b, &((&a + &d) % modulus));
+            a = (products.sqr(&a) + &b_sq) % modulus;
+            d = (products.sqr(&d) + b_sq) % modulus;
+            b = new_b;
+
+            // Multiply by [[1,1],[1,0]]: (a, b, d) -> (a+b, a, b)
+            if n.bit(i) {
+                let sum = (&a + &b) % modulus;
+                d = std::mem::replace(&mut b, std::mem::replace(&mut a, sum));
+            }
+
+            let progress = 1.0 - (i as f64 / num_bits as f64);
+            if frozen.should_report(progress) {
+                frozen.update(progress);
+                observer.on_progress(&ProgressUpdate::new(
+                    calc_index,
+                    "MatrixExponentiationMod",
+                    progress,
+                    num_bits - i,
+                    num_bits,
+                ));
+            }
+        }
+
+        Ok(b)
+    }
+}
+
+impl Default for MatrixExponentiationMod {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CoreCalculator for MatrixExponentiationMod {
+    fn calculate_core(
+        &self,
+        cancel: &CancellationToken,
+        observer: &dyn ProgressObserver,
+        calc_index: usize,
+        n: u64,
+        opts: &Options,
+    ) -> Result<BigUint, FibError> {
+        let modulus = result_modulus(opts, "MatrixExponentiationMod")?;
+        let n = reduced_index(&BigUint::from(n), &modulus, opts, cancel)?;
+        let result = Self::fibonacci_mod_big(
+            &n,
+            &modulus,
+            &opts.fft_config(),
+            cancel,
+            observer,
+            calc_index,
+        )?;
+        observer.on_progress(&ProgressUpdate::done(calc_index, "MatrixExponentiationMod"));
+        Ok(result)
+    }
+
+    fn calculate_big(
+        &self,
+        cancel: &CancellationToken,
+        observer: &dyn ProgressObserver,
+        calc_index: usize,
+        n: &BigUint,
+        opts: &Options,
+    ) -> Result<BigUint, FibError> {
+        let modulus = result_modulus(opts, "MatrixExponentiationMod")?;
+        let n = reduced_index(n, &modulus, opts, cancel)?;
+        let result = Self::fibonacci_mod_big(
+            &n,
+            &modulus,
+            &opts.fft_config(),
+            cancel,
+            observer,
+            calc_index,
+        )?;
+        observer.on_progress(&ProgressUpdate::done(calc_index, "MatrixExponentiationMod"));
+        Ok(result)
+    }
+
+    fn name(&self) -> &'static str {
+        "MatrixExponentiationMod"
+    }
+}
+
+/// `n` modulo `Options::pisano_period`, or modulo the period
+/// [`reduce_index`] finds for `modulus` when the run has none.
+fn reduced_index(
+    n: &BigUint,
+    modulus: &BigUint,
+    opts: &Options,
+    cancel: &CancellationToken,
+) -> Result<BigUint, FibError> {
+    match &opts.pisano_period {
+        Some(period) => Ok(n % period.as_ref()),
+        None => reduce_index(n, modulus, cancel),
+    }
+}
+
+/// The modulus of `opts`, from `--modulus` or `--last-digits`.
+fn result_modulus(opts: &Options, name: &str) -> Result<BigUint, FibError> {
+    opts.result_modulus()
+        .ok_or_else(|| FibError::Config(format!("{name} requires a modulus or last_digits > 0")))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -242,6 +615,59 @@ mod tests {
         assert_eq!(result, BigUint::one());
     }
 
+    #[test]
+    fn barrett_products_match_remainder() {
+        let cancel = CancellationToken::new();
+        let observer = NoOpObserver::new();
+        let moduli = [
+            BigUint::from(10u32).pow(3_000),
+            (BigUint::one() << 20_000u32) - BigUint::from(159u32),
+            BigUint::from(1_000_000_007u64),
+        ];
+        for modulus in &moduli {
+            for n in [0, 1, 2, 1_000, 123_457] {
+                let plain = FastDoublingMod::fibonacci_mod_with(
+                    n,
+                    modulus,
+                    &FFTConfig::with_threshold(usize::MAX),
+                    &cancel,
+                    &observer,
+                    0,
+                )
+                .unwrap();
+                let barrett = FastDoublingMod::fibonacci_mod_with(
+                    n,
+                    modulus,
+                    &FFTConfig::with_threshold(0),
+                    &cancel,
+                    &observer,
+                    0,
+                )
+                .unwrap();
+                assert_eq!(
+                    barrett,
+                    plain,
+                    "F({n}) mod a {}-bit modulus",
+                    modulus.bits()
+                );
+            }
+        }
+        // F(100_000) has 69_424 bits, far above the 9_966-bit modulus.
+        let (_, f) = crate::iterator::FibIterator::from_index(100_000)
+            .next()
+            .unwrap();
+        let result = FastDoublingMod::fibonacci_mod_with(
+            100_000,
+            &moduli[0],
+            &FFTConfig::with_threshold(0),
+            &cancel,
+            &observer,
+            0,
+        )
+        .unwrap();
+        assert_eq!(result, f % &moduli[0]);
+    }
+
     #[test]
     fn core_calculator_requires_last_digits() {
         let calc = FastDoublingMod::new();
@@ -279,6 +705,266 @@ mod tests {
         let _calc = FastDoublingMod::default();
     }
 
+    #[test]
+    fn matrix_mod_matches_fast_doubling() {
+        let cancel = CancellationToken::new();
+        let observer = NoOpObserver::new();
+        let moduli = [
+            BigUint::one(),
+            BigUint::from(10u32).pow(10),
+            BigUint::from(10u32).pow(3_000),
+        ];
+        for modulus in &moduli {
+            for n in [0, 1, 2, 3, 93, 1_000, 123_457] {
+                for threshold in [usize::MAX, 0] {
+                    let matrix = MatrixExponentiationMod::fibonacci_mod_with(
+                        n,
+                        modulus,
+                        &FFTConfig::with_threshold(threshold),
+                        &cancel,
+                        &observer,
+                        0,
+                    )
+                    .unwrap();
+                    let doubling = FastDoublingMod::fibonacci_mod_with(
+                        n,
+                        modulus,
+                        &FFTConfig::with_threshold(threshold),
+                        &cancel,
+                        &observer,
+                        0,
+                    )
+                    .unwrap();
+                    assert_eq!(
+                        matrix,
+                        doubling,
+                        "F({n}) mod a {}-bit modulus",
+                        modulus.bits()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn matrix_mod_core_calculator() {
+        let calc = MatrixExponentiationMod::new();
+        let cancel = CancellationToken::new();
+        let observer = NoOpObserver::new();
+        assert!(matches!(
+            calc.calculate_core(&cancel, &observer, 0, 100, &Options::default()),
+            Err(FibError::Config(_))
+        ));
+        let opts = Options {
+            last_digits: Some(6),
+            ..Default::default()
+        };
+        let result = calc
+            .calculate_core(&cancel, &observer, 0, 100, &opts)
+            .unwrap();
+        assert_eq!(result, BigUint::from(915_075_u64));
+        assert_eq!(CoreCalculator::name(&calc), "MatrixExponentiationMod");
+    }
+
+    #[test]
+    fn last_digits_of_huge_index() {
+        let cancel = CancellationToken::new();
+        let observer = NoOpObserver::new();
+        let modulus = BigUint::from(10u32).pow(10);
+        // The Pisano period of 10^10 is 1.5 * 10^10.
+        let period = 15_000_000_000;
+        let n = 1_000_000_000_000;
+        for threshold in [usize::MAX, 0] {
+            let doubling = FastDoublingMod::fibonacci_mod_with(
+                n,
+                &modulus,
+                &FFTConfig::with_threshold(threshold),
+                &cancel,
+                &observer,
+                0,
+            )
+            .unwrap();
+            let reduced = FastDoublingMod::fibonacci_mod_with(
+                n % period,
+                &modulus,
+                &FFTConfig::with_threshold(threshold),
+                &cancel,
+                &observer,
+                0,
+            )
+            .unwrap();
+            assert_eq!(doubling, reduced);
+            let matrix = MatrixExponentiationMod::fibonacci_mod_with(
+                n,
+                &modulus,
+                &FFTConfig::with_threshold(threshold),
+                &cancel,
+                &observer,
+                0,
+            )
+            .unwrap();
+            assert_eq!(matrix, doubling);
+        }
+    }
+
+    #[test]
+    fn word_sized_moduli_match_remainder() {
+        let cancel = CancellationToken::new();
+        let observer = NoOpObserver::new();
+        let (_, f) = crate::iterator::FibIterator::from_index(1_000)
+            .next()
+            .unwrap();
+        for m in [1, 2, 1_000_000_007, (1 << 61) - 1, u64::MAX - 58, u64::MAX] {
+            let modulus = BigUint::from(m);
+            let expected = &f % &modulus;
+            let doubling =
+                FastDoublingMod::fibonacci_mod(1_000, &modulus, &cancel, &observer, 0).unwrap();
+            let matrix = MatrixExponentiationMod::fibonacci_mod_with(
+                1_000,
+                &modulus,
+                &FFTConfig::with_threshold(DEFAULT_FFT_THRESHOLD),
+                &cancel,
+                &observer,
+                0,
+            )
+            .unwrap();
+            assert_eq!(doubling, expected, "F(1000) mod {m}");
+            assert_eq!(matrix, expected, "F(1000) mod {m}");
+        }
+        // The first modulus past the word-sized path.
+        let modulus = BigUint::from(u64::MAX) + 2u32;
+        let result =
+            FastDoublingMod::fibonacci_mod(1_000, &modulus, &cancel, &observer, 0).unwrap();
+        assert_eq!(result, &f % &modulus);
+    }
+
+    #[test]
+    fn big_index_matches_pisano_reduction() {
+        let cancel = CancellationToken::new();
+        let observer = NoOpObserver::new();
+        // 10^10 (word-sized) and 10^20 (BigUint) have Pisano periods
+        // 1.5 * 10^10 and 1.5 * 10^20.
+        for digits in [10, 20] {
+            let modulus = BigUint::from(10u32).pow(digits);
+            let period = &modulus * 3u32 / 2u32;
+            let n = BigUint::from(10u32).pow(40) + 12_345u32;
+            let reduced = &n % &period;
+            let expected = FastDoublingMod::fibonacci_mod_big(
+                &reduced,
+                &modulus,
+                &FFTConfig::with_threshold(DEFAULT_FFT_THRESHOLD),
+                &cancel,
+                &observer,
+                0,
+            )
+            .unwrap();
+            for result in [
+                FastDoublingMod::fibonacci_mod_big(
+                    &n,
+                    &modulus,
+                    &FFTConfig::with_threshold(DEFAULT_FFT_THRESHOLD),
+                    &cancel,
+                    &observer,
+                    0,
+                ),
+                MatrixExponentiationMod::fibonacci_mod_big(
+                    &n,
+                    &modulus,
+                    &FFTConfig::with_threshold(DEFAULT_FFT_THRESHOLD),
+                    &cancel,
+                    &observer,
+                    0,
+                ),
+            ] {
+                assert_eq!(result.unwrap(), expected, "mod 10^{digits}");
+            }
+        }
+    }
+
+    #[test]
+    fn calculators_take_big_indices() {
+        use crate::calculator::{Calculator, FibCalculator};
+        use crate::fastdoubling::OptimizedFastDoubling;
+        use std::sync::Arc;
+
+        // π(10) = 60 and 10^40 ≡ 40 (mod 60), so F(10^40) ≡ F(40) ≡ 5.
+        let opts = Options {
+            modulus: Some(Arc::new(BigUint::from(10u32))),
+            ..Default::default()
+        };
+        let n = BigUint::from(10u32).pow(40);
+        let cancel = CancellationToken::new();
+        let observer = NoOpObserver::new();
+        for calc in [
+            FibCalculator::new(Arc::new(FastDoublingMod::new())),
+            FibCalculator::new(Arc::new(MatrixExponentiationMod::new())),
+        ] {
+            let result = calc.calculate_big(&cancel, &observer, 0, &n, &opts);
+            assert_eq!(result.unwrap(), BigUint::from(5u32), "{}", calc.name());
+        }
+        let calc = FibCalculator::new(Arc::new(OptimizedFastDoubling::new()));
+        assert!(matches!(
+            calc.calculate_big(&cancel, &observer, 0, &n, &opts),
+            Err(FibError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn calculator_reduces_small_indices() {
+        use crate::calculator::{Calculator, FibCalculator};
+        use std::sync::Arc;
+
+        let calc = FibCalculator::new(Arc::new(FastDoublingMod::new()));
+        let opts = Options {
+            modulus: Some(Arc::new(BigUint::from(7u32))),
+            ..Default::default()
+        };
+        let result = calc
+            .calculate(
+                &CancellationToken::new(),
+                &NoOpObserver::new(),
+                0,
+                10,
+                &opts,
+            )
+            .unwrap();
+        assert_eq!(result, BigUint::from(55u32 % 7));
+    }
+
+    #[test]
+    fn calculators_reduce_by_pisano_period() {
+        use std::sync::Arc;
+
+        // π(10) = 60 and 10^18 ≡ 40 (mod 60), so F(10^18) ≡ F(40) ≡ 5.
+        let opts = Options {
+            modulus: Some(Arc::new(BigUint::from(10u32))),
+            ..Default::default()
+        };
+        let n = 1_000_000_000_000_000_000;
+        let cancel = CancellationToken::new();
+        let observer = NoOpObserver::new();
+        let calcs: [&dyn CoreCalculator; 2] =
+            [&FastDoublingMod::new(), &MatrixExponentiationMod::new()];
+        for calc in calcs {
+            let result = calc
+                .calculate_core(&cancel, &observer, 0, n, &opts)
+                .unwrap();
+            assert_eq!(result, BigUint::from(5u32), "{}", calc.name());
+        }
+
+        // A period found by the run is used as given.
+        let opts = Options {
+            pisano_period: Some(Arc::new(BigUint::from(60u32))),
+            ..opts
+        };
+        for calc in calcs {
+            let result = calc
+                .calculate_core(&cancel, &observer, 0, n, &opts)
+                .unwrap();
+            assert_eq!(result, BigUint::from(5u32), "{}", calc.name());
+        }
+    }
+
     #[test]
     fn modular_known_values_table() {
         let cancel = CancellationToken::new();
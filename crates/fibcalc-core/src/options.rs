@@ -1,8 +1,16 @@
 //! Calculation options and configuration.
 
+use std::sync::Arc;
+
+use fibcalc_bigfft::{FFTConfig, DEFAULT_NTT_THRESHOLD_BITS};
+use num_bigint::BigUint;
+
+use crate::checkpoint::{Checkpoint, CheckpointConfig};
 use crate::constants::{
     DEFAULT_FFT_THRESHOLD, DEFAULT_PARALLEL_THRESHOLD, DEFAULT_STRASSEN_THRESHOLD,
+    DEFAULT_TOOM3_THRESHOLD, DEFAULT_TOOM4_THRESHOLD,
 };
+use crate::lucas::Sequence;
 
 /// Options for Fibonacci calculation.
 ///
@@ -27,10 +35,33 @@ pub struct Options {
     pub fft_threshold: usize,
     /// Threshold (in bits) for Strassen multiplication.
     pub strassen_threshold: usize,
+    /// Threshold (in bits) for Toom-3 multiplication (`usize::MAX` = never).
+    pub toom3_threshold: usize,
+    /// Threshold (in bits) for Toom-4 multiplication (`usize::MAX` = never).
+    pub toom4_threshold: usize,
+    /// Threshold (in bits) at which FFT products switch from Fermat to NTT
+    /// (`0` = always, `usize::MAX` = never).
+    pub ntt_threshold: usize,
     /// Number of last digits to compute (`None` = full number).
     pub last_digits: Option<u32>,
+    /// Compute F(n) mod this modulus instead of F(n) (`None` = full number).
+    pub modulus: Option<Arc<BigUint>>,
+    /// Pisano period of the modulus, found once per run and shared by the
+    /// modular calculators (`None` = each looks for it itself).
+    pub pisano_period: Option<Arc<BigUint>>,
+    /// Sequence to compute: F(n) by default, or L(n), `U_n(P, Q)`, `V_n(P, Q)`.
+    pub sequence: Sequence,
     /// Memory limit in bytes (`None` = unlimited).
     pub memory_limit: Option<usize>,
+    /// Working-memory budget of one FFT product in bytes (`usize::MAX` =
+    /// unlimited); what `memory_limit` leaves after the result.
+    pub fft_memory_limit: usize,
+    /// Whether to adapt thresholds from per-iteration timings during a run.
+    pub dynamic_thresholds: bool,
+    /// Where and how often Fast Doubling loops write checkpoints (`None` = never).
+    pub checkpoint: Option<CheckpointConfig>,
+    /// Checkpoint to resume from instead of starting at F(0), F(1).
+    pub resume: Option<Arc<Checkpoint>>,
     /// Whether to show verbose output.
     pub verbose: bool,
     /// Whether to show detailed output.
@@ -43,8 +74,18 @@ impl Default for Options {
             parallel_threshold: DEFAULT_PARALLEL_THRESHOLD,
             fft_threshold: DEFAULT_FFT_THRESHOLD,
             strassen_threshold: DEFAULT_STRASSEN_THRESHOLD,
+            toom3_threshold: DEFAULT_TOOM3_THRESHOLD,
+            toom4_threshold: DEFAULT_TOOM4_THRESHOLD,
+            ntt_threshold: DEFAULT_NTT_THRESHOLD_BITS,
             last_digits: None,
+            modulus: None,
+            pisano_period: None,
+            sequence: Sequence::Fibonacci,
             memory_limit: None,
+            fft_memory_limit: usize::MAX,
+            dynamic_thresholds: false,
+            checkpoint: None,
+            resume: None,
             verbose: false,
             details: false,
         }
@@ -64,8 +105,42 @@ impl Options {
         if self.strassen_threshold == 0 {
             self.strassen_threshold = DEFAULT_STRASSEN_THRESHOLD;
         }
+        if self.toom3_threshold == 0 {
+            self.toom3_threshold = DEFAULT_TOOM3_THRESHOLD;
+        }
+        if self.toom4_threshold == 0 {
+            self.toom4_threshold = DEFAULT_TOOM4_THRESHOLD;
+        }
         self
     }
+
+    /// FFT configuration of this run: its FFT threshold, NTT crossover and
+    /// FFT memory budget.
+    #[must_use]
+    pub fn fft_config(&self) -> FFTConfig {
+        FFTConfig::with_threshold(self.fft_threshold)
+            .with_ntt_threshold(self.ntt_threshold)
+            .with_memory_limit(self.fft_memory_limit)
+    }
+
+    /// Modulus of a modular run: `modulus` if set, else `10^k` for
+    /// `last_digits = Some(k)` with `k > 0`.
+    #[must_use]
+    pub fn result_modulus(&self) -> Option<BigUint> {
+        if let Some(modulus) = &self.modulus {
+            return Some(BigUint::clone(modulus));
+        }
+        match self.last_digits {
+            Some(digits) if digits > 0 => Some(BigUint::from(10u32).pow(digits)),
+            _ => None,
+        }
+    }
+
+    /// Whether only F(n) modulo [`Options::result_modulus`] is computed.
+    #[must_use]
+    pub fn is_modular(&self) -> bool {
+        self.modulus.is_some() || matches!(self.last_digits, Some(k) if k > 0)
+    }
 }
 
 #[cfg(test)]
@@ -78,7 +153,47 @@ mod tests {
         assert_eq!(opts.parallel_threshold, DEFAULT_PARALLEL_THRESHOLD);
         assert_eq!(opts.fft_threshold, DEFAULT_FFT_THRESHOLD);
         assert_eq!(opts.strassen_threshold, DEFAULT_STRASSEN_THRESHOLD);
+        assert_eq!(opts.ntt_threshold, DEFAULT_NTT_THRESHOLD_BITS);
         assert!(opts.last_digits.is_none());
+        assert!(!opts.dynamic_thresholds);
+        assert!(!opts.is_modular());
+        assert!(opts.result_modulus().is_none());
+    }
+
+    #[test]
+    fn result_modulus_sources() {
+        let digits = Options {
+            last_digits: Some(3),
+            ..Default::default()
+        };
+        assert!(digits.is_modular());
+        assert_eq!(digits.result_modulus(), Some(BigUint::from(1000u32)));
+        let zero_digits = Options {
+            last_digits: Some(0),
+            ..Default::default()
+        };
+        assert!(!zero_digits.is_modular());
+        let modulus = Options {
+            modulus: Some(Arc::new(BigUint::from(97u32))),
+            ..Default::default()
+        };
+        assert!(modulus.is_modular());
+        assert_eq!(modulus.result_modulus(), Some(BigUint::from(97u32)));
+    }
+
+    #[test]
+    fn fft_config_carries_thresholds() {
+        let opts = Options {
+            fft_threshold: 1_000,
+            ntt_threshold: 50_000,
+            fft_memory_limit: 1 << 20,
+            ..Default::default()
+        };
+        let fft = opts.fft_config();
+        assert_eq!(fft.threshold_bits, 1_000);
+        assert_eq!(fft.memory_limit_bytes, 1 << 20);
+        assert_eq!(fft.backend_for(49_999), fibcalc_bigfft::FFTBackend::Fermat);
+        assert_eq!(fft.backend_for(50_000), fibcalc_bigfft::FFTBackend::Ntt);
     }
 
     #[test]
@@ -87,11 +202,15 @@ mod tests {
             parallel_threshold: 0,
             fft_threshold: 0,
             strassen_threshold: 0,
+            toom3_threshold: 0,
+            toom4_threshold: 0,
             ..Default::default()
         };
         let normalized = opts.normalize();
         assert_eq!(normalized.parallel_threshold, DEFAULT_PARALLEL_THRESHOLD);
         assert_eq!(normalized.fft_threshold, DEFAULT_FFT_THRESHOLD);
         assert_eq!(normalized.strassen_threshold, DEFAULT_STRASSEN_THRESHOLD);
+        assert_eq!(normalized.toom3_threshold, DEFAULT_TOOM3_THRESHOLD);
+        assert_eq!(normalized.toom4_threshold, DEFAULT_TOOM4_THRESHOLD);
     }
 }
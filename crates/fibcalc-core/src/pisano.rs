@@ -0,0 +1,573 @@
+//! Pisano periods: the period π(m) of the Fibonacci sequence modulo m.
+//!
+//! π(m) is found from the factorization of m. Small factors are removed
+//! by trial division and the rest is split with Miller-Rabin and Pollard
+//! rho on `u128` words, in Montgomery form so that products modulo a
+//! 128-bit number need no 256-bit division. For each prime power p^k:
+//!
+//! - π(2) = 3 and π(5) = 20;
+//! - π(p) divides p − 1 when p ≡ ±1 (mod 5), and 2(p + 1) otherwise;
+//! - π(p^k) = p^j · π(p) for some j < k.
+//!
+//! Each candidate is shrunk to the true period by dividing out primes
+//! while F(P) ≡ 0 and F(P + 1) ≡ 1 still hold, and π(m) is the lcm over
+//! the prime powers. The final value is checked once more against m with
+//! [`FastDoublingMod`], so a wrong factorization surfaces as an error
+//! rather than a wrong period.
+//!
+//! # Example
+//! ```
+//! use fibcalc_core::pisano::pisano_period;
+//! use fibcalc_core::progress::CancellationToken;
+//! use num_bigint::BigUint;
+//!
+//! let cancel = CancellationToken::new();
+//! let period = pisano_period(&BigUint::from(1000u32), &cancel).unwrap();
+//! assert_eq!(period, BigUint::from(1500u32));
+//! ```
+
+use fibcalc_bigfft::FFTConfig;
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::{One, ToPrimitive, Zero};
+
+use crate::calculator::FibError;
+use crate::constants::DEFAULT_FFT_THRESHOLD;
+use crate::modular::FastDoublingMod;
+use crate::observers::NoOpObserver;
+use crate::progress::CancellationToken;
+
+/// Largest trial divisor tried before Pollard rho takes over.
+const TRIAL_DIVISION_BOUND: u32 = 1 << 12;
+
+/// Bits of the cofactor left after trial division that
+/// [`index_period`] is willing to factor.
+const REDUCE_COFACTOR_BITS: u64 = 64;
+
+/// How many times the bits of π(m)'s bound 2^(bits(m)+3) an index needs
+/// before [`index_period`] looks for π(m).
+///
+/// Finding and verifying π(m) took 4 to 26 doubling steps per bit of m
+/// over primes, prime powers and products of both up to 100 bits, under
+/// ten for most, while reducing n saves one step per bit of n beyond π(m).
+const REDUCE_BITS_RATIO: u64 = 8;
+
+/// Miller-Rabin bases. The first 13 are deterministic below 3.3·10^24;
+/// above that a composite passing all of them is caught by the period
+/// checks instead.
+const WITNESSES: [u128; 25] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+];
+
+/// Compute the Pisano period π(m).
+///
+/// # Errors
+///
+/// Returns `FibError::Config` if `m` is zero, `FibError::InvalidInput` if
+/// `m` keeps a factor above 2^128 after trial division,
+/// `FibError::Calculation` if the period fails verification, or
+/// `FibError::Cancelled` on cancellation.
+pub fn pisano_period(m: &BigUint, cancel: &CancellationToken) -> Result<BigUint, FibError> {
+    if m.is_zero() {
+        return Err(FibError::Config("modulus cannot be zero".into()));
+    }
+    let factors = factorize(m, 128, cancel)?.ok_or_else(|| {
+        FibError::InvalidInput(format!(
+            "cannot factor {m}: a factor above 2^128 remains after trial division"
+        ))
+    })?;
+    period_from_factors(m, &factors, cancel)
+}
+
+/// π(m), when reducing `n` modulo it makes F(n) mod m cheaper.
+///
+/// The period is only looked for when `n` has more than
+/// [`REDUCE_BITS_RATIO`] times the bits of π(m)'s bound, which pays for
+/// finding it, and when m factors into trial divisors and one cofactor
+/// below 2^64. Otherwise this is `None`; F(n) ≡ F(n mod π(m)) (mod m)
+/// either way, so runs over several calculators find it once and share it.
+///
+/// # Errors
+///
+/// Returns `FibError::Calculation` if the period fails verification, or
+/// `FibError::Cancelled` on cancellation.
+pub fn index_period(
+    n: &BigUint,
+    m: &BigUint,
+    cancel: &CancellationToken,
+) -> Result<Option<BigUint>, FibError> {
+    if m.is_zero() || n.bits() <= REDUCE_BITS_RATIO * (m.bits() + 3) {
+        return Ok(None);
+    }
+    let Some(factors) = factorize(m, REDUCE_COFACTOR_BITS, cancel)? else {
+        return Ok(None);
+    };
+    period_from_factors(m, &factors, cancel).map(Some)
+}
+
+/// Reduce an index modulo π(m) when [`index_period`] finds it, and return
+/// `n` unchanged otherwise.
+///
+/// # Errors
+///
+/// Returns the errors of [`index_period`].
+pub fn reduce_index(
+    n: &BigUint,
+    m: &BigUint,
+    cancel: &CancellationToken,
+) -> Result<BigUint, FibError> {
+    Ok(match index_period(n, m, cancel)? {
+        Some(period) => n % period,
+        None => n.clone(),
+    })
+}
+
+/// π(m) from the prime factorization of m, verified against m.
+fn period_from_factors(
+    m: &BigUint,
+    factors: &[(u128, u32)],
+    cancel: &CancellationToken,
+) -> Result<BigUint, FibError> {
+    let mut period = BigUint::one();
+    for &(p, k) in factors {
+        let prime_power = BigUint::from(p).pow(k);
+        let mut local = prime_period(p, cancel)? * BigUint::from(p).pow(k - 1);
+        for _ in 1..k {
+            let smaller = &local / p;
+            if !is_period(&smaller, &prime_power, cancel)? {
+                break;
+            }
+            local = smaller;
+        }
+        period = period.lcm(&local);
+    }
+    if !is_period(&period, m, cancel)? {
+        return Err(FibError::Calculation(format!(
+            "Pisano period {period} failed verification modulo {m}"
+        )));
+    }
+    Ok(period)
+}
+
+/// π(p) for a prime p.
+fn prime_period(p: u128, cancel: &CancellationToken) -> Result<BigUint, FibError> {
+    // Candidate multiple of π(p), with its prime factors.
+    let (candidate, mut primes) = match p {
+        2 => (BigUint::from(3u32), vec![3]),
+        5 => (BigUint::from(20u32), vec![2, 5]),
+        _ if matches!(p % 5, 1 | 4) => (BigUint::from(p - 1), factor_u128(p - 1, cancel)?),
+        _ => {
+            // p is odd, so 2(p + 1) = 4 · (p / 2 + 1) without overflow.
+            let half = p / 2 + 1;
+            let mut primes = factor_u128(half, cancel)?;
+            primes.push(2);
+            (BigUint::from(half) << 2, primes)
+        }
+    };
+    primes.sort_unstable();
+    primes.dedup();
+
+    let modulus = BigUint::from(p);
+    if !is_period(&candidate, &modulus, cancel)? {
+        return Err(FibError::Calculation(format!(
+            "{p} is not prime: {candidate} is not a Fibonacci period modulo it"
+        )));
+    }
+    let mut period = candidate;
+    for q in primes {
+        loop {
+            let (quotient, remainder) = period.div_rem(&BigUint::from(q));
+            if !remainder.is_zero() || !is_period(&quotient, &modulus, cancel)? {
+                break;
+            }
+            period = quotient;
+        }
+    }
+    Ok(period)
+}
+
+/// Whether F(P) ≡ 0 and F(P + 1) ≡ 1 (mod m), i.e. P is a multiple of π(m).
+fn is_period(period: &BigUint, m: &BigUint, cancel: &CancellationToken) -> Result<bool, FibError> {
+    if period.is_zero() {
+        return Ok(false);
+    }
+    let observer = NoOpObserver::new();
+    let fib = |n: &BigUint| {
+        FastDoublingMod::fibonacci_mod_big(
+            n,
+            m,
+            &FFTConfig::with_threshold(DEFAULT_FFT_THRESHOLD),
+            cancel,
+            &observer,
+            0,
+        )
+    };
+    Ok(fib(period)?.is_zero() && fib(&(period + 1u32))? == BigUint::one() % m)
+}
+
+/// Factor m into `(prime, exponent)` pairs in increasing order.
+///
+/// Returns `None` if the cofactor left after trial division has more than
+/// `max_cofactor_bits` bits (at most 128).
+fn factorize(
+    m: &BigUint,
+    max_cofactor_bits: u64,
+    cancel: &CancellationToken,
+) -> Result<Option<Vec<(u128, u32)>>, FibError> {
+    let mut rest = m.clone();
+    let mut primes = Vec::new();
+    let mut divisor = 2u32;
+    while rest.bits() > max_cofactor_bits.min(128) {
+        if divisor > TRIAL_DIVISION_BOUND {
+            return Ok(None);
+        }
+        while (&rest % divisor).is_zero() {
+            rest /= divisor;
+            primes.push(u128::from(divisor));
+        }
+        divisor += if divisor == 2 { 1 } else { 2 };
+    }
+    let rest = rest.to_u128().expect("cofactor fits in 128 bits");
+    primes.extend(factor_u128(rest, cancel)?);
+    primes.sort_unstable();
+
+    let mut factors: Vec<(u128, u32)> = Vec::new();
+    for p in primes {
+        match factors.last_mut() {
+            Some((q, k)) if *q == p => *k += 1,
+            _ => factors.push((p, 1)),
+        }
+    }
+    Ok(Some(factors))
+}
+
+/// Prime factors of n with multiplicity, in no particular order.
+fn factor_u128(n: u128, cancel: &CancellationToken) -> Result<Vec<u128>, FibError> {
+    let mut primes = Vec::new();
+    let mut pending = vec![n];
+    while let Some(mut n) = pending.pop() {
+        for &p in &WITNESSES {
+            while n % p == 0 {
+                n /= p;
+                primes.push(p);
+            }
+        }
+        if n == 1 {
+            continue;
+        }
+        if is_probable_prime(n) {
+            primes.push(n);
+        } else {
+            let d = pollard_rho(n, cancel)?;
+            pending.push(d);
+            pending.push(n / d);
+        }
+    }
+    Ok(primes)
+}
+
+/// Miller-Rabin over [`WITNESSES`], for n coprime to all of them.
+fn is_probable_prime(n: u128) -> bool {
+    if n < 3 || n % 2 == 0 {
+        return n == 2;
+    }
+    let ring = Montgomery::new(n);
+    let minus_one = n - ring.one;
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    WITNESSES.iter().all(|&a| {
+        let mut x = ring.pow(ring.enter(a), d);
+        if x == ring.one || x == minus_one {
+            return true;
+        }
+        for _ in 1..s {
+            x = ring.mul(x, x);
+            if x == minus_one {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+/// A non-trivial factor of an odd composite n, by Brent's variant of
+/// Pollard rho.
+///
+/// The walk stays in Montgomery form: 2^128 is a unit modulo n, so the
+/// differences and their products share the same factors with n.
+fn pollard_rho(n: u128, cancel: &CancellationToken) -> Result<u128, FibError> {
+    /// Steps between two gcds.
+    const BATCH: u64 = 128;
+
+    let ring = Montgomery::new(n);
+    for c in 1.. {
+        let step = |x: u128| add_mod(ring.mul(x, x), c, n);
+        let (mut x, mut y, mut saved) = (0, 2, 2);
+        let (mut product, mut factor, mut run) = (1, 1, 1u64);
+        while factor == 1 {
+            x = y;
+            for _ in 0..run {
+                y = step(y);
+            }
+            let mut done = 0;
+            while done < run && factor == 1 {
+                if cancel.is_cancelled() {
+                    return Err(FibError::Cancelled);
+                }
+                saved = y;
+                for _ in 0..BATCH.min(run - done) {
+                    y = step(y);
+                    product = ring.mul(product, x.abs_diff(y));
+                }
+                factor = product.gcd(&n);
+                done += BATCH;
+            }
+            run *= 2;
+        }
+        if factor == n {
+            // The batch overshot: replay it one gcd at a time.
+            loop {
+                saved = step(saved);
+                factor = x.abs_diff(saved).gcd(&n);
+                if factor > 1 {
+                    break;
+                }
+            }
+        }
+        if factor != n {
+            return Ok(factor);
+        }
+    }
+    unreachable!("the increment range is unbounded")
+}
+
+fn add_mod(a: u128, b: u128, m: u128) -> u128 {
+    if a >= m - b {
+        a - (m - b)
+    } else {
+        a + b
+    }
+}
+
+/// The 256-bit product a · b as `(low, high)` halves.
+const fn widening_mul(a: u128, b: u128) -> (u128, u128) {
+    const MASK: u128 = u64::MAX as u128;
+    let (a0, a1) = (a & MASK, a >> 64);
+    let (b0, b1) = (b & MASK, b >> 64);
+    let (p00, p01, p10, p11) = (a0 * b0, a0 * b1, a1 * b0, a1 * b1);
+    let mid = (p00 >> 64) + (p01 & MASK) + (p10 & MASK);
+    let low = (p00 & MASK) | (mid << 64);
+    let high = p11 + (p01 >> 64) + (p10 >> 64) + (mid >> 64);
+    (low, high)
+}
+
+/// Arithmetic modulo an odd m > 1 on residues in Montgomery form,
+/// x · 2^128 mod m.
+struct Montgomery {
+    m: u128,
+    /// −m^(−1) mod 2^128.
+    neg_inv: u128,
+    /// 1 in Montgomery form, 2^128 mod m.
+    one: u128,
+    /// 2^256 mod m, to bring integers into Montgomery form.
+    r2: u128,
+}
+
+impl Montgomery {
+    fn new(m: u128) -> Self {
+        debug_assert!(m > 1 && m % 2 == 1, "Montgomery form needs an odd modulus");
+        // m · m ≡ 1 (mod 8), and each Newton step doubles the bits that
+        // are right: 3 → 6 → … → 192.
+        let mut inv = m;
+        for _ in 0..6 {
+            inv = inv.wrapping_mul(2u128.wrapping_sub(m.wrapping_mul(inv)));
+        }
+        let one = (u128::MAX % m + 1) % m;
+        let mut r2 = one;
+        for _ in 0..128 {
+            r2 = add_mod(r2, r2, m);
+        }
+        Self {
+            m,
+            neg_inv: inv.wrapping_neg(),
+            one,
+            r2,
+        }
+    }
+
+    /// `x` in Montgomery form.
+    fn enter(&self, x: u128) -> u128 {
+        self.mul(x % self.m, self.r2)
+    }
+
+    /// a · b · 2^(−128) mod m, for a, b < m.
+    fn mul(&self, a: u128, b: u128) -> u128 {
+        let (low, high) = widening_mul(a, b);
+        // Add the multiple of m that clears the low half, then drop it.
+        let (_, u_high) = widening_mul(low.wrapping_mul(self.neg_inv), self.m);
+        let carry = u128::from(low != 0);
+        let (sum, c1) = high.overflowing_add(u_high);
+        let (sum, c2) = sum.overflowing_add(carry);
+        if c1 || c2 || sum >= self.m {
+            sum.wrapping_sub(self.m)
+        } else {
+            sum
+        }
+    }
+
+    fn pow(&self, mut base: u128, mut exp: u128) -> u128 {
+        let mut result = self.one;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = self.mul(result, base);
+            }
+            base = self.mul(base, base);
+            exp >>= 1;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn period(m: impl Into<BigUint>) -> BigUint {
+        pisano_period(&m.into(), &CancellationToken::new()).unwrap()
+    }
+
+    /// π(m) by walking the sequence until (0, 1) comes back.
+    fn brute_force(m: u32) -> u32 {
+        let (mut a, mut b, mut steps) = (0, 1 % m, 0);
+        loop {
+            (a, b) = (b, (a + b) % m);
+            steps += 1;
+            if a == 0 && b == 1 % m {
+                return steps;
+            }
+        }
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        for m in 1..=1500u32 {
+            assert_eq!(period(m), BigUint::from(brute_force(m)), "π({m})");
+        }
+    }
+
+    #[test]
+    fn known_periods() {
+        assert_eq!(period(10u32), BigUint::from(60u32));
+        assert_eq!(period(1_000_000_007u64), BigUint::from(2_000_000_016u64));
+        for k in 3..=60u32 {
+            let m = BigUint::from(10u32).pow(k);
+            assert_eq!(
+                period(m),
+                BigUint::from(15u32) * BigUint::from(10u32).pow(k - 1)
+            );
+        }
+        // Mersenne prime ≡ 1 (mod 5): π divides p − 1.
+        let p = (1u64 << 61) - 1;
+        let pi = period(p);
+        assert!((BigUint::from(p - 1) % &pi).is_zero());
+    }
+
+    #[test]
+    fn factors_u128_moduli() {
+        let cancel = CancellationToken::new();
+        let (p, q) = ((1u128 << 61) - 1, (1u128 << 31) - 1);
+        let mut primes = factor_u128(p * q, &cancel).unwrap();
+        primes.sort_unstable();
+        assert_eq!(primes, vec![q, p]);
+        assert!(is_probable_prime((1u128 << 89) - 1));
+        assert!(!is_probable_prime(p * q));
+
+        // π is multiplicative over coprime moduli through the lcm.
+        let (pi_p, pi_q) = (period(p), period(q));
+        assert_eq!(period(p * q), pi_p.lcm(&pi_q));
+    }
+
+    #[test]
+    fn montgomery_products_match_biguint() {
+        for m in [
+            97u128,
+            (1 << 61) - 1,
+            (1 << 89) - 1,
+            u128::MAX - 158, // 2^128 − 159, prime
+            u128::MAX,
+        ] {
+            let ring = Montgomery::new(m);
+            let big = BigUint::from(m);
+            for (a, b) in [(0, 5), (1, m - 1), (m - 1, m - 1), (m / 3, m / 2 + 7)] {
+                let (a, b) = (a % m, b % m);
+                let product = ring.mul(ring.enter(a), ring.enter(b));
+                let expected = BigUint::from(a) * BigUint::from(b) % &big;
+                assert_eq!(BigUint::from(ring.mul(product, 1)), expected, "mod {m}");
+            }
+            let x = ring.enter(3);
+            assert_eq!(
+                BigUint::from(ring.mul(ring.pow(x, m - 2), 1)),
+                BigUint::from(3u32).modpow(&BigUint::from(m - 2), &big)
+            );
+        }
+        assert!(is_probable_prime(u128::MAX - 158));
+    }
+
+    #[test]
+    fn index_period_only_for_large_indices() {
+        let cancel = CancellationToken::new();
+        let m = BigUint::from(10u32);
+        let small = BigUint::from(1u64 << 40);
+        assert_eq!(index_period(&small, &m, &cancel).unwrap(), None);
+        assert_eq!(reduce_index(&small, &m, &cancel).unwrap(), small);
+        let large = BigUint::from(10u32).pow(18);
+        assert_eq!(
+            index_period(&large, &m, &cancel).unwrap(),
+            Some(BigUint::from(60u32))
+        );
+        assert_eq!(
+            reduce_index(&large, &m, &cancel).unwrap(),
+            BigUint::from(40u32)
+        );
+    }
+
+    #[test]
+    fn rejects_unfactorable_moduli() {
+        let cancel = CancellationToken::new();
+        assert!(matches!(
+            pisano_period(&BigUint::zero(), &cancel),
+            Err(FibError::Config(_))
+        ));
+        let big_prime = (BigUint::one() << 521) - 1u32;
+        assert!(matches!(
+            pisano_period(&big_prime, &cancel),
+            Err(FibError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn reduce_index_keeps_residue() {
+        let cancel = CancellationToken::new();
+        let observer = NoOpObserver::new();
+        let m = BigUint::from(1_000_000_007u64);
+        let n = BigUint::from(10u32).pow(400) + 12_345u32;
+        let reduced = reduce_index(&n, &m, &cancel).unwrap();
+        assert!(reduced < BigUint::from(2_000_000_016u64));
+        let fib = |n: &BigUint| {
+            FastDoublingMod::fibonacci_mod_big(
+                n,
+                &m,
+                &FFTConfig::with_threshold(DEFAULT_FFT_THRESHOLD),
+                &cancel,
+                &observer,
+                0,
+            )
+            .unwrap()
+        };
+        assert_eq!(fib(&reduced), fib(&n));
+
+        // Small indices are left alone.
+        let small = BigUint::from(1u32) << 200;
+        assert_eq!(reduce_index(&small, &m, &cancel).unwrap(), small);
+    }
+}
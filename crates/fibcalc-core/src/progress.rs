@@ -4,6 +4,8 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use parking_lot::{Condvar, Mutex};
+
 use crate::calculator::FibError;
 
 /// Progress update sent from calculators to observers.
@@ -103,6 +105,7 @@ const POWERS_OF_4: [f64; 64] = {
 #[derive(Clone)]
 pub struct CancellationToken {
     cancelled: Arc<AtomicBool>,
+    pause: PauseToken,
 }
 
 impl CancellationToken {
@@ -111,9 +114,24 @@ impl CancellationToken {
     pub fn new() -> Self {
         Self {
             cancelled: Arc::new(AtomicBool::new(false)),
+            pause: PauseToken::new(),
         }
     }
 
+    /// Get the pause token shared by all clones of this token.
+    #[must_use]
+    pub fn pause_token(&self) -> &PauseToken {
+        &self.pause
+    }
+
+    /// Block at an iteration boundary while a pause is requested.
+    ///
+    /// Returns as soon as the computation is resumed or cancelled.
+    #[inline]
+    pub fn wait_while_paused(&self) {
+        self.pause.wait_while_paused(self);
+    }
+
     /// Check if cancellation has been requested.
     #[must_use]
     pub fn is_cancelled(&self) -> bool {
@@ -158,6 +176,91 @@ impl Default for CancellationToken {
     }
 }
 
+/// How often a paused computation re-checks for cancellation.
+const PAUSE_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Cooperative pause/resume token, the pausable counterpart to
+/// [`CancellationToken`].
+///
+/// Algorithm loops call [`CancellationToken::wait_while_paused`] at
+/// iteration boundaries; while paused, the calling thread blocks there
+/// until [`resume`](Self::resume) is called or the computation is cancelled.
+///
+/// # Example
+/// ```
+/// use fibcalc_core::progress::CancellationToken;
+///
+/// let token = CancellationToken::new();
+/// let pause = token.pause_token().clone();
+/// pause.pause();
+/// assert!(token.pause_token().is_paused());
+///
+/// // A cancelled computation never stays blocked on a pause.
+/// token.cancel();
+/// token.wait_while_paused();
+/// ```
+#[derive(Clone)]
+pub struct PauseToken {
+    inner: Arc<PauseState>,
+}
+
+struct PauseState {
+    paused: AtomicBool,
+    lock: Mutex<()>,
+    resumed: Condvar,
+}
+
+impl PauseToken {
+    /// Create a new pause token in the running state.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(PauseState {
+                paused: AtomicBool::new(false),
+                lock: Mutex::new(()),
+                resumed: Condvar::new(),
+            }),
+        }
+    }
+
+    /// Check if a pause has been requested.
+    #[must_use]
+    pub fn is_paused(&self) -> bool {
+        self.inner.paused.load(Ordering::Acquire)
+    }
+
+    /// Request a pause at the next iteration boundary.
+    pub fn pause(&self) {
+        self.inner.paused.store(true, Ordering::Release);
+    }
+
+    /// Resume a paused computation.
+    pub fn resume(&self) {
+        let _guard = self.inner.lock.lock();
+        self.inner.paused.store(false, Ordering::Release);
+        self.inner.resumed.notify_all();
+    }
+
+    /// Block the calling thread while paused.
+    ///
+    /// Returns once resumed, or early if `cancel` is cancelled.
+    pub fn wait_while_paused(&self, cancel: &CancellationToken) {
+        if !self.is_paused() {
+            return;
+        }
+        let mut guard = self.inner.lock.lock();
+        while self.is_paused() && !cancel.is_cancelled() {
+            self.inner.resumed.wait_for(&mut guard, PAUSE_POLL_INTERVAL);
+        }
+    }
+}
+
+impl Default for PauseToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// A cancellation token with a timeout.
 ///
 /// Combines cooperative cancellation with an absolute deadline.
@@ -334,6 +437,67 @@ mod tests {
         assert!(calc_total_work(1000) > calc_total_work(100));
     }
 
+    #[test]
+    fn pause_token_initially_running() {
+        let token = CancellationToken::new();
+        assert!(!token.pause_token().is_paused());
+        // Must not block when not paused
+        token.wait_while_paused();
+    }
+
+    #[test]
+    fn pause_propagates_through_clone() {
+        let token1 = CancellationToken::new();
+        let token2 = token1.clone();
+        token1.pause_token().pause();
+        assert!(token2.pause_token().is_paused());
+        token2.pause_token().resume();
+        assert!(!token1.pause_token().is_paused());
+    }
+
+    #[test]
+    fn paused_worker_blocks_until_resumed() {
+        let token = CancellationToken::new();
+        token.pause_token().pause();
+
+        let steps = Arc::new(std::sync::atomic::AtomicU32::new(0));
+        let worker = {
+            let token = token.clone();
+            let steps = Arc::clone(&steps);
+            std::thread::spawn(move || {
+                token.wait_while_paused();
+                steps.fetch_add(1, Ordering::SeqCst);
+            })
+        };
+
+        std::thread::sleep(Duration::from_millis(100));
+        assert_eq!(steps.load(Ordering::SeqCst), 0, "worker ran while paused");
+
+        token.pause_token().resume();
+        worker.join().unwrap();
+        assert_eq!(steps.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn cancel_releases_paused_worker() {
+        let token = CancellationToken::new();
+        token.pause_token().pause();
+
+        let worker = {
+            let token = token.clone();
+            std::thread::spawn(move || {
+                token.wait_while_paused();
+                token.check_cancelled()
+            })
+        };
+
+        std::thread::sleep(Duration::from_millis(20));
+        token.cancel();
+        let result = worker.join().unwrap();
+        assert!(matches!(result, Err(FibError::Cancelled)));
+        assert!(token.pause_token().is_paused());
+    }
+
     #[test]
     fn cancellation_propagates_through_clone() {
         let token1 = CancellationToken::new();
@@ -10,7 +10,9 @@ use crate::calculator::{Calculator, FibCalculator, FibError};
 use crate::calculator_gmp::GmpCalculator;
 use crate::fastdoubling::OptimizedFastDoubling;
 use crate::fft_based::FFTBasedCalculator;
+use crate::lucas::{LucasFastDoubling, LucasMatrix};
 use crate::matrix::MatrixExponentiation;
+use crate::modular::{FastDoublingMod, MatrixExponentiationMod};
 
 /// Factory trait for creating calculators.
 pub trait CalculatorFactory: Send + Sync {
@@ -23,6 +25,20 @@ pub trait CalculatorFactory: Send + Sync {
 
     /// List all available calculator names.
     fn available(&self) -> Vec<&str>;
+
+    /// Name of the calculator computing F(n) mod 10^k in place of `name`
+    /// when `Options::last_digits` is set, if there is one.
+    fn modular_variant(&self, name: &str) -> Option<&'static str> {
+        let _ = name;
+        None
+    }
+
+    /// Name of the calculator computing `Options::sequence` in place of
+    /// `name` when the sequence is not Fibonacci, if there is one.
+    fn sequence_variant(&self, name: &str) -> Option<&'static str> {
+        let _ = name;
+        None
+    }
 }
 
 /// Default factory with lazy creation and cache.
@@ -58,6 +74,22 @@ impl DefaultFactory {
                 let core = Arc::new(GmpCalculator::new());
                 Ok(Arc::new(FibCalculator::new(core)))
             }
+            "fast-mod" => {
+                let core = Arc::new(FastDoublingMod::new());
+                Ok(Arc::new(FibCalculator::new(core)))
+            }
+            "matrix-mod" => {
+                let core = Arc::new(MatrixExponentiationMod::new());
+                Ok(Arc::new(FibCalculator::new(core)))
+            }
+            "lucas-fast" => {
+                let core = Arc::new(LucasFastDoubling::new());
+                Ok(Arc::new(FibCalculator::new(core)))
+            }
+            "lucas-matrix" => {
+                let core = Arc::new(LucasMatrix::new());
+                Ok(Arc::new(FibCalculator::new(core)))
+            }
             _ => Err(FibError::Config(format!("unknown calculator: {name}"))),
         }
     }
@@ -94,6 +126,24 @@ impl CalculatorFactory for DefaultFactory {
             vec!["fast", "matrix", "fft", "gmp"]
         }
     }
+
+    fn modular_variant(&self, name: &str) -> Option<&'static str> {
+        match name {
+            "fast" | "fastdoubling" | "fft" | "gmp" | "fast-mod" => Some("fast-mod"),
+            "matrix" | "matrix-mod" => Some("matrix-mod"),
+            _ => None,
+        }
+    }
+
+    fn sequence_variant(&self, name: &str) -> Option<&'static str> {
+        match name {
+            "fast" | "fastdoubling" | "fft" | "gmp" | "fast-mod" | "lucas-fast" => {
+                Some("lucas-fast")
+            }
+            "matrix" | "matrix-mod" | "lucas-matrix" => Some("lucas-matrix"),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -147,6 +197,46 @@ mod tests {
         assert!(available.contains(&"fft"));
     }
 
+    #[test]
+    fn factory_creates_modular_variants() {
+        let factory = DefaultFactory::new();
+        assert_eq!(factory.get("fast-mod").unwrap().name(), "FastDoublingMod");
+        assert_eq!(
+            factory.get("matrix-mod").unwrap().name(),
+            "MatrixExponentiationMod"
+        );
+        assert!(!factory.available().contains(&"fast-mod"));
+    }
+
+    #[test]
+    fn factory_modular_variants() {
+        let factory = DefaultFactory::new();
+        for name in factory.available() {
+            let variant = factory.modular_variant(name).unwrap();
+            assert!(factory.get(variant).is_ok());
+        }
+        assert_eq!(factory.modular_variant("fft"), Some("fast-mod"));
+        assert_eq!(factory.modular_variant("matrix"), Some("matrix-mod"));
+        assert_eq!(factory.modular_variant("nonexistent"), None);
+    }
+
+    #[test]
+    fn factory_sequence_variants() {
+        let factory = DefaultFactory::new();
+        for name in factory.available() {
+            let variant = factory.sequence_variant(name).unwrap();
+            assert!(factory.get(variant).is_ok());
+        }
+        assert_eq!(
+            factory.get("lucas-fast").unwrap().name(),
+            "LucasFastDoubling"
+        );
+        assert_eq!(factory.sequence_variant("matrix"), Some("lucas-matrix"));
+        assert_eq!(factory.sequence_variant("fast-mod"), Some("lucas-fast"));
+        assert_eq!(factory.sequence_variant("nonexistent"), None);
+        assert!(!factory.available().contains(&"lucas-fast"));
+    }
+
     #[cfg(feature = "gmp")]
     #[test]
     fn factory_creates_gmp() {
@@ -2,10 +2,20 @@
 //!
 //! `Multiplier` is the narrow interface for multiply/square operations.
 //! `DoublingStepExecutor` extends it for optimized Fast Doubling steps.
-//! Strategies include Karatsuba, FFT, and adaptive selection.
+//! Strategies include Karatsuba, Toom-Cook, FFT, and adaptive selection.
 
+use fibcalc_bigfft::{CacheStats, FFTCache, FFTConfig};
 use num_bigint::BigUint;
 
+use crate::constants::{DEFAULT_TOOM3_THRESHOLD, DEFAULT_TOOM4_THRESHOLD};
+use crate::fft_wrappers::{
+    always_fft, fft_doubling_step, fft_doubling_step_to, mul_fft, smart_multiply,
+    smart_sqr_add_sqr, smart_square, smart_sum_of_products, sqr_add_sqr_fft, sqr_fft,
+    sum_of_products_fft, STEP_CACHE_ENTRIES,
+};
+use crate::options::Options;
+use crate::toom::{toom3_mul, toom3_sqr, toom4_mul, toom4_sqr};
+
 /// Narrow interface for multiplication operations (ISP).
 pub trait Multiplier: Send + Sync {
     /// Multiply two big unsigned integers.
@@ -20,8 +30,38 @@ pub trait Multiplier: Send + Sync {
     fn name(&self) -> &str;
 }
 
+/// Sum of products of `terms` computed one product at a time.
+fn separate_products<M: Multiplier + ?Sized>(m: &M, terms: &[(&BigUint, &BigUint)]) -> BigUint {
+    terms
+        .iter()
+        .map(|&(a, b)| {
+            if std::ptr::eq(a, b) {
+                m.square(a)
+            } else {
+                m.multiply(a, b)
+            }
+        })
+        .sum()
+}
+
 /// Extended interface for optimized Fast Doubling steps.
 pub trait DoublingStepExecutor: Multiplier {
+    /// Compute `a^2 + b^2`.
+    ///
+    /// FFT strategies sum the squares in the transform domain, with a
+    /// single inverse transform.
+    fn sqr_add_sqr(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        self.square(a) + self.square(b)
+    }
+
+    /// Compute the sum of the products of `terms`; a term whose two sides
+    /// are the same value is squared.
+    ///
+    /// FFT strategies sum the products in the transform domain.
+    fn sum_of_products(&self, terms: &[(&BigUint, &BigUint)]) -> BigUint {
+        separate_products(self, terms)
+    }
+
     /// Execute a complete doubling step: given F(k) and F(k+1),
     /// compute F(2k) and F(2k+1).
     ///
@@ -31,9 +71,25 @@ pub trait DoublingStepExecutor: Multiplier {
         let t = (fk1 << 1u32) - fk;
         let f2k = self.multiply(fk, &t);
         // F(2k+1) = F(k)^2 + F(k+1)^2       — 2 squarings
-        let f2k1 = self.square(fk) + self.square(fk1);
+        let f2k1 = self.sqr_add_sqr(fk, fk1);
         (f2k, f2k1)
     }
+
+    /// [`execute_doubling_step`](Self::execute_doubling_step) into `f2k`
+    /// and `f2k1`.
+    ///
+    /// FFT strategies reassemble the results into the existing allocations,
+    /// so a caller rotating two register pairs stops allocating them once
+    /// warm.
+    fn execute_doubling_step_to(
+        &self,
+        f2k: &mut BigUint,
+        f2k1: &mut BigUint,
+        fk: &BigUint,
+        fk1: &BigUint,
+    ) {
+        (*f2k, *f2k1) = self.execute_doubling_step(fk, fk1);
+    }
 }
 
 /// Karatsuba multiplication strategy (default for small numbers).
@@ -121,14 +177,130 @@ impl DoublingStepExecutor for ParallelKaratsubaStrategy {
     }
 }
 
+/// Toom-Cook 3-way multiplication strategy.
+///
+/// Operands at or above the threshold are split into three pieces and
+/// multiplied with five recursive products; smaller ones use num-bigint.
+pub struct Toom3Strategy {
+    threshold: usize,
+}
+
+impl Toom3Strategy {
+    /// Create a Toom-3 strategy that recurses while operands reach `threshold` bits.
+    #[must_use]
+    pub fn new(threshold: usize) -> Self {
+        Self { threshold }
+    }
+}
+
+impl Default for Toom3Strategy {
+    fn default() -> Self {
+        Self::new(DEFAULT_TOOM3_THRESHOLD)
+    }
+}
+
+impl Multiplier for Toom3Strategy {
+    #[allow(clippy::cast_possible_truncation)]
+    fn multiply(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        if (a.bits().max(b.bits()) as usize) < self.threshold {
+            return a * b;
+        }
+        toom3_mul(a, b, &|x, y| self.multiply(x, y))
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn square(&self, a: &BigUint) -> BigUint {
+        if (a.bits() as usize) < self.threshold {
+            return a * a;
+        }
+        toom3_sqr(a, &|x| self.square(x))
+    }
+
+    fn name(&self) -> &'static str {
+        "Toom3"
+    }
+}
+
+impl DoublingStepExecutor for Toom3Strategy {}
+
+/// Toom-Cook 4-way multiplication strategy.
+///
+/// Operands at or above the threshold are split into four pieces and
+/// multiplied with seven recursive products; smaller ones use num-bigint.
+pub struct Toom4Strategy {
+    threshold: usize,
+}
+
+impl Toom4Strategy {
+    /// Create a Toom-4 strategy that recurses while operands reach `threshold` bits.
+    #[must_use]
+    pub fn new(threshold: usize) -> Self {
+        Self { threshold }
+    }
+}
+
+impl Default for Toom4Strategy {
+    fn default() -> Self {
+        Self::new(DEFAULT_TOOM4_THRESHOLD)
+    }
+}
+
+impl Multiplier for Toom4Strategy {
+    #[allow(clippy::cast_possible_truncation)]
+    fn multiply(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        if (a.bits().max(b.bits()) as usize) < self.threshold {
+            return a * b;
+        }
+        toom4_mul(a, b, &|x, y| self.multiply(x, y))
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn square(&self, a: &BigUint) -> BigUint {
+        if (a.bits() as usize) < self.threshold {
+            return a * a;
+        }
+        toom4_sqr(a, &|x| self.square(x))
+    }
+
+    fn name(&self) -> &'static str {
+        "Toom4"
+    }
+}
+
+impl DoublingStepExecutor for Toom4Strategy {}
+
 /// FFT-only multiplication strategy (for very large numbers).
-pub struct FFTOnlyStrategy;
+///
+/// On the Fermat backend, doubling steps look their operands up in a
+/// bounded [`FFTCache`], so F(k)'s transform serves the multiply and the
+/// square.
+pub struct FFTOnlyStrategy {
+    fft: FFTConfig,
+    cache: FFTCache,
+}
 
 impl FFTOnlyStrategy {
     /// Create a new FFT-only multiplication strategy.
     #[must_use]
     pub fn new() -> Self {
-        Self
+        Self {
+            fft: FFTConfig::with_threshold(0),
+            cache: FFTCache::new(STEP_CACHE_ENTRIES),
+        }
+    }
+
+    /// Run products under `fft`'s backend and NTT crossover; its FFT
+    /// threshold is ignored.
+    #[must_use]
+    pub fn with_fft_config(mut self, fft: FFTConfig) -> Self {
+        self.fft = always_fft(&fft);
+        self
+    }
+
+    /// Hit and miss counts of the doubling steps' transform cache.
+    #[must_use]
+    pub fn cache_stats(&self) -> CacheStats {
+        self.cache.stats()
     }
 }
 
@@ -140,11 +312,11 @@ impl Default for FFTOnlyStrategy {
 
 impl Multiplier for FFTOnlyStrategy {
     fn multiply(&self, a: &BigUint, b: &BigUint) -> BigUint {
-        fibcalc_bigfft::mul(a, b)
+        mul_fft(a, b, &self.fft)
     }
 
     fn square(&self, a: &BigUint) -> BigUint {
-        fibcalc_bigfft::sqr(a)
+        sqr_fft(a, &self.fft)
     }
 
     fn name(&self) -> &'static str {
@@ -152,43 +324,171 @@ impl Multiplier for FFTOnlyStrategy {
     }
 }
 
-impl DoublingStepExecutor for FFTOnlyStrategy {}
+impl DoublingStepExecutor for FFTOnlyStrategy {
+    fn sqr_add_sqr(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        sqr_add_sqr_fft(a, b, &self.fft)
+    }
+
+    fn sum_of_products(&self, terms: &[(&BigUint, &BigUint)]) -> BigUint {
+        sum_of_products_fft(terms, &self.fft)
+    }
+
+    fn execute_doubling_step(&self, fk: &BigUint, fk1: &BigUint) -> (BigUint, BigUint) {
+        fft_doubling_step(fk, fk1, &self.fft, &self.cache)
+    }
+
+    fn execute_doubling_step_to(
+        &self,
+        f2k: &mut BigUint,
+        f2k1: &mut BigUint,
+        fk: &BigUint,
+        fk1: &BigUint,
+    ) {
+        fft_doubling_step_to(f2k, f2k1, fk, fk1, &self.fft, &self.cache);
+    }
+}
+
+/// Multiplication algorithm chosen by [`TierThresholds::select`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MultiplicationTier {
+    /// num-bigint multiplication (Karatsuba).
+    Karatsuba,
+    /// Toom-Cook 3-way.
+    Toom3,
+    /// Toom-Cook 4-way.
+    Toom4,
+    /// FFT multiplication.
+    Fft,
+}
+
+/// Operand sizes (in bits) at which [`AdaptiveStrategy`] moves up a tier.
+///
+/// Each tier applies from its threshold up to the next higher one;
+/// `usize::MAX` disables a tier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TierThresholds {
+    /// Toom-3 from this size.
+    pub toom3: usize,
+    /// Toom-4 from this size.
+    pub toom4: usize,
+    /// FFT from this size.
+    pub fft: usize,
+}
+
+impl TierThresholds {
+    /// Karatsuba below `fft`, FFT at or above it, no Toom tiers.
+    #[must_use]
+    pub fn fft_only(fft: usize) -> Self {
+        Self {
+            toom3: usize::MAX,
+            toom4: usize::MAX,
+            fft,
+        }
+    }
+
+    /// Tier thresholds from (normalized) calculation options.
+    #[must_use]
+    pub fn from_options(opts: &Options) -> Self {
+        Self {
+            toom3: opts.toom3_threshold,
+            toom4: opts.toom4_threshold,
+            fft: opts.fft_threshold,
+        }
+    }
+
+    /// Tier for operands of `bits` bits.
+    #[must_use]
+    pub fn select(&self, bits: usize) -> MultiplicationTier {
+        if bits >= self.fft {
+            MultiplicationTier::Fft
+        } else if bits >= self.toom4 {
+            MultiplicationTier::Toom4
+        } else if bits >= self.toom3 {
+            MultiplicationTier::Toom3
+        } else {
+            MultiplicationTier::Karatsuba
+        }
+    }
+}
 
 /// Adaptive strategy that selects multiplication method based on operand size.
+///
+/// Products go through Karatsuba, Toom-3, Toom-4 or FFT according to
+/// [`TierThresholds`]; Toom point products are routed back through the
+/// same selection. Doubling steps at or above the FFT threshold reuse
+/// transforms like [`FFTOnlyStrategy`].
 pub struct AdaptiveStrategy {
-    fft_threshold: usize,
+    tiers: TierThresholds,
+    fft: FFTConfig,
+    cache: FFTCache,
 }
 
 impl AdaptiveStrategy {
     /// Create a new adaptive strategy that switches to FFT at the given bit threshold.
     #[must_use]
     pub fn new(fft_threshold: usize) -> Self {
-        Self { fft_threshold }
+        Self::with_tiers(TierThresholds::fft_only(fft_threshold))
     }
 
-    /// Get the bit length of a `BigUint`.
-    #[allow(clippy::cast_possible_truncation)]
-    fn bit_len(n: &BigUint) -> usize {
-        n.bits() as usize
+    /// Create an adaptive strategy over all multiplication tiers.
+    #[must_use]
+    pub fn with_tiers(tiers: TierThresholds) -> Self {
+        Self {
+            tiers,
+            fft: FFTConfig::with_threshold(tiers.fft),
+            cache: FFTCache::new(STEP_CACHE_ENTRIES),
+        }
+    }
+
+    /// Create an adaptive strategy with the tiers and FFT settings of `opts`.
+    #[must_use]
+    pub fn from_options(opts: &Options) -> Self {
+        Self::with_tiers(TierThresholds::from_options(opts)).with_fft_config(opts.fft_config())
+    }
+
+    /// Run the FFT tier under `fft`'s backend and NTT crossover; the tier
+    /// still starts at [`TierThresholds::fft`].
+    #[must_use]
+    pub fn with_fft_config(mut self, fft: FFTConfig) -> Self {
+        self.fft = FFTConfig {
+            threshold_bits: self.tiers.fft,
+            ..fft
+        };
+        self
+    }
+
+    /// The tier thresholds this strategy selects with.
+    #[must_use]
+    pub fn tiers(&self) -> TierThresholds {
+        self.tiers
+    }
+
+    /// Hit and miss counts of the FFT doubling steps' transform cache.
+    #[must_use]
+    pub fn cache_stats(&self) -> CacheStats {
+        self.cache.stats()
     }
 }
 
 impl Multiplier for AdaptiveStrategy {
+    #[allow(clippy::cast_possible_truncation)]
     fn multiply(&self, a: &BigUint, b: &BigUint) -> BigUint {
-        let max_bits = Self::bit_len(a).max(Self::bit_len(b));
-        if max_bits >= self.fft_threshold {
-            fibcalc_bigfft::mul(a, b)
-        } else {
-            a * b
+        let bits = a.bits().max(b.bits()) as usize;
+        match self.tiers.select(bits) {
+            MultiplicationTier::Karatsuba => a * b,
+            MultiplicationTier::Toom3 => toom3_mul(a, b, &|x, y| self.multiply(x, y)),
+            MultiplicationTier::Toom4 => toom4_mul(a, b, &|x, y| self.multiply(x, y)),
+            MultiplicationTier::Fft => smart_multiply(a, b, &self.fft),
         }
     }
 
+    #[allow(clippy::cast_possible_truncation)]
     fn square(&self, a: &BigUint) -> BigUint {
-        let bits = Self::bit_len(a);
-        if bits >= self.fft_threshold {
-            fibcalc_bigfft::sqr(a)
-        } else {
-            a * a
+        match self.tiers.select(a.bits() as usize) {
+            MultiplicationTier::Karatsuba => a * a,
+            MultiplicationTier::Toom3 => toom3_sqr(a, &|x| self.square(x)),
+            MultiplicationTier::Toom4 => toom4_sqr(a, &|x| self.square(x)),
+            MultiplicationTier::Fft => smart_square(a, &self.fft),
         }
     }
 
@@ -197,7 +497,55 @@ impl Multiplier for AdaptiveStrategy {
     }
 }
 
-impl DoublingStepExecutor for AdaptiveStrategy {}
+impl DoublingStepExecutor for AdaptiveStrategy {
+    #[allow(clippy::cast_possible_truncation)]
+    fn sqr_add_sqr(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        if a.bits().max(b.bits()) as usize >= self.tiers.fft {
+            return smart_sqr_add_sqr(a, b, &self.fft);
+        }
+        self.square(a) + self.square(b)
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn sum_of_products(&self, terms: &[(&BigUint, &BigUint)]) -> BigUint {
+        let max_bits = terms
+            .iter()
+            .map(|(a, b)| a.bits().max(b.bits()))
+            .max()
+            .unwrap_or(0) as usize;
+        if max_bits >= self.tiers.fft {
+            return smart_sum_of_products(terms, &self.fft);
+        }
+        separate_products(self, terms)
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn execute_doubling_step(&self, fk: &BigUint, fk1: &BigUint) -> (BigUint, BigUint) {
+        let max_bits = fk.bits().max(fk1.bits()) as usize;
+        if max_bits >= self.tiers.fft {
+            return fft_doubling_step(fk, fk1, &self.fft, &self.cache);
+        }
+        let t = (fk1 << 1u32) - fk;
+        let f2k = self.multiply(fk, &t);
+        let f2k1 = self.sqr_add_sqr(fk, fk1);
+        (f2k, f2k1)
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn execute_doubling_step_to(
+        &self,
+        f2k: &mut BigUint,
+        f2k1: &mut BigUint,
+        fk: &BigUint,
+        fk1: &BigUint,
+    ) {
+        if fk.bits().max(fk1.bits()) as usize >= self.tiers.fft {
+            fft_doubling_step_to(f2k, f2k1, fk, fk1, &self.fft, &self.cache);
+        } else {
+            (*f2k, *f2k1) = self.execute_doubling_step(fk, fk1);
+        }
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -468,4 +816,122 @@ mod tests {
         let multiplied = strat.multiply(&a, &a);
         assert_eq!(squared, multiplied);
     }
+
+    #[test]
+    fn toom_strategies_match_karatsuba() {
+        use num_traits::One;
+
+        let karatsuba = KaratsubaStrategy::new();
+        let toom3 = Toom3Strategy::new(2_000);
+        let toom4 = Toom4Strategy::new(2_000);
+        assert_eq!(toom3.name(), "Toom3");
+        assert_eq!(toom4.name(), "Toom4");
+
+        let a = (BigUint::one() << 20_000) / BigUint::from(7u64);
+        let b = (BigUint::one() << 19_000) - BigUint::from(3u64);
+        for strat in [&toom3 as &dyn Multiplier, &toom4] {
+            assert_eq!(strat.multiply(&a, &b), karatsuba.multiply(&a, &b));
+            assert_eq!(strat.square(&a), karatsuba.square(&a));
+        }
+
+        let fk = (BigUint::one() << 8_000) / BigUint::from(3u64);
+        let fk1 = (BigUint::one() << 8_001) / BigUint::from(5u64);
+        let expected = karatsuba.execute_doubling_step(&fk, &fk1);
+        assert_eq!(toom3.execute_doubling_step(&fk, &fk1), expected);
+        assert_eq!(toom4.execute_doubling_step(&fk, &fk1), expected);
+    }
+
+    #[test]
+    fn tier_thresholds_select() {
+        let tiers = TierThresholds {
+            toom3: 1_000,
+            toom4: 5_000,
+            fft: 50_000,
+        };
+        assert_eq!(tiers.select(999), MultiplicationTier::Karatsuba);
+        assert_eq!(tiers.select(1_000), MultiplicationTier::Toom3);
+        assert_eq!(tiers.select(5_000), MultiplicationTier::Toom4);
+        assert_eq!(tiers.select(50_000), MultiplicationTier::Fft);
+
+        let fft_only = TierThresholds::fft_only(50_000);
+        assert_eq!(fft_only.select(49_999), MultiplicationTier::Karatsuba);
+        assert_eq!(AdaptiveStrategy::new(50_000).tiers(), fft_only);
+
+        let opts = Options::default();
+        let from_opts = TierThresholds::from_options(&opts);
+        assert_eq!(from_opts.toom3, opts.toom3_threshold);
+        assert_eq!(from_opts.fft, opts.fft_threshold);
+    }
+
+    #[test]
+    fn adaptive_strategy_uses_toom_tiers() {
+        use num_traits::One;
+
+        let karatsuba = KaratsubaStrategy::new();
+        let adaptive = AdaptiveStrategy::with_tiers(TierThresholds {
+            toom3: 2_000,
+            toom4: 10_000,
+            fft: 40_000,
+        });
+        // Exercises the Toom-4 tier with Toom-3 and Karatsuba point products.
+        let fk = (BigUint::one() << 30_000) / BigUint::from(3u64);
+        let fk1 = (BigUint::one() << 30_001) / BigUint::from(5u64);
+        assert_eq!(
+            adaptive.execute_doubling_step(&fk, &fk1),
+            karatsuba.execute_doubling_step(&fk, &fk1)
+        );
+    }
+
+    #[test]
+    fn fused_sums_agree_across_strategies() {
+        use num_traits::One;
+
+        let a = (BigUint::one() << 30_000) / BigUint::from(3u64);
+        let b = (BigUint::one() << 20_001) / BigUint::from(7u64);
+        let c = BigUint::from(1_234_567u64);
+        let terms = [(&a, &b), (&b, &b), (&c, &a)];
+        let sum = &a * &b + &b * &b + &c * &a;
+        let squares = &a * &a + &b * &b;
+        let strategies: [&dyn DoublingStepExecutor; 4] = [
+            &KaratsubaStrategy::new(),
+            &Toom3Strategy::new(2_000),
+            &FFTOnlyStrategy::new(),
+            &AdaptiveStrategy::new(10_000),
+        ];
+        for strat in strategies {
+            assert_eq!(strat.sum_of_products(&terms), sum, "{}", strat.name());
+            assert_eq!(strat.sqr_add_sqr(&a, &b), squares, "{}", strat.name());
+        }
+    }
+
+    #[test]
+    fn fft_doubling_steps_match_karatsuba() {
+        use num_traits::One;
+
+        let fft = FFTOnlyStrategy::new();
+        let adaptive = AdaptiveStrategy::new(10_000);
+        let karatsuba = KaratsubaStrategy::new();
+        let fk = (BigUint::one() << 30_000) / BigUint::from(3u64);
+        let fk1 = (BigUint::one() << 30_001) / BigUint::from(5u64);
+
+        let expected = karatsuba.execute_doubling_step(&fk, &fk1);
+        assert_eq!(fft.execute_doubling_step(&fk, &fk1), expected);
+        assert_eq!(adaptive.execute_doubling_step(&fk, &fk1), expected);
+
+        let strategies: [&dyn DoublingStepExecutor; 3] = [&fft, &adaptive, &karatsuba];
+        for strategy in strategies {
+            let (mut f2k, mut f2k1) = (fk1.clone(), fk.clone());
+            strategy.execute_doubling_step_to(&mut f2k, &mut f2k1, &fk, &fk1);
+            assert_eq!((f2k, f2k1), expected, "{}", strategy.name());
+        }
+        // Two steps each: F(k)'s transform is a cache hit once per step.
+        assert_eq!(fft.cache_stats().hits, 2);
+        assert_eq!(adaptive.cache_stats().hits, 2);
+
+        let small = BigUint::from(5u64);
+        assert_eq!(
+            adaptive.execute_doubling_step(&small, &small),
+            (BigUint::from(25u64), BigUint::from(50u64))
+        );
+    }
 }
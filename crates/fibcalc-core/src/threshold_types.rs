@@ -26,6 +26,8 @@ pub struct IterationMetric {
     pub duration_ns: u64,
     /// Which multiplication method was actually used.
     pub method: MultiplicationMethod,
+    /// Whether the products ran in parallel.
+    pub parallel: bool,
     /// Number of bits processed in this iteration.
     pub bits_processed: u64,
     /// Whether a cache hit occurred for this iteration.
@@ -33,7 +35,7 @@ pub struct IterationMetric {
 }
 
 impl IterationMetric {
-    /// Create a basic metric with minimal fields (method defaults to Karatsuba).
+    /// Create a basic metric with minimal fields (method defaults to sequential Karatsuba).
     #[must_use]
     pub fn basic(
         bit_length: usize,
@@ -47,6 +49,7 @@ impl IterationMetric {
             parallel_speedup,
             duration_ns,
             method: MultiplicationMethod::Karatsuba,
+            parallel: false,
             bits_processed: bit_length as u64,
             cache_hit: false,
         }
@@ -144,6 +147,7 @@ mod tests {
         let m = IterationMetric::basic(1000, 0.1, 0.05, 500_000);
         assert_eq!(m.bit_length, 1000);
         assert_eq!(m.method, MultiplicationMethod::Karatsuba);
+        assert!(!m.parallel);
         assert_eq!(m.bits_processed, 1000);
         assert!(!m.cache_hit);
     }
@@ -0,0 +1,241 @@
+//! Toom-Cook 3-way and 4-way multiplication.
+//!
+//! Operands are split into `k` pieces of `s` bits, read as polynomials
+//! evaluated at `x = 2^s`. The product polynomial is evaluated at
+//! `2k - 1` points, with one recursive product per point, and recovered
+//! by exact interpolation:
+//!
+//! - Toom-3: points `0, 1, -1, 2, ∞` (5 products of ~n/3 bits).
+//! - Toom-4: points `0, 1, -1, 2, -2, 3, ∞` (7 products of ~n/4 bits).
+//!
+//! Point products go through caller-supplied closures, so a strategy can
+//! route each one back through its own tier selection.
+
+use num_bigint::{BigInt, BigUint, Sign};
+
+/// Below this size a split would not shrink the point products, so the
+/// Toom functions multiply directly.
+const TOOM_MIN_BITS: usize = 1024;
+
+/// Product of two (possibly negative) point values through `mul`.
+fn signed_mul(a: &BigInt, b: &BigInt, mul: &dyn Fn(&BigUint, &BigUint) -> BigUint) -> BigInt {
+    let sign = a.sign() * b.sign();
+    BigInt::from_biguint(sign, mul(a.magnitude(), b.magnitude()))
+}
+
+/// Square of a point value through `sqr`; always non-negative.
+fn signed_sqr(a: &BigInt, sqr: &dyn Fn(&BigUint) -> BigUint) -> BigInt {
+    BigInt::from_biguint(Sign::Plus, sqr(a.magnitude()))
+}
+
+/// Split `a` into `k` little-endian pieces of `piece_bits` bits.
+fn split(a: &BigUint, k: usize, piece_bits: usize) -> Vec<BigInt> {
+    let mask = (BigUint::from(1u32) << piece_bits) - 1u32;
+    (0..k)
+        .map(|i| BigInt::from((a >> (i * piece_bits)) & &mask))
+        .collect()
+}
+
+/// Piece size so that both operands fit in `k` pieces.
+#[allow(clippy::cast_possible_truncation)]
+fn piece_bits(a: &BigUint, b: &BigUint, k: usize) -> usize {
+    let bits = a.bits().max(b.bits()) as usize;
+    bits.div_ceil(k)
+}
+
+/// `sum coeffs[i] * 2^(i * piece_bits)`; the coefficients of a product of
+/// non-negative polynomials are non-negative.
+fn recompose(coeffs: &[BigInt], piece_bits: usize) -> BigUint {
+    coeffs
+        .iter()
+        .enumerate()
+        .fold(BigUint::ZERO, |acc, (i, c)| {
+            let c = c
+                .to_biguint()
+                .expect("Toom interpolation yields non-negative coefficients");
+            acc + (c << (i * piece_bits))
+        })
+}
+
+/// Evaluate `p0 + p1 x + p2 x^2` at `0, 1, -1, 2` (∞ is `p2`).
+fn eval3(p: &[BigInt]) -> [BigInt; 5] {
+    let even = &p[0] + &p[2];
+    [
+        p[0].clone(),
+        &even + &p[1],
+        &even - &p[1],
+        &p[0] + (&p[1] << 1u32) + (&p[2] << 2u32),
+        p[2].clone(),
+    ]
+}
+
+/// Recover the 5 coefficients of a degree-4 product from its values at
+/// `0, 1, -1, 2, ∞`.
+fn interpolate3(r: [BigInt; 5]) -> [BigInt; 5] {
+    let [r0, r1, rm1, r2, rinf] = r;
+    // Even part: c0 + c2 + c4; odd part: c1 + c3.
+    let even1: BigInt = (&r1 + &rm1) >> 1u32;
+    let odd1: BigInt = (&r1 - &rm1) >> 1u32;
+    let c2 = &even1 - &r0 - &rinf;
+    // (r(2) - c0 - 4 c2 - 16 c4) / 2 = c1 + 4 c3
+    let w: BigInt = (&r2 - &r0 - (&c2 << 2u32) - (&rinf << 4u32)) >> 1u32;
+    let c3: BigInt = (&w - &odd1) / 3;
+    let c1 = &odd1 - &c3;
+    [r0, c1, c2, c3, rinf]
+}
+
+/// Evaluate `p0 + p1 x + p2 x^2 + p3 x^3` at `0, 1, -1, 2, -2, 3`
+/// (∞ is `p3`).
+fn eval4(p: &[BigInt]) -> [BigInt; 7] {
+    let even1 = &p[0] + &p[2];
+    let odd1 = &p[1] + &p[3];
+    let even2 = &p[0] + (&p[2] << 2u32);
+    let odd2 = (&p[1] << 1u32) + (&p[3] << 3u32);
+    let at3 = &p[0] + &p[1] * 3 + &p[2] * 9 + &p[3] * 27;
+    [
+        p[0].clone(),
+        &even1 + &odd1,
+        &even1 - &odd1,
+        &even2 + &odd2,
+        &even2 - &odd2,
+        at3,
+        p[3].clone(),
+    ]
+}
+
+/// Recover the 7 coefficients of a degree-6 product from its values at
+/// `0, 1, -1, 2, -2, 3, ∞`.
+fn interpolate4(values: [BigInt; 7]) -> [BigInt; 7] {
+    let [r0, r1, rm1, r2, rm2, r3, rinf] = values;
+    // Even coefficients: c2 + c4 = even1 and 4 c2 + 16 c4 = even2.
+    let even1: BigInt = ((&r1 + &rm1) >> 1u32) - &r0 - &rinf;
+    let even2: BigInt = ((&r2 + &rm2) >> 1u32) - &r0 - (&rinf << 6u32);
+    let c4: BigInt = (&even2 - (&even1 << 2u32)) / 12;
+    let c2 = &even1 - &c4;
+    // Odd coefficients: c1 + c3 + c5, c1 + 4 c3 + 16 c5, c1 + 9 c3 + 81 c5.
+    let odd1: BigInt = (&r1 - &rm1) >> 1u32;
+    let odd2: BigInt = (&r2 - &rm2) >> 2u32;
+    let odd3: BigInt = (&r3 - &r0 - &c2 * 9 - &c4 * 81 - &rinf * 729) / 3;
+    let odd21: BigInt = (&odd2 - &odd1) / 3; // c3 + 5 c5
+    let odd32: BigInt = (&odd3 - &odd2) / 5; // c3 + 13 c5
+    let c5: BigInt = (&odd32 - &odd21) >> 3u32;
+    let c3 = &odd21 - &c5 * 5;
+    let c1 = &odd1 - &c3 - &c5;
+    [r0, c1, c2, c3, c4, c5, rinf]
+}
+
+/// Toom-3 product of `a` and `b`, with point products through `mul`.
+#[must_use]
+pub(crate) fn toom3_mul(
+    a: &BigUint,
+    b: &BigUint,
+    mul: &dyn Fn(&BigUint, &BigUint) -> BigUint,
+) -> BigUint {
+    let s = piece_bits(a, b, 3);
+    if s * 3 < TOOM_MIN_BITS {
+        return a * b;
+    }
+    let ea = eval3(&split(a, 3, s));
+    let eb = eval3(&split(b, 3, s));
+    let r = std::array::from_fn(|i| signed_mul(&ea[i], &eb[i], mul));
+    recompose(&interpolate3(r), s)
+}
+
+/// Toom-3 square of `a`: one evaluation, five point squares through `sqr`.
+#[must_use]
+pub(crate) fn toom3_sqr(a: &BigUint, sqr: &dyn Fn(&BigUint) -> BigUint) -> BigUint {
+    let s = piece_bits(a, a, 3);
+    if s * 3 < TOOM_MIN_BITS {
+        return a * a;
+    }
+    let ea = eval3(&split(a, 3, s));
+    let r = std::array::from_fn(|i| signed_sqr(&ea[i], sqr));
+    recompose(&interpolate3(r), s)
+}
+
+/// Toom-4 product of `a` and `b`, with point products through `mul`.
+#[must_use]
+pub(crate) fn toom4_mul(
+    a: &BigUint,
+    b: &BigUint,
+    mul: &dyn Fn(&BigUint, &BigUint) -> BigUint,
+) -> BigUint {
+    let s = piece_bits(a, b, 4);
+    if s * 4 < TOOM_MIN_BITS {
+        return a * b;
+    }
+    let ea = eval4(&split(a, 4, s));
+    let eb = eval4(&split(b, 4, s));
+    let r = std::array::from_fn(|i| signed_mul(&ea[i], &eb[i], mul));
+    recompose(&interpolate4(r), s)
+}
+
+/// Toom-4 square of `a`: one evaluation, seven point squares through `sqr`.
+#[must_use]
+pub(crate) fn toom4_sqr(a: &BigUint, sqr: &dyn Fn(&BigUint) -> BigUint) -> BigUint {
+    let s = piece_bits(a, a, 4);
+    if s * 4 < TOOM_MIN_BITS {
+        return a * a;
+    }
+    let ea = eval4(&split(a, 4, s));
+    let r = std::array::from_fn(|i| signed_sqr(&ea[i], sqr));
+    recompose(&interpolate4(r), s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_traits::One;
+
+    fn schoolbook(a: &BigUint, b: &BigUint) -> BigUint {
+        a * b
+    }
+
+    fn operands() -> Vec<(BigUint, BigUint)> {
+        vec![
+            (BigUint::from(12_345u64), BigUint::from(67_890u64)),
+            (
+                (BigUint::one() << 5_000) - BigUint::one(),
+                (BigUint::one() << 5_000) - BigUint::one(),
+            ),
+            (
+                (BigUint::one() << 12_345) / BigUint::from(7u64),
+                (BigUint::one() << 3_001) + BigUint::from(99u64),
+            ),
+            (
+                BigUint::ZERO,
+                (BigUint::one() << 4_096) / BigUint::from(3u64),
+            ),
+        ]
+    }
+
+    #[test]
+    fn toom3_matches_schoolbook() {
+        for (a, b) in operands() {
+            assert_eq!(toom3_mul(&a, &b, &schoolbook), &a * &b);
+            assert_eq!(toom3_sqr(&a, &|x| x * x), &a * &a);
+        }
+    }
+
+    #[test]
+    fn toom4_matches_schoolbook() {
+        for (a, b) in operands() {
+            assert_eq!(toom4_mul(&a, &b, &schoolbook), &a * &b);
+            assert_eq!(toom4_sqr(&a, &|x| x * x), &a * &a);
+        }
+    }
+
+    #[test]
+    fn recursive_point_products() {
+        fn rec3(a: &BigUint, b: &BigUint) -> BigUint {
+            toom3_mul(a, b, &rec3)
+        }
+        fn rec4(a: &BigUint) -> BigUint {
+            toom4_sqr(a, &rec4)
+        }
+        let a = (BigUint::one() << 40_000) / BigUint::from(13u64);
+        let b = (BigUint::one() << 39_999) - BigUint::from(5u64);
+        assert_eq!(rec3(&a, &b), &a * &b);
+        assert_eq!(rec4(&a), &a * &a);
+    }
+}
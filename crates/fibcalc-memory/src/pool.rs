@@ -3,17 +3,18 @@
 use std::collections::HashMap;
 
 use num_bigint::BigUint;
+use num_traits::Zero;
 use parking_lot::Mutex;
 
 use crate::stats::{AtomicPoolStats, PoolStats};
 
 /// Clear a `BigUint` by setting it to zero.
 ///
-/// `BigUint::ZERO` is the simplest approach -- num-bigint doesn't expose its
-/// internal Vec, so we can't preserve capacity. The allocation cost is negligible
-/// compared to computation.
+/// `set_zero` truncates num-bigint's digit vector without freeing it, so a
+/// pooled value keeps its capacity and in-place writes such as
+/// `assign_from_slice` reuse it.
 fn clear_value(value: &mut BigUint) {
-    *value = BigUint::ZERO;
+    value.set_zero();
 }
 
 /// Pool for `BigUint` objects, organized by size class (power of 4).
@@ -3,6 +3,8 @@
 use std::sync::Arc;
 
 use fibcalc_core::calculator::{Calculator, FibError};
+use fibcalc_core::lucas::Sequence;
+use fibcalc_core::options::Options;
 use fibcalc_core::registry::CalculatorFactory;
 
 /// Get calculators to run based on algorithm selection.
@@ -30,6 +32,50 @@ pub fn get_calculators_to_run(
     }
 }
 
+/// Get calculators to run for `algo`, switching to their modular variants
+/// when `opts` asks for F(n) modulo `--modulus` or `10^k`, and to their
+/// sequence variants when `opts.sequence` is not Fibonacci.
+///
+/// Every selected algorithm is replaced by the calculator that computes
+/// the requested value; `"all"` runs each distinct variant once, so the
+/// results still cross-validate. The sequence variants handle modular runs
+/// themselves.
+///
+/// # Errors
+///
+/// Returns `FibError` if the requested algorithm name is unknown or has
+/// no variant for the requested mode.
+pub fn get_calculators_for(
+    algo: &str,
+    factory: &dyn CalculatorFactory,
+    opts: &Options,
+) -> Result<Vec<Arc<dyn Calculator>>, FibError> {
+    let sequence = opts.sequence != Sequence::Fibonacci;
+    if !sequence && !opts.is_modular() {
+        return get_calculators_to_run(algo, factory);
+    }
+    let variant = |name: &str| {
+        let (variant, mode) = if sequence {
+            (factory.sequence_variant(name), "sequence")
+        } else {
+            (factory.modular_variant(name), "modular")
+        };
+        variant.ok_or_else(|| FibError::Config(format!("{name} has no {mode} variant")))
+    };
+    let mut names: Vec<&str> = Vec::new();
+    if algo == "all" {
+        for name in factory.available() {
+            let variant = variant(name)?;
+            if !names.contains(&variant) {
+                names.push(variant);
+            }
+        }
+    } else {
+        names.push(variant(algo)?);
+    }
+    names.into_iter().map(|name| factory.get(name)).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -68,6 +114,78 @@ mod tests {
         assert_eq!(calcs[0].name(), "FastDoubling");
     }
 
+    fn last_digits(k: u32) -> Options {
+        Options {
+            last_digits: Some(k),
+            ..Options::default()
+        }
+    }
+
+    #[test]
+    fn select_all_last_digits() {
+        let factory = DefaultFactory::new();
+        let calcs = get_calculators_for("all", &factory, &last_digits(10)).unwrap();
+        let names: Vec<_> = calcs.iter().map(|c| c.name()).collect();
+        assert_eq!(names, ["FastDoublingMod", "MatrixExponentiationMod"]);
+    }
+
+    #[test]
+    fn select_single_last_digits() {
+        let factory = DefaultFactory::new();
+        let calcs = get_calculators_for("fft", &factory, &last_digits(10)).unwrap();
+        assert_eq!(calcs.len(), 1);
+        assert_eq!(calcs[0].name(), "FastDoublingMod");
+        assert!(get_calculators_for("unknown", &factory, &last_digits(10)).is_err());
+    }
+
+    #[test]
+    fn select_with_modulus() {
+        let factory = DefaultFactory::new();
+        let opts = Options {
+            modulus: Some(Arc::new(97u32.into())),
+            ..Options::default()
+        };
+        let calcs = get_calculators_for("matrix", &factory, &opts).unwrap();
+        assert_eq!(calcs[0].name(), "MatrixExponentiationMod");
+    }
+
+    #[test]
+    fn select_sequence_variants() {
+        let factory = DefaultFactory::new();
+        for modulus in [None, Some(Arc::new(97u32.into()))] {
+            let opts = Options {
+                sequence: Sequence::Lucas,
+                modulus,
+                ..Options::default()
+            };
+            let names: Vec<&str> = get_calculators_for("all", &factory, &opts)
+                .unwrap()
+                .iter()
+                .map(|c| c.name())
+                .collect();
+            assert_eq!(names, ["LucasFastDoubling", "LucasMatrix"]);
+        }
+        let opts = Options {
+            sequence: Sequence::LucasU { p: 3, q: 2 },
+            ..Options::default()
+        };
+        let calcs = get_calculators_for("matrix", &factory, &opts).unwrap();
+        assert_eq!(calcs[0].name(), "LucasMatrix");
+        let Err(err) = get_calculators_for("unknown", &factory, &opts) else {
+            panic!("unknown algorithm was accepted");
+        };
+        assert!(err.to_string().contains("no sequence variant"), "{err}");
+    }
+
+    #[test]
+    fn select_without_last_digits() {
+        let factory = DefaultFactory::new();
+        for opts in [Options::default(), last_digits(0)] {
+            let calcs = get_calculators_for("matrix", &factory, &opts).unwrap();
+            assert_eq!(calcs[0].name(), "MatrixExponentiation");
+        }
+    }
+
     #[test]
     fn select_unknown() {
         let factory = DefaultFactory::new();
@@ -33,15 +33,34 @@ pub trait ResultPresenter: Send + Sync {
 
     /// Present an error.
     fn present_error(&self, error: &str);
+
+    /// Present every result of a run, then the comparison if several
+    /// algorithms ran.
+    ///
+    /// The default forwards to the per-result methods; presenters that emit
+    /// a single document for the whole run override it.
+    fn present_all(&self, n: u64, results: &[CalculationResult], details: bool) {
+        for result in results {
+            match &result.outcome {
+                Ok(value) => {
+                    self.present_result(&result.algorithm, n, value, result.duration, details);
+                }
+                Err(error) => self.present_error(&error.to_string()),
+            }
+        }
+        if results.len() > 1 {
+            self.present_comparison(results);
+        }
+    }
 }
 
-/// Result of a single calculation.
+/// Result of a single calculation; `T` is `BigInt` for signed indices.
 #[derive(Debug, Clone)]
-pub struct CalculationResult {
+pub struct CalculationResult<T = BigUint> {
     /// Algorithm name.
     pub algorithm: String,
     /// The computed value or a structured error.
-    pub outcome: Result<BigUint, FibError>,
+    pub outcome: Result<T, FibError>,
     /// Computation duration.
     pub duration: Duration,
 }
@@ -10,6 +10,7 @@ use fibcalc_core::observer::ProgressObserver;
 use fibcalc_core::observers::NoOpObserver;
 use fibcalc_core::options::Options;
 use fibcalc_core::progress::CancellationToken;
+use num_bigint::BigInt;
 
 use crate::interfaces::CalculationResult;
 
@@ -33,13 +34,39 @@ pub fn execute_calculations_with_observer(
     timeout: Option<Duration>,
     observer: &dyn ProgressObserver,
 ) -> Vec<CalculationResult> {
+    execute_with(calculators, timeout, |calc, i| {
+        calc.calculate(cancel, observer, i, n, opts)
+    })
+}
+
+/// Execute calculations of the term at a signed index with all given
+/// calculators, through [`Calculator::calculate_signed`].
+pub fn execute_signed_calculations(
+    calculators: &[Arc<dyn Calculator>],
+    n: i64,
+    opts: &Options,
+    cancel: &CancellationToken,
+    timeout: Option<Duration>,
+) -> Vec<CalculationResult<BigInt>> {
+    let observer = NoOpObserver::new();
+    execute_with(calculators, timeout, |calc, i| {
+        calc.calculate_signed(cancel, &observer, i, n, opts)
+    })
+}
+
+/// Run `run` once per calculator, in parallel when there are several.
+fn execute_with<T: Send>(
+    calculators: &[Arc<dyn Calculator>],
+    timeout: Option<Duration>,
+    run: impl Fn(&dyn Calculator, usize) -> Result<T, FibError> + Sync,
+) -> Vec<CalculationResult<T>> {
     let start_time = Instant::now();
 
     if calculators.len() == 1 {
         // Single calculator: run directly
         let calc = &calculators[0];
         let start = Instant::now();
-        let result = calc.calculate(cancel, observer, 0, n, opts);
+        let result = run(calc.as_ref(), 0);
         let duration = start.elapsed();
 
         return vec![CalculationResult {
@@ -50,7 +77,7 @@ pub fn execute_calculations_with_observer(
     }
 
     // Multiple calculators: run in parallel using rayon
-    let results: Vec<CalculationResult> = calculators
+    let results: Vec<CalculationResult<T>> = calculators
         .iter()
         .enumerate()
         .collect::<Vec<_>>()
@@ -69,7 +96,7 @@ pub fn execute_calculations_with_observer(
                 }
             }
 
-            let result = calc.calculate(cancel, observer, i, n, opts);
+            let result = run(calc.as_ref(), i);
             let duration = start.elapsed();
 
             CalculationResult {
@@ -89,8 +116,10 @@ pub fn execute_calculations_with_observer(
 ///
 /// Returns `FibError::Calculation` if no valid results exist, or
 /// `FibError::Mismatch` if results disagree.
-pub fn analyze_comparison_results(results: &[CalculationResult]) -> Result<(), FibError> {
-    let valid_results: Vec<&CalculationResult> =
+pub fn analyze_comparison_results<T: PartialEq>(
+    results: &[CalculationResult<T>],
+) -> Result<(), FibError> {
+    let valid_results: Vec<&CalculationResult<T>> =
         results.iter().filter(|r| r.outcome.is_ok()).collect();
 
     if valid_results.is_empty() {
@@ -139,6 +168,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn execute_signed_calculations_apply_the_sign() {
+        use fibcalc_core::matrix::MatrixExponentiation;
+
+        let calcs: Vec<Arc<dyn Calculator>> = vec![
+            Arc::new(FibCalculator::new(Arc::new(OptimizedFastDoubling::new()))),
+            Arc::new(FibCalculator::new(Arc::new(MatrixExponentiation::new()))),
+        ];
+        let cancel = CancellationToken::new();
+        let results = execute_signed_calculations(&calcs, -10, &Options::default(), &cancel, None);
+        assert_eq!(results.len(), 2);
+        for result in &results {
+            assert_eq!(result.outcome.as_ref().unwrap(), &BigInt::from(-55));
+        }
+        assert!(analyze_comparison_results(&results).is_ok());
+    }
+
     #[test]
     fn analyze_matching_results() {
         let results = vec![
@@ -178,7 +224,7 @@ mod tests {
 
     #[test]
     fn analyze_no_valid_results() {
-        let results = vec![CalculationResult {
+        let results: Vec<CalculationResult> = vec![CalculationResult {
             algorithm: "A".into(),
             outcome: Err(FibError::Calculation("failed".into())),
             duration: Duration::from_millis(1),
@@ -7,11 +7,16 @@ use ratatui::widgets::{Block, Borders, Paragraph};
 use ratatui::Frame;
 
 /// Render the header panel.
-pub fn render_header(frame: &mut Frame, area: Rect, n: u64, algo: &str) {
-    let text = vec![Line::from(vec![
+pub fn render_header(frame: &mut Frame, area: Rect, n: u64, algo: &str, paused: bool) {
+    let mut spans = vec![
         Span::styled("FibCalc-rs", Style::default().fg(Color::Cyan)),
         Span::raw(format!(" | N={n} | Algorithm: {algo}")),
-    ])];
+    ];
+    if paused {
+        spans.push(Span::raw(" | "));
+        spans.push(Span::styled("PAUSED", Style::default().fg(Color::Yellow)));
+    }
+    let text = vec![Line::from(spans)];
 
     let block = Block::default()
         .borders(Borders::BOTTOM)
@@ -34,7 +39,7 @@ mod tests {
         terminal
             .draw(|frame| {
                 let area = frame.area();
-                render_header(frame, area, 1000, "FastDoubling");
+                render_header(frame, area, 1000, "FastDoubling", false);
             })
             .unwrap();
     }
@@ -46,7 +51,7 @@ mod tests {
         let buf = terminal
             .draw(|frame| {
                 let area = frame.area();
-                render_header(frame, area, 42, "Matrix");
+                render_header(frame, area, 42, "Matrix", false);
             })
             .unwrap();
 
@@ -70,7 +75,7 @@ mod tests {
         let buf = terminal
             .draw(|frame| {
                 let area = frame.area();
-                render_header(frame, area, 100, "FFT-Based");
+                render_header(frame, area, 100, "FFT-Based", false);
             })
             .unwrap();
 
@@ -93,7 +98,7 @@ mod tests {
         terminal
             .draw(|frame| {
                 let area = frame.area();
-                render_header(frame, area, 999_999, "FastDoubling");
+                render_header(frame, area, 999_999, "FastDoubling", false);
             })
             .unwrap();
     }
@@ -105,8 +110,31 @@ mod tests {
         terminal
             .draw(|frame| {
                 let area = frame.area();
-                render_header(frame, area, 0, "None");
+                render_header(frame, area, 0, "None", false);
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn render_header_shows_paused() {
+        let backend = TestBackend::new(80, 3);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let buf = terminal
+            .draw(|frame| {
+                let area = frame.area();
+                render_header(frame, area, 42, "Matrix", true);
             })
             .unwrap();
+
+        let mut all_content = String::new();
+        for y in 0..buf.area.height {
+            for x in 0..buf.area.width {
+                all_content.push_str(buf.buffer[(x, y)].symbol());
+            }
+        }
+        assert!(
+            all_content.contains("PAUSED"),
+            "Buffer did not contain PAUSED: {all_content}"
+        );
     }
 }
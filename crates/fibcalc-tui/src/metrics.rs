@@ -91,9 +91,12 @@ pub fn render_metrics(
     memory_mb: f64,
     cpu_percent: f64,
     throughput_bits_per_sec: f64,
+    eta_secs: Option<f64>,
 ) {
+    let eta = eta_secs.map_or_else(|| "--".to_string(), |secs| format!("{secs:.1}s"));
     let text = vec![
         Line::raw(format!("Elapsed:    {elapsed_secs:.1}s")),
+        Line::raw(format!("ETA:        {eta}")),
         Line::raw(format!("Memory:     {memory_mb:.1} MB")),
         Line::raw(format!("CPU:        {cpu_percent:.0}%")),
         Line::raw(format!(
@@ -211,7 +214,7 @@ mod tests {
         terminal
             .draw(|frame| {
                 let area = frame.area();
-                render_metrics(frame, area, 12.5, 2048.0, 75.0, 500_000.0);
+                render_metrics(frame, area, 12.5, 2048.0, 75.0, 500_000.0, None);
             })
             .unwrap();
     }
@@ -223,7 +226,7 @@ mod tests {
         let buf = terminal
             .draw(|frame| {
                 let area = frame.area();
-                render_metrics(frame, area, 42.3, 1024.0, 50.0, 0.0);
+                render_metrics(frame, area, 42.3, 1024.0, 50.0, 0.0, None);
             })
             .unwrap();
 
@@ -242,15 +245,15 @@ mod tests {
         let buf = terminal
             .draw(|frame| {
                 let area = frame.area();
-                render_metrics(frame, area, 0.0, 512.5, 0.0, 0.0);
+                render_metrics(frame, area, 0.0, 512.5, 0.0, 0.0, None);
             })
             .unwrap();
 
-        let row2: String = (0..buf.area.width)
-            .map(|x| buf.buffer[(x, 2)].symbol().to_string())
+        let row3: String = (0..buf.area.width)
+            .map(|x| buf.buffer[(x, 3)].symbol().to_string())
             .collect();
-        assert!(row2.contains("Memory"));
-        assert!(row2.contains("512.5"));
+        assert!(row3.contains("Memory"));
+        assert!(row3.contains("512.5"));
     }
 
     #[test]
@@ -260,15 +263,33 @@ mod tests {
         let buf = terminal
             .draw(|frame| {
                 let area = frame.area();
-                render_metrics(frame, area, 0.0, 0.0, 99.0, 0.0);
+                render_metrics(frame, area, 0.0, 0.0, 99.0, 0.0, None);
             })
             .unwrap();
 
-        let row3: String = (0..buf.area.width)
-            .map(|x| buf.buffer[(x, 3)].symbol().to_string())
+        let row4: String = (0..buf.area.width)
+            .map(|x| buf.buffer[(x, 4)].symbol().to_string())
+            .collect();
+        assert!(row4.contains("CPU"));
+        assert!(row4.contains("99"));
+    }
+
+    #[test]
+    fn render_metrics_contains_eta() {
+        let backend = TestBackend::new(40, 8);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let buf = terminal
+            .draw(|frame| {
+                let area = frame.area();
+                render_metrics(frame, area, 10.0, 0.0, 0.0, 0.0, Some(30.0));
+            })
+            .unwrap();
+
+        let row2: String = (0..buf.area.width)
+            .map(|x| buf.buffer[(x, 2)].symbol().to_string())
             .collect();
-        assert!(row3.contains("CPU"));
-        assert!(row3.contains("99"));
+        assert!(row2.contains("ETA"));
+        assert!(row2.contains("30.0s"));
     }
 
     #[test]
@@ -278,7 +299,7 @@ mod tests {
         terminal
             .draw(|frame| {
                 let area = frame.area();
-                render_metrics(frame, area, 0.0, 0.0, 0.0, 0.0);
+                render_metrics(frame, area, 0.0, 0.0, 0.0, 0.0, None);
             })
             .unwrap();
     }
@@ -290,7 +311,7 @@ mod tests {
         terminal
             .draw(|frame| {
                 let area = frame.area();
-                render_metrics(frame, area, 100.0, 4096.0, 100.0, 0.0);
+                render_metrics(frame, area, 100.0, 4096.0, 100.0, 0.0, None);
             })
             .unwrap();
     }
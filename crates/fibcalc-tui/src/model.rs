@@ -8,6 +8,7 @@ use crossbeam_channel::Receiver;
 use crossterm::event::{self, Event};
 use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
 use crossterm::{event::DisableMouseCapture, event::EnableMouseCapture, execute};
+use fibcalc_core::progress::PauseToken;
 use ratatui::backend::CrosstermBackend;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::Terminal;
@@ -76,6 +77,12 @@ pub struct TuiApp {
     last_progress_sum: f64,
     /// Last timestamp for throughput calculation.
     last_throughput_time: Option<Instant>,
+    /// Token used to pause and resume the running calculation.
+    pause_token: Option<PauseToken>,
+    /// When the current pause started.
+    paused_at: Option<Instant>,
+    /// Total time spent paused since the calculation started.
+    paused_total: Duration,
 }
 
 impl TuiApp {
@@ -107,6 +114,9 @@ impl TuiApp {
             finished_elapsed: None,
             last_progress_sum: 0.0,
             last_throughput_time: None,
+            pause_token: None,
+            paused_at: None,
+            paused_total: Duration::ZERO,
         }
     }
 
@@ -115,6 +125,11 @@ impl TuiApp {
         self.n_value = n;
     }
 
+    /// Set the token used to pause and resume the background calculation.
+    pub fn set_pause_token(&mut self, token: PauseToken) {
+        self.pause_token = Some(token);
+    }
+
     /// Get the current generation.
     #[must_use]
     pub fn generation(&self) -> u64 {
@@ -168,7 +183,10 @@ impl TuiApp {
                 }
             }
             TuiMessage::Started => {
-                self.start_time = Some(Instant::now());
+                let now = Instant::now();
+                self.start_time = Some(now);
+                self.paused_at = self.paused.then_some(now);
+                self.paused_total = Duration::ZERO;
                 self.finished_elapsed = None;
                 self.last_progress_sum = 0.0;
                 self.last_throughput_time = None;
@@ -202,14 +220,16 @@ impl TuiApp {
                 self.logs.push_back(format!("[ERROR] {err}"));
             }
             TuiMessage::Finished => {
-                self.finished_elapsed = self.start_time.map(|t| t.elapsed());
+                self.finished_elapsed = self.elapsed();
             }
             TuiMessage::SystemMetrics(metrics) => {
                 // Ignore updates after calculations have finished
                 if self.finished_elapsed.is_none() {
                     self.cpu_percent = metrics.cpu_percent;
                     self.memory_mb = metrics.memory_mb;
-
+                }
+                // Throughput is frozen while paused
+                if self.finished_elapsed.is_none() && !self.paused {
                     // Calculate throughput from progress delta over time
                     let current_progress_sum: f64 = self.progress.iter().sum();
                     let now = Instant::now();
@@ -247,10 +267,10 @@ impl TuiApp {
                 self.should_quit = true;
             }
             KeyAction::Pause => {
-                self.paused = true;
+                self.set_paused(true);
             }
             KeyAction::Resume => {
-                self.paused = false;
+                self.set_paused(false);
             }
             KeyAction::ToggleDetails => {
                 self.show_details = !self.show_details;
@@ -288,6 +308,32 @@ impl TuiApp {
         }
     }
 
+    /// Pause or resume the calculation and the elapsed timer.
+    fn set_paused(&mut self, paused: bool) {
+        if self.paused == paused {
+            return;
+        }
+        self.paused = paused;
+        if let Some(token) = &self.pause_token {
+            if paused {
+                token.pause();
+            } else {
+                token.resume();
+            }
+        }
+        if paused {
+            self.paused_at = Some(Instant::now());
+            self.handle_message(TuiMessage::Log("Calculation paused".to_string()));
+        } else {
+            if let Some(at) = self.paused_at.take() {
+                self.paused_total += at.elapsed();
+            }
+            // Restart throughput sampling so the pause is not averaged in
+            self.last_throughput_time = None;
+            self.handle_message(TuiMessage::Log("Calculation resumed".to_string()));
+        }
+    }
+
     /// Scroll logs to the bottom.
     fn scroll_logs_to_bottom(&mut self) {
         self.log_scroll_offset = self.logs.len().saturating_sub(1);
@@ -324,11 +370,33 @@ impl TuiApp {
     /// Get the elapsed time since calculation started.
     ///
     /// Returns the frozen elapsed time if calculations have finished,
-    /// or the live elapsed time if still running.
+    /// or the live elapsed time (excluding time spent paused) if still running.
     #[must_use]
     pub fn elapsed(&self) -> Option<Duration> {
-        self.finished_elapsed
-            .or_else(|| self.start_time.map(|t| t.elapsed()))
+        self.finished_elapsed.or_else(|| {
+            self.start_time.map(|start| {
+                let now = self.paused_at.unwrap_or_else(Instant::now);
+                now.duration_since(start).saturating_sub(self.paused_total)
+            })
+        })
+    }
+
+    /// Estimate the time remaining from the average progress so far.
+    ///
+    /// Returns `None` before any progress is reported and once finished.
+    /// Frozen while paused, since neither elapsed time nor progress advances.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn eta(&self) -> Option<Duration> {
+        if self.finished_elapsed.is_some() || self.progress.is_empty() {
+            return None;
+        }
+        let avg = self.progress.iter().sum::<f64>() / self.progress.len() as f64;
+        if avg <= 0.0 || avg >= 1.0 {
+            return None;
+        }
+        let elapsed = self.elapsed()?.as_secs_f64();
+        Some(Duration::from_secs_f64(elapsed * (1.0 - avg) / avg))
     }
 
     /// Compute the adaptive 60/40 layout.
@@ -403,7 +471,7 @@ impl TuiApp {
         } else {
             self.algorithms.join(", ")
         };
-        render_header(frame, header_area, self.n_value, &algo_display);
+        render_header(frame, header_area, self.n_value, &algo_display, self.paused);
 
         // Progress panel (60% top)
         render_progress(frame, progress_area, &self.algorithms, &self.progress);
@@ -441,6 +509,7 @@ impl TuiApp {
             self.memory_mb,
             self.cpu_percent,
             self.throughput_bits_per_sec,
+            self.eta().map(|d| d.as_secs_f64()),
         );
         render_sparkline(frame, sparkline_rect, sparkline_slice, "Throughput");
     }
@@ -685,6 +754,56 @@ mod tests {
         assert!(!app.paused);
     }
 
+    #[test]
+    fn key_action_pause_resume_drives_token() {
+        let (mut app, _tx) = make_app();
+        let token = PauseToken::new();
+        app.set_pause_token(token.clone());
+        app.handle_key_action(KeyAction::Pause);
+        assert!(token.is_paused());
+        app.handle_key_action(KeyAction::Resume);
+        assert!(!token.is_paused());
+    }
+
+    #[test]
+    fn pause_freezes_elapsed_and_eta() {
+        let (mut app, _tx) = make_app();
+        app.handle_message(TuiMessage::Started);
+        app.handle_message(TuiMessage::Progress {
+            index: 0,
+            progress: 0.5,
+            algorithm: "FastDoubling",
+        });
+        std::thread::sleep(Duration::from_millis(5));
+        app.handle_key_action(KeyAction::Pause);
+        let elapsed = app.elapsed().unwrap();
+        let eta = app.eta().unwrap();
+
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(app.elapsed().unwrap(), elapsed);
+        assert_eq!(app.eta().unwrap(), eta);
+
+        // Time spent paused is excluded after resuming
+        app.handle_key_action(KeyAction::Resume);
+        assert!(app.elapsed().unwrap() < elapsed + Duration::from_millis(20));
+    }
+
+    #[test]
+    fn eta_none_without_progress_or_after_finish() {
+        let (mut app, _tx) = make_app();
+        assert!(app.eta().is_none());
+        app.handle_message(TuiMessage::Started);
+        assert!(app.eta().is_none());
+        app.handle_message(TuiMessage::Progress {
+            index: 0,
+            progress: 0.25,
+            algorithm: "FastDoubling",
+        });
+        assert!(app.eta().is_some());
+        app.handle_message(TuiMessage::Finished);
+        assert!(app.eta().is_none());
+    }
+
     #[test]
     fn key_action_toggle_details() {
         let (mut app, _tx) = make_app();
@@ -1,22 +1,36 @@
 //! Application entry point and dispatch.
 
+use std::path::Path;
 use std::sync::Arc;
+use std::time::Instant;
 
 use anyhow::Result;
 
-use fibcalc_cli::output::write_to_file;
-use fibcalc_cli::presenter::CLIResultPresenter;
-use fibcalc_core::calculator::Calculator;
+use fibcalc_bigfft::{FFTBackend, FFTLayout, DEFAULT_NTT_THRESHOLD_BITS};
+use fibcalc_calibration::CalibrationProfile;
+use fibcalc_cli::json_presenter::{JsonResultPresenter, ValueField};
+use fibcalc_cli::output::{format_result, write_signed_sequence_term, write_signed_value_file};
+use fibcalc_cli::presenter::{CLIResultPresenter, ResultFormat};
+use fibcalc_core::calculator::{signed_term, Calculator, FibError};
+use fibcalc_core::checkpoint::{self, Checkpoint, CheckpointConfig};
+use fibcalc_core::decimal::DecimalConverter;
+use fibcalc_core::generator_iterative::IterativeGenerator;
+use fibcalc_core::int_expr::parse_uint_expr;
+use fibcalc_core::lucas::Sequence;
+use fibcalc_core::observers::{LoggingObserver, NoOpObserver};
 use fibcalc_core::options::Options;
+use fibcalc_core::pisano::{index_period, pisano_period};
 use fibcalc_core::progress::CancellationToken;
 use fibcalc_core::registry::DefaultFactory;
-use fibcalc_orchestration::calculator_selection::get_calculators_to_run;
-use fibcalc_orchestration::interfaces::ResultPresenter;
+use fibcalc_core::threshold_types::ThresholdSnapshot;
+use fibcalc_orchestration::calculator_selection::get_calculators_for;
+use fibcalc_orchestration::interfaces::{CalculationResult, ResultPresenter};
 use fibcalc_orchestration::orchestrator::{
-    analyze_comparison_results, execute_calculations, execute_calculations_with_observer,
+    analyze_comparison_results, execute_calculations_with_observer, execute_signed_calculations,
 };
+use num_bigint::{BigInt, BigUint, Sign};
 
-use crate::config::AppConfig;
+use crate::config::{AppConfig, Command, PisanoArgs, SeqArgs, SequenceKind};
 
 /// Return the list of available CPU core IDs on this system.
 ///
@@ -49,6 +63,13 @@ pub fn run(config: &AppConfig) -> Result<()> {
         return Ok(());
     }
 
+    // Handle subcommands
+    match &config.command {
+        Some(Command::Seq(args)) => return run_seq(args),
+        Some(Command::Pisano(args)) => return run_pisano(args),
+        None => {}
+    }
+
     // Handle calibration
     if config.calibrate || config.auto_calibrate {
         return run_calibration(config);
@@ -63,17 +84,104 @@ pub fn run(config: &AppConfig) -> Result<()> {
     run_cli(config)
 }
 
-/// Build `Options` from `AppConfig`, validating the memory-limit string.
-fn build_options(config: &AppConfig) -> Result<Options> {
-    Ok(Options {
-        parallel_threshold: config.threshold,
-        fft_threshold: config.fft_threshold,
-        strassen_threshold: config.strassen_threshold,
+/// Where an effective threshold value came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ThresholdSource {
+    /// Set explicitly on the command line.
+    Cli,
+    /// Read from a saved calibration profile.
+    Profile,
+    /// Built-in default from `fibcalc_core::constants`.
+    Default,
+}
+
+impl ThresholdSource {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Cli => "CLI flag",
+            Self::Profile => "profile",
+            Self::Default => "built-in default",
+        }
+    }
+}
+
+/// Provenance of each threshold in the resolved `Options`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ThresholdSources {
+    parallel: ThresholdSource,
+    fft: ThresholdSource,
+    strassen: ThresholdSource,
+}
+
+/// Load the calibration profile selected by `--profile` / `--no-profile`.
+///
+/// Without either flag the validated profile from the standard location is
+/// used if one exists. An explicit `--profile` path that cannot be loaded is
+/// an error rather than a silent fallback.
+fn load_calibration_profile(config: &AppConfig) -> Result<Option<CalibrationProfile>> {
+    if config.no_profile {
+        return Ok(None);
+    }
+    match &config.profile {
+        Some(path) => fibcalc_calibration::io::load_validated_profile_from(Path::new(path))
+            .map(Some)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "invalid --profile '{path}': missing, unreadable, or incompatible with this machine"
+                )
+            }),
+        None => Ok(fibcalc_calibration::io::load_validated_profile()),
+    }
+}
+
+/// Pick the CLI value if set, then the profile value, then the default (0).
+fn resolve_threshold(cli: usize, profile: Option<usize>) -> (usize, ThresholdSource) {
+    if cli != 0 {
+        (cli, ThresholdSource::Cli)
+    } else if let Some(value) = profile {
+        (value, ThresholdSource::Profile)
+    } else {
+        (0, ThresholdSource::Default)
+    }
+}
+
+/// Build `Options` from `AppConfig`, validating the memory-limit and
+/// modulus strings.
+///
+/// Thresholds left at 0 on the command line are taken from the calibration
+/// profile when one is available, and from the built-in defaults otherwise.
+/// The Toom-3/Toom-4 tiers have no flags and come from the profile alone.
+fn build_options(config: &AppConfig) -> Result<(Options, ThresholdSources)> {
+    let profile = load_calibration_profile(config)?;
+    let ntt_threshold = resolve_ntt_threshold(config, profile.as_ref());
+    let (parallel_threshold, parallel) = resolve_threshold(
+        config.threshold,
+        profile.as_ref().map(|p| p.parallel_threshold),
+    );
+    let (fft_threshold, fft) = resolve_threshold(
+        config.fft_threshold,
+        profile.as_ref().map(|p| p.fft_threshold),
+    );
+    let (strassen_threshold, strassen) = resolve_threshold(
+        config.strassen_threshold,
+        profile.as_ref().map(|p| p.strassen_threshold),
+    );
+
+    let opts = Options {
+        parallel_threshold,
+        fft_threshold,
+        strassen_threshold,
+        toom3_threshold: profile.as_ref().map_or(0, |p| p.toom3_threshold),
+        toom4_threshold: profile.as_ref().map_or(0, |p| p.toom4_threshold),
+        ntt_threshold,
         last_digits: if config.last_digits == 0 {
             None
         } else {
             Some(config.last_digits)
         },
+        modulus: config.modulus.as_deref().map(parse_modulus).transpose()?,
+        pisano_period: None,
+        sequence: config.sequences()[0],
         memory_limit: if config.memory_limit.is_empty() {
             None
         } else {
@@ -83,33 +191,250 @@ fn build_options(config: &AppConfig) -> Result<Options> {
                 )?,
             )
         },
+        fft_memory_limit: usize::MAX,
+        dynamic_thresholds: config.dynamic_thresholds,
+        checkpoint: build_checkpoint_config(config)?,
+        resume: load_resume_checkpoint(config)?,
         verbose: config.verbose,
         details: config.details,
     }
-    .normalize())
+    .normalize();
+
+    Ok((
+        opts,
+        ThresholdSources {
+            parallel,
+            fft,
+            strassen,
+        },
+    ))
+}
+
+/// Parse `--modulus`, which must be positive.
+fn parse_modulus(text: &str) -> Result<Arc<BigUint>> {
+    let modulus =
+        parse_uint_expr(text).map_err(|e| anyhow::anyhow!("invalid --modulus '{text}': {e}"))?;
+    if modulus == BigUint::ZERO {
+        anyhow::bail!("invalid --modulus '{text}': must be positive");
+    }
+    Ok(Arc::new(modulus))
+}
+
+/// `-n` as a `u64`, for the TUI; the CLI also handles negative and, in
+/// modular runs, larger indices.
+fn small_index(config: &AppConfig) -> Result<u64> {
+    if is_negative_index(config) {
+        anyhow::bail!("--tui needs a non-negative n; run without --tui for F(−n)");
+    }
+    u64::try_from(config.n.magnitude())
+        .map_err(|_| anyhow::anyhow!("--tui needs n below 2^64; run without --tui with --modulus"))
+}
+
+fn is_negative_index(config: &AppConfig) -> bool {
+    config.n.sign() == Sign::Minus
+}
+
+fn index_too_large(config: &AppConfig) -> anyhow::Error {
+    let sign = if is_negative_index(config) { "-" } else { "" };
+    anyhow::anyhow!(
+        "n = {sign}{} is too large; pass --modulus or --last-digits for larger indices",
+        format_result(config.n.magnitude(), false, &mut DecimalConverter::new())
+    )
+}
+
+/// Operand size at which FFT products switch from Fermat to NTT, from
+/// `--fft-backend`.
+///
+/// `auto` switches at the crossover measured by calibration, or at
+/// [`DEFAULT_NTT_THRESHOLD_BITS`] without a profile; `fermat` never
+/// switches and `ntt` always does.
+fn resolve_ntt_threshold(config: &AppConfig, profile: Option<&CalibrationProfile>) -> usize {
+    match FFTBackend::from(config.fft_backend) {
+        FFTBackend::Auto => profile.map_or(DEFAULT_NTT_THRESHOLD_BITS, |p| p.ntt_threshold),
+        FFTBackend::Fermat => usize::MAX,
+        FFTBackend::Ntt => 0,
+    }
+}
+
+/// Build the checkpoint schedule from `--checkpoint*` flags.
+///
+/// When only `--resume` is given, the resumed run keeps checkpointing to the
+/// same file so it can be interrupted and resumed again.
+fn build_checkpoint_config(config: &AppConfig) -> Result<Option<CheckpointConfig>> {
+    let Some(path) = config.checkpoint.as_ref().or(config.resume.as_ref()) else {
+        return Ok(None);
+    };
+    let interval = config.checkpoint_interval_duration().ok_or_else(|| {
+        anyhow::anyhow!(
+            "invalid --checkpoint-interval '{}'",
+            config.checkpoint_interval
+        )
+    })?;
+    Ok(Some(CheckpointConfig {
+        path: path.into(),
+        every_iterations: config.checkpoint_every,
+        interval: Some(interval),
+    }))
+}
+
+/// Load the checkpoint named by `--resume`, if any.
+fn load_resume_checkpoint(config: &AppConfig) -> Result<Option<Arc<Checkpoint>>> {
+    let Some(path) = &config.resume else {
+        return Ok(None);
+    };
+    let cp = Checkpoint::load(Path::new(path))
+        .map_err(|e| anyhow::anyhow!("invalid --resume '{path}': {e}"))?;
+    Ok(Some(Arc::new(cp)))
+}
+
+/// Checkpointing only works for a single calculator with a doubling loop.
+///
+/// A resumed checkpoint is checked against n and the calculator by the
+/// loop itself, when it takes its starting state.
+fn check_checkpoint_target(opts: &Options, calculators: &[Arc<dyn Calculator>]) -> Result<()> {
+    if opts.checkpoint.is_none() && opts.resume.is_none() {
+        return Ok(());
+    }
+    let [calc] = calculators else {
+        anyhow::bail!("checkpointing needs a single algorithm; pass --algo fast or --algo fft");
+    };
+    if !checkpoint::supports(calc.name()) {
+        anyhow::bail!(
+            "{} does not support checkpointing; pass --algo fast or --algo fft",
+            calc.name()
+        );
+    }
+    Ok(())
+}
+
+/// Describe the effective thresholds and where each one came from.
+fn threshold_sources_report(opts: &Options, sources: ThresholdSources) -> String {
+    format!(
+        "Thresholds:\n  Parallel: {} bits ({})\n  FFT: {} bits ({})\n  Strassen: {} bits ({})",
+        opts.parallel_threshold,
+        sources.parallel.label(),
+        opts.fft_threshold,
+        sources.fft.label(),
+        opts.strassen_threshold,
+        sources.strassen.label()
+    )
 }
 
 /// Check whether the estimated memory for computing F(n) fits within the
-/// configured budget.  Bails with a human-readable message when it does not.
-fn check_memory_budget(n: u64, opts: &Options) -> Result<()> {
-    let estimate = fibcalc_core::memory_budget::MemoryEstimate::estimate(n);
-    if !estimate.fits_in(opts.memory_limit) {
+/// configured budget, and cap FFT products to what is left of it.
+fn check_memory_budget(n: u64, opts: &mut Options) -> Result<()> {
+    opts.fft_memory_limit = fft_memory_budget(n, opts)?;
+    Ok(())
+}
+
+/// FFT memory budget for F(n) under `--memory-limit` (`usize::MAX` when
+/// unlimited).  Bails with a human-readable message when the budget cannot
+/// even hold the result and non-FFT temporaries.
+///
+/// FFT workspace does not count against the check: FFT products shrink
+/// their plans (or segment) to stay within whatever the rest leaves.
+/// Modular runs never hold more than a few multiples of the modulus, so
+/// the full-F(n) estimate does not apply to them; nor does it apply to
+/// `U_n(P, Q)` and `V_n(P, Q)`, whose size depends on P and Q.
+fn fft_memory_budget(n: u64, opts: &Options) -> Result<usize> {
+    let Some(limit) = opts.memory_limit else {
+        return Ok(usize::MAX);
+    };
+    if opts.is_modular() || has_parameters(opts.sequence) {
+        return Ok(limit);
+    }
+    let estimate = fibcalc_core::memory_budget::MemoryEstimate::estimate_with_fft_threshold(
+        n,
+        opts.fft_threshold,
+    );
+    let Some(fft_budget) = estimate.fft_budget(limit) else {
         anyhow::bail!(
             "Estimated memory ({} MB) exceeds limit ({} MB)",
             estimate.total_bytes / (1024 * 1024),
-            opts.memory_limit.unwrap_or(0) / (1024 * 1024)
+            limit / (1024 * 1024)
         );
+    };
+    Ok(fft_budget)
+}
+
+/// Layout of the largest FFT product of F(n), or `None` when F(n) stays
+/// below the FFT threshold or only F(n) mod m is computed.
+///
+/// The last doubling step multiplies operands of about half the result size.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+fn largest_fft_layout(n: u64, opts: &Options) -> Option<FFTLayout> {
+    if opts.is_modular() || has_parameters(opts.sequence) {
+        return None;
     }
-    Ok(())
+    let result_bits = (n as f64 * 0.6942).ceil() as usize;
+    let operand_bits = result_bits.div_ceil(2);
+    (operand_bits >= opts.fft_threshold)
+        .then(|| FFTLayout::for_product(operand_bits, operand_bits, &opts.fft_config()))
+}
+
+/// Whether `sequence` is `U_n(P, Q)` or `V_n(P, Q)`, whose growth is not that
+/// of F(n).
+fn has_parameters(sequence: Sequence) -> bool {
+    matches!(sequence, Sequence::LucasU { .. } | Sequence::LucasV { .. })
+}
+
+/// Print the FFT parameters chosen for the largest product of F(n).
+#[allow(clippy::cast_precision_loss)]
+fn print_fft_layout(n: u64, opts: &Options) {
+    let Some(layout) = largest_fft_layout(n, opts) else {
+        return;
+    };
+    println!("FFT layout: {layout}");
+    let limit = opts.fft_memory_limit;
+    if limit != usize::MAX {
+        println!(
+            "  FFT memory budget: {:.1} MiB",
+            limit as f64 / (1024.0 * 1024.0)
+        );
+    }
+}
+
+/// Print the final thresholds and adjustment history of a dynamic run.
+fn print_threshold_snapshot(algorithm: &str, snapshot: &ThresholdSnapshot) {
+    println!("Dynamic thresholds ({algorithm}):");
+    println!(
+        "  Final: parallel {} bits, FFT {} bits, Strassen {} bits",
+        snapshot.parallel_threshold, snapshot.fft_threshold, snapshot.strassen_threshold
+    );
+    println!("  Adjustments: {}", snapshot.adjustment_count);
+    for adj in &snapshot.adjustment_history {
+        println!(
+            "    {}: {} -> {} (benefit {:+.3})",
+            adj.threshold_name, adj.old_value, adj.new_value, adj.trigger_benefit
+        );
+    }
+}
+
+/// Calculators and options resolved from the CLI configuration.
+struct RunSetup {
+    calculators: Vec<Arc<dyn Calculator>>,
+    opts: Options,
+    sources: ThresholdSources,
 }
 
-/// Build options, check memory budget, and create the calculator list.
-fn setup_calculators(config: &AppConfig) -> Result<(Vec<Arc<dyn Calculator>>, Options)> {
-    let opts = build_options(config)?;
-    check_memory_budget(config.n, &opts)?;
+/// Build options, check memory budget, and create the calculator list
+/// for F(n).
+fn setup_calculators(config: &AppConfig, n: u64, cancel: &CancellationToken) -> Result<RunSetup> {
+    let (mut opts, sources) = build_options(config)?;
+    check_memory_budget(n, &mut opts)?;
+    find_pisano_period(config, &BigUint::from(n), &mut opts, cancel)?;
     let factory = DefaultFactory::new();
-    let calculators = get_calculators_to_run(&config.algo, &factory)?;
-    Ok((calculators, opts))
+    let calculators = get_calculators_for(&config.algo, &factory, &opts)?;
+    check_checkpoint_target(&opts, &calculators)?;
+    Ok(RunSetup {
+        calculators,
+        opts,
+        sources,
+    })
 }
 
 fn run_cli(config: &AppConfig) -> Result<()> {
@@ -124,48 +449,283 @@ fn run_cli(config: &AppConfig) -> Result<()> {
 
 /// Core CLI logic shared by `run_cli` (with ctrlc) and tests (without).
 fn run_cli_core(config: &AppConfig, cancel: &CancellationToken) -> Result<()> {
-    let (calculators, opts) = setup_calculators(config)?;
+    let Ok(index) = i64::try_from(&config.n) else {
+        return run_big_index(config, cancel);
+    };
+    let (n, negative) = (index.unsigned_abs(), index < 0);
+    let RunSetup {
+        calculators,
+        opts,
+        sources,
+    } = setup_calculators(config, n, cancel)?;
+    if config.output_header && !config.output_format.is_binary() {
+        anyhow::bail!("--output-header needs --output-format bin-le or bin-be");
+    }
+    if config.output_header && negative {
+        anyhow::bail!("--output-header needs a non-negative n");
+    }
+    let sequences = config.sequences();
+    if sequences.len() > 1 {
+        if config.format == ResultFormat::Json {
+            anyhow::bail!("--sequence lucas-uv prints two results; use --format ndjson");
+        }
+        if config.output.is_some() {
+            anyhow::bail!("--sequence lucas-uv prints two results and cannot use --output");
+        }
+    }
+    if !opts.is_modular() && !sequences.iter().all(|s| s.is_non_negative()) {
+        anyhow::bail!(
+            "--p {} --q {} give negative terms; pass --modulus or --last-digits",
+            config.p,
+            config.q
+        );
+    }
+    let text = config.format == ResultFormat::Text;
+    if config.details && !config.quiet && text {
+        println!("{}", threshold_sources_report(&opts, sources));
+        print_fft_layout(n, &opts);
+    }
     let timeout = Some(config.timeout_duration());
-    let results = execute_calculations(&calculators, config.n, &opts, cancel, timeout);
 
-    // Analyze results
+    // The selected calculators compute every sequence; U_n and V_n of
+    // `lucas-uv` are two runs over the same calculators.
+    let mut results = Vec::new();
+    let mut negated = false;
+    for sequence in sequences {
+        let opts = Options {
+            sequence,
+            ..opts.clone()
+        };
+        let signed = execute_signed_calculations(&calculators, index, &opts, cancel, timeout);
+        // Sequences without integer terms at −n, and resume checkpoints
+        // that do not match the run, fail every calculator alike.
+        if let Some(Err(error @ (FibError::InvalidInput(_) | FibError::Config(_)))) =
+            signed.first().map(|r| &r.outcome)
+        {
+            return Err(error.clone().into());
+        }
+        (results, negated) = split_signs(signed);
+
+        // Analyze results
+        if results.len() > 1 {
+            if let Err(e) = analyze_comparison_results(&results) {
+                eprintln!("Warning: {e}");
+            }
+        }
+
+        // Present results (and the comparison if multiple)
+        let presenter: Box<dyn ResultPresenter> = if text {
+            Box::new(
+                CLIResultPresenter::new(config.verbose, config.quiet)
+                    .with_last_digits(opts.last_digits)
+                    .with_modulus(config.modulus.clone())
+                    .with_sequence(sequence)
+                    .with_negative_index(negative, negated)
+                    .with_converter(DecimalConverter::with_fft_config(opts.fft_config())),
+            )
+        } else {
+            Box::new(
+                JsonResultPresenter::new(config.format, n, json_value_field(config))
+                    .with_sequence(sequence)
+                    .with_negative_index(negative, negated)
+                    .with_converter(DecimalConverter::with_fft_config(opts.fft_config())),
+            )
+        };
+        presenter.present_all(n, &results, config.details);
+    }
+
+    if config.details && !config.quiet && text {
+        for calc in &calculators {
+            if let Some(snapshot) = calc.threshold_snapshot() {
+                print_threshold_snapshot(calc.name(), &snapshot);
+            }
+        }
+    }
+
+    // Write to file if requested
+    if let Some(ref path) = config.output {
+        if let Some(result) = results.iter().find(|r| r.outcome.is_ok()) {
+            let header = config.output_header.then_some(n);
+            write_signed_value_file(
+                path,
+                result.outcome.as_ref().unwrap(),
+                negated,
+                config.output_format,
+                header,
+                &mut DecimalConverter::with_fft_config(opts.fft_config()),
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// F(n) mod m for an index beyond `i64`, by the modular calculators'
+/// loops over the bits of n.
+///
+/// Calculators take `i64` indices, so these runs bypass the orchestrator:
+/// each calculator `--algo` picks runs [`Calculator::calculate_big`], the
+/// results are compared, and only text output is available. Negative
+/// indices use F(−n) = (−1)^{n+1}·F(n) mod m.
+fn run_big_index(config: &AppConfig, cancel: &CancellationToken) -> Result<()> {
+    let (mut opts, _) = build_options(config)?;
+    let Some(modulus) = opts.result_modulus() else {
+        return Err(index_too_large(config));
+    };
+    if config.sequence != SequenceKind::Fib {
+        anyhow::bail!("Lucas sequences need |n| below 2^63");
+    }
+    if config.format != ResultFormat::Text {
+        anyhow::bail!("--format json and ndjson need |n| below 2^63");
+    }
+    if config.output.is_some() || opts.checkpoint.is_some() {
+        anyhow::bail!("--output and checkpoints need |n| below 2^63");
+    }
+    let calculators = get_calculators_for(&config.algo, &DefaultFactory::new(), &opts)?;
+    let magnitude = config.n.magnitude();
+    find_pisano_period(config, magnitude, &mut opts, cancel)?;
+    let negative = is_negative_index(config);
+    let negated = negative && !magnitude.bit(0);
+
+    let mut results = Vec::new();
+    for calc in &calculators {
+        let start = Instant::now();
+        let outcome = calc
+            .calculate_big(cancel, &NoOpObserver::new(), 0, magnitude, &opts)
+            .map(|value| signed_term(value, negated, Some(&modulus)).into_parts().1);
+        // Calculators that need a `u64` index fail alike.
+        if let Err(error @ (FibError::InvalidInput(_) | FibError::Config(_))) = &outcome {
+            return Err(error.clone().into());
+        }
+        results.push(CalculationResult {
+            algorithm: calc.name().to_string(),
+            outcome,
+            duration: start.elapsed(),
+        });
+    }
     if results.len() > 1 {
         if let Err(e) = analyze_comparison_results(&results) {
             eprintln!("Warning: {e}");
         }
     }
 
-    // Present results
-    let presenter = CLIResultPresenter::new(config.verbose, config.quiet);
+    let presenter = CLIResultPresenter::new(config.verbose, config.quiet)
+        .with_last_digits(opts.last_digits)
+        .with_modulus(config.modulus.clone())
+        .with_negative_index(negative, false)
+        .with_converter(DecimalConverter::with_fft_config(opts.fft_config()));
     for result in &results {
-        if let Ok(value) = &result.outcome {
-            presenter.present_result(
-                &result.algorithm,
-                config.n,
-                value,
-                result.duration,
-                config.details,
-            );
-        } else if let Err(error) = &result.outcome {
-            presenter.present_error(&error.to_string());
+        match &result.outcome {
+            Ok(value) => {
+                presenter.present_big_index(&result.algorithm, magnitude, value, result.duration);
+            }
+            Err(error) => presenter.present_error(&error.to_string()),
         }
     }
-
-    // Present comparison if multiple
     if results.len() > 1 {
         presenter.present_comparison(&results);
     }
+    Ok(())
+}
 
-    // Write to file if requested
-    if let Some(ref path) = config.output {
-        if let Some(result) = results.iter().find(|r| r.outcome.is_ok()) {
-            write_to_file(path, result.outcome.as_ref().unwrap())?;
+/// Find the Pisano period of a modular Fibonacci run once, for all of its
+/// calculators, when reducing `n` by it pays off.
+fn find_pisano_period(
+    config: &AppConfig,
+    n: &BigUint,
+    opts: &mut Options,
+    cancel: &CancellationToken,
+) -> Result<()> {
+    if config.sequence == SequenceKind::Fib {
+        if let Some(modulus) = opts.result_modulus() {
+            opts.pisano_period = index_period(n, &modulus, cancel)?.map(Arc::new);
         }
     }
+    Ok(())
+}
+
+/// The magnitudes of signed results, and whether they are negative.
+///
+/// Every calculator computes the same term, so the results share a sign;
+/// residues are reduced into `[0, m)` and are never negative.
+fn split_signs(results: Vec<CalculationResult<BigInt>>) -> (Vec<CalculationResult>, bool) {
+    let mut negative = false;
+    let results = results
+        .into_iter()
+        .map(|result| CalculationResult {
+            outcome: result.outcome.map(|value| {
+                let (sign, magnitude) = value.into_parts();
+                negative |= sign == Sign::Minus;
+                magnitude
+            }),
+            algorithm: result.algorithm,
+            duration: result.duration,
+        })
+        .collect();
+    (results, negative)
+}
+
+fn run_seq(args: &SeqArgs) -> Result<()> {
+    let cancel = CancellationToken::new();
 
+    // Set up Ctrl+C handler
+    let cancel_clone = cancel.clone();
+    ctrlc_handler(cancel_clone);
+
+    run_seq_core(args, &cancel)
+}
+
+/// Stream the terms from `args.from` to `args.to` to the output file or
+/// stdout, walking down with `args.descending`.
+///
+/// Terms are written as they are produced, so memory use stays bounded by
+/// the size of the largest term rather than the length of the range.
+fn run_seq_core(args: &SeqArgs, cancel: &CancellationToken) -> Result<()> {
+    let mut writer: Box<dyn std::io::Write> = match &args.output {
+        Some(path) => Box::new(std::io::BufWriter::new(std::fs::File::create(path)?)),
+        None => Box::new(std::io::BufWriter::new(std::io::stdout().lock())),
+    };
+    let observer = LoggingObserver::new(500);
+    let mut conv = DecimalConverter::new();
+    IterativeGenerator::new().generate_signed(
+        args.from,
+        args.to,
+        args.descending,
+        cancel,
+        &observer,
+        &mut |i, v| {
+            write_signed_sequence_term(&mut writer, i, v, &mut conv)
+                .map_err(|e| FibError::Calculation(format!("failed to write F({i}): {e}")))
+        },
+    )?;
+    writer.flush()?;
     Ok(())
 }
 
+fn run_pisano(args: &PisanoArgs) -> Result<()> {
+    let cancel = CancellationToken::new();
+    ctrlc_handler(cancel.clone());
+
+    let period = pisano_period(&args.modulus, &cancel)?;
+    println!("{period}");
+    Ok(())
+}
+
+/// Value carried by JSON records: F(n) mod M with `--modulus M`, the last
+/// K digits with `--last-digits K`, the full number with `--calculate`,
+/// otherwise only its metadata.
+fn json_value_field(config: &AppConfig) -> ValueField {
+    if config.modulus.is_some() {
+        ValueField::Residue
+    } else if config.last_digits > 0 {
+        ValueField::LastDigits(config.last_digits)
+    } else if config.calculate {
+        ValueField::Full
+    } else {
+        ValueField::None
+    }
+}
+
 fn run_calibration(config: &AppConfig) -> Result<()> {
     use fibcalc_calibration::calibration::{CalibrationEngine, CalibrationMode};
 
@@ -183,6 +743,9 @@ fn run_calibration(config: &AppConfig) -> Result<()> {
         println!("  Parallel threshold: {} bits", profile.parallel_threshold);
         println!("  FFT threshold: {} bits", profile.fft_threshold);
         println!("  Strassen threshold: {} bits", profile.strassen_threshold);
+        println!("  NTT threshold: {} bits", profile.ntt_threshold);
+        println!("  Toom-3 threshold: {} bits", profile.toom3_threshold);
+        println!("  Toom-4 threshold: {} bits", profile.toom4_threshold);
     }
 
     fibcalc_calibration::io::save_profile(&profile)?;
@@ -190,8 +753,14 @@ fn run_calibration(config: &AppConfig) -> Result<()> {
 }
 
 fn run_tui(config: &AppConfig) -> Result<()> {
-    let (calculators, opts) = setup_calculators(config)?;
+    if config.sequence != SequenceKind::Fib {
+        anyhow::bail!("the TUI only computes Fibonacci numbers");
+    }
+    let n = small_index(config)?;
     let cancel = CancellationToken::new();
+    let RunSetup {
+        calculators, opts, ..
+    } = setup_calculators(config, n, &cancel)?;
 
     // Set up Ctrl+C handler
     let cancel_clone = cancel.clone();
@@ -202,7 +771,8 @@ fn run_tui(config: &AppConfig) -> Result<()> {
 
     // Create TUI app
     let mut app = fibcalc_tui::TuiApp::new(rx);
-    app.set_n(config.n);
+    app.set_n(n);
+    app.set_pause_token(cancel.pause_token().clone());
 
     // Spawn metrics collection thread (pinned to core 0 alongside TUI)
     let metrics_tx = tx.clone();
@@ -226,7 +796,6 @@ fn run_tui(config: &AppConfig) -> Result<()> {
     });
 
     // Spawn background thread for calculations (pinned to core 1+ if available)
-    let n = config.n;
     let timeout = Some(config.timeout_duration());
     std::thread::spawn(move || {
         pin_to_core(1);
@@ -292,34 +861,55 @@ fn ctrlc_handler(cancel: CancellationToken) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::FFTBackendKind;
+    use fibcalc_cli::output::{read_value_file, ValueFormat};
+    use num_bigint::BigInt;
     use tempfile::TempDir;
 
     /// Helper to build a minimal AppConfig for testing.
     fn test_config() -> AppConfig {
         AppConfig {
-            n: 100,
+            n: BigInt::from(100u32),
             algo: "fast".to_string(),
             calculate: false,
             verbose: false,
             details: false,
             output: None,
+            output_format: ValueFormat::Dec,
+            output_header: false,
             quiet: false,
+            format: ResultFormat::Text,
             calibrate: false,
             auto_calibrate: false,
             timeout: "5m".to_string(),
             threshold: 0,
             fft_threshold: 0,
             strassen_threshold: 0,
+            fft_backend: FFTBackendKind::Auto,
             tui: false,
             completion: None,
             last_digits: 0,
+            modulus: None,
+            sequence: SequenceKind::Fib,
+            p: 1,
+            q: -1,
             memory_limit: String::new(),
+            profile: None,
+            no_profile: true,
+            dynamic_thresholds: false,
+            checkpoint: None,
+            checkpoint_every: 0,
+            checkpoint_interval: "5m".to_string(),
+            resume: None,
+            command: None,
         }
     }
 
     /// Build Options from config (delegates to the shared build_options helper).
     fn opts_from_config(config: &AppConfig) -> Options {
-        build_options(config).expect("test config should always produce valid options")
+        build_options(config)
+            .expect("test config should always produce valid options")
+            .0
     }
 
     /// Execute `run_cli_core` with a fresh cancellation token (no ctrlc).
@@ -343,7 +933,7 @@ mod tests {
     fn run_cli_all_algorithms() {
         let mut config = test_config();
         config.algo = "all".to_string();
-        config.n = 50;
+        config.n = BigInt::from(50u64);
         let result = execute_cli_logic(&config);
         assert!(
             result.is_ok(),
@@ -356,7 +946,7 @@ mod tests {
     fn run_cli_matrix_algorithm() {
         let mut config = test_config();
         config.algo = "matrix".to_string();
-        config.n = 50;
+        config.n = BigInt::from(50u64);
         let result = execute_cli_logic(&config);
         assert!(
             result.is_ok(),
@@ -369,7 +959,7 @@ mod tests {
     fn run_cli_fft_algorithm() {
         let mut config = test_config();
         config.algo = "fft".to_string();
-        config.n = 50;
+        config.n = BigInt::from(50u64);
         let result = execute_cli_logic(&config);
         assert!(
             result.is_ok(),
@@ -423,6 +1013,172 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn run_cli_last_digits_of_huge_index() {
+        let mut config = test_config();
+        config.n = BigInt::from(1_000_000_000_000u64);
+        config.algo = "all".to_string();
+        config.last_digits = 13;
+        config.memory_limit = "1M".to_string();
+        let setup =
+            setup_calculators(&config, 1_000_000_000_000, &CancellationToken::new()).unwrap();
+        let names: Vec<_> = setup.calculators.iter().map(|c| c.name()).collect();
+        assert_eq!(names, ["FastDoublingMod", "MatrixExponentiationMod"]);
+        assert!(execute_cli_logic(&config).is_ok());
+    }
+
+    #[test]
+    fn setup_finds_pisano_period_once() {
+        let mut config = test_config();
+        config.algo = "all".to_string();
+        config.modulus = Some("10".to_string());
+        let cancel = CancellationToken::new();
+        let setup = setup_calculators(&config, 1_000_000_000_000_000_000, &cancel).unwrap();
+        assert_eq!(
+            setup.opts.pisano_period.as_deref(),
+            Some(&BigUint::from(60u32))
+        );
+        let setup = setup_calculators(&config, 1_000, &cancel).unwrap();
+        assert_eq!(setup.opts.pisano_period, None);
+    }
+
+    #[test]
+    fn run_cli_with_modulus() {
+        let mut config = test_config();
+        config.algo = "all".to_string();
+        config.modulus = Some("10^9+7".to_string());
+        let (opts, _) = build_options(&config).unwrap();
+        assert_eq!(
+            opts.modulus.as_deref(),
+            Some(&BigUint::from(1_000_000_007u64))
+        );
+        let setup = setup_calculators(&config, 100, &CancellationToken::new()).unwrap();
+        assert_eq!(setup.calculators.len(), 2);
+        assert!(execute_cli_logic(&config).is_ok());
+        config.format = ResultFormat::Json;
+        assert!(execute_cli_logic(&config).is_ok());
+    }
+
+    #[test]
+    fn invalid_modulus_is_rejected() {
+        for bad in ["0", "10^9-", "abc", "1-2"] {
+            let mut config = test_config();
+            config.modulus = Some(bad.to_string());
+            let err = build_options(&config).unwrap_err();
+            assert!(err.to_string().contains("--modulus"), "{err}");
+        }
+    }
+
+    #[test]
+    fn big_index_needs_modular_text_run() {
+        let mut config = test_config();
+        config.n = BigInt::from(10u32).pow(40);
+        let err = execute_cli_logic(&config).unwrap_err();
+        assert!(err.to_string().contains("too large"), "{err}");
+
+        config.modulus = Some("2^61-1".to_string());
+        assert!(execute_cli_logic(&config).is_ok());
+        config.quiet = true;
+        assert!(execute_cli_logic(&config).is_ok());
+        for format in [ResultFormat::Json, ResultFormat::Ndjson] {
+            config.format = format;
+            let err = execute_cli_logic(&config).unwrap_err();
+            assert!(err.to_string().contains("--format"), "{err}");
+        }
+
+        config.format = ResultFormat::Text;
+        config.modulus = None;
+        config.last_digits = 12;
+        assert!(execute_cli_logic(&config).is_ok());
+    }
+
+    #[test]
+    fn run_cli_lucas_sequences() {
+        let mut config = test_config();
+        config.algo = "all".to_string();
+        config.sequence = SequenceKind::Lucas;
+        let setup = setup_calculators(&config, 100, &CancellationToken::new()).unwrap();
+        let names: Vec<_> = setup.calculators.iter().map(|c| c.name()).collect();
+        assert_eq!(names, ["LucasFastDoubling", "LucasMatrix"]);
+        assert!(execute_cli_logic(&config).is_ok());
+
+        config.sequence = SequenceKind::LucasUv;
+        (config.p, config.q) = (3, 2);
+        assert!(execute_cli_logic(&config).is_ok());
+        config.format = ResultFormat::Ndjson;
+        assert!(execute_cli_logic(&config).is_ok());
+        config.format = ResultFormat::Json;
+        assert!(execute_cli_logic(&config).is_err());
+
+        // Negative terms only make sense modulo m.
+        config.format = ResultFormat::Text;
+        config.p = -3;
+        let err = execute_cli_logic(&config).unwrap_err();
+        assert!(err.to_string().contains("negative terms"), "{err}");
+        config.modulus = Some("97".to_string());
+        assert!(execute_cli_logic(&config).is_ok());
+        config.n = BigInt::from(10u32).pow(40);
+        assert!(execute_cli_logic(&config).is_err());
+    }
+
+    #[test]
+    fn run_cli_lucas_output_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("lucas.txt");
+        let mut config = test_config();
+        config.n = BigInt::from(10u32);
+        config.sequence = SequenceKind::Lucas;
+        config.output = Some(path.to_string_lossy().to_string());
+        execute_cli_logic(&config).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "123");
+
+        config.sequence = SequenceKind::LucasUv;
+        assert!(execute_cli_logic(&config).is_err());
+    }
+
+    #[test]
+    fn run_cli_negative_index_output_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("negafib.txt");
+        let mut config = test_config();
+        config.output = Some(path.to_string_lossy().to_string());
+        for (n, expected) in [(-10, "-55"), (-9, "34"), (-1, "1")] {
+            config.n = BigInt::from(n);
+            execute_cli_logic(&config).unwrap();
+            assert_eq!(std::fs::read_to_string(&path).unwrap(), expected, "n = {n}");
+        }
+        config.output_format = ValueFormat::Hex;
+        config.n = BigInt::from(-10);
+        execute_cli_logic(&config).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "-37");
+        for format in [ValueFormat::Dec, ValueFormat::Hex] {
+            config.output_format = format;
+            config.n = BigInt::from(-100);
+            execute_cli_logic(&config).unwrap();
+            let loaded = read_value_file(&path, format, false).unwrap();
+            let (_, f100) = fibcalc_core::iterator::FibIterator::from_index(100)
+                .next()
+                .unwrap();
+            assert_eq!(loaded.value, -BigInt::from(f100), "{format:?}");
+        }
+
+        // F(-8) = -21 ≡ 979 (mod 1000).
+        config.output_format = ValueFormat::Dec;
+        config.n = BigInt::from(-8);
+        config.modulus = Some("1000".to_string());
+        execute_cli_logic(&config).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "979");
+
+        config.modulus = None;
+        config.output_format = ValueFormat::BinLe;
+        assert!(execute_cli_logic(&config).is_err());
+        config.sequence = SequenceKind::LucasUv;
+        config.p = 3;
+        config.q = 2;
+        config.output = None;
+        assert!(execute_cli_logic(&config).is_err());
+    }
+
     #[test]
     fn run_cli_with_custom_thresholds() {
         let mut config = test_config();
@@ -433,10 +1189,26 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn run_cli_dynamic_thresholds_details() {
+        let mut config = test_config();
+        config.n = BigInt::from(20_000u64);
+        config.algo = "all".to_string();
+        config.dynamic_thresholds = true;
+        config.details = true;
+        assert!(opts_from_config(&config).dynamic_thresholds);
+        let result = execute_cli_logic(&config);
+        assert!(
+            result.is_ok(),
+            "dynamic run should succeed: {:?}",
+            result.err()
+        );
+    }
+
     #[test]
     fn run_cli_memory_limit_exceeded() {
         let mut config = test_config();
-        config.n = 100_000_000;
+        config.n = BigInt::from(100_000_000u64);
         config.memory_limit = "1B".to_string();
         let result = execute_cli_logic(&config);
         assert!(result.is_err(), "Should fail when memory limit is exceeded");
@@ -450,12 +1222,42 @@ mod tests {
     #[test]
     fn run_cli_memory_limit_sufficient() {
         let mut config = test_config();
-        config.n = 100;
+        config.n = BigInt::from(100u64);
         config.memory_limit = "8G".to_string();
         let result = execute_cli_logic(&config);
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn memory_budget_over_fft_workspace_only_limits_fft() {
+        let mut opts = opts_from_config(&test_config());
+        opts.fft_threshold = 100_000;
+        let n = 10_000_000;
+        let estimate = fibcalc_core::memory_budget::MemoryEstimate::estimate_with_fft_threshold(
+            n,
+            opts.fft_threshold,
+        );
+        assert!(estimate.fft_bytes > 0);
+        opts.memory_limit = Some(estimate.total_bytes - 1);
+        assert_eq!(fft_memory_budget(n, &opts).unwrap(), estimate.fft_bytes - 1);
+        opts.memory_limit = Some(estimate.total_bytes - estimate.fft_bytes - 1);
+        assert!(fft_memory_budget(n, &opts).is_err());
+        opts.memory_limit = None;
+        assert_eq!(fft_memory_budget(n, &opts).unwrap(), usize::MAX);
+        opts.memory_limit = Some(1024);
+        opts.last_digits = Some(10);
+        assert_eq!(fft_memory_budget(n, &opts).unwrap(), 1024);
+        assert!(largest_fft_layout(n, &opts).is_none());
+    }
+
+    #[test]
+    fn largest_fft_layout_only_above_threshold() {
+        let mut opts = opts_from_config(&test_config());
+        opts.fft_threshold = 100_000;
+        assert_eq!(largest_fft_layout(100, &opts), None);
+        assert!(largest_fft_layout(10_000_000, &opts).is_some());
+    }
+
     #[test]
     fn run_cli_unknown_algorithm_fails() {
         let mut config = test_config();
@@ -467,7 +1269,7 @@ mod tests {
     #[test]
     fn run_cli_n_zero() {
         let mut config = test_config();
-        config.n = 0;
+        config.n = BigInt::from(0u64);
         let result = execute_cli_logic(&config);
         assert!(result.is_ok(), "n=0 should be handled: {:?}", result.err());
     }
@@ -475,7 +1277,7 @@ mod tests {
     #[test]
     fn run_cli_n_one() {
         let mut config = test_config();
-        config.n = 1;
+        config.n = BigInt::from(1u64);
         let result = execute_cli_logic(&config);
         assert!(result.is_ok());
     }
@@ -483,7 +1285,7 @@ mod tests {
     #[test]
     fn run_cli_large_n_fast_path() {
         let mut config = test_config();
-        config.n = 93;
+        config.n = BigInt::from(93u64);
         let result = execute_cli_logic(&config);
         assert!(result.is_ok());
     }
@@ -491,7 +1293,7 @@ mod tests {
     #[test]
     fn run_cli_just_above_fast_path() {
         let mut config = test_config();
-        config.n = 94;
+        config.n = BigInt::from(94u64);
         let result = execute_cli_logic(&config);
         assert!(result.is_ok());
     }
@@ -500,7 +1302,7 @@ mod tests {
     fn run_cli_comparison_with_all_algos() {
         let mut config = test_config();
         config.algo = "all".to_string();
-        config.n = 1000;
+        config.n = BigInt::from(1000u64);
         let result = execute_cli_logic(&config);
         assert!(result.is_ok());
     }
@@ -564,7 +1366,7 @@ mod tests {
     fn run_cli_with_timeout() {
         let mut config = test_config();
         config.timeout = "30s".to_string();
-        config.n = 50;
+        config.n = BigInt::from(50u64);
         let result = execute_cli_logic(&config);
         assert!(result.is_ok());
     }
@@ -602,11 +1404,125 @@ mod tests {
         assert!(opts.details);
     }
 
+    #[test]
+    fn resolve_ntt_threshold_maps_flag() {
+        let mut config = test_config();
+        config.no_profile = true;
+        assert_eq!(
+            resolve_ntt_threshold(&config, None),
+            DEFAULT_NTT_THRESHOLD_BITS
+        );
+        let (opts, _) = build_options(&config).unwrap();
+        assert_eq!(opts.ntt_threshold, DEFAULT_NTT_THRESHOLD_BITS);
+
+        config.fft_backend = FFTBackendKind::Ntt;
+        assert_eq!(resolve_ntt_threshold(&config, None), 0);
+        let (opts, _) = build_options(&config).unwrap();
+        assert_eq!(opts.fft_config().backend_for(1), FFTBackend::Ntt);
+
+        config.fft_backend = FFTBackendKind::Fermat;
+        assert_eq!(resolve_ntt_threshold(&config, None), usize::MAX);
+        let (opts, _) = build_options(&config).unwrap();
+        assert_eq!(opts.fft_config().backend_for(1 << 40), FFTBackend::Fermat);
+    }
+
+    /// Write a valid calibration profile for this machine into `dir`.
+    fn write_test_profile(dir: &TempDir) -> String {
+        let path = dir.path().join("profile.json");
+        let profile = CalibrationProfile {
+            parallel_threshold: 7777,
+            fft_threshold: 900_000,
+            strassen_threshold: 5555,
+            cpu_fingerprint: fibcalc_calibration::profile::cpu_fingerprint(),
+            ..Default::default()
+        };
+        fibcalc_calibration::io::save_to_path(&profile, &path).unwrap();
+        path.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn build_options_uses_profile_for_zero_thresholds() {
+        let dir = TempDir::new().unwrap();
+        let mut config = test_config();
+        config.no_profile = false;
+        config.profile = Some(write_test_profile(&dir));
+        config.fft_threshold = 600_000;
+        let (opts, sources) = build_options(&config).unwrap();
+        assert_eq!(opts.parallel_threshold, 7777);
+        assert_eq!(opts.fft_threshold, 600_000);
+        assert_eq!(opts.strassen_threshold, 5555);
+        assert_eq!(sources.parallel, ThresholdSource::Profile);
+        assert_eq!(sources.fft, ThresholdSource::Cli);
+        assert_eq!(sources.strassen, ThresholdSource::Profile);
+    }
+
+    #[test]
+    fn build_options_no_profile_uses_defaults() {
+        let config = test_config();
+        let (opts, sources) = build_options(&config).unwrap();
+        assert_eq!(
+            opts.parallel_threshold,
+            fibcalc_core::constants::DEFAULT_PARALLEL_THRESHOLD
+        );
+        assert_eq!(sources.parallel, ThresholdSource::Default);
+        assert_eq!(sources.fft, ThresholdSource::Default);
+        assert_eq!(sources.strassen, ThresholdSource::Default);
+    }
+
+    #[test]
+    fn build_options_missing_profile_path_fails() {
+        let dir = TempDir::new().unwrap();
+        let mut config = test_config();
+        config.no_profile = false;
+        config.profile = Some(
+            dir.path()
+                .join("missing.json")
+                .to_string_lossy()
+                .to_string(),
+        );
+        let err = build_options(&config).unwrap_err().to_string();
+        assert!(err.contains("--profile"), "unexpected error: {err}");
+    }
+
+    #[test]
+    fn run_cli_details_with_profile() {
+        let dir = TempDir::new().unwrap();
+        let mut config = test_config();
+        config.no_profile = false;
+        config.profile = Some(write_test_profile(&dir));
+        config.details = true;
+        config.threshold = 4096;
+        assert!(execute_cli_logic(&config).is_ok());
+
+        let (opts, sources) = build_options(&config).unwrap();
+        let report = threshold_sources_report(&opts, sources);
+        assert!(
+            report.contains("Parallel: 4096 bits (CLI flag)"),
+            "{report}"
+        );
+        assert!(report.contains("FFT: 900000 bits (profile)"), "{report}");
+        assert!(report.contains("Strassen: 5555 bits (profile)"), "{report}");
+    }
+
+    #[test]
+    fn tui_index_must_be_non_negative_u64() {
+        let mut config = test_config();
+        config.n = BigInt::from(-10);
+        let err = small_index(&config).unwrap_err();
+        assert!(err.to_string().contains("--tui"), "{err}");
+        config.n = BigInt::from(u64::MAX) + 1;
+        let err = small_index(&config).unwrap_err();
+        assert!(err.to_string().contains("below 2^64"), "{err}");
+        config.n = BigInt::from(u64::MAX);
+        assert_eq!(small_index(&config).unwrap(), u64::MAX);
+    }
+
     #[test]
     fn memory_budget_check_zero_unlimited() {
         let config = test_config();
         let opts = opts_from_config(&config);
-        let estimate = fibcalc_core::memory_budget::MemoryEstimate::estimate(config.n);
+        let estimate =
+            fibcalc_core::memory_budget::MemoryEstimate::estimate(small_index(&config).unwrap());
         // Default memory_limit="" parses to None, which means unlimited
         assert_eq!(opts.memory_limit, None);
         assert!(estimate.fits_in(opts.memory_limit));
@@ -617,20 +1533,49 @@ mod tests {
         let dir = TempDir::new().unwrap();
         let output_path = dir.path().join("fib10.txt");
         let mut config = test_config();
-        config.n = 10; // F(10) = 55
+        config.n = BigInt::from(10u64); // F(10) = 55
         config.output = Some(output_path.to_string_lossy().to_string());
         execute_cli_logic(&config).unwrap();
         let content = std::fs::read_to_string(&output_path).unwrap();
         assert_eq!(content, "55");
     }
 
+    #[test]
+    fn run_cli_binary_output_with_header_round_trips() {
+        let dir = TempDir::new().unwrap();
+        let output_path = dir.path().join("fib1000.bin");
+        let mut config = test_config();
+        config.n = BigInt::from(1000u64);
+        config.output = Some(output_path.to_string_lossy().to_string());
+        config.output_format = ValueFormat::BinBe;
+        config.output_header = true;
+        execute_cli_logic(&config).unwrap();
+        let loaded = read_value_file(&output_path, ValueFormat::BinBe, true).unwrap();
+        assert_eq!(loaded.header.unwrap().n, 1000);
+        let (_, expected) = fibcalc_core::iterator::FibIterator::from_index(1000)
+            .next()
+            .unwrap();
+        assert_eq!(loaded.value, expected.into());
+    }
+
+    #[test]
+    fn run_cli_rejects_header_for_text_output() {
+        let dir = TempDir::new().unwrap();
+        let mut config = test_config();
+        config.output = Some(dir.path().join("fib.hex").to_string_lossy().to_string());
+        config.output_format = ValueFormat::Hex;
+        config.output_header = true;
+        let err = execute_cli_logic(&config).unwrap_err();
+        assert!(err.to_string().contains("--output-header"));
+    }
+
     #[test]
     fn run_cli_all_algos_output_file() {
         let dir = TempDir::new().unwrap();
         let output_path = dir.path().join("fib_all.txt");
         let mut config = test_config();
         config.algo = "all".to_string();
-        config.n = 10;
+        config.n = BigInt::from(10u64);
         config.output = Some(output_path.to_string_lossy().to_string());
         execute_cli_logic(&config).unwrap();
         let content = std::fs::read_to_string(&output_path).unwrap();
@@ -668,4 +1613,151 @@ mod tests {
         let result = run(&config);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn run_cli_checkpoint_then_resume() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("fib.ckpt");
+        let mut config = test_config();
+        config.n = BigInt::from(10_000u64);
+        config.checkpoint = Some(path.to_string_lossy().to_string());
+        config.checkpoint_every = 1;
+        execute_cli_logic(&config).unwrap();
+
+        let cp = Checkpoint::load(&path).unwrap();
+        assert_eq!(cp.n, 10_000);
+        assert_eq!(cp.algorithm, "FastDoubling");
+        assert_eq!(cp.remaining_bits, 0);
+
+        config.checkpoint = None;
+        config.resume = Some(path.to_string_lossy().to_string());
+        let opts = opts_from_config(&config);
+        assert_eq!(opts.checkpoint.as_ref().map(|c| c.path.clone()), Some(path));
+        assert!(opts.resume.is_some());
+        execute_cli_logic(&config).unwrap();
+    }
+
+    #[test]
+    fn run_cli_resume_rejects_mismatched_run() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("fib.ckpt");
+        let mut config = test_config();
+        config.n = BigInt::from(1_000u64);
+        config.checkpoint = Some(path.to_string_lossy().to_string());
+        config.checkpoint_every = 1;
+        execute_cli_logic(&config).unwrap();
+        config.checkpoint = None;
+        config.resume = Some(path.to_string_lossy().to_string());
+
+        config.n = BigInt::from(2_000u64);
+        let err = execute_cli_logic(&config).unwrap_err().to_string();
+        assert!(err.contains("n=1000"), "{err}");
+
+        config.n = BigInt::from(1_000u64);
+        config.algo = "fft".to_string();
+        let err = execute_cli_logic(&config).unwrap_err().to_string();
+        assert!(err.contains("FastDoubling"), "{err}");
+    }
+
+    #[test]
+    fn run_cli_checkpoint_requires_supported_single_algorithm() {
+        let dir = TempDir::new().unwrap();
+        let mut config = test_config();
+        config.checkpoint = Some(dir.path().join("fib.ckpt").to_string_lossy().to_string());
+        config.algo = "all".to_string();
+        assert!(execute_cli_logic(&config).is_err());
+        config.algo = "matrix".to_string();
+        assert!(execute_cli_logic(&config).is_err());
+    }
+
+    #[test]
+    fn run_cli_resume_missing_file_fails() {
+        let mut config = test_config();
+        config.resume = Some("/nonexistent/fib.ckpt".to_string());
+        let err = execute_cli_logic(&config).unwrap_err().to_string();
+        assert!(err.contains("--resume"), "{err}");
+    }
+
+    #[test]
+    fn run_seq_writes_range_to_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("seq.txt");
+        let args = SeqArgs {
+            from: 98,
+            to: 100,
+            descending: false,
+            output: Some(path.to_string_lossy().to_string()),
+        };
+        run_seq_core(&args, &CancellationToken::new()).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("98 "));
+        assert_eq!(lines[2], "100 354224848179261915075");
+    }
+
+    #[test]
+    fn run_seq_rejects_reversed_range() {
+        let args = SeqArgs {
+            from: 10,
+            to: 5,
+            descending: false,
+            output: None,
+        };
+        assert!(run_seq_core(&args, &CancellationToken::new()).is_err());
+    }
+
+    #[test]
+    fn run_seq_descends_past_zero() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("seq.txt");
+        let args = SeqArgs {
+            from: 1,
+            to: -4,
+            descending: true,
+            output: Some(path.to_string_lossy().to_string()),
+        };
+        run_seq_core(&args, &CancellationToken::new()).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(content, "1 1\n0 0\n-1 1\n-2 -1\n-3 2\n-4 -3\n");
+    }
+
+    #[test]
+    fn run_seq_cancelled() {
+        let dir = TempDir::new().unwrap();
+        let args = SeqArgs {
+            from: 0,
+            to: 1_000,
+            descending: false,
+            output: Some(dir.path().join("seq.txt").to_string_lossy().to_string()),
+        };
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        let err = run_seq_core(&args, &cancel).unwrap_err();
+        assert!(err.to_string().contains("cancel"), "{err}");
+    }
+
+    #[test]
+    fn json_value_field_selection() {
+        let mut config = test_config();
+        assert_eq!(json_value_field(&config), ValueField::None);
+        config.calculate = true;
+        assert_eq!(json_value_field(&config), ValueField::Full);
+        config.last_digits = 12;
+        assert_eq!(json_value_field(&config), ValueField::LastDigits(12));
+        config.last_digits = 0;
+        config.modulus = Some("97".to_string());
+        assert_eq!(json_value_field(&config), ValueField::Residue);
+    }
+
+    #[test]
+    fn run_cli_json_formats() {
+        for format in [ResultFormat::Json, ResultFormat::Ndjson] {
+            let mut config = test_config();
+            config.algo = "all".to_string();
+            config.details = true;
+            config.format = format;
+            assert!(execute_cli_logic(&config).is_ok());
+        }
+    }
 }
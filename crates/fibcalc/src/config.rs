@@ -1,15 +1,22 @@
 //! Application configuration from CLI flags and environment.
 
-use clap::Parser;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use fibcalc_bigfft::FFTBackend;
+use fibcalc_cli::output::ValueFormat;
+use fibcalc_cli::ResultFormat;
+use fibcalc_core::int_expr::{parse_int_expr, parse_uint_expr};
+use fibcalc_core::lucas::Sequence;
+use num_bigint::{BigInt, BigUint};
 
 /// FibCalc-rs — High-performance Fibonacci calculator.
 #[derive(Parser, Debug)]
 #[command(name = "fibcalc", version, about)]
 #[allow(clippy::struct_excessive_bools)]
 pub struct AppConfig {
-    /// Fibonacci number to compute.
-    #[arg(short, long, default_value = "100000000", env = "FIBCALC_N")]
-    pub n: u64,
+    /// Fibonacci number to compute (e.g., "1000", "10^12", "-100"); indices
+    /// beyond ±(2^63 − 1) need --modulus or --last-digits.
+    #[arg(short, long, default_value = "100000000", env = "FIBCALC_N", value_parser = parse_int_expr, allow_hyphen_values = true)]
+    pub n: BigInt,
 
     /// Algorithm to use: fast, matrix, fft, or all.
     #[arg(long, default_value = "all")]
@@ -31,10 +38,23 @@ pub struct AppConfig {
     #[arg(short, long)]
     pub output: Option<String>,
 
+    /// Encoding of the `--output` file.
+    #[arg(long, value_enum, default_value_t = ValueFormat::Dec, requires = "output")]
+    pub output_format: ValueFormat,
+
+    /// Prefix binary `--output` files with a header (n, bit length, SHA-256).
+    #[arg(long, requires = "output")]
+    pub output_header: bool,
+
     /// Quiet mode (only output the number).
     #[arg(short, long)]
     pub quiet: bool,
 
+    /// Result format: human-readable text, a JSON document, or NDJSON lines;
+    /// indices beyond ±(2^63 − 1) are only printed as text.
+    #[arg(long, value_enum, default_value_t = ResultFormat::Text)]
+    pub format: ResultFormat,
+
     /// Run full calibration.
     #[arg(long)]
     pub calibrate: bool,
@@ -59,7 +79,25 @@ pub struct AppConfig {
     #[arg(long, default_value = "0")]
     pub strassen_threshold: usize,
 
-    /// Launch interactive TUI.
+    /// FFT backend: auto (profile's NTT crossover, Fermat without one),
+    /// fermat or ntt.
+    #[arg(long, value_enum, default_value_t = FFTBackendKind::Auto)]
+    pub fft_backend: FFTBackendKind,
+
+    /// Load calibration thresholds from this profile file.
+    #[arg(long, value_name = "PATH", conflicts_with = "no_profile")]
+    pub profile: Option<String>,
+
+    /// Ignore any saved calibration profile.
+    #[arg(long)]
+    pub no_profile: bool,
+
+    /// Adapt multiplication thresholds from per-iteration timings during the run.
+    #[arg(long)]
+    pub dynamic_thresholds: bool,
+
+    /// Launch interactive TUI; it computes F(n) for non-negative n below
+    /// 2^64 only.
     #[arg(long)]
     pub tui: bool,
 
@@ -71,9 +109,117 @@ pub struct AppConfig {
     #[arg(long, default_value = "0")]
     pub last_digits: u32,
 
+    /// Compute F(n) mod M (e.g., "1000000007", "0xFFFF", "2^61-1", "10^9+7").
+    #[arg(long, value_name = "M", conflicts_with = "last_digits")]
+    pub modulus: Option<String>,
+
+    /// Sequence to compute: Fibonacci F(n), Lucas L(n), or both Lucas
+    /// sequences `U_n(P, Q)` and `V_n(P, Q)`.
+    #[arg(long, value_enum, default_value_t = SequenceKind::Fib)]
+    pub sequence: SequenceKind,
+
+    /// Parameter P of `--sequence lucas-uv`.
+    #[arg(long, default_value = "1", allow_hyphen_values = true)]
+    pub p: i64,
+
+    /// Parameter Q of `--sequence lucas-uv`.
+    #[arg(long, default_value = "-1", allow_hyphen_values = true)]
+    pub q: i64,
+
     /// Memory limit (e.g., "8G", "512M").
     #[arg(long, default_value = "")]
     pub memory_limit: String,
+
+    /// Write resumable checkpoints of the doubling loop to this file.
+    #[arg(long, value_name = "PATH")]
+    pub checkpoint: Option<String>,
+
+    /// Save a checkpoint every N doubling iterations (0 = time-based only).
+    #[arg(long, value_name = "N", default_value = "0")]
+    pub checkpoint_every: u32,
+
+    /// Save a checkpoint at least this often (e.g., "30s", "5m").
+    #[arg(long, value_name = "DURATION", default_value = "5m")]
+    pub checkpoint_interval: String,
+
+    /// Resume from a checkpoint file; n and --algo must match the saved run.
+    #[arg(long, value_name = "PATH")]
+    pub resume: Option<String>,
+
+    /// Optional subcommand; without one, compute a single F(n).
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Sequence selected with `--sequence`.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SequenceKind {
+    /// Fibonacci numbers F(n).
+    #[default]
+    Fib,
+    /// Lucas numbers L(n).
+    Lucas,
+    /// Lucas sequences `U_n(P, Q)` and `V_n(P, Q)`.
+    LucasUv,
+}
+
+/// FFT backend selected with `--fft-backend`.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FFTBackendKind {
+    /// Fermat below the calibrated NTT crossover, NTT at or above it.
+    #[default]
+    Auto,
+    /// Schönhage-Strassen over Fermat rings.
+    Fermat,
+    /// Three-prime NTT.
+    Ntt,
+}
+
+impl From<FFTBackendKind> for FFTBackend {
+    fn from(kind: FFTBackendKind) -> Self {
+        match kind {
+            FFTBackendKind::Auto => Self::Auto,
+            FFTBackendKind::Fermat => Self::Fermat,
+            FFTBackendKind::Ntt => Self::Ntt,
+        }
+    }
+}
+
+/// Subcommands of `fibcalc`.
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Stream a range of Fibonacci numbers as "index value" lines.
+    Seq(SeqArgs),
+    /// Print the Pisano period π(m), the period of F(n) mod m.
+    Pisano(PisanoArgs),
+}
+
+/// Arguments of `fibcalc seq`.
+#[derive(Args, Debug)]
+pub struct SeqArgs {
+    /// First index to emit; negative indices give F(−n) = (−1)^{n+1}·F(n).
+    #[arg(long, default_value = "0", allow_hyphen_values = true)]
+    pub from: i64,
+
+    /// Last index to emit (inclusive).
+    #[arg(long, allow_hyphen_values = true)]
+    pub to: i64,
+
+    /// Walk down from `--from` to `--to` instead of up.
+    #[arg(long)]
+    pub descending: bool,
+
+    /// Write the sequence to this file instead of stdout.
+    #[arg(short, long, value_name = "PATH")]
+    pub output: Option<String>,
+}
+
+/// Arguments of `fibcalc pisano`.
+#[derive(Args, Debug)]
+pub struct PisanoArgs {
+    /// Modulus m, as a number or an expression such as `10^9+7`.
+    #[arg(value_name = "M", value_parser = parse_uint_expr)]
+    pub modulus: BigUint,
 }
 
 impl AppConfig {
@@ -83,11 +229,29 @@ impl AppConfig {
         <Self as Parser>::parse()
     }
 
+    /// Sequences to compute, in output order: `lucas-uv` yields `U_n(P, Q)`
+    /// then `V_n(P, Q)`.
+    #[must_use]
+    pub fn sequences(&self) -> Vec<Sequence> {
+        let (p, q) = (self.p, self.q);
+        match self.sequence {
+            SequenceKind::Fib => vec![Sequence::Fibonacci],
+            SequenceKind::Lucas => vec![Sequence::Lucas],
+            SequenceKind::LucasUv => vec![Sequence::LucasU { p, q }, Sequence::LucasV { p, q }],
+        }
+    }
+
     /// Parse timeout string into Duration.
     #[must_use]
     pub fn timeout_duration(&self) -> std::time::Duration {
         parse_duration(&self.timeout).unwrap_or(std::time::Duration::from_secs(300))
     }
+
+    /// Parse the checkpoint interval string (`None` if malformed).
+    #[must_use]
+    pub fn checkpoint_interval_duration(&self) -> Option<std::time::Duration> {
+        parse_duration(&self.checkpoint_interval)
+    }
 }
 
 /// Parse a duration string like "5m", "1h", "30s".
@@ -142,4 +306,22 @@ mod tests {
             Some(std::time::Duration::from_millis(500))
         );
     }
+
+    #[test]
+    fn sequences_from_flags() {
+        let parse = |args: &[&str]| {
+            let mut argv = vec!["fibcalc"];
+            argv.extend_from_slice(args);
+            AppConfig::try_parse_from(argv).unwrap().sequences()
+        };
+        assert_eq!(parse(&[]), [Sequence::Fibonacci]);
+        assert_eq!(parse(&["--sequence", "lucas"]), [Sequence::Lucas]);
+        assert_eq!(
+            parse(&["--sequence", "lucas-uv", "--p", "-3", "--q", "2"]),
+            [
+                Sequence::LucasU { p: -3, q: 2 },
+                Sequence::LucasV { p: -3, q: 2 }
+            ]
+        );
+    }
 }
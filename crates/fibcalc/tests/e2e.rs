@@ -78,6 +78,33 @@ fn details_mode() {
         .success();
 }
 
+#[test]
+fn details_shows_fft_layout() {
+    fibcalc()
+        .args(["-n", "1000000", "--fft-threshold", "100000", "--no-profile"])
+        .args(["--algo", "fast", "-c", "-d"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "FFT layout: Fermat, 1024 x 1024-bit pieces, shift 2560, 1.3 MiB\n",
+        ))
+        .stdout(predicate::str::contains("FFT memory budget").not());
+}
+
+#[test]
+fn details_shows_segmented_layout_under_memory_limit() {
+    fibcalc()
+        .args(["-n", "1000000", "--fft-threshold", "100000", "--no-profile"])
+        .args(["--memory-limit", "1000K", "--algo", "fast", "-c", "-d"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "FFT layout: Fermat segmented into 86784-bit blocks, 4 x 65536-bit pieces, \
+             shift 131076, 0.3 MiB per block\n  FFT memory budget: 0.5 MiB\n",
+        ))
+        .stdout(predicate::str::contains("(208988 digits)"));
+}
+
 #[test]
 fn invalid_algo() {
     fibcalc()
@@ -103,6 +130,187 @@ fn last_digits_mode() {
         .success();
 }
 
+#[test]
+fn last_digits_of_huge_index_are_zero_padded() {
+    // F(10^12) ends in ...0299560546875.
+    fibcalc()
+        .args([
+            "-n",
+            "1000000000000",
+            "--algo",
+            "fast",
+            "-q",
+            "--last-digits",
+            "13",
+        ])
+        .assert()
+        .success()
+        .stdout("0299560546875\n");
+}
+
+#[test]
+fn modulus_mode_accepts_expressions() {
+    fibcalc()
+        .args(["-n", "1000", "--algo", "fast", "-q", "--modulus", "0xFFFF"])
+        .assert()
+        .success()
+        .stdout("33690\n");
+    // n beyond i64: F(10^40) mod (10^9 + 7), from each modular algorithm.
+    for algo in ["fast", "matrix"] {
+        fibcalc()
+            .args(["-n", "10^40", "-q", "--modulus", "10^9+7", "--algo", algo])
+            .assert()
+            .success()
+            .stdout("6544481\n");
+    }
+    fibcalc()
+        .args(["-n", "10^40", "--modulus", "10^9+7"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("MatrixExponentiationMod"))
+        .stdout(predicate::str::contains("Comparison Results"))
+        .stderr(predicate::str::contains("Warning").not());
+    fibcalc()
+        .args(["-n", "10^40", "--modulus", "10^9+7", "--algo", "nope"])
+        .assert()
+        .failure();
+    fibcalc().args(["-n", "10^40", "-q"]).assert().failure();
+    fibcalc()
+        .args(["-n", "100", "--modulus", "7", "--last-digits", "3"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn pisano_subcommand() {
+    fibcalc()
+        .args(["pisano", "10^9+7"])
+        .assert()
+        .success()
+        .stdout("2000000016\n");
+    fibcalc()
+        .args(["pisano", "10^30"])
+        .assert()
+        .success()
+        .stdout(format!("15{}\n", "0".repeat(29)));
+    fibcalc().args(["pisano", "0"]).assert().failure();
+    fibcalc().args(["pisano", "2^521-1"]).assert().failure();
+    // Indices far above π(m) are reduced before the doubling loop.
+    fibcalc()
+        .args([
+            "-n",
+            "10^100000",
+            "-q",
+            "--modulus",
+            "10^9+7",
+            "--algo",
+            "fast",
+        ])
+        .assert()
+        .success()
+        .stdout("322994487\n");
+}
+
+#[test]
+fn lucas_sequences() {
+    fibcalc()
+        .args(["-n", "10", "--sequence", "lucas", "--algo", "fast", "-q"])
+        .assert()
+        .success()
+        .stdout("123\n");
+    // U_n(3, 2) = 2^n - 1 and V_n(3, 2) = 2^n + 1.
+    fibcalc()
+        .args([
+            "-n",
+            "64",
+            "--algo",
+            "matrix",
+            "--sequence",
+            "lucas-uv",
+            "--p",
+            "3",
+            "--q",
+            "2",
+            "-q",
+        ])
+        .assert()
+        .success()
+        .stdout("18446744073709551615\n18446744073709551617\n");
+    fibcalc()
+        .args(["-n", "10", "--sequence", "lucas", "--algo", "fast"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("L(10) = 123"));
+}
+
+#[test]
+fn lucas_sequences_with_negative_terms_need_a_modulus() {
+    let args = [
+        "-n",
+        "5",
+        "--algo",
+        "fast",
+        "--sequence",
+        "lucas-uv",
+        "--p",
+        "-1",
+        "--q",
+        "1",
+    ];
+    fibcalc()
+        .args(args)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--modulus"));
+    // U_5(-1, 1) = V_5(-1, 1) = -1 ≡ 6 (mod 7).
+    fibcalc()
+        .args(args)
+        .args(["--modulus", "7", "-q"])
+        .assert()
+        .success()
+        .stdout("6\n6\n");
+}
+
+#[test]
+fn negative_indices() {
+    for (n, expected) in [("-10", "-55\n"), ("-9", "34\n"), ("-0", "0\n")] {
+        fibcalc()
+            .args(["-n", n, "--algo", "fast", "-q"])
+            .assert()
+            .success()
+            .stdout(expected);
+    }
+    // F(-8) = -21 ≡ 979 (mod 1000).
+    fibcalc()
+        .args(["-n", "-8", "--algo", "fast", "-q", "--modulus", "1000"])
+        .assert()
+        .success()
+        .stdout("979\n");
+    fibcalc()
+        .args(["-n", "-3", "--sequence", "lucas", "--algo", "fast", "-q"])
+        .assert()
+        .success()
+        .stdout("-4\n");
+    fibcalc()
+        .args(["-n", "-10", "--algo", "fast"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("F(-10) = -55"));
+    fibcalc()
+        .args([
+            "-n",
+            "-10",
+            "--sequence",
+            "lucas-uv",
+            "--p",
+            "3",
+            "--q",
+            "2",
+        ])
+        .assert()
+        .failure();
+}
+
 #[test]
 fn quiet_mode() {
     fibcalc()
@@ -261,3 +469,128 @@ fn calibration_auto() {
         .assert()
         .success();
 }
+
+#[test]
+fn seq_streams_range_to_stdout() {
+    fibcalc()
+        .args(["seq", "--from", "10", "--to", "12"])
+        .assert()
+        .success()
+        .stdout("10 55\n11 89\n12 144\n");
+}
+
+#[test]
+fn seq_reversed_range_fails() {
+    fibcalc()
+        .args(["seq", "--from", "12", "--to", "10"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn seq_signed_and_descending_ranges() {
+    fibcalc()
+        .args(["seq", "--from", "-3", "--to", "0"])
+        .assert()
+        .success()
+        .stdout("-3 2\n-2 -1\n-1 1\n0 0\n");
+    fibcalc()
+        .args(["seq", "--from", "12", "--to", "10", "--descending"])
+        .assert()
+        .success()
+        .stdout("12 144\n11 89\n10 55\n");
+    fibcalc()
+        .args(["seq", "--from", "10", "--to", "12", "--descending"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn output_format_hex_writes_hex_digits() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("fib.hex");
+    fibcalc()
+        .args([
+            "-n",
+            "100",
+            "--algo",
+            "fast",
+            "-q",
+            "--output-format",
+            "hex",
+            "-o",
+        ])
+        .arg(&path)
+        .assert()
+        .success();
+    // F(100) = 354224848179261915075 = 0x1333db76a7c594bfc3
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        "1333db76a7c594bfc3"
+    );
+}
+
+#[test]
+fn output_header_rejected_for_decimal() {
+    fibcalc()
+        .args([
+            "-n",
+            "10",
+            "--algo",
+            "fast",
+            "-q",
+            "--output-header",
+            "-o",
+            "unused.txt",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--output-header"));
+}
+
+#[test]
+fn ndjson_emits_records_and_summary() {
+    let output = fibcalc()
+        .args(["-n", "100", "--algo", "all", "--format", "ndjson", "-c"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let records: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let summary = records.last().unwrap();
+    assert_eq!(summary["type"], "comparison");
+    assert_eq!(summary["consistent"], true);
+    for record in &records[..records.len() - 1] {
+        assert_eq!(record["type"], "result");
+        assert_eq!(record["n"], 100);
+        assert_eq!(record["value"], "354224848179261915075");
+        assert_eq!(record["digits"], 21);
+    }
+}
+
+#[test]
+fn json_document_for_single_algorithm() {
+    let output = fibcalc()
+        .args([
+            "-n",
+            "100",
+            "--algo",
+            "fast",
+            "--format",
+            "json",
+            "--last-digits",
+            "5",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let doc: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let record = &doc["results"][0];
+    assert_eq!(record["algorithm"], "FastDoublingMod");
+    assert_eq!(record["last_digits"], "15075");
+    assert!(record["hash"].as_str().unwrap().starts_with("sha256:"));
+    assert!(doc.get("comparison").is_none());
+}
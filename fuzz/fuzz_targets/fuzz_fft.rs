@@ -1,8 +1,11 @@
 #![no_main]
 
 use libfuzzer_sys::fuzz_target;
+use num_bigint::BigUint;
 use std::sync::Arc;
 
+use fibcalc_bigfft::{mul_with, sqr_with, FFTBackend, FFTConfig};
+
 use fibcalc_core::calculator::{Calculator, FibCalculator};
 use fibcalc_core::fft_based::FFTBasedCalculator;
 use fibcalc_core::observers::NoOpObserver;
@@ -25,4 +28,19 @@ fuzz_target!(|data: &[u8]| {
 
     // Should not panic
     let _ = calc.calculate(&cancel, &observer, 0, n, &opts);
+
+    // Cross-check the Fermat and NTT backends on the remaining bytes
+    let (a, b) = data[4..].split_at((data.len() - 4) / 2);
+    let a = BigUint::from_bytes_le(a);
+    let b = BigUint::from_bytes_le(b);
+    let fermat = FFTConfig::with_threshold(0).with_backend(FFTBackend::Fermat);
+    let ntt = FFTConfig::with_threshold(0).with_backend(FFTBackend::Ntt);
+    let product = mul_with(&a, &b, &fermat);
+    assert_eq!(product, mul_with(&a, &b, &ntt), "backends disagree on a*b");
+    assert_eq!(product, &a * &b, "FFT product disagrees with num-bigint");
+    assert_eq!(
+        sqr_with(&a, &fermat),
+        sqr_with(&a, &ntt),
+        "backends disagree on a^2"
+    );
 });
@@ -21,3 +21,29 @@ fn fft_computation_uses_active_infrastructure() {
     let digits = result.unwrap().to_string().len();
     assert_eq!(digits, 2090, "F(10000) should have 2090 digits");
 }
+
+#[test]
+fn fft_sized_computation_reuses_pooled_buffers() {
+    let calc = FFTBasedCalculator::new();
+    let cancel = CancellationToken::new();
+    let observer = NoOpObserver::new();
+    let opts = Options {
+        fft_threshold: 20_000,
+        ..Options::default()
+    }
+    .normalize();
+
+    // F(500000) has about 347K bits, so the last steps run Fermat FFT
+    // products whose pointwise temporaries come from the global pool.
+    let before = fibcalc_bigfft::pool_stats();
+    let result = calc
+        .calculate_core(&cancel, &observer, 0, 500_000, &opts)
+        .unwrap();
+    let after = fibcalc_bigfft::pool_stats();
+
+    assert_eq!(result.to_string().len(), 104_494);
+    assert!(
+        after.hits > before.hits,
+        "expected pooled buffer reuse, stats before {before:?} after {after:?}"
+    );
+}
@@ -1,7 +1,8 @@
 //! Golden file integration tests.
 //!
-//! Reads tests/testdata/fibonacci_golden.json and verifies all 3 algorithms
-//! produce the correct values for known Fibonacci numbers.
+//! Reads `tests/testdata/fibonacci_golden.json` and verifies all 3 algorithms
+//! produce the correct values for known Fibonacci numbers, and
+//! `tests/testdata/lucas_golden.json` for the Lucas calculators.
 
 use std::sync::Arc;
 use std::time::Duration;
@@ -12,6 +13,7 @@ use serde::Deserialize;
 use fibcalc_core::calculator::{Calculator, CoreCalculator, FibCalculator, FibError};
 use fibcalc_core::fastdoubling::OptimizedFastDoubling;
 use fibcalc_core::fft_based::FFTBasedCalculator;
+use fibcalc_core::lucas::{LucasFastDoubling, LucasMatrix, Sequence};
 use fibcalc_core::matrix::MatrixExponentiation;
 use fibcalc_core::observers::NoOpObserver;
 use fibcalc_core::options::Options;
@@ -53,10 +55,13 @@ fn load_golden_data() -> GoldenData {
 // ---------------------------------------------------------------------------
 
 fn compute_with_core(core: &dyn CoreCalculator, n: u64) -> BigUint {
+    compute_with_core_opts(core, n, &Options::default())
+}
+
+fn compute_with_core_opts(core: &dyn CoreCalculator, n: u64, opts: &Options) -> BigUint {
     let cancel = CancellationToken::new();
     let observer = NoOpObserver::new();
-    let opts = Options::default();
-    core.calculate_core(&cancel, &observer, 0, n, &opts)
+    core.calculate_core(&cancel, &observer, 0, n, opts)
         .unwrap()
 }
 
@@ -122,6 +127,46 @@ fn golden_exact_fft_based() {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Golden: matrix multiplication paths (symmetric / Strassen, schoolbook / FFT)
+// ---------------------------------------------------------------------------
+
+#[test]
+fn golden_matrix_forced_paths() {
+    let algo = MatrixExponentiation::new();
+    let data = load_golden_data();
+    let paths = [
+        ("symmetric", usize::MAX, usize::MAX),
+        ("strassen", 1, usize::MAX),
+        ("symmetric+fft", usize::MAX, 1),
+        ("strassen+fft", 1, 1),
+    ];
+    for (label, strassen_threshold, fft_threshold) in paths {
+        let opts = Options {
+            strassen_threshold,
+            fft_threshold,
+            ..Options::default()
+        };
+        for entry in &data.values {
+            if let Some(expected) = &entry.fib {
+                let result = compute_with_core_opts(&algo, entry.n, &opts);
+                assert_eq!(
+                    result.to_string(),
+                    *expected,
+                    "Matrix ({label}) mismatch at n={}",
+                    entry.n,
+                );
+            }
+        }
+        let f10000 = compute_with_core_opts(&algo, 10_000, &opts);
+        assert_eq!(
+            f10000,
+            compute_with_core(&algo, 10_000),
+            "Matrix ({label}) mismatch at n=10000",
+        );
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Golden: prefix & digit count (n=5000, n=10000)
 // ---------------------------------------------------------------------------
@@ -402,6 +447,128 @@ fn all_algorithms_agree_medium_values() {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Golden: Lucas numbers and Lucas sequences U_n(P, Q), V_n(P, Q)
+// ---------------------------------------------------------------------------
+
+#[derive(Deserialize)]
+struct LucasGoldenData {
+    #[allow(dead_code)]
+    description: String,
+    values: Vec<LucasGoldenEntry>,
+}
+
+#[derive(Deserialize)]
+struct LucasGoldenEntry {
+    sequence: String,
+    p: i64,
+    q: i64,
+    n: u64,
+    #[serde(default)]
+    modulus: Option<String>,
+    value: String,
+}
+
+impl LucasGoldenEntry {
+    fn options(&self) -> Options {
+        let (p, q) = (self.p, self.q);
+        let sequence = match self.sequence.as_str() {
+            "L" => Sequence::Lucas,
+            "U" => Sequence::LucasU { p, q },
+            "V" => Sequence::LucasV { p, q },
+            other => panic!("unknown sequence {other}"),
+        };
+        Options {
+            sequence,
+            modulus: self.modulus.as_ref().map(|m| Arc::new(m.parse().unwrap())),
+            ..Options::default()
+        }
+    }
+}
+
+fn load_lucas_golden_data() -> LucasGoldenData {
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/testdata/lucas_golden.json"
+    );
+    let data = std::fs::read_to_string(path).expect("failed to read golden file");
+    serde_json::from_str(&data).expect("failed to parse golden JSON")
+}
+
+#[test]
+fn golden_lucas_fast_doubling_and_matrix() {
+    let fast = LucasFastDoubling::new();
+    let matrix = LucasMatrix::new();
+    let data = load_lucas_golden_data();
+    for entry in &data.values {
+        let opts = entry.options();
+        let expected: BigUint = entry.value.parse().unwrap();
+        for core in [&fast as &dyn CoreCalculator, &matrix] {
+            assert_eq!(
+                compute_with_core_opts(core, entry.n, &opts),
+                expected,
+                "{} mismatch for {}_{}({}, {}) mod {:?}",
+                core.name(),
+                entry.sequence,
+                entry.n,
+                entry.p,
+                entry.q,
+                entry.modulus
+            );
+        }
+    }
+}
+
+#[test]
+fn golden_lucas_via_factory() {
+    use fibcalc_core::CalculatorFactory;
+    let factory = fibcalc_core::registry::DefaultFactory::new();
+    let data = load_lucas_golden_data();
+    let cancel = CancellationToken::new();
+    let observer = NoOpObserver::new();
+    for name in ["lucas-fast", "lucas-matrix"] {
+        let calc = factory.get(name).unwrap();
+        for entry in data.values.iter().filter(|e| e.sequence == "L") {
+            let result = calc
+                .calculate(&cancel, &observer, 0, entry.n, &entry.options())
+                .unwrap();
+            assert_eq!(result.to_string(), entry.value, "{name} L({})", entry.n);
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Golden: negative indices, F(-n) = (-1)^(n+1) F(n)
+// ---------------------------------------------------------------------------
+
+#[test]
+fn golden_negafibonacci() {
+    use fibcalc_core::CalculatorFactory;
+    let factory = fibcalc_core::registry::DefaultFactory::new();
+    let data = load_golden_data();
+    let cancel = CancellationToken::new();
+    let observer = NoOpObserver::new();
+    let opts = Options::default();
+    for name in ["fast", "matrix", "fft"] {
+        let calc = factory.get(name).unwrap();
+        for entry in &data.values {
+            let Some(expected) = &entry.fib else {
+                continue;
+            };
+            let n = i64::try_from(entry.n).unwrap();
+            let result = calc
+                .calculate_signed(&cancel, &observer, 0, -n, &opts)
+                .unwrap();
+            let expected = if entry.n % 2 == 0 && entry.n > 0 {
+                format!("-{expected}")
+            } else {
+                expected.clone()
+            };
+            assert_eq!(result.to_string(), expected, "{name} F(-{})", entry.n);
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Invalid config tests
 // ---------------------------------------------------------------------------